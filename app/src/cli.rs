@@ -1,15 +1,21 @@
+#[cfg(feature = "networking")]
 use std::net::{IpAddr, Ipv4Addr};
+use std::{fs::File, path::PathBuf};
 
 use anyhow::{Context, Result};
-use bevy::prelude::*;
+use bevy::{asset::AssetPath, prelude::*, window::PrimaryWindow};
+#[cfg(feature = "networking")]
 use bevy_replicon::prelude::*;
+#[cfg(feature = "networking")]
 use bevy_replicon_renet::{
     renet::{ConnectionConfig, RenetClient, RenetServer},
     RenetChannelsExt,
 };
 use clap::{Args, Parser, Subcommand};
+use serde::{Deserialize, Serialize};
 
 use project_harmonia_base::{
+    asset::manifest::object_manifest::{self, ObjectCategory, ObjectManifest},
     core::GameState,
     error_message::error_message,
     game_world::{
@@ -18,8 +24,14 @@ use project_harmonia_base::{
         family::FamilyMembers,
         GameLoad, WorldName, WorldState,
     },
-    network::{self, DEFAULT_PORT},
 };
+#[cfg(feature = "networking")]
+use project_harmonia_base::{
+    game_paths::GamePaths,
+    game_world::spectator::Spectator,
+    network::{self, ConnectionInfo, DEFAULT_PORT},
+};
+use project_harmonia_ui::preview::GenerateThumbnails;
 
 /// Logic for command line interface.
 ///
@@ -45,8 +57,13 @@ impl Plugin for CliPlugin {
 impl CliPlugin {
     fn apply_subcommand(
         mut commands: Commands,
+        mut exit_events: EventWriter<AppExit>,
         cli: Res<Cli>,
-        network_channels: Res<RepliconChannels>,
+        #[cfg(feature = "networking")] network_channels: Res<RepliconChannels>,
+        #[cfg(feature = "networking")] game_paths: Res<GamePaths>,
+        asset_server: Res<AssetServer>,
+        manifests: Res<Assets<ObjectManifest>>,
+        mut window: Single<&mut Window, With<PrimaryWindow>>,
     ) -> Result<()> {
         if let Some(subcommand) = &cli.subcommand {
             match subcommand {
@@ -55,6 +72,7 @@ impl CliPlugin {
                     commands.insert_resource(WorldName(world_load.world_name.clone()));
                     commands.trigger(GameLoad);
                 }
+                #[cfg(feature = "networking")]
                 GameCommand::Host { world_load, port } => {
                     info!(
                         "hosting world '{}' on port {port} from CLI",
@@ -73,18 +91,54 @@ impl CliPlugin {
                     commands.insert_resource(WorldName(world_load.world_name.clone()));
                     commands.trigger(GameLoad);
                 }
-                GameCommand::Join { ip, port } => {
+                #[cfg(feature = "networking")]
+                GameCommand::Join {
+                    ip,
+                    port,
+                    spectator,
+                } => {
                     info!("joining world at {ip}:{port} from CLI");
                     let client = RenetClient::new(ConnectionConfig {
                         server_channels_config: network_channels.get_server_configs(),
                         client_channels_config: network_channels.get_client_configs(),
                         ..Default::default()
                     });
-                    let transport =
-                        network::create_client(*ip, *port).context("unable to create client")?;
+                    let info = ConnectionInfo {
+                        player_id: network::load_player_id(&game_paths.player_id)?,
+                        spectator: *spectator,
+                    };
+                    let transport = network::create_client(*ip, *port, info)
+                        .context("unable to create client")?;
 
                     commands.insert_resource(client);
                     commands.insert_resource(transport);
+                    if *spectator {
+                        commands.insert_resource(Spectator);
+                    }
+                }
+                GameCommand::GenerateThumbnails => {
+                    info!("generating thumbnails from CLI");
+                    window.visible = false;
+                    commands.trigger(GenerateThumbnails);
+                }
+                GameCommand::ExportCatalog { path } => {
+                    info!("exporting object catalog to {path:?} from CLI");
+                    let mut catalog: Vec<_> = manifests
+                        .iter()
+                        .filter_map(|(id, manifest)| {
+                            let manifest_path = asset_server.get_path(id)?;
+                            Some(CatalogEntry::new(&manifest_path, manifest))
+                        })
+                        .collect();
+                    catalog.sort_by(|a, b| a.id.cmp(&b.id));
+
+                    let file =
+                        File::create(path).with_context(|| format!("unable to create {path:?}"))?;
+                    serde_json::to_writer_pretty(file, &catalog)
+                        .context("unable to write catalog")?;
+
+                    info!("exported {} objects", catalog.len());
+                    exit_events.send(AppExit::Success);
                 }
             }
         }
@@ -143,6 +197,7 @@ impl Cli {
     fn quick_load(&self) -> Option<&QuickLoad> {
         match &self.subcommand {
             Some(GameCommand::Play(world_load)) => world_load.quick_load.as_ref(),
+            #[cfg(feature = "networking")]
             Some(GameCommand::Host { world_load, .. }) => world_load.quick_load.as_ref(),
             _ => None,
         }
@@ -158,6 +213,7 @@ impl Default for Cli {
 #[derive(Subcommand, Clone)]
 enum GameCommand {
     Play(WorldLoad),
+    #[cfg(feature = "networking")]
     Host {
         #[command(flatten)]
         world_load: WorldLoad,
@@ -166,6 +222,7 @@ enum GameCommand {
         #[clap(short, long, default_value_t = DEFAULT_PORT)]
         port: u16,
     },
+    #[cfg(feature = "networking")]
     Join {
         /// Server IP address.
         #[clap(short, long, default_value_t = Ipv4Addr::LOCALHOST.into())]
@@ -174,6 +231,17 @@ enum GameCommand {
         /// Server port.
         #[clap(short, long, default_value_t = DEFAULT_PORT)]
         port: u16,
+
+        /// Join without controlling a family.
+        #[clap(short, long)]
+        spectator: bool,
+    },
+    /// Render previews for all objects into the thumbnail cache and exit.
+    GenerateThumbnails,
+    /// Write all loaded objects to a JSON file and exit.
+    ExportCatalog {
+        /// Output file path.
+        path: PathBuf,
     },
 }
 
@@ -194,3 +262,107 @@ enum QuickLoad {
     City { name: String },
     Family { name: String },
 }
+
+/// Serializable description of a loaded object for external tools.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct CatalogEntry {
+    id: String,
+    name: String,
+    author: String,
+    license: String,
+    category: ObjectCategory,
+    price: u32,
+    source: ContentSource,
+    /// Short type names of all components from the manifest.
+    ///
+    /// Includes components that provide interactions.
+    components: Vec<String>,
+}
+
+impl CatalogEntry {
+    fn new(manifest_path: &AssetPath, manifest: &ObjectManifest) -> Self {
+        let components = manifest
+            .components
+            .iter()
+            .chain(&manifest.place_components)
+            .chain(&manifest.spawn_components)
+            .map(|component| component.reflect_short_type_path().to_string())
+            .collect();
+
+        Self {
+            id: object_manifest::object_id(manifest_path).to_string(),
+            name: manifest.general.name.clone(),
+            author: manifest.general.author.clone(),
+            license: manifest.general.license.clone(),
+            category: manifest.category.clone(),
+            price: manifest.price,
+            source: ContentSource::new(manifest_path),
+            components,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ContentSource {
+    /// Shipped with the game.
+    BuiltIn,
+    /// Added by the user.
+    User,
+}
+
+impl ContentSource {
+    /// Determines the source by the top-level asset directory.
+    fn new(manifest_path: &AssetPath) -> Self {
+        if manifest_path.path().starts_with(BUILT_IN_DIR) {
+            Self::BuiltIn
+        } else {
+            Self::User
+        }
+    }
+}
+
+/// Asset directory with content shipped with the game.
+const BUILT_IN_DIR: &str = "base";
+
+#[cfg(test)]
+mod tests {
+    use project_harmonia_base::asset::manifest::GeneralManifest;
+
+    use super::*;
+
+    #[test]
+    fn catalog_roundtrip() {
+        let manifest = ObjectManifest {
+            general: GeneralManifest {
+                name: "Retro TV".to_string(),
+                author: "Author".to_string(),
+                license: "CC BY-SA 4.0".to_string(),
+            },
+            scene: "base/objects/electronics/retro_tv/retro_tv.gltf#Scene0".into(),
+            category: ObjectCategory("electronics".to_string()),
+            subcategory: None,
+            price: 0,
+            preview_translation: Vec3::ZERO,
+            components: vec![Box::new(Name::new("Test"))],
+            place_components: Vec::new(),
+            spawn_components: Vec::new(),
+        };
+
+        let catalog = vec![
+            CatalogEntry::new(
+                &"base/objects/electronics/retro_tv/retro_tv.object.ron".into(),
+                &manifest,
+            ),
+            CatalogEntry::new(&"my_mod/retro_tv.object.ron".into(), &manifest),
+        ];
+        assert_eq!(catalog[0].id, "retro_tv");
+        assert_eq!(catalog[0].source, ContentSource::BuiltIn);
+        assert_eq!(catalog[1].source, ContentSource::User);
+        assert_eq!(catalog[0].components, ["Name"]);
+
+        let json = serde_json::to_string_pretty(&catalog).unwrap();
+        let parsed: Vec<CatalogEntry> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, catalog);
+    }
+}
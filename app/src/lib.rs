@@ -3,8 +3,12 @@ mod cursor_controller;
 
 use avian3d::{prelude::*, sync::SyncConfig};
 use bevy::{
-    app::PluginGroupBuilder, core_pipeline::experimental::taa::TemporalAntiAliasPlugin,
-    pbr::wireframe::WireframePlugin, prelude::*, render::RenderPlugin,
+    app::PluginGroupBuilder,
+    core_pipeline::experimental::taa::TemporalAntiAliasPlugin,
+    log::{Level, LogPlugin},
+    pbr::wireframe::WireframePlugin,
+    prelude::*,
+    render::RenderPlugin,
 };
 use bevy_atmosphere::prelude::*;
 use bevy_enhanced_input::prelude::*;
@@ -13,9 +17,13 @@ use bevy_inspector_egui::quick::WorldInspectorPlugin;
 use bevy_mod_billboard::prelude::*;
 use bevy_mod_outline::OutlinePlugin;
 use bevy_replicon::prelude::*;
+#[cfg(feature = "networking")]
 use bevy_replicon_renet::RepliconRenetPlugins;
 use bevy_simple_text_input::TextInputPlugin;
-use project_harmonia_base::{game_world::navigation::Obstacle, CorePlugins};
+use project_harmonia_base::{
+    file_log, game_paths::GamePaths, game_world::navigation::Obstacle, settings::Settings,
+    CorePlugins,
+};
 use project_harmonia_ui::UiPlugins;
 use project_harmonia_widgets::WidgetsPlugin;
 use vleue_navigator::prelude::*;
@@ -36,8 +44,18 @@ impl PluginGroup for AppPlugins {
 // Separate entry point for Android, which doesn't use `main.rs`.
 #[bevy_main]
 pub fn main() {
+    // Logging is initialized before settings are loaded.
+    let game_paths = GamePaths::default();
+    let settings = Settings::read(&game_paths.settings).unwrap_or_default();
+    let log_level = if settings.developer.debug_logs {
+        Level::DEBUG
+    } else {
+        Level::INFO
+    };
+
     let mut app = App::new();
     app.init_resource::<Cli>()
+        .insert_resource(game_paths)
         .insert_resource(SyncConfig {
             position_to_transform: false,
             ..Default::default()
@@ -45,6 +63,11 @@ pub fn main() {
         .insert_resource(Time::<Fixed>::from_hz(30.0))
         .add_plugins((
             DefaultPlugins
+                .set(LogPlugin {
+                    level: log_level,
+                    custom_layer: file_log::file_layer,
+                    ..Default::default()
+                })
                 .set(RenderPlugin {
                     synchronous_pipeline_compilation: true,
                     ..Default::default()
@@ -54,11 +77,12 @@ pub fn main() {
                         title: "Project Harmonia".to_string(),
                         ..Default::default()
                     }),
+                    // Handled by the UI to confirm exiting from a world.
+                    close_when_requested: false,
                     ..Default::default()
                 }),
             TemporalAntiAliasPlugin,
             RepliconPlugins,
-            RepliconRenetPlugins,
             WireframePlugin,
             AtmospherePlugin,
             EnhancedInputPlugin,
@@ -76,6 +100,9 @@ pub fn main() {
         ))
         .add_plugins((CorePlugins, WidgetsPlugin, UiPlugins, AppPlugins));
 
+    #[cfg(feature = "networking")]
+    app.add_plugins(RepliconRenetPlugins);
+
     #[cfg(feature = "inspector")]
     app.add_plugins(WorldInspectorPlugin::default());
 
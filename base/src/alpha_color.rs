@@ -1,32 +1,50 @@
 use std::iter;
 
-use bevy::{prelude::*, scene::SceneInstanceReady};
+use bevy::{prelude::*, scene::SceneInstanceReady, utils::HashMap};
 
-use crate::game_world::{family::FamilyMode, WorldState};
+use crate::{
+    core::GameState,
+    game_world::{family::FamilyMode, WorldState},
+};
 
+/// Tints materials of entities with [`AlphaColor`].
+///
+/// Tinted materials are cached in [`AlphaMaterials`], so meshes that share a material
+/// keep sharing it after tinting and can still be batched together.
 pub(super) struct AlphaColorPlugin;
 
 impl Plugin for AlphaColorPlugin {
     fn build(&self, app: &mut App) {
-        app.add_observer(Self::init_scene).add_systems(
-            PostUpdate,
-            Self::update_materials
-                .run_if(in_state(WorldState::City).or(in_state(FamilyMode::Building))),
-        );
+        app.init_resource::<AlphaMaterials>()
+            .add_observer(Self::init_scene)
+            .add_systems(
+                PostUpdate,
+                Self::update_materials
+                    .run_if(in_state(WorldState::City).or(in_state(FamilyMode::Building))),
+            )
+            .add_systems(OnExit(GameState::InGame), Self::clear_cache);
     }
 }
 
 impl AlphaColorPlugin {
     fn init_scene(
         trigger: Trigger<SceneInstanceReady>,
+        mut commands: Commands,
         mut materials: ResMut<Assets<StandardMaterial>>,
+        mut alpha_materials: ResMut<AlphaMaterials>,
         alpha_entities: Query<(Entity, &AlphaColor)>,
         children: Query<&Children>,
-        mut material_handles: Query<&mut MeshMaterial3d<StandardMaterial>>,
+        mut material_handles: Query<(
+            Entity,
+            &mut MeshMaterial3d<StandardMaterial>,
+            Option<&SourceMaterial>,
+        )>,
     ) {
         if let Ok((entity, &alpha_color)) = alpha_entities.get(trigger.entity()) {
             apply_alpha_color(
+                &mut commands,
                 &mut materials,
+                &mut alpha_materials,
                 &mut material_handles,
                 &children,
                 entity,
@@ -36,14 +54,22 @@ impl AlphaColorPlugin {
     }
 
     pub(super) fn update_materials(
+        mut commands: Commands,
         mut materials: ResMut<Assets<StandardMaterial>>,
+        mut alpha_materials: ResMut<AlphaMaterials>,
         alpha_entities: Query<(Entity, &AlphaColor), Changed<AlphaColor>>,
         children: Query<&Children>,
-        mut material_handles: Query<&mut MeshMaterial3d<StandardMaterial>>,
+        mut material_handles: Query<(
+            Entity,
+            &mut MeshMaterial3d<StandardMaterial>,
+            Option<&SourceMaterial>,
+        )>,
     ) {
         for (entity, &alpha_color) in &alpha_entities {
             apply_alpha_color(
+                &mut commands,
                 &mut materials,
+                &mut alpha_materials,
                 &mut material_handles,
                 &children,
                 entity,
@@ -51,11 +77,21 @@ impl AlphaColorPlugin {
             );
         }
     }
+
+    fn clear_cache(mut alpha_materials: ResMut<AlphaMaterials>) {
+        alpha_materials.clear();
+    }
 }
 
 fn apply_alpha_color(
+    commands: &mut Commands,
     materials: &mut Assets<StandardMaterial>,
-    material_handles: &mut Query<&mut MeshMaterial3d<StandardMaterial>>,
+    alpha_materials: &mut AlphaMaterials,
+    material_handles: &mut Query<(
+        Entity,
+        &mut MeshMaterial3d<StandardMaterial>,
+        Option<&SourceMaterial>,
+    )>,
     children: &Query<&Children>,
     entity: Entity,
     alpha_color: Color,
@@ -63,24 +99,127 @@ fn apply_alpha_color(
     debug!("setting alpha to `{alpha_color:?}`");
     let mut iter =
         material_handles.iter_many_mut(iter::once(entity).chain(children.iter_descendants(entity)));
-    while let Some(mut material_handle) = iter.fetch_next() {
-        let Some(material) = materials.get(&*material_handle) else {
+    while let Some((mesh_entity, mut material_handle, source_material)) = iter.fetch_next() {
+        // Always tint the original material, the current one could be already tinted.
+        let source_handle = match source_material {
+            Some(source_material) => source_material.0.clone(),
+            None => material_handle.0.clone(),
+        };
+        let Some(material) = materials.get(&source_handle) else {
             // Skip non-loaded, their alpha color will be updated only after full scene loading anyway.
             return;
         };
 
-        // If color matches, assume that we don't need any update.
-        if material.base_color == alpha_color {
-            return;
+        let key = (source_handle.id(), alpha_color.to_srgba().to_u8_array());
+        let handle = if let Some(handle) = alpha_materials.get(&key) {
+            handle.clone()
+        } else {
+            let mut material = material.clone();
+            material.base_color = alpha_color;
+            material.alpha_mode = AlphaMode::Add;
+            let handle = materials.add(material);
+            alpha_materials.insert(key, handle.clone());
+            handle
+        };
+        if material_handle.0 != handle {
+            *material_handle = handle.into();
+        }
+        if source_material.is_none() {
+            commands
+                .entity(mesh_entity)
+                .insert(SourceMaterial(source_handle));
         }
-
-        let mut material = material.clone();
-        material.base_color = alpha_color;
-        material.alpha_mode = AlphaMode::Add;
-        *material_handle = materials.add(material).into();
     }
 }
 
+/// Tinted materials by the source material and the color.
+///
+/// Colors are quantized to 8 bits per channel to be used as keys.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct AlphaMaterials(HashMap<(AssetId<StandardMaterial>, [u8; 4]), Handle<StandardMaterial>>);
+
+/// Material of a mesh before it was tinted.
+#[derive(Component)]
+struct SourceMaterial(Handle<StandardMaterial>);
+
 /// Blends material texture with the given color.
 #[derive(Component, Clone, Copy, Debug, Deref, DerefMut)]
 pub(super) struct AlphaColor(pub(super) Color);
+
+#[cfg(test)]
+mod tests {
+    use bevy::color::palettes::css::{GREEN, RED};
+
+    use super::*;
+
+    #[test]
+    fn shared_materials() {
+        let mut app = App::new();
+        app.init_resource::<Assets<StandardMaterial>>()
+            .init_resource::<AlphaMaterials>()
+            .add_systems(Update, AlphaColorPlugin::update_materials);
+
+        let material = app
+            .world_mut()
+            .resource_mut::<Assets<StandardMaterial>>()
+            .add(StandardMaterial::default());
+        let entities: Vec<_> = (0..2)
+            .map(|_| {
+                app.world_mut()
+                    .spawn((MeshMaterial3d(material.clone()), AlphaColor(RED.into())))
+                    .id()
+            })
+            .collect();
+
+        app.update();
+
+        let [first, second] = [entities[0], entities[1]].map(|entity| {
+            app.world()
+                .get::<MeshMaterial3d<StandardMaterial>>(entity)
+                .unwrap()
+        });
+        assert_ne!(first.0, material);
+        assert_eq!(
+            first.0, second.0,
+            "entities should share the tinted material"
+        );
+        assert_eq!(app.world().resource::<Assets<StandardMaterial>>().len(), 2);
+    }
+
+    #[test]
+    fn color_toggling() {
+        let mut app = App::new();
+        app.init_resource::<Assets<StandardMaterial>>()
+            .init_resource::<AlphaMaterials>()
+            .add_systems(Update, AlphaColorPlugin::update_materials);
+
+        let material = app
+            .world_mut()
+            .resource_mut::<Assets<StandardMaterial>>()
+            .add(StandardMaterial::default());
+        let entities: Vec<_> = (0..200)
+            .map(|_| {
+                app.world_mut()
+                    .spawn((MeshMaterial3d(material.clone()), AlphaColor(RED.into())))
+                    .id()
+            })
+            .collect();
+
+        for color in [GREEN, RED, GREEN, RED] {
+            for &entity in &entities {
+                app.world_mut().get_mut::<AlphaColor>(entity).unwrap().0 = color.into();
+            }
+            app.update();
+        }
+
+        assert_eq!(
+            app.world().resource::<Assets<StandardMaterial>>().len(),
+            3,
+            "toggling colors should reuse tinted materials"
+        );
+        for &entity in &entities {
+            let source_material = app.world().get::<SourceMaterial>(entity).unwrap();
+            assert_eq!(source_material.0, material);
+        }
+    }
+}
@@ -1,19 +1,38 @@
+pub mod bills;
 pub(super) mod collection;
+pub mod credits;
+pub mod difficulties;
+pub mod jobs;
 pub mod manifest;
 pub(super) mod material;
+pub(crate) mod ron_loader;
+pub mod speeds;
 
 use std::path::Path;
 
 use bevy::{asset::AssetPath, prelude::*};
 
+use bills::BillsPlugin;
+use credits::CreditsPlugin;
+use difficulties::DifficultiesPlugin;
+use jobs::JobsPlugin;
 use manifest::ManifestPlugin;
 use material::MaterialPlugin;
+use speeds::SpeedsPlugin;
 
 pub(super) struct AssetPlugin;
 
 impl Plugin for AssetPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((MaterialPlugin, ManifestPlugin));
+        app.add_plugins((
+            BillsPlugin,
+            CreditsPlugin,
+            DifficultiesPlugin,
+            JobsPlugin,
+            MaterialPlugin,
+            ManifestPlugin,
+            SpeedsPlugin,
+        ));
     }
 }
 
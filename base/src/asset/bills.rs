@@ -0,0 +1,86 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use super::ron_loader::RonAssetLoader;
+
+pub(super) struct BillsPlugin;
+
+impl Plugin for BillsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<BillRates>()
+            .register_asset_loader(RonAssetLoader::<BillRates>::new(BILLS_EXTENSION))
+            .init_resource::<BillRatesHandle>();
+    }
+}
+
+const BILLS_EXTENSION: &str = "bills.ron";
+
+/// Defines how often families pay for utilities and how much.
+#[derive(Asset, TypePath, Deserialize)]
+pub struct BillRates {
+    /// Number of days between bills.
+    pub interval_days: u32,
+
+    /// Charged regardless of the owned objects.
+    pub base_fee: u32,
+
+    /// Part of the total object price added to each bill.
+    pub object_rate: f32,
+}
+
+impl BillRates {
+    /// Returns the bill for objects with the specified total price.
+    pub fn amount(&self, objects_price: u32) -> u32 {
+        let objects_fee = (objects_price as f32 * self.object_rate) as u32;
+        self.base_fee.saturating_add(objects_fee)
+    }
+
+    /// Returns `true` if bills should be issued at the start of the day.
+    pub fn is_due(&self, day: u32) -> bool {
+        self.interval_days != 0 && day % self.interval_days == 0
+    }
+}
+
+/// Keeps the bill rates loaded.
+///
+/// Used by the server to charge families.
+#[derive(Resource, Deref)]
+pub struct BillRatesHandle(pub(crate) Handle<BillRates>);
+
+impl FromWorld for BillRatesHandle {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        Self(asset_server.load("base/bills.bills.ron"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use anyhow::Result;
+    use bevy::scene::ron;
+
+    use super::*;
+
+    #[test]
+    fn deserialization() -> Result<()> {
+        let data = fs::read_to_string("../app/assets/base/bills.bills.ron")?;
+        ron::from_str::<BillRates>(&data)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn amount() {
+        let rates = BillRates {
+            interval_days: 2,
+            base_fee: 10,
+            object_rate: 0.1,
+        };
+        assert_eq!(rates.amount(0), 10);
+        assert_eq!(rates.amount(1000), 110);
+        assert!(!rates.is_due(1));
+        assert!(rates.is_due(2));
+    }
+}
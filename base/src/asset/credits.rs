@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use super::ron_loader::RonAssetLoader;
+
+pub(super) struct CreditsPlugin;
+
+impl Plugin for CreditsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Credits>()
+            .register_asset_loader(RonAssetLoader::<Credits>::new(CREDITS_EXTENSION));
+    }
+}
+
+pub(crate) const CREDITS_EXTENSION: &str = "credits.ron";
+
+/// List of people and projects displayed in the credits menu.
+#[derive(Asset, TypePath, Deserialize)]
+pub struct Credits {
+    pub sections: Vec<CreditsSection>,
+}
+
+#[derive(Deserialize)]
+pub struct CreditsSection {
+    pub title: String,
+    pub names: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use anyhow::Result;
+    use bevy::scene::ron;
+
+    use super::*;
+
+    #[test]
+    fn deserialization() -> Result<()> {
+        let data = fs::read_to_string("../app/assets/base/credits.credits.ron")?;
+        ron::from_str::<Credits>(&data)?;
+
+        Ok(())
+    }
+}
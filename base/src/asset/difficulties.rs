@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use super::ron_loader::RonAssetLoader;
+use crate::game_world::difficulty::{Difficulty, DifficultyModifiers};
+
+pub(super) struct DifficultiesPlugin;
+
+impl Plugin for DifficultiesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<DifficultyTable>()
+            .register_asset_loader(RonAssetLoader::<DifficultyTable>::new(
+                DIFFICULTIES_EXTENSION,
+            ))
+            .init_resource::<DifficultyTableHandle>();
+    }
+}
+
+const DIFFICULTIES_EXTENSION: &str = "difficulties.ron";
+
+/// Modifiers for each [`Difficulty`].
+#[derive(Asset, TypePath, Deserialize)]
+pub struct DifficultyTable {
+    relaxed: DifficultyModifiers,
+    normal: DifficultyModifiers,
+    hard: DifficultyModifiers,
+}
+
+impl DifficultyTable {
+    pub fn modifiers(&self, difficulty: Difficulty) -> &DifficultyModifiers {
+        match difficulty {
+            Difficulty::Relaxed => &self.relaxed,
+            Difficulty::Normal => &self.normal,
+            Difficulty::Hard => &self.hard,
+        }
+    }
+}
+
+/// Keeps the difficulty table loaded.
+///
+/// Used by the server to apply modifiers.
+#[derive(Resource, Deref)]
+pub struct DifficultyTableHandle(pub(crate) Handle<DifficultyTable>);
+
+impl FromWorld for DifficultyTableHandle {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        Self(asset_server.load("base/difficulties.difficulties.ron"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use anyhow::Result;
+    use bevy::scene::ron;
+
+    use super::*;
+
+    #[test]
+    fn deserialization() -> Result<()> {
+        let data = fs::read_to_string("../app/assets/base/difficulties.difficulties.ron")?;
+        ron::from_str::<DifficultyTable>(&data)?;
+
+        Ok(())
+    }
+}
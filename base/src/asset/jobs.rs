@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use super::ron_loader::RonAssetLoader;
+use crate::game_world::actor::job::Job;
+
+pub(super) struct JobsPlugin;
+
+impl Plugin for JobsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<JobList>()
+            .register_asset_loader(RonAssetLoader::<JobList>::new(JOBS_EXTENSION))
+            .init_resource::<JobListHandle>();
+    }
+}
+
+const JOBS_EXTENSION: &str = "jobs.ron";
+
+/// Jobs that actors can take.
+#[derive(Asset, TypePath, Deserialize)]
+pub struct JobList {
+    pub jobs: Vec<Job>,
+}
+
+impl JobList {
+    pub fn get(&self, title: &str) -> Option<&Job> {
+        self.jobs.iter().find(|job| job.title == title)
+    }
+}
+
+/// Keeps the job list loaded.
+///
+/// Used by the server to validate assignments.
+#[derive(Resource, Deref)]
+pub struct JobListHandle(Handle<JobList>);
+
+impl FromWorld for JobListHandle {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        Self(asset_server.load("base/jobs.jobs.ron"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use anyhow::Result;
+    use bevy::scene::ron;
+
+    use super::*;
+
+    #[test]
+    fn deserialization() -> Result<()> {
+        let data = fs::read_to_string("../app/assets/base/jobs.jobs.ron")?;
+        ron::from_str::<JobList>(&data)?;
+
+        Ok(())
+    }
+}
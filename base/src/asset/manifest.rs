@@ -1,3 +1,4 @@
+pub mod category_manifest;
 pub mod object_manifest;
 pub mod road_manifest;
 
@@ -8,8 +9,12 @@ use serde::{Deserialize, Serialize};
 use strum::{EnumIter, IntoEnumIterator};
 use walkdir::WalkDir;
 
-use crate::core::GameState;
-use object_manifest::{ObjectLoader, ObjectManifest};
+use crate::{
+    asset::{bills::BillRatesHandle, difficulties::DifficultyTableHandle, jobs::JobListHandle},
+    core::{localization::Localization, GameState},
+};
+use category_manifest::{CategoryLoader, CategoryManifest, ObjectCategories};
+use object_manifest::{CatalogIndex, ObjectLoader, ObjectManifest};
 use road_manifest::{RoadLoader, RoadManifest};
 
 pub(super) struct ManifestPlugin;
@@ -18,11 +23,18 @@ impl Plugin for ManifestPlugin {
     fn build(&self, app: &mut App) {
         app.init_asset::<ObjectManifest>()
             .init_asset::<RoadManifest>()
+            .init_asset::<CategoryManifest>()
             .init_asset_loader::<ObjectLoader>()
             .init_asset_loader::<RoadLoader>()
+            .init_asset_loader::<CategoryLoader>()
+            .init_resource::<ObjectCategories>()
+            .init_resource::<CatalogIndex>()
             .add_systems(
                 Update,
-                Self::wait_for_loading.run_if(in_state(GameState::ManifestsLoading)),
+                (
+                    Self::update_catalog_index,
+                    Self::wait_for_loading.run_if(in_state(GameState::ManifestsLoading)),
+                ),
             );
     }
 
@@ -33,17 +45,80 @@ impl Plugin for ManifestPlugin {
 }
 
 impl ManifestPlugin {
+    /// Keeps the index in sync with loaded and hot-reloaded manifests.
+    fn update_catalog_index(
+        mut asset_events: EventReader<AssetEvent<ObjectManifest>>,
+        manifests: Res<Assets<ObjectManifest>>,
+        mut catalog_index: ResMut<CatalogIndex>,
+    ) {
+        for &event in asset_events.read() {
+            match event {
+                AssetEvent::Added { id } | AssetEvent::Modified { id } => {
+                    if let Some(manifest) = manifests.get(id) {
+                        debug!("indexing object manifest {id}");
+                        catalog_index.insert(id, manifest);
+                    }
+                }
+                AssetEvent::Removed { id } => catalog_index.remove(id),
+                AssetEvent::Unused { .. } | AssetEvent::LoadedWithDependencies { .. } => (),
+            }
+        }
+    }
+
     fn wait_for_loading(
         mut commands: Commands,
         manifests: Res<AssetManifests>,
+        job_list_handle: Res<JobListHandle>,
+        bill_rates_handle: Res<BillRatesHandle>,
+        difficulty_table_handle: Res<DifficultyTableHandle>,
+        localization: Res<Localization>,
         asset_server: Res<AssetServer>,
+        category_manifests: Res<Assets<CategoryManifest>>,
+        object_manifests: Res<Assets<ObjectManifest>>,
+        mut categories: ResMut<ObjectCategories>,
     ) {
         let objects = manifests.objects.iter().map(|handle| handle.id().untyped());
         let roads = manifests.roads.iter().map(Into::into);
+        let category_handles = manifests.categories.iter().map(Into::into);
         if objects
             .chain(roads)
+            .chain(category_handles)
+            .chain([
+                job_list_handle.id().untyped(),
+                bill_rates_handle.id().untyped(),
+                difficulty_table_handle.id().untyped(),
+            ])
+            .chain(localization.asset_ids())
             .all(|handle| asset_server.is_loaded(handle))
         {
+            for handle in &manifests.categories {
+                let manifest = category_manifests
+                    .get(handle)
+                    .expect("categories should be loaded");
+                categories.extend(manifest);
+            }
+
+            for (id, manifest) in object_manifests.iter() {
+                let Some(info) = categories.get(&manifest.category) else {
+                    error!(
+                        "object {:?} references unknown category `{}`",
+                        asset_server.get_path(id),
+                        manifest.category
+                    );
+                    continue;
+                };
+
+                if let Some(subcategory) = &manifest.subcategory {
+                    if !info.has_subcategory(subcategory) {
+                        error!(
+                            "object {:?} references unknown subcategory `{subcategory}` of `{}`",
+                            asset_server.get_path(id),
+                            manifest.category
+                        );
+                    }
+                }
+            }
+
             info!("finished loading asset manifests");
             commands.set_state(GameState::Menu);
         }
@@ -55,6 +130,7 @@ impl ManifestPlugin {
 struct AssetManifests {
     objects: Vec<Handle<ObjectManifest>>,
     roads: Vec<Handle<RoadManifest>>,
+    categories: Vec<Handle<CategoryManifest>>,
 }
 
 impl FromWorld for AssetManifests {
@@ -65,6 +141,7 @@ impl FromWorld for AssetManifests {
         let mut manifests = AssetManifests {
             objects: Default::default(),
             roads: Default::default(),
+            categories: Default::default(),
         };
         let asset_server = world.resource::<AssetServer>();
         for path in WalkDir::new(&assets_dir)
@@ -88,6 +165,9 @@ impl FromWorld for AssetManifests {
                 ManifestFormat::Road => {
                     manifests.roads.push(asset_server.load(relative_path));
                 }
+                ManifestFormat::Category => {
+                    manifests.categories.push(asset_server.load(relative_path));
+                }
             }
         }
 
@@ -99,6 +179,7 @@ impl FromWorld for AssetManifests {
 enum ManifestFormat {
     Object,
     Road,
+    Category,
 }
 
 impl ManifestFormat {
@@ -120,6 +201,7 @@ impl ManifestFormat {
         match self {
             ManifestFormat::Object => &["object.ron"],
             ManifestFormat::Road => &["road.ron"],
+            ManifestFormat::Category => &["categories.ron"],
         }
     }
 }
@@ -167,7 +249,8 @@ mod tests {
         registry.register::<Door>();
         registry.register::<SceneColliderConstructor>();
 
-        let mut objects_count = 0;
+        let mut object_categories = Vec::new();
+        let mut categories = ObjectCategories::default();
         let mut roads_count = 0;
         for path in WalkDir::new("../app/assets/base")
             .into_iter()
@@ -186,19 +269,35 @@ mod tests {
                         registry: &registry,
                         dir: None,
                     };
-                    ron::Options::default().from_str_seed(&string, seed)?;
-                    objects_count += 1;
+                    let manifest = ron::Options::default().from_str_seed(&string, seed)?;
+                    object_categories.push((path, manifest.category, manifest.subcategory));
                 }
                 ManifestFormat::Road => {
                     let seed = RoadManifestDeserializer { dir: None };
                     ron::Options::default().from_str_seed(&string, seed)?;
                     roads_count += 1;
                 }
+                ManifestFormat::Category => {
+                    let manifest: CategoryManifest = ron::from_str(&string)?;
+                    categories.extend(&manifest);
+                }
             }
         }
 
-        assert!(objects_count > 0);
+        assert!(!object_categories.is_empty());
         assert!(roads_count > 0);
+        assert!(!categories.is_empty());
+        for (path, category, subcategory) in object_categories {
+            let info = categories
+                .get(&category)
+                .unwrap_or_else(|| panic!("{path:?} should reference a declared category"));
+            if let Some(subcategory) = subcategory {
+                assert!(
+                    info.has_subcategory(&subcategory),
+                    "{path:?} should reference a declared subcategory"
+                );
+            }
+        }
 
         Ok(())
     }
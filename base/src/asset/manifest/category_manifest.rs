@@ -0,0 +1,103 @@
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+    scene::ron,
+};
+use serde::Deserialize;
+
+use super::{object_manifest::ObjectCategory, ManifestFormat};
+
+#[derive(Default)]
+pub(super) struct CategoryLoader;
+
+impl AssetLoader for CategoryLoader {
+    type Asset = CategoryManifest;
+    type Settings = ();
+    type Error = anyhow::Error;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut string = String::new();
+        reader.read_to_string(&mut string).await?;
+
+        let manifest = ron::from_str(&string)?;
+
+        Ok(manifest)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        ManifestFormat::Category.extensions()
+    }
+}
+
+/// Object categories declared by a content package.
+///
+/// Categories from all manifests are merged into [`ObjectCategories`].
+#[derive(TypePath, Deserialize, Asset)]
+pub struct CategoryManifest {
+    pub categories: Vec<CategoryInfo>,
+}
+
+#[derive(Clone, Deserialize)]
+pub struct CategoryInfo {
+    pub id: ObjectCategory,
+    pub glyph: String,
+
+    /// Modes in which objects from this category can be placed.
+    pub groups: Vec<CategoryGroup>,
+
+    #[serde(default)]
+    pub subcategories: Vec<SubcategoryInfo>,
+}
+
+impl CategoryInfo {
+    pub fn has_subcategory(&self, id: &str) -> bool {
+        self.subcategories
+            .iter()
+            .any(|subcategory| subcategory.id == id)
+    }
+}
+
+#[derive(Clone, Deserialize)]
+pub struct SubcategoryInfo {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+pub enum CategoryGroup {
+    City,
+    Family,
+}
+
+/// All loaded object categories in declaration order.
+///
+/// Initialized after loading all manifests.
+#[derive(Resource, Default, Deref)]
+pub struct ObjectCategories(Vec<CategoryInfo>);
+
+impl ObjectCategories {
+    /// Adds categories from a manifest, skipping already declared ones.
+    pub(super) fn extend(&mut self, manifest: &CategoryManifest) {
+        for info in &manifest.categories {
+            if self.get(&info.id).is_some() {
+                error!("category `{}` is declared more than once", info.id);
+                continue;
+            }
+            self.0.push(info.clone());
+        }
+    }
+
+    pub fn get(&self, id: &ObjectCategory) -> Option<&CategoryInfo> {
+        self.iter().find(|info| info.id == *id)
+    }
+
+    /// Returns categories available in the group.
+    pub fn group(&self, group: CategoryGroup) -> impl Iterator<Item = &CategoryInfo> {
+        self.iter().filter(move |info| info.groups.contains(&group))
+    }
+}
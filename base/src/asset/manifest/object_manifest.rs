@@ -9,10 +9,11 @@ use bevy::{
     prelude::*,
     reflect::{serde::TypedReflectDeserializer, TypeRegistry, TypeRegistryArc},
     scene::ron,
+    utils::HashMap,
 };
 use serde::{
     de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor},
-    Deserialize, Deserializer,
+    Deserialize, Deserializer, Serialize,
 };
 use strum::{IntoStaticStr, VariantNames};
 
@@ -66,6 +67,10 @@ pub struct ObjectManifest {
     pub general: GeneralManifest,
     pub scene: AssetPath<'static>,
     pub category: ObjectCategory,
+    /// Subcategory ID within [`Self::category`].
+    pub subcategory: Option<String>,
+    /// Cost of buying the object in a family mode.
+    pub price: u32,
     pub preview_translation: Vec3,
     pub components: Vec<Box<dyn PartialReflect>>,
     pub place_components: Vec<Box<dyn PartialReflect>>,
@@ -78,6 +83,39 @@ impl MapPaths for ObjectManifest {
     }
 }
 
+/// Lowercase search text for each loaded [`ObjectManifest`].
+///
+/// Includes the name, category and subcategory, so filtering doesn't need to touch manifests.
+#[derive(Resource, Default)]
+pub struct CatalogIndex(HashMap<AssetId<ObjectManifest>, String>);
+
+impl CatalogIndex {
+    pub(super) fn insert(&mut self, id: AssetId<ObjectManifest>, manifest: &ObjectManifest) {
+        let mut text = manifest.general.name.to_lowercase();
+        text.push(' ');
+        text.push_str(&manifest.category.0.to_lowercase());
+        if let Some(subcategory) = &manifest.subcategory {
+            text.push(' ');
+            text.push_str(&subcategory.to_lowercase());
+        }
+
+        self.0.insert(id, text);
+    }
+
+    pub(super) fn remove(&mut self, id: AssetId<ObjectManifest>) {
+        self.0.remove(&id);
+    }
+
+    /// Returns `true` if the object contains all words from the query.
+    ///
+    /// The query is expected to be lowercase.
+    pub fn matches(&self, id: AssetId<ObjectManifest>, query: &str) -> bool {
+        self.0
+            .get(&id)
+            .is_some_and(|text| query.split_whitespace().all(|word| text.contains(word)))
+    }
+}
+
 /// Fields of [`ObjectManifest`] for manual deserialization.
 #[derive(Deserialize, VariantNames, IntoStaticStr)]
 #[strum(serialize_all = "snake_case")]
@@ -86,56 +124,33 @@ enum ObjectManifestField {
     General,
     Scene,
     Category,
+    Subcategory,
+    Price,
     PreviewTranslation,
     Components,
     PlaceComponents,
     SpawnComponents,
 }
 
-#[derive(Clone, Component, Copy, Deserialize, PartialEq)]
-pub enum ObjectCategory {
-    Rocks,
-    Foliage,
-    OutdoorFurniture,
-    OutdoorActivities,
-    Street,
-    Electronics,
-    Furniture,
-    Windows,
-    Doors,
+/// Returns manifest file name without extensions.
+///
+/// Used as a stable object identifier in tools and commands.
+pub fn object_id<'a>(path: &'a AssetPath) -> &'a str {
+    path.path()
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.split('.').next())
+        .unwrap_or_default()
 }
 
-impl ObjectCategory {
-    pub const CITY_CATEGORIES: &'static [ObjectCategory] = &[
-        ObjectCategory::Rocks,
-        ObjectCategory::Foliage,
-        ObjectCategory::OutdoorFurniture,
-        ObjectCategory::OutdoorActivities,
-        ObjectCategory::Street,
-    ];
-
-    pub const FAMILY_CATEGORIES: &'static [ObjectCategory] = &[
-        ObjectCategory::Rocks,
-        ObjectCategory::Foliage,
-        ObjectCategory::OutdoorFurniture,
-        ObjectCategory::Electronics,
-        ObjectCategory::Furniture,
-        ObjectCategory::Windows,
-        ObjectCategory::Doors,
-    ];
-
-    pub fn glyph(self) -> &'static str {
-        match self {
-            ObjectCategory::Rocks => "🗻",
-            ObjectCategory::Foliage => "🍀",
-            ObjectCategory::OutdoorFurniture => "🏡",
-            ObjectCategory::OutdoorActivities => "🔤",
-            ObjectCategory::Street => "🚃",
-            ObjectCategory::Electronics => "📺",
-            ObjectCategory::Furniture => "💺",
-            ObjectCategory::Windows => "🔲",
-            ObjectCategory::Doors => "🚪",
-        }
+/// Identifier of a category from [`ObjectCategories`](super::category_manifest::ObjectCategories).
+#[derive(Clone, Component, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct ObjectCategory(pub String);
+
+impl fmt::Display for ObjectCategory {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.0)
     }
 }
 
@@ -167,6 +182,8 @@ impl<'de> Visitor<'de> for ObjectManifestDeserializer<'_> {
         let mut general = None;
         let mut scene = None;
         let mut category = None;
+        let mut subcategory = None;
+        let mut price = None;
         let mut preview_translation = None;
         let mut components = None;
         let mut place_components = None;
@@ -197,6 +214,22 @@ impl<'de> Visitor<'de> for ObjectManifestDeserializer<'_> {
                     }
                     category = Some(map.next_value()?);
                 }
+                ObjectManifestField::Subcategory => {
+                    if subcategory.is_some() {
+                        return Err(de::Error::duplicate_field(
+                            ObjectManifestField::Subcategory.into(),
+                        ));
+                    }
+                    subcategory = Some(map.next_value()?);
+                }
+                ObjectManifestField::Price => {
+                    if price.is_some() {
+                        return Err(de::Error::duplicate_field(
+                            ObjectManifestField::Price.into(),
+                        ));
+                    }
+                    price = Some(map.next_value()?);
+                }
                 ObjectManifestField::PreviewTranslation => {
                     if preview_translation.is_some() {
                         return Err(de::Error::duplicate_field(
@@ -247,6 +280,8 @@ impl<'de> Visitor<'de> for ObjectManifestDeserializer<'_> {
         let preview_translation = preview_translation.ok_or_else(|| {
             de::Error::missing_field(ObjectManifestField::PreviewTranslation.into())
         })?;
+        let subcategory = subcategory.flatten();
+        let price = price.unwrap_or_default();
         let components = components.unwrap_or_default();
         let place_components = place_components.unwrap_or_default();
         let spawn_components = spawn_components.unwrap_or_default();
@@ -255,6 +290,8 @@ impl<'de> Visitor<'de> for ObjectManifestDeserializer<'_> {
             general,
             scene,
             category,
+            subcategory,
+            price,
             preview_translation,
             components,
             place_components,
@@ -376,3 +413,102 @@ impl<'de> Visitor<'de> for ShortReflectDeserializer<'_> {
         Ok(reflect_default.default().into_partial_reflect())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        hint,
+        time::{Duration, Instant},
+    };
+
+    use super::*;
+
+    #[test]
+    fn catalog_search() {
+        let manifest = manifest("Wooden Chair", "furniture", "chairs");
+
+        let mut manifests = Assets::default();
+        let id = manifests.add(manifest).id();
+        let other_id = manifests.reserve_handle().id();
+        let mut catalog_index = CatalogIndex::default();
+        catalog_index.insert(id, manifests.get(id).unwrap());
+
+        assert!(catalog_index.matches(id, ""));
+        assert!(catalog_index.matches(id, "chair"));
+        assert!(catalog_index.matches(id, "wood  furniture"));
+        assert!(!catalog_index.matches(id, "table"));
+        assert!(!catalog_index.matches(other_id, "chair"));
+
+        catalog_index.remove(id);
+        assert!(!catalog_index.matches(id, ""));
+    }
+
+    /// Compares filtering a large catalog with the index and with lowercasing manifests on each query.
+    ///
+    /// Run with `cargo test --release -- --ignored --nocapture search_time`.
+    #[test]
+    #[ignore = "measurement"]
+    fn search_time() {
+        const OBJECTS_COUNT: usize = 10_000;
+
+        let mut manifests = Assets::default();
+        let mut catalog_index = CatalogIndex::default();
+        for index in 0..OBJECTS_COUNT {
+            let name = format!("Wooden Chair {index}");
+            let handle = manifests.add(manifest(&name, "furniture", "chairs"));
+            catalog_index.insert(handle.id(), manifests.get(&handle).unwrap());
+        }
+
+        let indexed = measure_search(|query| {
+            manifests
+                .ids()
+                .filter(|&id| catalog_index.matches(id, query))
+                .count()
+        });
+        let direct = measure_search(|query| {
+            manifests
+                .iter()
+                .filter(|(_, manifest)| {
+                    let name = manifest.general.name.to_lowercase();
+                    let category = manifest.category.0.to_lowercase();
+                    let subcategory = manifest.subcategory.as_deref().unwrap_or_default();
+                    let subcategory = subcategory.to_lowercase();
+                    query.split_whitespace().all(|word| {
+                        name.contains(word) || category.contains(word) || subcategory.contains(word)
+                    })
+                })
+                .count()
+        });
+        println!("indexed: {indexed:.2?}, direct: {direct:.2?} per {OBJECTS_COUNT} objects");
+        assert!(indexed < direct);
+    }
+
+    /// Returns the average time of filtering by a few typical queries.
+    fn measure_search(mut filter: impl FnMut(&str) -> usize) -> Duration {
+        const QUERIES: [&str; 4] = ["c", "chair", "wooden chair", "missing"];
+
+        let start = Instant::now();
+        for query in QUERIES {
+            hint::black_box(filter(query));
+        }
+        start.elapsed() / QUERIES.len() as u32
+    }
+
+    fn manifest(name: &str, category: &str, subcategory: &str) -> ObjectManifest {
+        ObjectManifest {
+            general: GeneralManifest {
+                name: name.to_string(),
+                author: Default::default(),
+                license: Default::default(),
+            },
+            scene: Default::default(),
+            category: ObjectCategory(category.to_string()),
+            subcategory: Some(subcategory.to_string()),
+            price: 0,
+            preview_translation: Vec3::ZERO,
+            components: Vec::new(),
+            place_components: Vec::new(),
+            spawn_components: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use std::marker::PhantomData;
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+    scene::ron,
+};
+use serde::de::DeserializeOwned;
+
+/// Loads assets deserialized from RON files with the extension.
+///
+/// Should be registered for each asset type.
+pub(crate) struct RonAssetLoader<T> {
+    extensions: [&'static str; 1],
+    marker: PhantomData<T>,
+}
+
+impl<T> RonAssetLoader<T> {
+    pub(crate) fn new(extension: &'static str) -> Self {
+        Self {
+            extensions: [extension],
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Asset + DeserializeOwned> AssetLoader for RonAssetLoader<T> {
+    type Asset = T;
+    type Settings = ();
+    type Error = anyhow::Error;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut data = String::new();
+        reader.read_to_string(&mut data).await?;
+        let asset = ron::from_str(&data)?;
+
+        Ok(asset)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &self.extensions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::asset::LoadState;
+
+    use super::*;
+    use crate::asset::credits::{Credits, CREDITS_EXTENSION};
+
+    #[test]
+    fn loading() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin {
+                file_path: "../app/assets".to_string(),
+                ..Default::default()
+            },
+        ))
+        .init_asset::<Credits>()
+        .register_asset_loader(RonAssetLoader::<Credits>::new(CREDITS_EXTENSION));
+
+        let handle: Handle<Credits> = app
+            .world()
+            .resource::<AssetServer>()
+            .load("base/credits.credits.ron");
+
+        loop {
+            app.update();
+            match app.world().resource::<AssetServer>().load_state(&handle) {
+                LoadState::Loaded => break,
+                LoadState::Failed(e) => panic!("credits should be loaded: {e}"),
+                _ => (),
+            }
+        }
+
+        let credits = app
+            .world()
+            .resource::<Assets<Credits>>()
+            .get(&handle)
+            .unwrap();
+        assert!(!credits.sections.is_empty());
+    }
+}
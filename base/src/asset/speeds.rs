@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use super::ron_loader::RonAssetLoader;
+use crate::game_world::actor::{LifeStage, MovementSpeed};
+
+pub(super) struct SpeedsPlugin;
+
+impl Plugin for SpeedsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<BaseSpeeds>()
+            .register_asset_loader(RonAssetLoader::<BaseSpeeds>::new(SPEEDS_EXTENSION))
+            .init_resource::<BaseSpeedsHandle>();
+    }
+}
+
+const SPEEDS_EXTENSION: &str = "speeds.ron";
+
+/// Actor speeds for each life stage.
+#[derive(Asset, TypePath, Deserialize)]
+pub struct BaseSpeeds {
+    pub walk: f32,
+    pub run: f32,
+
+    /// Multiplier for both speeds of children.
+    pub child_scale: f32,
+
+    /// Multiplier for both speeds of elders.
+    pub elder_scale: f32,
+}
+
+impl BaseSpeeds {
+    pub fn for_stage(&self, life_stage: LifeStage) -> MovementSpeed {
+        let scale = match life_stage {
+            LifeStage::Child => self.child_scale,
+            LifeStage::Adult => 1.0,
+            LifeStage::Elder => self.elder_scale,
+        };
+
+        MovementSpeed {
+            walk: self.walk * scale,
+            run: self.run * scale,
+        }
+    }
+}
+
+/// Keeps the speeds loaded.
+///
+/// Used by the server to update actors.
+#[derive(Resource, Deref)]
+pub struct BaseSpeedsHandle(Handle<BaseSpeeds>);
+
+impl FromWorld for BaseSpeedsHandle {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        Self(asset_server.load("base/actors/movement.speeds.ron"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use anyhow::Result;
+    use bevy::scene::ron;
+
+    use super::*;
+
+    #[test]
+    fn deserialization() -> Result<()> {
+        let data = fs::read_to_string("../app/assets/base/actors/movement.speeds.ron")?;
+        ron::from_str::<BaseSpeeds>(&data)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+use std::fmt::{self, Display, Formatter};
+
+use bevy::prelude::*;
+
+/// Information about the running build.
+///
+/// Embedded at compile time by the build script.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct BuildInfo {
+    pub version: &'static str,
+    pub git_hash: &'static str,
+    pub build_date: &'static str,
+}
+
+impl BuildInfo {
+    pub const CURRENT: Self = Self {
+        version: env!("CARGO_PKG_VERSION"),
+        git_hash: env!("GIT_HASH"),
+        build_date: env!("BUILD_DATE"),
+    };
+}
+
+impl Default for BuildInfo {
+    fn default() -> Self {
+        Self::CURRENT
+    }
+}
+
+impl Display for BuildInfo {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} ({}, {})",
+            self.version, self.git_hash, self.build_date
+        )
+    }
+}
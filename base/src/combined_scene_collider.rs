@@ -8,58 +8,139 @@ use bevy::{
     scene::SceneInstanceReady,
 };
 
+use crate::settings::Settings;
+
+/// Generates colliders for scenes with [`SceneColliderConstructor`].
+///
+/// With [`DeveloperSettings::lazy_colliders`](crate::settings::DeveloperSettings::lazy_colliders)
+/// enabled, colliders are generated only for scenes within [`COLLIDER_DISTANCE`]
+/// from any [`ColliderFocus`]. Other scenes are marked with [`PendingCollider`].
 pub(super) struct SceneColliderConstructorPlugin;
 
 impl Plugin for SceneColliderConstructorPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<SceneColliderConstructor>()
-            .add_observer(Self::init);
+            .add_observer(Self::init)
+            .add_systems(
+                Update,
+                Self::init_pending.run_if(any_with_component::<PendingCollider>),
+            );
     }
 }
 
+/// Maximum distance from a [`ColliderFocus`] for lazily generated colliders.
+const COLLIDER_DISTANCE: f32 = 50.0;
+
 impl SceneColliderConstructorPlugin {
     fn init(
         trigger: Trigger<SceneInstanceReady>,
+        mut commands: Commands,
+        settings: Res<Settings>,
         meshes: Res<Assets<Mesh>>,
-        mut scenes: Query<(&Children, &SceneColliderConstructor, &mut Collider)>,
+        mut scenes: Query<(
+            &Children,
+            &SceneColliderConstructor,
+            &mut Collider,
+            &GlobalTransform,
+        )>,
         scene_meshes: Query<(&Transform, Option<&Mesh3d>, Option<&Children>)>,
+        focuses: Query<&GlobalTransform, With<ColliderFocus>>,
     ) {
-        let Ok((children, constructor, mut collider)) = scenes.get_mut(trigger.entity()) else {
+        let Ok((children, constructor, mut collider, transform)) = scenes.get_mut(trigger.entity())
+        else {
             return;
         };
 
+        if settings.developer.lazy_colliders && !is_focused(transform, &focuses) {
+            debug!("deferring collider for scene `{}`", trigger.entity());
+            commands.entity(trigger.entity()).insert(PendingCollider);
+            return;
+        }
+
         debug!("generating collider for scene `{}`", trigger.entity());
+        *collider = generate_collider(&meshes, &scene_meshes, children, constructor);
+        commands.entity(trigger.entity()).insert(GeneratedCollider);
+    }
 
-        let mut combined_mesh = Mesh::new(PrimitiveTopology::TriangleList, Default::default())
-            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, Vec::<Vec3>::new())
-            .with_inserted_indices(Indices::U32(Vec::new()));
+    fn init_pending(
+        mut commands: Commands,
+        settings: Res<Settings>,
+        meshes: Res<Assets<Mesh>>,
+        mut scenes: Query<
+            (
+                Entity,
+                &Children,
+                &SceneColliderConstructor,
+                &mut Collider,
+                &GlobalTransform,
+            ),
+            With<PendingCollider>,
+        >,
+        scene_meshes: Query<(&Transform, Option<&Mesh3d>, Option<&Children>)>,
+        focuses: Query<&GlobalTransform, With<ColliderFocus>>,
+    ) {
+        for (entity, children, constructor, mut collider, transform) in &mut scenes {
+            if settings.developer.lazy_colliders && !is_focused(transform, &focuses) {
+                continue;
+            }
 
-        for &child_entity in children {
-            recursive_merge(
-                &meshes,
-                &scene_meshes,
-                child_entity,
-                Default::default(),
-                &mut combined_mesh,
-            );
+            debug!("generating deferred collider for scene `{entity}`");
+            *collider = generate_collider(&meshes, &scene_meshes, children, constructor);
+            commands
+                .entity(entity)
+                .remove::<PendingCollider>()
+                .insert(GeneratedCollider);
         }
+    }
+}
 
-        *collider = match constructor {
-            SceneColliderConstructor::Aabb => {
-                let aabb = combined_mesh
-                    .compute_aabb()
-                    .expect("object mesh should be in compatible format");
-                let center: Vec3 = aabb.center.into();
-                let cuboid = Collider::cuboid(
-                    aabb.half_extents.x * 2.0,
-                    aabb.half_extents.y * 2.0,
-                    aabb.half_extents.z * 2.0,
-                );
-                Collider::compound(vec![(center, Rotation::default(), cuboid)])
-            }
-            SceneColliderConstructor::ConvexHull => Collider::convex_hull_from_mesh(&combined_mesh)
-                .expect("object mesh should be in compatible format"),
-        };
+fn is_focused(
+    transform: &GlobalTransform,
+    focuses: &Query<&GlobalTransform, With<ColliderFocus>>,
+) -> bool {
+    focuses.iter().any(|focus_transform| {
+        focus_transform
+            .translation()
+            .distance_squared(transform.translation())
+            <= COLLIDER_DISTANCE * COLLIDER_DISTANCE
+    })
+}
+
+fn generate_collider(
+    meshes: &Assets<Mesh>,
+    scene_meshes: &Query<(&Transform, Option<&Mesh3d>, Option<&Children>)>,
+    children: &Children,
+    constructor: &SceneColliderConstructor,
+) -> Collider {
+    let mut combined_mesh = Mesh::new(PrimitiveTopology::TriangleList, Default::default())
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, Vec::<Vec3>::new())
+        .with_inserted_indices(Indices::U32(Vec::new()));
+
+    for &child_entity in children {
+        recursive_merge(
+            meshes,
+            scene_meshes,
+            child_entity,
+            Default::default(),
+            &mut combined_mesh,
+        );
+    }
+
+    match constructor {
+        SceneColliderConstructor::Aabb => {
+            let aabb = combined_mesh
+                .compute_aabb()
+                .expect("object mesh should be in compatible format");
+            let center: Vec3 = aabb.center.into();
+            let cuboid = Collider::cuboid(
+                aabb.half_extents.x * 2.0,
+                aabb.half_extents.y * 2.0,
+                aabb.half_extents.z * 2.0,
+            );
+            Collider::compound(vec![(center, Rotation::default(), cuboid)])
+        }
+        SceneColliderConstructor::ConvexHull => Collider::convex_hull_from_mesh(&combined_mesh)
+            .expect("object mesh should be in compatible format"),
     }
 }
 
@@ -104,3 +185,134 @@ pub(super) enum SceneColliderConstructor {
     Aabb,
     ConvexHull,
 }
+
+/// Scene that waits for a [`ColliderFocus`] nearby to generate its collider.
+#[derive(Component)]
+struct PendingCollider;
+
+/// Scene whose [`Collider`] was generated from its meshes.
+///
+/// Before that the scene has a placeholder collider.
+#[derive(Component)]
+pub(crate) struct GeneratedCollider;
+
+/// Entity around which colliders are generated when they are lazy.
+#[derive(Component, Default)]
+pub(crate) struct ColliderFocus;
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+
+    #[test]
+    fn lazy_generation() {
+        let mut app = App::new();
+        app.init_resource::<Assets<Mesh>>().add_systems(
+            Update,
+            SceneColliderConstructorPlugin::init_pending
+                .run_if(any_with_component::<PendingCollider>),
+        );
+
+        let mut settings = Settings::default();
+        settings.developer.lazy_colliders = true;
+        app.insert_resource(settings);
+
+        let mesh = app
+            .world_mut()
+            .resource_mut::<Assets<Mesh>>()
+            .add(Cuboid::default());
+        let scene_entity = app
+            .world_mut()
+            .spawn((
+                SceneColliderConstructor::Aabb,
+                PendingCollider,
+                GlobalTransform::default(),
+            ))
+            .with_child((Transform::default(), Mesh3d(mesh)))
+            .id();
+        let focus_entity = app
+            .world_mut()
+            .spawn((
+                ColliderFocus,
+                GlobalTransform::from_xyz(COLLIDER_DISTANCE * 2.0, 0.0, 0.0),
+            ))
+            .id();
+
+        app.update();
+
+        assert!(
+            app.world().get::<PendingCollider>(scene_entity).is_some(),
+            "collider shouldn't be generated far from the focus"
+        );
+
+        app.world_mut()
+            .entity_mut(focus_entity)
+            .insert(GlobalTransform::from_xyz(COLLIDER_DISTANCE / 2.0, 0.0, 0.0));
+
+        app.update();
+
+        assert!(app.world().get::<PendingCollider>(scene_entity).is_none());
+        let collider = app.world().get::<Collider>(scene_entity).unwrap();
+        let aabb = collider.aabb(Vec3::ZERO, Rotation::default());
+        assert!(aabb.size().abs_diff_eq(Vec3::ONE, 0.001));
+    }
+
+    /// Compares collider generation time for a large world with and without lazy colliders.
+    ///
+    /// Run with `cargo test --release -- --ignored --nocapture startup_time`.
+    #[test]
+    #[ignore = "measurement"]
+    fn startup_time() {
+        let eager = measure_generation(false);
+        let lazy = measure_generation(true);
+        println!("eager: {eager:.2?}, lazy: {lazy:.2?}");
+        assert!(lazy < eager);
+    }
+
+    /// Returns the time of the first update for a grid of scenes spread over 1 km
+    /// with a focus in the center.
+    fn measure_generation(lazy_colliders: bool) -> Duration {
+        const GRID_SIZE: usize = 40;
+        const SPACING: f32 = 25.0;
+
+        let mut app = App::new();
+        app.init_resource::<Assets<Mesh>>().add_systems(
+            Update,
+            SceneColliderConstructorPlugin::init_pending
+                .run_if(any_with_component::<PendingCollider>),
+        );
+
+        let mut settings = Settings::default();
+        settings.developer.lazy_colliders = lazy_colliders;
+        app.insert_resource(settings);
+
+        let mesh = app
+            .world_mut()
+            .resource_mut::<Assets<Mesh>>()
+            .add(Sphere::default().mesh().ico(4).unwrap());
+        let offset = GRID_SIZE as f32 * SPACING / 2.0;
+        for x in 0..GRID_SIZE {
+            for z in 0..GRID_SIZE {
+                app.world_mut()
+                    .spawn((
+                        SceneColliderConstructor::ConvexHull,
+                        PendingCollider,
+                        GlobalTransform::from_xyz(
+                            x as f32 * SPACING - offset,
+                            0.0,
+                            z as f32 * SPACING - offset,
+                        ),
+                    ))
+                    .with_child((Transform::default(), Mesh3d(mesh.clone())));
+            }
+        }
+        app.world_mut()
+            .spawn((ColliderFocus, GlobalTransform::default()));
+
+        let start = Instant::now();
+        app.update();
+        start.elapsed()
+    }
+}
@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use bevy_replicon::prelude::*;
 
 /// Like [`in_state`], but checks for multiple states.
 pub fn in_any_state<S: States, const SIZE: usize>(
@@ -9,3 +10,8 @@ pub fn in_any_state<S: States, const SIZE: usize>(
         None => false,
     }
 }
+
+/// Returns `true` when hosting or connected to a server.
+pub fn in_multiplayer(server: Option<Res<RepliconServer>>, client: Res<RepliconClient>) -> bool {
+    server.is_some_and(|server| server.is_running()) || client.is_connected()
+}
@@ -1,10 +1,17 @@
+pub mod localization;
+
 use bevy::prelude::*;
 
+use crate::build_info::BuildInfo;
+use localization::LocalizationPlugin;
+
 pub(super) struct CorePlugin;
 
 impl Plugin for CorePlugin {
     fn build(&self, app: &mut App) {
-        app.init_state::<GameState>()
+        app.add_plugins(LocalizationPlugin)
+            .init_resource::<BuildInfo>()
+            .init_state::<GameState>()
             .enable_state_scoped_entities::<GameState>();
     }
 }
@@ -0,0 +1,280 @@
+use std::{env, fs, path::Path, sync::Mutex};
+
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+use serde::Deserialize;
+
+use crate::{asset::ron_loader::RonAssetLoader, settings::Settings};
+
+pub(super) struct LocalizationPlugin;
+
+impl Plugin for LocalizationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Locale>()
+            .register_asset_loader(RonAssetLoader::<Locale>::new(LOCALE_EXTENSION))
+            .init_resource::<Localization>()
+            .add_systems(
+                PreUpdate,
+                Self::reload_strings.run_if(resource_exists::<Settings>),
+            )
+            .add_systems(PostUpdate, Self::update_texts);
+    }
+}
+
+impl LocalizationPlugin {
+    /// Switches to the selected locale on language change and copies its strings when the file is (re)loaded.
+    fn reload_strings(
+        mut asset_events: EventReader<AssetEvent<Locale>>,
+        settings: Res<Settings>,
+        locales: Res<Assets<Locale>>,
+        mut localization: ResMut<Localization>,
+    ) {
+        if settings.is_changed() && localization.current() != settings.language.locale {
+            localization.select(&settings.language.locale, &locales);
+        }
+
+        let Some(handle) = localization.handle(&localization.current) else {
+            return;
+        };
+
+        let mut modified = false;
+        for &event in asset_events.read() {
+            if let AssetEvent::Added { id } | AssetEvent::Modified { id } = event {
+                modified |= id == handle.id();
+            }
+        }
+        if modified {
+            localization.load_strings(&locales);
+        }
+    }
+
+    fn update_texts(
+        localization: Res<Localization>,
+        mut texts: Query<(Ref<LocalizedText>, &mut Text)>,
+    ) {
+        for (localized_text, mut text) in &mut texts {
+            if localization.is_changed() || localized_text.is_changed() {
+                text.0 = localized_text.format(&localization);
+            }
+        }
+    }
+}
+
+const LOCALE_EXTENSION: &str = "locale.ron";
+
+/// Translated UI strings for a single language.
+#[derive(Asset, TypePath, Deserialize)]
+pub struct Locale {
+    /// Language name written in the language itself.
+    pub name: String,
+    pub strings: HashMap<String, String>,
+}
+
+/// Strings of the language selected in [`Settings`].
+///
+/// Locales are loaded from all files inside `base/locales`, the file name
+/// without extension is used as the locale ID.
+#[derive(Resource)]
+pub struct Localization {
+    locales: Vec<(String, Handle<Locale>)>,
+    /// ID of the locale whose strings are used.
+    ///
+    /// Matches the settings, except while the language is previewed in the settings menu.
+    current: String,
+    strings: HashMap<String, String>,
+    /// Keys that were already reported as missing.
+    missing: Mutex<HashSet<String>>,
+}
+
+/// Directory with locale files, relative to the assets root.
+const LOCALES_DIR: &str = "base/locales";
+
+impl Localization {
+    /// Returns the string for the key in the current language.
+    ///
+    /// Falls back to the key itself if the string is missing.
+    pub fn tr(&self, key: &str) -> String {
+        if let Some(string) = self.strings.get(key) {
+            return string.clone();
+        }
+
+        if self.missing.lock().unwrap().insert(key.to_string()) {
+            warn!("missing translation for `{key}`");
+        }
+        key.to_string()
+    }
+
+    /// Returns IDs and names of all available locales.
+    ///
+    /// Uses ID as the name for locales that aren't loaded yet.
+    pub fn locales<'a>(
+        &'a self,
+        assets: &'a Assets<Locale>,
+    ) -> impl Iterator<Item = (&'a str, &'a str)> {
+        self.locales.iter().map(|(id, handle)| {
+            let name = assets.get(handle).map(|locale| locale.name.as_str());
+            (id.as_str(), name.unwrap_or(id))
+        })
+    }
+
+    /// Switches strings to the locale.
+    ///
+    /// Keeps the current strings if the locale doesn't exist.
+    pub fn select(&mut self, locale_id: &str, locales: &Assets<Locale>) {
+        if self.handle(locale_id).is_none() {
+            error!("locale `{locale_id}` doesn't exist");
+            return;
+        }
+
+        self.current = locale_id.to_string();
+        self.load_strings(locales);
+    }
+
+    /// Returns ID of the locale whose strings are used.
+    pub fn current(&self) -> &str {
+        &self.current
+    }
+
+    /// Returns IDs of all locale assets.
+    pub(crate) fn asset_ids(&self) -> impl Iterator<Item = UntypedAssetId> + '_ {
+        self.locales.iter().map(|(_, handle)| handle.id().untyped())
+    }
+
+    fn load_strings(&mut self, locales: &Assets<Locale>) {
+        let Some(locale) = self
+            .handle(&self.current)
+            .and_then(|handle| locales.get(handle))
+        else {
+            return;
+        };
+
+        info!("loading strings for `{}`", self.current);
+        self.strings = locale.strings.clone();
+        self.missing.get_mut().unwrap().clear();
+    }
+
+    fn handle(&self, locale_id: &str) -> Option<&Handle<Locale>> {
+        self.locales
+            .iter()
+            .find(|(id, _)| id == locale_id)
+            .map(|(_, handle)| handle)
+    }
+}
+
+impl FromWorld for Localization {
+    fn from_world(world: &mut World) -> Self {
+        let assets_dir =
+            Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap_or_default()).join("assets");
+        let locales_dir = assets_dir.join(LOCALES_DIR);
+
+        let mut locale_ids: Vec<_> = fs::read_dir(&locales_dir)
+            .map_err(|e| error!("unable to read {locales_dir:?}: {e}"))
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let file_name = entry.file_name().into_string().ok()?;
+                let id = file_name
+                    .strip_suffix(LOCALE_EXTENSION)?
+                    .strip_suffix('.')?;
+                Some(id.to_string())
+            })
+            .collect();
+        locale_ids.sort();
+
+        let asset_server = world.resource::<AssetServer>();
+        let locales = locale_ids
+            .into_iter()
+            .map(|id| {
+                let path = format!("{LOCALES_DIR}/{id}.{LOCALE_EXTENSION}");
+                debug!("loading locale '{path}'");
+                (id, asset_server.load(path))
+            })
+            .collect();
+
+        Self {
+            locales,
+            current: Default::default(),
+            strings: Default::default(),
+            missing: Default::default(),
+        }
+    }
+}
+
+/// Text that is taken from [`Localization`] by key.
+///
+/// Updated automatically when the language changes.
+#[derive(Component, PartialEq)]
+#[require(Text)]
+pub struct LocalizedText {
+    key: &'static str,
+    args: Vec<(&'static str, String)>,
+}
+
+impl LocalizedText {
+    pub fn new(key: &'static str) -> Self {
+        Self {
+            key,
+            args: Default::default(),
+        }
+    }
+
+    /// Replaces `{name}` in the translated string with the value.
+    pub fn with_arg(mut self, name: &'static str, value: impl ToString) -> Self {
+        self.args.push((name, value.to_string()));
+        self
+    }
+
+    fn format(&self, localization: &Localization) -> String {
+        let mut text = localization.tr(self.key);
+        for (name, value) in &self.args {
+            text = text.replace(&format!("{{{name}}}"), value);
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use bevy::scene::ron;
+
+    use super::*;
+
+    #[test]
+    fn deserialization() -> Result<()> {
+        let dir = Path::new("../app/assets").join(LOCALES_DIR);
+        let english: Locale = ron::from_str(&fs::read_to_string(dir.join("en.locale.ron"))?)?;
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            let locale: Locale = ron::from_str(&fs::read_to_string(&path)?)?;
+            for key in english.strings.keys() {
+                assert!(
+                    locale.strings.contains_key(key),
+                    "{path:?} should contain `{key}`"
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn fallback() {
+        let localization = Localization {
+            locales: Default::default(),
+            current: "en".to_string(),
+            strings: [("greeting".to_string(), "Hello, {name}!".to_string())]
+                .into_iter()
+                .collect(),
+            missing: Default::default(),
+        };
+
+        let text = LocalizedText::new("greeting").with_arg("name", "World");
+        assert_eq!(text.format(&localization), "Hello, World!");
+        assert_eq!(localization.tr("unknown"), "unknown");
+        assert!(localization.missing.lock().unwrap().contains("unknown"));
+    }
+}
@@ -0,0 +1,157 @@
+use std::{
+    backtrace::Backtrace,
+    fmt::Write,
+    fs,
+    panic::{self, PanicHookInfo},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+use anyhow::{Context, Result};
+use bevy::prelude::*;
+
+use crate::{build_info::BuildInfo, file_log, game_paths::GamePaths, game_world::WorldName};
+
+/// Writes a report file on panic and shows it on the next launch.
+pub(super) struct CrashReportPlugin;
+
+impl Plugin for CrashReportPlugin {
+    fn build(&self, app: &mut App) {
+        let crashes_dir = app.world().resource::<GamePaths>().crashes.clone();
+        match take_pending(&crashes_dir) {
+            Ok(Some(report_path)) => {
+                info!("found crash report from the previous launch: {report_path:?}");
+                app.insert_resource(PendingCrashReport(report_path));
+            }
+            Ok(None) => (),
+            Err(e) => error!("unable to read pending crash report: {e:#}"),
+        }
+
+        let context = CrashContext::default();
+        install_hook(crashes_dir, context.clone());
+
+        app.insert_resource(context).add_systems(
+            Update,
+            (
+                Self::update_world_name.run_if(resource_exists_and_changed::<WorldName>),
+                Self::clear_world_name.run_if(resource_removed::<WorldName>),
+            ),
+        );
+    }
+}
+
+impl CrashReportPlugin {
+    fn update_world_name(world_name: Res<WorldName>, context: Res<CrashContext>) {
+        if let Ok(mut name) = context.world_name.lock() {
+            *name = Some(world_name.0.clone());
+        }
+    }
+
+    fn clear_world_name(context: Res<CrashContext>) {
+        if let Ok(mut name) = context.world_name.lock() {
+            *name = None;
+        }
+    }
+}
+
+/// Name of the file that contains the name of the report that wasn't shown yet.
+const PENDING_NAME: &str = "pending";
+
+fn install_hook(crashes_dir: PathBuf, context: CrashContext) {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let world_name = context
+            .world_name
+            .try_lock()
+            .ok()
+            .and_then(|name| name.clone());
+        let report = format_report(info, world_name.as_deref());
+        match write_report(&crashes_dir, &report) {
+            Ok(path) => eprintln!("crash report written to {path:?}"),
+            Err(e) => eprintln!("unable to write crash report: {e:#}"),
+        }
+
+        default_hook(info);
+    }));
+}
+
+fn format_report(info: &PanicHookInfo, world_name: Option<&str>) -> String {
+    let mut report = String::new();
+    writeln!(report, "Project Harmonia crash report").unwrap();
+    writeln!(report, "Version: {}", BuildInfo::CURRENT).unwrap();
+    writeln!(report, "World: {}", world_name.unwrap_or("none")).unwrap();
+    writeln!(report).unwrap();
+    writeln!(report, "{info}").unwrap();
+    writeln!(report).unwrap();
+    writeln!(report, "Backtrace:\n{}", Backtrace::force_capture()).unwrap();
+    writeln!(report, "Recent logs:").unwrap();
+    for message in file_log::recent_logs() {
+        report.push_str(&message);
+    }
+
+    report
+}
+
+/// Writes the report into a new file and marks it as pending.
+fn write_report(crashes_dir: &Path, report: &str) -> Result<PathBuf> {
+    let timestamp = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH)?;
+    let file_name = format!("crash-{}.txt", timestamp.as_secs());
+    let path = crashes_dir.join(&file_name);
+    fs::write(&path, report).with_context(|| format!("unable to write {path:?}"))?;
+
+    let pending_path = crashes_dir.join(PENDING_NAME);
+    fs::write(&pending_path, file_name)
+        .with_context(|| format!("unable to write {pending_path:?}"))?;
+
+    Ok(path)
+}
+
+/// Returns the report that wasn't shown yet and clears the mark.
+fn take_pending(crashes_dir: &Path) -> Result<Option<PathBuf>> {
+    let pending_path = crashes_dir.join(PENDING_NAME);
+    if !pending_path.exists() {
+        return Ok(None);
+    }
+
+    let file_name = fs::read_to_string(&pending_path)
+        .with_context(|| format!("unable to read {pending_path:?}"))?;
+    fs::remove_file(&pending_path).with_context(|| format!("unable to remove {pending_path:?}"))?;
+
+    Ok(Some(crashes_dir.join(file_name.trim())))
+}
+
+/// State that the panic hook can't read from the world.
+#[derive(Resource, Default, Clone)]
+struct CrashContext {
+    world_name: Arc<Mutex<Option<String>>>,
+}
+
+/// Report written during the previous launch.
+///
+/// Inserted on startup if the game crashed last time.
+#[derive(Resource, Deref)]
+pub struct PendingCrashReport(pub PathBuf);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_paths::TempDir;
+
+    #[test]
+    fn pending_report() -> Result<()> {
+        let dir = TempDir::new("pending_report");
+
+        assert!(take_pending(&dir)?.is_none());
+
+        let path = write_report(&dir, "report")?;
+        assert_eq!(fs::read_to_string(&path)?, "report");
+        assert_eq!(take_pending(&dir)?, Some(path));
+        assert!(
+            take_pending(&dir)?.is_none(),
+            "report should be shown only once"
+        );
+
+        Ok(())
+    }
+}
@@ -11,4 +11,4 @@ pub fn error_message(In(result): In<Result<()>>, mut commands: Commands) {
 
 /// Contains error that was reported using [`error_message`] adapter.
 #[derive(Event, Deref)]
-pub struct ErrorMessage(String);
+pub struct ErrorMessage(pub(crate) String);
@@ -0,0 +1,181 @@
+use std::{
+    collections::VecDeque,
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Mutex,
+    },
+    thread,
+};
+
+use bevy::{
+    log::{
+        tracing_subscriber::{fmt, Layer},
+        BoxedLayer,
+    },
+    prelude::*,
+};
+
+use crate::game_paths::GamePaths;
+
+/// Maximum size of a log file before it's rotated.
+const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Number of rotated files to keep in addition to the current one.
+const RETAINED_FILES: usize = 5;
+
+/// Number of recent messages kept in memory for crash reports.
+const RECENT_MESSAGES: usize = 100;
+
+/// Latest messages, available even if the writing thread is behind.
+static RECENT_LOGS: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+const LOG_NAME: &str = "game";
+const LOG_EXTENSION: &str = "log";
+
+/// Creates a layer for `LogPlugin::custom_layer` that writes logs into [`GamePaths::logs`].
+///
+/// Expects [`GamePaths`] to be inserted before the plugin.
+/// Files are written from a separate thread, so slow disks don't block the game.
+pub fn file_layer(app: &mut App) -> Option<BoxedLayer> {
+    let dir = app.world().resource::<GamePaths>().logs.clone();
+    let path = log_path(&dir, 0);
+    if let Err(e) = rotate(&dir) {
+        // The logger isn't initialized yet.
+        eprintln!("unable to rotate logs in {dir:?}: {e}");
+    }
+    let file = match File::create(&path) {
+        Ok(file) => file,
+        Err(e) => {
+            eprintln!("unable to create {path:?}: {e}");
+            return None;
+        }
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    thread::Builder::new()
+        .name("file log".to_string())
+        .spawn(move || write_logs(receiver, file, &dir))
+        .expect("log thread should be spawnable");
+
+    let layer = fmt::layer()
+        .with_ansi(false)
+        .with_writer(move || LogSender(sender.clone()));
+
+    Some(layer.boxed())
+}
+
+/// Returns the latest log messages.
+///
+/// Doesn't block, so it's safe to call from a panic hook.
+/// Returns nothing if the messages are being written at the moment.
+pub fn recent_logs() -> Vec<String> {
+    RECENT_LOGS
+        .try_lock()
+        .map(|logs| logs.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+fn write_logs(receiver: Receiver<Vec<u8>>, mut file: File, dir: &Path) {
+    let mut size = 0;
+    for message in receiver {
+        if size + message.len() as u64 > MAX_FILE_SIZE {
+            match rotate(dir).and_then(|()| File::create(log_path(dir, 0))) {
+                Ok(new_file) => {
+                    file = new_file;
+                    size = 0;
+                }
+                Err(e) => eprintln!("unable to rotate logs in {dir:?}: {e}"),
+            }
+        }
+
+        if let Err(e) = file.write_all(&message) {
+            eprintln!("unable to write log: {e}");
+        }
+        size += message.len() as u64;
+    }
+}
+
+/// Shifts existing log files by one index, removing the oldest.
+fn rotate(dir: &Path) -> io::Result<()> {
+    let oldest_path = log_path(dir, RETAINED_FILES);
+    if oldest_path.exists() {
+        fs::remove_file(oldest_path)?;
+    }
+
+    for index in (0..RETAINED_FILES).rev() {
+        let path = log_path(dir, index);
+        if path.exists() {
+            fs::rename(path, log_path(dir, index + 1))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns path to the log file.
+///
+/// Index 0 is the current file, higher indices are older files.
+fn log_path(dir: &Path, index: usize) -> PathBuf {
+    let name = if index == 0 {
+        format!("{LOG_NAME}.{LOG_EXTENSION}")
+    } else {
+        format!("{LOG_NAME}.{index}.{LOG_EXTENSION}")
+    };
+    dir.join(name)
+}
+
+/// Forwards formatted messages to the writing thread.
+struct LogSender(Sender<Vec<u8>>);
+
+impl Write for LogSender {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if let Ok(mut logs) = RECENT_LOGS.lock() {
+            if logs.len() == RECENT_MESSAGES {
+                logs.pop_front();
+            }
+            logs.push_back(String::from_utf8_lossy(buf).into_owned());
+        }
+
+        // Ignore errors since the thread can't stop while the sender exists.
+        let _ = self.0.send(buf.to_vec());
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_paths::TempDir;
+
+    #[test]
+    fn rotation() -> io::Result<()> {
+        let dir = TempDir::new("log_rotation");
+
+        for index in 0..=RETAINED_FILES {
+            fs::write(log_path(&dir, 0), index.to_string())?;
+            rotate(&dir)?;
+        }
+
+        assert!(!log_path(&dir, 0).exists());
+        assert_eq!(
+            fs::read_to_string(log_path(&dir, 1))?,
+            RETAINED_FILES.to_string(),
+            "newest file should have the lowest index"
+        );
+        assert_eq!(
+            fs::read_to_string(log_path(&dir, RETAINED_FILES))?,
+            "1",
+            "the oldest file should be removed"
+        );
+        assert!(!log_path(&dir, RETAINED_FILES + 1).exists());
+
+        Ok(())
+    }
+}
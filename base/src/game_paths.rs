@@ -1,11 +1,12 @@
 use std::{
+    ffi::OsString,
     fs::{self, DirEntry},
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
 use anyhow::{Context, Result};
 use app_dirs2::{AppDataType, AppInfo};
-use bevy::prelude::*;
+use bevy::{asset::AssetPath, prelude::*};
 
 /// Initializes [`GamePaths`] resource.
 pub(super) struct GamePathsPlugin;
@@ -17,12 +18,29 @@ impl Plugin for GamePathsPlugin {
 }
 
 const SCENE_EXTENSION: &str = "scn";
+const AUTOSAVE_EXTENSION: &str = "autosave";
+/// Number of autosaves kept for each world.
+const AUTOSAVE_SLOTS: usize = 3;
+const THUMBNAIL_EXTENSION: &str = "png";
+const REPLAY_EXTENSION: &str = "replay";
+/// Extension appended to the previous version of a file when it's replaced.
+pub(crate) const BACKUP_EXTENSION: &str = "bak";
 
 /// Paths with game files, such as settings and savegames.
 #[derive(Resource)]
 pub struct GamePaths {
     pub settings: PathBuf,
+    /// File with the persistent player ID used for connections.
+    pub player_id: PathBuf,
     pub worlds: PathBuf,
+    /// Directory with rotated log files.
+    pub logs: PathBuf,
+    /// Directory with reports written on panic.
+    pub crashes: PathBuf,
+    /// Cache for pre-rendered object previews.
+    pub thumbnails: PathBuf,
+    /// Directory with recorded sessions.
+    pub replays: PathBuf,
 }
 
 impl GamePaths {
@@ -32,6 +50,72 @@ impl GamePaths {
         path
     }
 
+    /// Returns paths to all autosave slots of a world.
+    ///
+    /// Uses a different extension, so autosaves aren't listed as worlds.
+    pub fn autosave_paths(&self, name: &str) -> impl Iterator<Item = PathBuf> {
+        let path = self.worlds.join(name);
+        (0..AUTOSAVE_SLOTS).map(move |slot| {
+            let mut path = path.clone();
+            path.set_extension(format!("{AUTOSAVE_EXTENSION}.{slot}"));
+            path
+        })
+    }
+
+    /// Returns path to the most recently written autosave of a world if exists.
+    pub fn latest_autosave_path(&self, name: &str) -> Option<PathBuf> {
+        self.autosave_paths(name)
+            .filter_map(|path| {
+                let modified = path.metadata().and_then(|meta| meta.modified()).ok()?;
+                Some((modified, path))
+            })
+            .max_by_key(|&(modified, _)| modified)
+            .map(|(_, path)| path)
+    }
+
+    /// Returns path for the next autosave of a world.
+    ///
+    /// Picks an unused slot or overwrites the oldest one,
+    /// so a damaged autosave doesn't replace the only copy.
+    pub fn next_autosave_path(&self, name: &str) -> PathBuf {
+        self.autosave_paths(name)
+            .min_by_key(|path| path.metadata().and_then(|meta| meta.modified()).ok())
+            .expect("there should be at least one autosave slot")
+    }
+
+    /// Removes the world file, its autosaves and their backups.
+    pub fn delete_world(&self, name: &str) -> Result<()> {
+        let world_path = self.world_path(name);
+        fs::remove_file(&world_path).with_context(|| format!("unable to remove {world_path:?}"))?;
+
+        let backups = [world_path.clone()]
+            .into_iter()
+            .chain(self.autosave_paths(name))
+            .map(|path| append_extension(&path, BACKUP_EXTENSION));
+        for path in self.autosave_paths(name).chain(backups) {
+            if path.exists() {
+                fs::remove_file(&path).with_context(|| format!("unable to remove {path:?}"))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn replay_path(&self, name: &str) -> PathBuf {
+        let mut path = self.replays.join(name);
+        path.set_extension(REPLAY_EXTENSION);
+        path
+    }
+
+    /// Returns path to the cached preview for a manifest.
+    pub fn thumbnail_path(&self, manifest_path: &AssetPath) -> PathBuf {
+        let mut path = self.thumbnails.join(manifest_path.path());
+        // Manifests have double extensions.
+        path.set_extension("");
+        path.set_extension(THUMBNAIL_EXTENSION);
+        path
+    }
+
     pub fn get_world_names(&self) -> Result<Vec<String>> {
         let entries = self
             .worlds
@@ -64,15 +148,45 @@ impl Default for GamePaths {
         settings.push(app_info.name);
         settings.set_extension("ron");
 
+        let player_id = config_dir.join("player_id");
+
+        let logs = config_dir.join("logs");
+        fs::create_dir_all(&logs).unwrap_or_else(|e| panic!("{logs:?} should be writable: {e}"));
+
+        let crashes = config_dir.join("crashes");
+        fs::create_dir_all(&crashes)
+            .unwrap_or_else(|e| panic!("{crashes:?} should be writable: {e}"));
+
+        let replays = config_dir.join("replays");
+
         let mut worlds = config_dir;
         worlds.push("worlds");
         fs::create_dir_all(&worlds)
             .unwrap_or_else(|e| panic!("{worlds:?} should be writable: {e}"));
 
-        Self { settings, worlds }
+        let thumbnails = app_dirs2::app_dir(AppDataType::UserCache, &app_info, "thumbnails")
+            .expect("cache directory should be accessible");
+
+        Self {
+            settings,
+            player_id,
+            worlds,
+            logs,
+            crashes,
+            thumbnails,
+            replays,
+        }
     }
 }
 
+/// Returns the path with an additional extension, keeping the existing one.
+pub(crate) fn append_extension(path: &Path, extension: &str) -> PathBuf {
+    let mut path = OsString::from(path);
+    path.push(".");
+    path.push(extension);
+    path.into()
+}
+
 fn world_name(entry: &DirEntry) -> Option<String> {
     let file_type = entry.file_type().ok()?;
     if !file_type.is_file() {
@@ -87,3 +201,84 @@ fn world_name(entry: &DirEntry) -> Option<String> {
 
     path.file_stem()?.to_str().map(|stem| stem.to_string())
 }
+
+/// Directory for test files, removed on drop.
+#[cfg(test)]
+pub(crate) struct TempDir(PathBuf);
+
+#[cfg(test)]
+impl TempDir {
+    /// Creates an empty directory unique to the test and the process.
+    pub(crate) fn new(name: &str) -> Self {
+        let path =
+            std::env::temp_dir().join(format!("project_harmonia_{name}_{}", std::process::id()));
+        // Could be left from a previous failed run.
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap_or_else(|e| panic!("{path:?} should be writable: {e}"));
+
+        Self(path)
+    }
+
+    /// Returns game paths inside the directory.
+    pub(crate) fn game_paths(&self) -> GamePaths {
+        GamePaths {
+            settings: self.join("settings.ron"),
+            player_id: self.join("player_id"),
+            worlds: self.join("worlds"),
+            logs: self.join("logs"),
+            crashes: self.join("crashes"),
+            thumbnails: self.join("thumbnails"),
+            replays: self.join("replays"),
+        }
+    }
+}
+
+#[cfg(test)]
+impl std::ops::Deref for TempDir {
+    type Target = std::path::Path;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delete_world() -> Result<()> {
+        let dir = TempDir::new("delete_world");
+        let game_paths = dir.game_paths();
+        fs::create_dir_all(&game_paths.worlds)?;
+
+        let world_path = game_paths.world_path("World");
+        let mut paths = vec![world_path.clone()];
+        paths.extend(game_paths.autosave_paths("World"));
+        let backups: Vec<_> = paths
+            .iter()
+            .map(|path| append_extension(path, BACKUP_EXTENSION))
+            .collect();
+        paths.extend(backups);
+        for path in &paths {
+            fs::write(path, "")?;
+        }
+        let other_path = game_paths.world_path("Other");
+        fs::write(&other_path, "")?;
+
+        game_paths.delete_world("World")?;
+        for path in &paths {
+            assert!(!path.exists(), "{path:?} should be removed");
+        }
+        assert!(other_path.exists(), "other worlds should be kept");
+
+        Ok(())
+    }
+}
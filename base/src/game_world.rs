@@ -1,34 +1,69 @@
 pub mod actor;
+mod autosave;
 pub mod city;
+pub mod clock;
 pub mod commands_history;
+pub mod difficulty;
 pub mod family;
+pub mod game_rng;
 pub mod highlighting;
+pub mod minimap;
+pub mod moderation;
 pub mod navigation;
 pub mod object;
 mod player_camera;
+mod remote_cursor;
+pub mod replay;
 mod segment;
+pub mod spectator;
 
-use std::fs;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+};
 
-use anyhow::{Context, Result};
+use anyhow::{ensure, Context, Result};
 use avian3d::prelude::*;
 use bevy::{
     prelude::*,
+    reflect::TypeRegistry,
     scene::{ron, serde::SceneDeserializer},
+    tasks::{block_on, AsyncComputeTaskPool, Task},
+    utils::Instant,
 };
 use bevy_replicon::prelude::*;
-use serde::de::DeserializeSeed;
+#[cfg(feature = "networking")]
+use bevy_replicon_renet::{
+    netcode::{NetcodeClientTransport, NetcodeServerTransport},
+    renet::{RenetClient, RenetServer},
+};
+use bincode::{DefaultOptions, Options};
+use serde::{de::DeserializeSeed, Deserialize, Serialize};
 
-use super::{core::GameState, error_message::error_message, game_paths::GamePaths};
-use actor::{Actor, ActorPlugin};
+use super::{
+    core::GameState,
+    error_message::error_message,
+    game_paths::{self, GamePaths, BACKUP_EXTENSION},
+};
+use actor::{job::WorkedSecs, Actor, ActorPlugin};
+use autosave::AutosavePlugin;
 use city::CityPlugin;
+use clock::{ClockPlugin, WorldClock};
 use commands_history::CommandHistoryPlugin;
+use difficulty::{Difficulty, DifficultyPlugin};
 use family::FamilyPlugin;
+use game_rng::{GameRng, GameRngPlugin, WorldSeed};
 use highlighting::HighlightingPlugin;
+use minimap::MinimapPlugin;
+use moderation::{BannedPlayers, ModerationPlugin};
 use navigation::NavigationPlugin;
 use object::ObjectPlugin;
 use player_camera::PlayerCameraPlugin;
+use remote_cursor::RemoteCursorPlugin;
+use replay::ReplayPlugin;
 use segment::SegmentPlugin;
+use spectator::{Spectator, SpectatorPlugin};
 
 pub(super) struct GameWorldPlugin;
 
@@ -36,64 +71,158 @@ impl Plugin for GameWorldPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins((
             ActorPlugin,
+            AutosavePlugin,
             CityPlugin,
+            ClockPlugin,
             SegmentPlugin,
+            DifficultyPlugin,
             FamilyPlugin,
+            GameRngPlugin,
             HighlightingPlugin,
+            ModerationPlugin,
             NavigationPlugin,
             ObjectPlugin,
+        ))
+        .add_plugins((
+            MinimapPlugin,
             PlayerCameraPlugin,
+            RemoteCursorPlugin,
+            ReplayPlugin,
             CommandHistoryPlugin,
+            SpectatorPlugin,
         ))
+        .register_type::<WorldDescription>()
+        .init_resource::<UnsavedChanges>()
+        .init_resource::<SaveState>()
         .add_sub_state::<WorldState>()
         .enable_state_scoped_entities::<WorldState>()
         .add_observer(Self::save.pipe(error_message))
         .add_observer(Self::load.pipe(error_message))
+        .add_observer(Self::load_autosave.pipe(error_message))
+        .add_observer(Self::reset_unsaved)
         .add_systems(
             PreUpdate,
             Self::start_game
                 .after(ClientSet::Receive)
                 .run_if(client_just_connected),
         )
+        .add_systems(
+            Update,
+            Self::finish_save
+                .pipe(error_message)
+                .run_if(resource_exists::<PendingSave>),
+        )
+        .add_systems(
+            Last,
+            Self::wait_for_save
+                .pipe(error_message)
+                .run_if(on_event::<AppExit>)
+                .run_if(resource_exists::<PendingSave>),
+        )
+        .add_systems(OnEnter(GameState::InGame), Self::reset_unsaved_on_enter)
+        .add_systems(PostUpdate, Self::track_changes)
         .add_systems(OnExit(GameState::InGame), Self::cleanup);
     }
 }
 
 impl GameWorldPlugin {
     /// Saves world to disk with the name from [`WorldName`] resource.
+    ///
+    /// The file is written in the background, see [`start_saving`].
     fn save(
         _trigger: Trigger<GameSave>,
+        mut commands: Commands,
         world: &World,
         world_name: Res<WorldName>,
         game_paths: Res<GamePaths>,
-        registry: Res<AppTypeRegistry>,
-        actors: Query<Entity, With<Actor>>,
+        spectator: Option<Res<Spectator>>,
     ) -> Result<()> {
+        ensure!(spectator.is_none(), "spectators can't save the world");
+
         let world_path = game_paths.world_path(&world_name.0);
         info!("saving world to {world_path:?}");
 
-        fs::create_dir_all(&game_paths.worlds)
-            .with_context(|| format!("unable to create {world_path:?}"))?;
+        start_saving(
+            &mut commands,
+            world,
+            game_paths.worlds.clone(),
+            world_path,
+            SaveKind::Manual,
+        )
+    }
+
+    /// Reports the result once the save task completes.
+    fn finish_save(
+        mut commands: Commands,
+        mut pending_save: ResMut<PendingSave>,
+        save_state: Res<SaveState>,
+    ) -> Result<()> {
+        if !pending_save.task.is_finished() {
+            return Ok(());
+        }
+
+        commands.remove_resource::<PendingSave>();
+        save_state.finish();
+        block_on(&mut pending_save.task)?;
 
-        // Extract components that we don't replicate, but serialize.
-        let mut scene = DynamicSceneBuilder::from_world(world)
-            .deny_all()
-            .allow_component::<Transform>()
-            .extract_entities(actors.iter())
-            .build();
+        info!("finished saving in {:.2?}", pending_save.start.elapsed());
+        if pending_save.kind == SaveKind::Manual {
+            commands.trigger(GameSaved);
+        }
 
-        // Extract all replicated components that are reflected.
-        let registry = registry.read();
-        bevy_replicon::scene::replicate_into(&mut scene, world);
-        let bytes = scene
-            .serialize(&registry)
-            .expect("game world should be serialized");
+        Ok(())
+    }
+
+    fn reset_unsaved(_trigger: Trigger<GameSaved>, mut unsaved: ResMut<UnsavedChanges>) {
+        unsaved.0 = false;
+    }
 
-        fs::write(&world_path, bytes)
-            .with_context(|| format!("unable to save game to {world_path:?}"))
+    fn reset_unsaved_on_enter(mut unsaved: ResMut<UnsavedChanges>) {
+        unsaved.0 = false;
+    }
+
+    /// Marks the world as changed when time passes or replicated entities
+    /// are spawned, moved or removed.
+    ///
+    /// Runs in all states to consume changes from loading before entering the game.
+    fn track_changes(
+        mut removed: RemovedComponents<Replicated>,
+        mut unsaved: ResMut<UnsavedChanges>,
+        game_state: Res<State<GameState>>,
+        changed: Query<
+            (),
+            (
+                With<Replicated>,
+                Or<(Added<Replicated>, Changed<Transform>, Changed<WorldClock>)>,
+            ),
+        >,
+    ) {
+        let removed = removed.read().count() != 0;
+        if unsaved.0 || *game_state != GameState::InGame {
+            return;
+        }
+
+        if removed || !changed.is_empty() {
+            debug!("marking world as unsaved");
+            unsaved.0 = true;
+        }
+    }
+
+    /// Blocks until the pending save is written since tasks are cancelled on exit.
+    fn wait_for_save(
+        mut commands: Commands,
+        mut pending_save: ResMut<PendingSave>,
+        save_state: Res<SaveState>,
+    ) -> Result<()> {
+        info!("waiting for the save to finish before exiting");
+        commands.remove_resource::<PendingSave>();
+        save_state.finish();
+        block_on(&mut pending_save.task)
     }
 
     /// Loads world from disk with the name from [`WorldName`] resource.
+    ///
+    /// If the file is damaged and an autosave exists, triggers [`CorruptWorld`] instead of failing.
     fn load(
         _trigger: Trigger<GameLoad>,
         mut commands: Commands,
@@ -108,14 +237,41 @@ impl GameWorldPlugin {
 
         let bytes =
             fs::read(&world_path).with_context(|| format!("unable to load {world_path:?}"))?;
-        let mut deserializer = ron::Deserializer::from_bytes(&bytes)
-            .with_context(|| format!("unable to parse {world_path:?}"))?;
-        let scene_deserializer = SceneDeserializer {
-            type_registry: &registry.read(),
+        let scene = match deserialize_world(&bytes, &registry.read()) {
+            Ok(scene) => scene,
+            Err(e) if game_paths.latest_autosave_path(&world_name.0).is_some() => {
+                error!("unable to deserialize {world_path:?}: {e:#}");
+                commands.trigger(CorruptWorld);
+                return Ok(());
+            }
+            Err(e) => return Err(e.context(format!("unable to deserialize {world_path:?}"))),
         };
-        let scene = scene_deserializer
-            .deserialize(&mut deserializer)
-            .with_context(|| format!("unable to deserialize {world_path:?}"))?;
+
+        scene_spawner.spawn_dynamic(scenes.add(scene));
+        commands.set_state(GameState::InGame);
+
+        Ok(())
+    }
+
+    /// Loads the latest autosave of the world with the name from [`WorldName`] resource.
+    fn load_autosave(
+        _trigger: Trigger<AutosaveLoad>,
+        mut commands: Commands,
+        mut scene_spawner: ResMut<SceneSpawner>,
+        mut scenes: ResMut<Assets<DynamicScene>>,
+        world_name: Res<WorldName>,
+        game_paths: Res<GamePaths>,
+        registry: Res<AppTypeRegistry>,
+    ) -> Result<()> {
+        let autosave_path = game_paths
+            .latest_autosave_path(&world_name.0)
+            .with_context(|| format!("world {} has no autosaves", world_name.0))?;
+        info!("loading autosave from {autosave_path:?}");
+
+        let bytes = fs::read(&autosave_path)
+            .with_context(|| format!("unable to load {autosave_path:?}"))?;
+        let scene = deserialize_world(&bytes, &registry.read())
+            .with_context(|| format!("unable to deserialize {autosave_path:?}"))?;
 
         scene_spawner.spawn_dynamic(scenes.add(scene));
         commands.set_state(GameState::InGame);
@@ -129,25 +285,343 @@ impl GameWorldPlugin {
         commands.set_state(GameState::InGame);
     }
 
-    fn cleanup(mut commands: Commands) {
+    /// Tears down the world and closes the connection when leaving the game.
+    ///
+    /// Replicon resets its replication state once the connection resources are removed.
+    fn cleanup(
+        mut commands: Commands,
+        #[cfg(feature = "networking")] mut server: Option<ResMut<RenetServer>>,
+        replicated: Query<
+            Entity,
+            (
+                With<Replicated>,
+                Without<StateScoped<GameState>>,
+                Without<Parent>,
+            ),
+        >,
+    ) {
         commands.remove_resource::<WorldName>();
+        commands.remove_resource::<WorldDescription>();
+
+        // Entities received from the server may miss components that make them state-scoped.
+        for entity in &replicated {
+            debug!("despawning unscoped `{entity}`");
+            commands.entity(entity).despawn_recursive();
+        }
+
+        #[cfg(feature = "networking")]
+        {
+            if let Some(server) = &mut server {
+                info!("stopping server");
+                server.disconnect_all();
+                commands.remove_resource::<RenetServer>();
+                commands.remove_resource::<NetcodeServerTransport>();
+            }
+            commands.remove_resource::<RenetClient>();
+            commands.remove_resource::<NetcodeClientTransport>();
+        }
     }
 }
 
+/// Serializes all replicated entities and saved components into bytes.
+///
+/// Used to store the world on disk.
+pub fn serialize_world(world: &World) -> Result<Vec<u8>> {
+    // Extract components that we don't replicate, but serialize.
+    let actors = world
+        .iter_entities()
+        .filter(|entity| entity.contains::<Actor>())
+        .map(|entity| entity.id());
+    let mut scene = DynamicSceneBuilder::from_world(world)
+        .deny_all()
+        .allow_component::<Transform>()
+        .allow_component::<WorkedSecs>()
+        .allow_resource::<WorldDescription>()
+        .allow_resource::<Difficulty>()
+        .allow_resource::<BannedPlayers>()
+        .allow_resource::<WorldSeed>()
+        .allow_resource::<GameRng>()
+        .extract_entities(actors)
+        .extract_resources()
+        .build();
+
+    // Extract all replicated components that are reflected.
+    bevy_replicon::scene::replicate_into(&mut scene, world);
+
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let payload = scene
+        .serialize(&registry)
+        .context("unable to serialize game world")?
+        .into_bytes();
+
+    let meta = WorldMeta {
+        payload_len: payload.len() as u64,
+        checksum: crc32(&payload),
+    };
+    let options = DefaultOptions::new();
+    let mut bytes = WORLD_MAGIC.to_vec();
+    options
+        .serialize_into(&mut bytes, &WORLD_VERSION)
+        .context("unable to serialize world version")?;
+    options
+        .serialize_into(&mut bytes, &meta)
+        .context("unable to serialize world header")?;
+    bytes.extend(payload);
+
+    Ok(bytes)
+}
+
+/// Deserializes bytes produced by [`serialize_world`] into a scene.
+///
+/// If the header can't be read, the bytes are parsed as a plain scene
+/// to support worlds saved before [`WorldMeta`] was introduced.
+/// Returns an error instead of panicking if the bytes are truncated or damaged.
+pub fn deserialize_world(bytes: &[u8], registry: &TypeRegistry) -> Result<DynamicScene> {
+    let (meta, payload) = match read_header(bytes) {
+        Ok(header) => header,
+        Err(e) => {
+            debug!("reading world without header: {e:#}");
+            // Report the header error since the file is most likely damaged if it's not a scene.
+            return deserialize_scene(bytes, registry).map_err(|_| e);
+        }
+    };
+    ensure!(
+        crc32(payload) == meta.checksum,
+        "world data is damaged: checksum mismatch"
+    );
+
+    deserialize_scene(payload, registry)
+}
+
+/// Reads and validates the header, returning it with the remaining bytes.
+fn read_header(bytes: &[u8]) -> Result<(WorldMeta, &[u8])> {
+    let mut payload = bytes
+        .strip_prefix(&WORLD_MAGIC)
+        .context("world header is missing")?;
+
+    let options = DefaultOptions::new();
+    let version: u16 = options
+        .deserialize_from(&mut payload)
+        .context("unable to read world version")?;
+    ensure!(
+        version <= WORLD_VERSION,
+        "world was saved in format {version}, but only {WORLD_VERSION} is supported"
+    );
+    let meta: WorldMeta = options
+        .deserialize_from(&mut payload)
+        .context("unable to read world header")?;
+    ensure!(
+        payload.len() as u64 == meta.payload_len,
+        "world data is damaged: expected {} bytes, but got {}",
+        meta.payload_len,
+        payload.len()
+    );
+
+    Ok((meta, payload))
+}
+
+fn deserialize_scene(bytes: &[u8], registry: &TypeRegistry) -> Result<DynamicScene> {
+    let mut deserializer =
+        ron::Deserializer::from_bytes(bytes).context("unable to parse game world")?;
+    let scene_deserializer = SceneDeserializer {
+        type_registry: registry,
+    };
+    let scene = scene_deserializer.deserialize(&mut deserializer)?;
+
+    Ok(scene)
+}
+
+/// Serializes the world on the main thread and writes it into a file from a background task.
+///
+/// Only one save can be in progress, tracked by [`SaveState`].
+/// The result is reported by [`GameWorldPlugin::finish_save`].
+fn start_saving(
+    commands: &mut Commands,
+    world: &World,
+    worlds_dir: PathBuf,
+    path: PathBuf,
+    kind: SaveKind,
+) -> Result<()> {
+    let save_state = world.resource::<SaveState>();
+    ensure!(save_state.start(), "previous save is still in progress");
+
+    let bytes = match serialize_world(world) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            save_state.finish();
+            return Err(e);
+        }
+    };
+    let task = AsyncComputeTaskPool::get().spawn(async move {
+        fs::create_dir_all(&worlds_dir)
+            .with_context(|| format!("unable to create {worlds_dir:?}"))?;
+        replace_file(&path, &bytes).with_context(|| format!("unable to save game to {path:?}"))
+    });
+    commands.insert_resource(PendingSave {
+        task,
+        kind,
+        start: Instant::now(),
+    });
+
+    Ok(())
+}
+
+/// Writes bytes into a temporary file and then moves it over the file at the path.
+///
+/// The previous file is kept as a single backup.
+/// If writing fails, the previous file stays intact.
+fn replace_file(path: &Path, bytes: &[u8]) -> Result<()> {
+    let temp_path = game_paths::append_extension(path, "tmp");
+    if let Err(e) = fs::write(&temp_path, bytes) {
+        // Could be not created at all.
+        let _ = fs::remove_file(&temp_path);
+        return Err(e).with_context(|| format!("unable to write {temp_path:?}"));
+    }
+
+    if path.exists() {
+        let backup_path = game_paths::append_extension(path, BACKUP_EXTENSION);
+        fs::copy(path, &backup_path)
+            .with_context(|| format!("unable to backup {path:?} to {backup_path:?}"))?;
+    }
+
+    fs::rename(&temp_path, path)
+        .with_context(|| format!("unable to move {temp_path:?} to {path:?}"))
+}
+
+/// Bytes at the start of world files with a header.
+///
+/// Followed by [`WORLD_VERSION`] and [`WorldMeta`].
+const WORLD_MAGIC: [u8; 4] = *b"PHW\0";
+
+/// Version of the world header, incremented on incompatible changes.
+///
+/// Stored separately from [`WorldMeta`], so it can be checked before parsing the header.
+const WORLD_VERSION: u16 = 1;
+
+/// Header stored before the scene in world files.
+///
+/// Allows to detect truncated or damaged files without parsing the scene.
+#[derive(Deserialize, Serialize)]
+struct WorldMeta {
+    /// Size of the serialized scene in bytes.
+    payload_len: u64,
+    /// CRC32 of the serialized scene.
+    checksum: u32,
+}
+
+/// Lookup table for the reflected CRC32 polynomial.
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut index = 0;
+    while index < table.len() {
+        let mut value = index as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            value = if value & 1 == 1 {
+                (value >> 1) ^ 0xEDB8_8320
+            } else {
+                value >> 1
+            };
+            bit += 1;
+        }
+        table[index] = value;
+        index += 1;
+    }
+    table
+};
+
+/// Calculates CRC32 (IEEE) checksum.
+fn crc32(bytes: &[u8]) -> u32 {
+    let crc = bytes.iter().fold(u32::MAX, |crc, &byte| {
+        CRC32_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8)
+    });
+    !crc
+}
+
 /// Event that indicates that game is about to be saved to the file name based on [`WorldName`] resource.
 #[derive(Default, Event)]
 pub struct GameSave;
 
+/// Event that is triggered after [`GameSave`] once the file is written.
+#[derive(Event)]
+pub struct GameSaved;
+
+/// Indicates that the world was changed since it was loaded or saved.
+///
+/// Autosaves don't reset it since they are written to separate files.
+#[derive(Resource, Default, Deref)]
+pub struct UnsavedChanges(bool);
+
+/// Tracks whether a save is being written.
+///
+/// Unlike [`PendingSave`], which is inserted via commands, it changes immediately,
+/// so saves started in the same frame can't overlap.
+#[derive(Resource, Default)]
+struct SaveState {
+    in_progress: AtomicBool,
+}
+
+impl SaveState {
+    /// Marks a save as started.
+    ///
+    /// Returns `false` if another save is still in progress.
+    fn start(&self) -> bool {
+        !self.in_progress.swap(true, Ordering::Relaxed)
+    }
+
+    fn finish(&self) {
+        self.in_progress.store(false, Ordering::Relaxed);
+    }
+
+    fn in_progress(&self) -> bool {
+        self.in_progress.load(Ordering::Relaxed)
+    }
+}
+
+/// Save that is being written in the background.
+///
+/// Removed together with resetting [`SaveState`].
+#[derive(Resource)]
+struct PendingSave {
+    task: Task<Result<()>>,
+    kind: SaveKind,
+    /// Used to report the save duration.
+    start: Instant,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum SaveKind {
+    Manual,
+    Autosave,
+}
+
 /// Event that indicates that game is about to be loaded from the file name based on [`WorldName`] resource.
 ///
 /// Sets game state to [`GameState::World`].
 #[derive(Default, Event)]
 pub struct GameLoad;
 
+/// Event that indicates that the latest autosave for the world from [`WorldName`] resource is about to be loaded.
+///
+/// Used to recover after [`CorruptWorld`].
+#[derive(Default, Event)]
+pub struct AutosaveLoad;
+
+/// Triggered when the world file from [`WorldName`] can't be deserialized, but its autosave exists.
+#[derive(Event)]
+pub struct CorruptWorld;
+
 /// Contains metadata of the currently loaded world.
 #[derive(Default, Resource)]
 pub struct WorldName(pub String);
 
+/// Optional user-provided description of the currently loaded world.
+///
+/// Stored inside the world file.
+#[derive(Default, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct WorldDescription(pub String);
+
 #[derive(SubStates, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
 #[source(GameState = GameState::InGame)]
 pub enum WorldState {
@@ -171,3 +645,335 @@ pub(super) enum Layer {
     Road,
     PlacingRoad,
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        ecs::{entity::EntityHashMap, world::CommandQueue},
+        state::app::StatesPlugin,
+    };
+    #[cfg(feature = "networking")]
+    use bevy_replicon_renet::renet::ConnectionConfig;
+
+    use super::*;
+    use crate::game_paths::TempDir;
+    use actor::FirstName;
+
+    #[test]
+    fn cleanup() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .init_state::<GameState>()
+            .enable_state_scoped_entities::<GameState>()
+            .add_systems(OnExit(GameState::InGame), GameWorldPlugin::cleanup);
+
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::InGame);
+        app.update();
+
+        let entities_count = app.world().entities().len();
+        #[cfg(feature = "networking")]
+        app.insert_resource(RenetServer::new(ConnectionConfig::default()))
+            .insert_resource(RenetClient::new(ConnectionConfig::default()));
+        app.insert_resource(WorldName("World".to_string()));
+        app.world_mut()
+            .spawn((Replicated, StateScoped(GameState::InGame)))
+            .with_child(Replicated);
+        app.world_mut().spawn(Replicated).with_child(Replicated);
+
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Menu);
+        app.update();
+
+        assert_eq!(
+            app.world().entities().len(),
+            entities_count,
+            "all world entities should be despawned"
+        );
+        #[cfg(feature = "networking")]
+        {
+            assert!(!app.world().contains_resource::<RenetServer>());
+            assert!(!app.world().contains_resource::<RenetClient>());
+        }
+        assert!(!app.world().contains_resource::<WorldName>());
+    }
+
+    #[test]
+    fn unsaved_changes() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .init_state::<GameState>()
+            .init_resource::<UnsavedChanges>()
+            .add_observer(GameWorldPlugin::reset_unsaved)
+            .add_systems(
+                OnEnter(GameState::InGame),
+                GameWorldPlugin::reset_unsaved_on_enter,
+            )
+            .add_systems(PostUpdate, GameWorldPlugin::track_changes);
+
+        // Simulate loading.
+        app.world_mut().spawn(Replicated);
+        app.update();
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::InGame);
+        app.update();
+        assert!(
+            !**app.world().resource::<UnsavedChanges>(),
+            "loaded entities shouldn't count as changes"
+        );
+
+        app.world_mut().spawn(Replicated);
+        app.update();
+        assert!(**app.world().resource::<UnsavedChanges>());
+
+        app.world_mut().trigger(GameSaved);
+        assert!(!**app.world().resource::<UnsavedChanges>());
+
+        app.update();
+        assert!(
+            !**app.world().resource::<UnsavedChanges>(),
+            "nothing changed after the save"
+        );
+    }
+
+    #[test]
+    fn world_round_trip() -> Result<()> {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .register_type::<FirstName>()
+            .replicate::<FirstName>();
+
+        app.world_mut()
+            .spawn((Replicated, FirstName("Alice".to_string())));
+        app.world_mut().spawn(FirstName("Ignored".to_string()));
+
+        let bytes = serialize_world(app.world())?;
+        let registry = app.world().resource::<AppTypeRegistry>().clone();
+        let scene = deserialize_world(&bytes, &registry.read())?;
+
+        let mut world = World::new();
+        world.insert_resource(registry);
+        scene.write_to_world(&mut world, &mut EntityHashMap::default())?;
+
+        let names: Vec<_> = world
+            .query::<&FirstName>()
+            .iter(&world)
+            .map(|name| name.0.clone())
+            .collect();
+        assert_eq!(names, ["Alice"], "only replicated entities should be saved");
+
+        Ok(())
+    }
+
+    #[test]
+    fn checksum() -> Result<()> {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(&[]), 0);
+
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .register_type::<FirstName>()
+            .replicate::<FirstName>();
+
+        app.world_mut()
+            .spawn((Replicated, FirstName("Alice".to_string())));
+
+        let bytes = serialize_world(app.world())?;
+        let registry = app.world().resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+        assert!(
+            deserialize_world(&bytes, &registry).is_ok(),
+            "matching checksum should pass"
+        );
+
+        // Keep the scene valid, so only the checksum can detect the change.
+        let name_index = bytes
+            .windows(5)
+            .position(|window| window == b"Alice")
+            .expect("name should be serialized as is");
+        let mut tampered = bytes.clone();
+        tampered[name_index] = b'B';
+        let error = deserialize_world(&tampered, &registry)
+            .expect_err("mismatching checksum should be detected");
+        assert!(error.to_string().contains("checksum"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn interrupted_write() -> Result<()> {
+        let dir = TempDir::new("interrupted_write");
+        let path = dir.join("world.scn");
+
+        replace_file(&path, b"old")?;
+        replace_file(&path, b"new")?;
+        assert_eq!(fs::read(&path)?, b"new");
+        assert_eq!(
+            fs::read(game_paths::append_extension(&path, BACKUP_EXTENSION))?,
+            b"old"
+        );
+
+        // A directory in place of the temporary file makes the write fail.
+        fs::create_dir_all(game_paths::append_extension(&path, "tmp"))?;
+        assert!(replace_file(&path, b"broken").is_err());
+        assert_eq!(
+            fs::read(&path)?,
+            b"new",
+            "failed write should keep the previous save"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn background_save() -> Result<()> {
+        let dir = TempDir::new("background_save");
+        let game_paths = dir.game_paths();
+        let world_path = game_paths.world_path("World");
+
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .init_resource::<SaveState>()
+            .insert_resource(game_paths)
+            .insert_resource(WorldName("World".to_string()))
+            .add_observer(GameWorldPlugin::save.pipe(error_message))
+            .add_systems(
+                Update,
+                GameWorldPlugin::finish_save
+                    .pipe(error_message)
+                    .run_if(resource_exists::<PendingSave>),
+            );
+
+        app.world_mut().trigger(GameSave);
+        app.world_mut().flush();
+        assert!(
+            app.world().contains_resource::<PendingSave>(),
+            "save should continue in the background"
+        );
+
+        let mut updates = 0;
+        while app.world().contains_resource::<PendingSave>() {
+            assert!(updates < 1000, "save should finish");
+            app.update();
+            updates += 1;
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        assert!(world_path.exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn headerless_world() -> Result<()> {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .register_type::<FirstName>()
+            .replicate::<FirstName>();
+
+        app.world_mut()
+            .spawn((Replicated, FirstName("Alice".to_string())));
+
+        // Simulate a world saved before the header was introduced.
+        let bytes = serialize_world(app.world())?;
+        let (_, payload) = read_header(&bytes)?;
+
+        let registry = app.world().resource::<AppTypeRegistry>().clone();
+        let scene = deserialize_world(payload, &registry.read())?;
+
+        let mut world = World::new();
+        world.insert_resource(registry);
+        scene.write_to_world(&mut world, &mut EntityHashMap::default())?;
+
+        let names: Vec<_> = world
+            .query::<&FirstName>()
+            .iter(&world)
+            .map(|name| name.0.clone())
+            .collect();
+        assert_eq!(names, ["Alice"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn newer_version() -> Result<()> {
+        let mut bytes = WORLD_MAGIC.to_vec();
+        DefaultOptions::new().serialize_into(&mut bytes, &(WORLD_VERSION + 1))?;
+
+        let error = deserialize_world(&bytes, &TypeRegistry::default())
+            .expect_err("newer format shouldn't be parsed");
+        assert!(error.to_string().contains("format"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn overlapping_saves() -> Result<()> {
+        let dir = TempDir::new("overlapping_saves");
+        let game_paths = dir.game_paths();
+        let world_path = game_paths.world_path("World");
+
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .init_resource::<SaveState>();
+
+        // Simulate a manual save and an autosave started in the same frame.
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, app.world());
+        start_saving(
+            &mut commands,
+            app.world(),
+            game_paths.worlds.clone(),
+            world_path.clone(),
+            SaveKind::Manual,
+        )?;
+        let result = start_saving(
+            &mut commands,
+            app.world(),
+            game_paths.worlds.clone(),
+            world_path,
+            SaveKind::Autosave,
+        );
+        assert!(
+            result.is_err(),
+            "second save shouldn't start before the commands are applied"
+        );
+
+        queue.apply(app.world_mut());
+        let mut pending_save = app
+            .world_mut()
+            .remove_resource::<PendingSave>()
+            .expect("first save should be started");
+        block_on(&mut pending_save.task)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn corrupt_world() -> Result<()> {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .register_type::<FirstName>()
+            .replicate::<FirstName>();
+
+        app.world_mut()
+            .spawn((Replicated, FirstName("Alice".to_string())));
+
+        let bytes = serialize_world(app.world())?;
+        let registry = app.world().resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+
+        let truncated = &bytes[..bytes.len() / 2];
+        assert!(deserialize_world(truncated, &registry).is_err());
+        assert!(deserialize_world(&[], &registry).is_err());
+
+        let mut damaged = bytes.clone();
+        let middle = damaged.len() / 2;
+        damaged[middle..].fill(0xFF);
+        assert!(deserialize_world(&damaged, &registry).is_err());
+
+        Ok(())
+    }
+}
@@ -1,6 +1,9 @@
 mod animation_state;
 pub(super) mod human;
+pub mod job;
+mod nameplate;
 pub mod needs;
+pub mod schedule;
 pub mod task;
 
 use std::fmt::Write;
@@ -18,18 +21,28 @@ use serde::{Deserialize, Serialize};
 use strum::EnumIter;
 
 use super::{
-    family::editor::{EditorFirstName, EditorLastName, EditorSex},
+    family::{
+        editor::{EditorFirstName, EditorLastName, EditorSex},
+        Budget,
+    },
     highlighting::HIGHLIGHTING_VOLUME,
     navigation::Navigation,
     Layer, WorldState,
 };
 use crate::{
-    asset::collection::{AssetCollection, Collection},
+    asset::{
+        collection::{AssetCollection, Collection},
+        speeds::{BaseSpeeds, BaseSpeedsHandle},
+    },
+    combined_scene_collider::ColliderFocus,
     core::GameState,
 };
 use animation_state::{AnimationState, AnimationStatePlugin};
 use human::HumanPlugin;
+use job::JobPlugin;
+use nameplate::NameplatePlugin;
 use needs::NeedsPlugin;
+use schedule::{DailySchedule, SchedulePlugin};
 use task::{TaskGroups, TaskPlugin};
 
 pub(super) struct ActorPlugin;
@@ -37,17 +50,35 @@ pub(super) struct ActorPlugin;
 impl Plugin for ActorPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<Collection<ActorAnimation>>()
-            .add_plugins((AnimationStatePlugin, NeedsPlugin, HumanPlugin, TaskPlugin))
+            .add_plugins((
+                AnimationStatePlugin,
+                NeedsPlugin,
+                HumanPlugin,
+                JobPlugin,
+                NameplatePlugin,
+                SchedulePlugin,
+                TaskPlugin,
+            ))
             .register_type::<Transform>()
             .register_type::<Actor>()
             .register_type::<FirstName>()
             .register_type::<Sex>()
             .register_type::<LastName>()
             .register_type::<Movement>()
+            .register_type::<LifeStage>()
+            .register_type::<MovementSpeed>()
             .replicate_mapped::<Actor>()
             .replicate::<FirstName>()
             .replicate::<Sex>()
             .replicate::<LastName>()
+            .replicate::<LifeStage>()
+            .replicate::<MovementSpeed>()
+            .add_systems(
+                Update,
+                Self::update_speeds
+                    .run_if(server_or_singleplayer)
+                    .run_if(in_state(GameState::InGame)),
+            )
             .add_systems(
                 OnExit(WorldState::Family),
                 Self::remove_selection.never_param_warn(),
@@ -78,6 +109,23 @@ impl ActorPlugin {
         }
     }
 
+    /// Applies speeds for the life stage of each actor.
+    ///
+    /// Checks all actors to also apply speeds after the asset loads or reloads.
+    fn update_speeds(
+        speeds_handle: Res<BaseSpeedsHandle>,
+        base_speeds: Res<Assets<BaseSpeeds>>,
+        mut actors: Query<(&LifeStage, &mut MovementSpeed)>,
+    ) {
+        let Some(base_speeds) = base_speeds.get(&**speeds_handle) else {
+            return;
+        };
+
+        for (&life_stage, mut speed) in &mut actors {
+            speed.set_if_neq(base_speeds.for_stage(life_stage));
+        }
+    }
+
     fn remove_selection(
         mut commands: Commands,
         selected_entity: Single<Entity, With<SelectedActor>>,
@@ -142,6 +190,11 @@ pub struct SelectedActor;
     AnimationState,
     SceneRoot,
     ActorTaskGroups,
+    DailySchedule,
+    LifeStage,
+    MovementSpeed,
+    // Used only with `BudgetPolicy::PerActor`.
+    Budget(Budget::empty),
     RigidBody(|| RigidBody::Kinematic),
     Collider(|| Collider::capsule_endpoints(
         ACTOR_RADIUS,
@@ -152,7 +205,8 @@ pub struct SelectedActor;
         Layer::Actor,
         LayerMask::NONE,
     )),
-    OutlineVolume(|| HIGHLIGHTING_VOLUME)
+    OutlineVolume(|| HIGHLIGHTING_VOLUME),
+    ColliderFocus
 )]
 pub struct Actor {
     pub family_entity: Entity,
@@ -225,10 +279,50 @@ pub(super) enum Movement {
 }
 
 impl Movement {
-    pub(super) fn speed(self) -> f32 {
-        match self {
-            Movement::Walk => 2.0,
-            Movement::Run => 4.0,
+    /// Upgrades walking to running if the actor is in a hurry.
+    pub(super) fn with_urgency(self, urgent: bool) -> Self {
+        if urgent {
+            Movement::Run
+        } else {
+            self
+        }
+    }
+}
+
+/// Speeds of an actor for each [`Movement`].
+///
+/// Calculated on server from [`LifeStage`] and [`BaseSpeeds`].
+#[derive(Clone, Component, Copy, Debug, Deserialize, PartialEq, Reflect, Serialize)]
+#[reflect(Component)]
+pub struct MovementSpeed {
+    pub walk: f32,
+    pub run: f32,
+}
+
+impl MovementSpeed {
+    pub(super) fn get(self, movement: Movement) -> f32 {
+        match movement {
+            Movement::Walk => self.walk,
+            Movement::Run => self.run,
+        }
+    }
+}
+
+impl Default for MovementSpeed {
+    fn default() -> Self {
+        // Adult speeds, animations are authored for them.
+        Self {
+            walk: 2.0,
+            run: 4.0,
         }
     }
 }
+
+#[derive(Clone, Component, Copy, Debug, Default, Deserialize, PartialEq, Reflect, Serialize)]
+#[reflect(Component)]
+pub enum LifeStage {
+    Child,
+    #[default]
+    Adult,
+    Elder,
+}
@@ -1,7 +1,7 @@
 use bevy::{animation::RepeatAnimation, prelude::*, scene::SceneInstanceReady, utils::Duration};
 use strum::EnumCount;
 
-use super::{ActorAnimation, Movement, Sex};
+use super::{ActorAnimation, MovementSpeed, Sex};
 use crate::{
     asset::collection::Collection,
     core::GameState,
@@ -75,7 +75,13 @@ impl AnimationStatePlugin {
 
     fn update(
         mut commands: Commands,
-        mut actors: Query<(Entity, &mut AnimationState, &Navigation, Ref<NavPath>)>,
+        mut actors: Query<(
+            Entity,
+            &mut AnimationState,
+            &Navigation,
+            &MovementSpeed,
+            Ref<NavPath>,
+        )>,
         mut players: Query<(
             &mut AnimationPlayer,
             &mut AnimationTransitions,
@@ -83,7 +89,7 @@ impl AnimationStatePlugin {
         )>,
         mut graphs: ResMut<Assets<AnimationGraph>>,
     ) {
-        for (actor_entity, mut state, navigation, path) in &mut actors {
+        for (actor_entity, mut state, navigation, speed, path) in &mut actors {
             let Some(player_entity) = state.player_entity else {
                 continue;
             };
@@ -126,7 +132,7 @@ impl AnimationStatePlugin {
 
             let node = if path.is_empty() {
                 AnimationNode::Idle
-            } else if navigation.speed() <= Movement::Walk.speed() {
+            } else if navigation.speed() <= speed.walk {
                 AnimationNode::Walk
             } else {
                 AnimationNode::Run
@@ -141,6 +147,16 @@ impl AnimationStatePlugin {
 
                 state.current_node = node;
             }
+
+            // Clips are authored for adult speeds.
+            let reference_speed = match node {
+                AnimationNode::Walk => MovementSpeed::default().walk,
+                AnimationNode::Run => MovementSpeed::default().run,
+                AnimationNode::Idle | AnimationNode::Montage => continue,
+            };
+            if let Some(animation) = player.animation_mut(state.nodes[node as usize]) {
+                animation.set_speed(navigation.speed() / reference_speed);
+            }
         }
     }
 }
@@ -0,0 +1,328 @@
+use bevy::{ecs::entity::MapEntities, prelude::*};
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    schedule::{Activity, DailySchedule, DailyScheduleEntry, ScheduledTask},
+    task::ActiveTask,
+    Actor,
+};
+use crate::{
+    asset::jobs::{JobList, JobListHandle},
+    core::GameState,
+    game_world::{
+        clock::{WorldClock, GAME_SECONDS_PER_SECOND, SECS_PER_HOUR},
+        family::{Budget, BudgetPolicy},
+        spectator::Spectators,
+    },
+};
+
+/// Pays actors for the time spent at work.
+pub(super) struct JobPlugin;
+
+impl Plugin for JobPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Job>()
+            .register_type::<WorkedSecs>()
+            .replicate::<Job>()
+            .init_resource::<LastPayDay>()
+            .add_mapped_client_event::<JobChange>(ChannelKind::Ordered)
+            .add_systems(
+                PreUpdate,
+                Self::change
+                    .after(ClientSet::Receive)
+                    .run_if(server_or_singleplayer),
+            )
+            .add_systems(
+                Update,
+                (Self::track_work, Self::pay.never_param_warn())
+                    .chain()
+                    .run_if(server_or_singleplayer)
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(OnExit(GameState::InGame), Self::reset_day);
+    }
+}
+
+impl JobPlugin {
+    /// Counts the time of active scheduled work for employed actors.
+    fn track_work(
+        time: Res<Time>,
+        tasks: Query<(&Parent, &ScheduledTask), With<ActiveTask>>,
+        mut actors: Query<&mut WorkedSecs, With<Job>>,
+    ) {
+        for (parent, task) in &tasks {
+            if task.0 != Activity::Work {
+                continue;
+            }
+            if let Ok(mut worked_secs) = actors.get_mut(**parent) {
+                **worked_secs += time.delta_secs() * GAME_SECONDS_PER_SECOND;
+            }
+        }
+    }
+
+    /// Pays daily income when a new day starts.
+    ///
+    /// Income is proportional to the worked part of the job hours.
+    fn pay(
+        mut last_day: ResMut<LastPayDay>,
+        clock: Single<&WorldClock>,
+        mut actors: Query<(Entity, &Actor, &Job, &mut WorkedSecs)>,
+        families: Query<&BudgetPolicy>,
+        mut budgets: Query<&mut Budget>,
+    ) {
+        let day = clock.day();
+        let previous_day = last_day.replace(day);
+        if previous_day.is_none_or(|previous_day| previous_day == day) {
+            return;
+        }
+
+        for (actor_entity, actor, job, mut worked_secs) in &mut actors {
+            let worked_part = (**worked_secs / job.secs_per_day()).min(1.0);
+            **worked_secs = 0.0;
+            let income = (job.income_per_day as f32 * worked_part) as u32;
+            if income == 0 {
+                continue;
+            }
+
+            let policy = families
+                .get(actor.family_entity)
+                .expect("actors should belong to a family");
+            let payer_entity = policy.payer(actor_entity, actor);
+            let mut budget = budgets
+                .get_mut(payer_entity)
+                .expect("payer should have a budget");
+
+            info!(
+                "paying {income} to `{payer_entity}` for '{}' of `{actor_entity}`",
+                job.title
+            );
+            budget.deposit(income);
+        }
+    }
+
+    /// Prevents paying on the first frame of the next loaded world.
+    fn reset_day(mut last_day: ResMut<LastPayDay>) {
+        **last_day = None;
+    }
+
+    fn change(
+        mut commands: Commands,
+        mut change_events: EventReader<FromClient<JobChange>>,
+        spectators: Res<Spectators>,
+        job_list_handle: Res<JobListHandle>,
+        job_lists: Res<Assets<JobList>>,
+        mut actors: Query<&mut DailySchedule, With<Actor>>,
+    ) {
+        for FromClient { client_id, event } in change_events.read() {
+            if spectators.contains(*client_id) {
+                warn!("ignoring job change from spectator `{client_id:?}`");
+                continue;
+            }
+            let Ok(mut schedule) = actors.get_mut(event.actor_entity) else {
+                error!(
+                    "received job change for an invalid actor `{}`",
+                    event.actor_entity
+                );
+                continue;
+            };
+
+            let Some(title) = &event.title else {
+                info!("`{client_id:?}` quits job for `{}`", event.actor_entity);
+                schedule.0.retain(|entry| entry.activity != Activity::Work);
+                commands
+                    .entity(event.actor_entity)
+                    .remove::<(Job, WorkedSecs)>();
+                continue;
+            };
+
+            let Some(job_list) = job_lists.get(&**job_list_handle) else {
+                error!("unable to assign '{title}' from `{client_id:?}`: job list is not loaded");
+                continue;
+            };
+            let Some(job) = job_list.get(title) else {
+                error!("received unknown job '{title}' from `{client_id:?}`");
+                continue;
+            };
+
+            info!(
+                "`{client_id:?}` assigns '{title}' for `{}`",
+                event.actor_entity
+            );
+            // Work is scheduled only for the job hours.
+            schedule.0.retain(|entry| entry.activity != Activity::Work);
+            let (start, end) = job.hours;
+            schedule
+                .0
+                .push(DailyScheduleEntry::new(start, end, Activity::Work));
+            commands
+                .entity(event.actor_entity)
+                .insert((job.clone(), WorkedSecs::default()));
+        }
+    }
+}
+
+/// Occupation of an actor.
+///
+/// Work is added to the actor's [`DailySchedule`] for the job hours.
+#[derive(Component, Clone, Debug, Deserialize, PartialEq, Reflect, Serialize)]
+#[reflect(Component)]
+#[require(WorkedSecs)]
+pub struct Job {
+    pub title: String,
+    pub income_per_day: u32,
+    /// Start and end hours, wraps around midnight like [`DailyScheduleEntry`].
+    pub hours: (u8, u8),
+}
+
+impl Job {
+    fn secs_per_day(&self) -> f32 {
+        let (start, end) = self.hours;
+        let hours = (i32::from(end) - i32::from(start)).rem_euclid(24);
+        hours as f32 * SECS_PER_HOUR
+    }
+}
+
+/// Game seconds worked since the last payment.
+///
+/// Not replicated since only the server needs it, but saved with the world.
+#[derive(Component, Default, Deref, DerefMut, Reflect)]
+#[reflect(Component)]
+pub(crate) struct WorkedSecs(f32);
+
+/// Day on which [`JobPlugin::pay`] last checked for payments.
+///
+/// Reset on world exit, so the first day of a loaded world isn't paid.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct LastPayDay(Option<u32>);
+
+/// Assigns a job from [`JobList`] by title or removes the current job if `None`.
+#[derive(Clone, Deserialize, Event, Serialize)]
+pub struct JobChange {
+    pub actor_entity: Entity,
+    pub title: Option<String>,
+}
+
+impl MapEntities for JobChange {
+    fn map_entities<T: EntityMapper>(&mut self, entity_mapper: &mut T) {
+        self.actor_entity = entity_mapper.map_entity(self.actor_entity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::{state::app::StatesPlugin, time::TimeUpdateStrategy};
+
+    use super::*;
+    use crate::game_world::family::Family;
+
+    #[test]
+    fn daily_payout() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+                SECS_PER_HOUR / GAME_SECONDS_PER_SECOND,
+            )))
+            .init_resource::<LastPayDay>()
+            .add_systems(Update, (JobPlugin::track_work, JobPlugin::pay).chain());
+
+        // Each update is a game hour.
+        app.world_mut()
+            .resource_mut::<Time<Virtual>>()
+            .set_max_delta(Duration::from_secs(60));
+
+        let clock_entity = app.world_mut().spawn(WorldClock::at(0, 9.0)).id();
+        let family_entity = app.world_mut().spawn(Family).id();
+        let job = Job {
+            title: "Dummy".to_string(),
+            income_per_day: 100,
+            hours: (9, 19),
+        };
+        let actor_entity = app
+            .world_mut()
+            .spawn((Actor { family_entity }, job))
+            .with_child((ScheduledTask(Activity::Work), ActiveTask))
+            .id();
+
+        let budget = **app.world().get::<Budget>(family_entity).unwrap();
+
+        // The first update doesn't advance the time, so 4 hours are worked.
+        for _ in 0..5 {
+            app.update();
+        }
+
+        // Work is counted for one more hour before the payment.
+        app.world_mut()
+            .entity_mut(clock_entity)
+            .insert(WorldClock::at(1, 0.0));
+        app.update();
+
+        let worked_secs = **app.world().get::<WorkedSecs>(actor_entity).unwrap();
+        assert_eq!(worked_secs, 0.0, "worked time should reset after payment");
+        assert_eq!(
+            **app.world().get::<Budget>(family_entity).unwrap(),
+            budget + 50,
+            "half of the job hours should be paid"
+        );
+    }
+
+    #[test]
+    fn world_reload() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .init_state::<GameState>()
+            .init_resource::<LastPayDay>()
+            .add_systems(Update, JobPlugin::pay.run_if(in_state(GameState::InGame)))
+            .add_systems(OnExit(GameState::InGame), JobPlugin::reset_day);
+
+        let first_world = spawn_world(&mut app, 0);
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::InGame);
+        app.update();
+
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Menu);
+        app.update();
+        for entity in first_world {
+            app.world_mut().despawn(entity);
+        }
+
+        // Load a world on a different day.
+        let [_, family_entity, _] = spawn_world(&mut app, 2);
+        let budget = **app.world().get::<Budget>(family_entity).unwrap();
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::InGame);
+        app.update();
+
+        assert_eq!(
+            **app.world().get::<Budget>(family_entity).unwrap(),
+            budget,
+            "loading a world shouldn't be treated as a new day"
+        );
+    }
+
+    /// Spawns a clock and a family with a single actor that worked the whole day.
+    ///
+    /// Returns the clock, family and actor entities.
+    fn spawn_world(app: &mut App, day: u32) -> [Entity; 3] {
+        let clock_entity = app.world_mut().spawn(WorldClock::at(day, 12.0)).id();
+        let family_entity = app.world_mut().spawn(Family).id();
+        let job = Job {
+            title: "Dummy".to_string(),
+            income_per_day: 100,
+            hours: (9, 19),
+        };
+        let worked_secs = WorkedSecs(job.secs_per_day());
+        let actor_entity = app
+            .world_mut()
+            .spawn((Actor { family_entity }, job, worked_secs))
+            .id();
+
+        [clock_entity, family_entity, actor_entity]
+    }
+}
@@ -0,0 +1,259 @@
+use bevy::{
+    color::palettes::css::{BLACK, GOLD, WHITE},
+    prelude::*,
+};
+use bevy_mod_billboard::prelude::*;
+
+use super::{needs::Need, Actor, FirstName, SelectedActor, ACTOR_HEIGHT};
+use crate::{core::GameState, game_world::player_camera::PlayerCamera, settings::Settings};
+
+/// Displays floating labels with names and moods above actors.
+pub(super) struct NameplatePlugin;
+
+impl Plugin for NameplatePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NameplateFonts>()
+            .add_observer(Self::spawn)
+            .add_observer(Self::despawn)
+            .add_systems(
+                PostUpdate,
+                (
+                    Self::update_names,
+                    Self::update_moods,
+                    Self::update_transforms
+                        .never_param_warn()
+                        .before(TransformSystem::TransformPropagate),
+                )
+                    .run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+/// Plates further from the camera will be hidden.
+const MAX_DISTANCE: f32 = 30.0;
+
+/// Offset from the top of the actor.
+const VERTICAL_OFFSET: f32 = 0.3;
+
+/// Scale of the plate for the selected actor.
+const SELECTED_SCALE: f32 = 1.3;
+
+/// Local offset of the text shadow relative to the text.
+///
+/// The plate always looks at the camera, so positive Z moves the shadow behind the text.
+const SHADOW_OFFSET: Vec3 = Vec3::new(0.015, -0.015, 0.01);
+
+impl NameplatePlugin {
+    fn spawn(
+        trigger: Trigger<OnAdd, Actor>,
+        mut commands: Commands,
+        fonts: Res<NameplateFonts>,
+        actors: Query<&FirstName>,
+    ) {
+        let first_name = actors.get(trigger.entity()).unwrap();
+
+        debug!("spawning nameplate for `{}`", trigger.entity());
+        let name_entities = spawn_text(
+            &mut commands,
+            &fonts.text,
+            60.0,
+            first_name.0.clone(),
+            Vec3::ZERO,
+        );
+        let mood_entities = spawn_text(
+            &mut commands,
+            &fonts.symbol,
+            50.0,
+            String::new(),
+            Vec3::Y * 0.3,
+        );
+
+        commands
+            .spawn(Nameplate {
+                actor_entity: trigger.entity(),
+                name_entities,
+                mood_entities,
+            })
+            .add_children(&name_entities)
+            .add_children(&mood_entities);
+    }
+
+    fn despawn(
+        trigger: Trigger<OnRemove, Actor>,
+        mut commands: Commands,
+        plates: Query<(Entity, &Nameplate)>,
+    ) {
+        if let Some((entity, _)) = plates
+            .iter()
+            .find(|(_, plate)| plate.actor_entity == trigger.entity())
+        {
+            debug!("despawning nameplate `{entity}` for `{}`", trigger.entity());
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+
+    fn update_names(
+        plates: Query<&Nameplate>,
+        actors: Query<(Ref<FirstName>, Has<SelectedActor>)>,
+        mut texts: Query<(&mut BillboardText, &mut TextColor)>,
+    ) {
+        for plate in &plates {
+            let Ok((first_name, selected)) = actors.get(plate.actor_entity) else {
+                continue;
+            };
+
+            if first_name.is_changed() {
+                trace!("updating nameplate name for `{}`", plate.actor_entity);
+                for entity in plate.name_entities {
+                    let (mut text, _) = texts.get_mut(entity).unwrap();
+                    text.0.clone_from(&first_name.0);
+                }
+            }
+
+            let color = if selected { GOLD } else { WHITE };
+            let (_, mut text_color) = texts.get_mut(plate.name_entities[0]).unwrap();
+            text_color.set_if_neq(TextColor(color.into()));
+        }
+    }
+
+    fn update_moods(
+        plates: Query<&Nameplate>,
+        actors: Query<&Children>,
+        needs: Query<Ref<Need>>,
+        mut texts: Query<&mut BillboardText>,
+    ) {
+        for plate in &plates {
+            let Ok(children) = actors.get(plate.actor_entity) else {
+                continue;
+            };
+
+            let mut changed = false;
+            let mut min_value = None;
+            for need in needs.iter_many(children) {
+                changed |= need.is_changed();
+                min_value = Some(min_value.map_or(need.0, |value: f32| value.min(need.0)));
+            }
+
+            let Some(min_value) = min_value.filter(|_| changed) else {
+                continue;
+            };
+
+            let glyph = mood_glyph(min_value);
+            for entity in plate.mood_entities {
+                let mut text = texts.get_mut(entity).unwrap();
+                if text.0 != glyph {
+                    trace!("updating mood to '{glyph}' for `{}`", plate.actor_entity);
+                    text.0.clear();
+                    text.0.push_str(glyph);
+                }
+            }
+        }
+    }
+
+    fn update_transforms(
+        settings: Res<Settings>,
+        camera_transform: Single<&GlobalTransform, With<PlayerCamera>>,
+        actors: Query<(&GlobalTransform, Has<SelectedActor>)>,
+        mut plates: Query<(&Nameplate, &mut Transform, &mut Visibility)>,
+    ) {
+        let camera_pos = camera_transform.translation();
+        for (plate, mut transform, mut visibility) in &mut plates {
+            let Ok((actor_transform, selected)) = actors.get(plate.actor_entity) else {
+                continue;
+            };
+
+            let translation =
+                actor_transform.translation() + Vec3::Y * (ACTOR_HEIGHT + VERTICAL_OFFSET);
+            if !settings.video.nameplates || translation.distance(camera_pos) > MAX_DISTANCE {
+                visibility.set_if_neq(Visibility::Hidden);
+                continue;
+            }
+
+            visibility.set_if_neq(Visibility::Inherited);
+            let scale = if selected { SELECTED_SCALE } else { 1.0 };
+            *transform = Transform::from_translation(translation)
+                .looking_at(camera_pos, Vec3::Y)
+                .with_scale(Vec3::splat(scale));
+        }
+    }
+}
+
+/// Spawns text and its shadow for better readability on any background.
+fn spawn_text(
+    commands: &mut Commands,
+    font: &Handle<Font>,
+    font_size: f32,
+    text: String,
+    translation: Vec3,
+) -> [Entity; 2] {
+    let text_font = TextFont {
+        font: font.clone(),
+        font_size,
+        ..Default::default()
+    };
+    let scale = Vec3::splat(0.005);
+
+    let text_entity = commands
+        .spawn((
+            BillboardText(text.clone()),
+            text_font.clone(),
+            TextColor::WHITE,
+            Transform::from_translation(translation).with_scale(scale),
+        ))
+        .id();
+
+    let shadow_entity = commands
+        .spawn((
+            BillboardText(text),
+            text_font,
+            TextColor(BLACK.into()),
+            Transform::from_translation(translation + SHADOW_OFFSET).with_scale(scale),
+        ))
+        .id();
+
+    [text_entity, shadow_entity]
+}
+
+/// Returns a glyph for the lowest need value.
+fn mood_glyph(min_value: f32) -> &'static str {
+    if min_value >= 70.0 {
+        "😀"
+    } else if min_value >= 40.0 {
+        "🙂"
+    } else if min_value >= 15.0 {
+        "😐"
+    } else {
+        "😫"
+    }
+}
+
+#[derive(Component)]
+#[require(
+    Name(|| Name::new("Nameplate")),
+    Transform,
+    Visibility,
+    StateScoped::<GameState>(|| StateScoped(GameState::InGame)),
+)]
+struct Nameplate {
+    actor_entity: Entity,
+    /// Text and shadow entities for the name.
+    name_entities: [Entity; 2],
+    /// Text and shadow entities for the mood glyph.
+    mood_entities: [Entity; 2],
+}
+
+#[derive(Resource)]
+struct NameplateFonts {
+    text: Handle<Font>,
+    symbol: Handle<Font>,
+}
+
+impl FromWorld for NameplateFonts {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        Self {
+            text: asset_server.load("base/fonts/FiraSans-Bold.ttf"),
+            symbol: asset_server.load("base/fonts/NotoEmoji-Regular.ttf"),
+        }
+    }
+}
@@ -4,6 +4,12 @@ use bevy::{prelude::*, time::common_conditions::on_timer};
 use bevy_replicon::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::game_world::{
+    clock::{TimeSkipped, GAME_SECONDS_PER_SECOND},
+    difficulty::CurrentModifiers,
+    game_rng::GameRng,
+};
+
 pub(super) struct NeedsPlugin;
 
 impl Plugin for NeedsPlugin {
@@ -22,22 +28,48 @@ impl Plugin for NeedsPlugin {
             .replicate::<Energy>()
             .replicate::<Bladder>()
             .replicate::<Need>()
+            .add_observer(Self::apply_skipped)
             .add_systems(
                 Update,
                 Self::update_values
-                    .run_if(on_timer(Duration::from_secs(1)))
+                    .run_if(on_timer(UPDATE_INTERVAL))
                     .run_if(server_or_singleplayer),
             );
     }
 }
 
+const UPDATE_INTERVAL: Duration = Duration::from_secs(1);
+
 impl NeedsPlugin {
-    fn update_values(mut needs: Query<(&mut Need, &NeedRate)>) {
-        for (mut need, rate) in &mut needs {
-            if need.0 > rate.0 {
-                need.0 += rate.0;
-            } else {
-                need.0 = 0.0;
+    fn update_values(
+        mut commands: Commands,
+        modifiers: CurrentModifiers,
+        mut needs: Query<(Entity, &mut Need, &NeedRate)>,
+    ) {
+        let decay = modifiers.get().need_decay;
+        for (entity, mut need, rate) in &mut needs {
+            if need.apply_rate(*rate, 1.0, decay) {
+                debug!("need `{entity}` depleted");
+                commands.trigger_targets(NeedDepleted, entity);
+            }
+        }
+    }
+
+    /// Applies changes as if the skipped time passed normally.
+    fn apply_skipped(
+        trigger: Trigger<TimeSkipped>,
+        mut commands: Commands,
+        modifiers: CurrentModifiers,
+        mut needs: Query<(Entity, &mut Need, &NeedRate)>,
+    ) {
+        let real_secs = **trigger / GAME_SECONDS_PER_SECOND;
+        let updates = real_secs / UPDATE_INTERVAL.as_secs_f32();
+        let decay = modifiers.get().need_decay;
+        debug!("applying {updates} skipped need updates");
+        for (entity, mut need, rate) in &mut needs {
+            if need.apply_rate(*rate, updates, decay) {
+                debug!("need `{entity}` depleted during skipped time");
+                commands.trigger_targets(NeedDepleted, entity);
             }
         }
     }
@@ -102,14 +134,149 @@ pub(crate) struct Bladder;
 #[require(ParentSync, Replicated)]
 pub struct Need(pub f32);
 
+impl Need {
+    /// Returns `true` if the actor should hurry to fulfill the need.
+    pub fn is_critical(&self) -> bool {
+        self.0 < CRITICAL_NEED
+    }
+
+    /// Changes the value by the rate for the number of updates.
+    ///
+    /// Decay multiplier is applied only to negative rates.
+    /// Returns `true` if the need dropped to zero.
+    fn apply_rate(&mut self, rate: NeedRate, updates: f32, decay: f32) -> bool {
+        let rate = if rate.0 < 0.0 { rate.0 * decay } else { rate.0 };
+        let was_empty = self.0 == 0.0;
+        self.0 = (self.0 + rate * updates).clamp(0.0, 100.0);
+        !was_empty && self.0 == 0.0
+    }
+}
+
 impl Default for Need {
     fn default() -> Self {
         Self(100.0)
     }
 }
 
-#[derive(Component)]
-struct NeedRate(f32);
+/// Triggered on a [`Need`] entity when its value drops to zero.
+///
+/// Triggered only once until the need is restored.
+#[derive(Event)]
+pub struct NeedDepleted;
+
+/// Need value below which actors run instead of walking.
+const CRITICAL_NEED: f32 = 15.0;
+
+/// Returns `true` if any need of the actor is critical.
+pub(super) fn has_critical_need(children: Option<&Children>, needs: &Query<&Need>) -> bool {
+    children.is_some_and(|children| needs.iter_many(children).any(Need::is_critical))
+}
+
+/// Picks one of the needs for an actor to fulfill.
+///
+/// Lower needs are more likely to be picked, full needs are never picked.
+pub fn choose_need(rng: &mut GameRng, needs: &[(Entity, &Need)]) -> Option<Entity> {
+    rng.choose_weighted(needs, |(_, need)| 100.0 - need.0)
+        .map(|&(entity, _)| entity)
+}
+
+/// Change of the need value per update.
+#[derive(Component, Clone, Copy)]
+pub struct NeedRate(pub f32);
 
 #[derive(Component)]
 pub struct NeedGlyph(pub &'static str);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_world::difficulty;
+
+    #[test]
+    fn decay() {
+        let mut app = App::new();
+        app.init_resource::<Depletions>()
+            .add_observer(count_depletions)
+            .add_systems(Update, NeedsPlugin::update_values);
+        difficulty::insert_table(&mut app);
+
+        // Rates are exactly representable to avoid accumulating float errors.
+        let hunger = app.world_mut().spawn((Need(100.0), NeedRate(-0.25))).id();
+        let bladder = app.world_mut().spawn((Need(10.0), NeedRate(-0.5))).id();
+
+        // A minute of updates.
+        for _ in 0..60 {
+            app.update();
+        }
+
+        let need = app.world().get::<Need>(hunger).unwrap();
+        assert_eq!(need.0, 85.0);
+
+        let need = app.world().get::<Need>(bladder).unwrap();
+        assert_eq!(need.0, 0.0, "shouldn't decay below zero");
+
+        assert_eq!(
+            app.world().resource::<Depletions>().0,
+            [bladder],
+            "depletion should be reported once"
+        );
+    }
+
+    #[test]
+    fn skipped_decay() {
+        let mut app = App::new();
+        app.init_resource::<Depletions>()
+            .add_observer(count_depletions)
+            .add_observer(NeedsPlugin::apply_skipped);
+        difficulty::insert_table(&mut app);
+
+        let hunger = app.world_mut().spawn((Need(100.0), NeedRate(-0.4))).id();
+        let bladder = app.world_mut().spawn((Need(10.0), NeedRate(-0.5))).id();
+
+        // 10 real seconds.
+        let skipped_secs = 10.0 * GAME_SECONDS_PER_SECOND;
+        app.world_mut().trigger(TimeSkipped(skipped_secs));
+
+        let need = app.world().get::<Need>(hunger).unwrap();
+        assert_eq!(need.0, 96.0, "should decay as after 10 updates");
+
+        app.world_mut().trigger(TimeSkipped(skipped_secs * 2.0));
+
+        let need = app.world().get::<Need>(bladder).unwrap();
+        assert_eq!(need.0, 0.0, "shouldn't decay below zero");
+        assert_eq!(app.world().resource::<Depletions>().0, [bladder]);
+    }
+
+    #[test]
+    fn seeded_choices() {
+        let hunger = Need(20.0);
+        let fun = Need(60.0);
+        let energy = Need(100.0);
+        let needs = [
+            (Entity::from_raw(0), &hunger),
+            (Entity::from_raw(1), &fun),
+            (Entity::from_raw(2), &energy),
+        ];
+
+        let run = |seed| {
+            let mut rng = GameRng::new(seed);
+            (0..20)
+                .map(|_| choose_need(&mut rng, &needs).unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        let choices = run(42);
+        assert_eq!(choices, run(42), "same seed should produce same choices");
+        assert!(
+            !choices.contains(&Entity::from_raw(2)),
+            "full needs shouldn't be chosen"
+        );
+    }
+
+    fn count_depletions(trigger: Trigger<NeedDepleted>, mut depletions: ResMut<Depletions>) {
+        depletions.0.push(trigger.entity());
+    }
+
+    #[derive(Resource, Default)]
+    struct Depletions(Vec<Entity>);
+}
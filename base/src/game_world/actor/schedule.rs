@@ -0,0 +1,397 @@
+use std::time::Duration;
+
+use bevy::{ecs::entity::MapEntities, prelude::*};
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    task::{Task, TaskDuration, TaskGroups},
+    Actor,
+};
+use crate::{
+    core::GameState,
+    game_world::{
+        clock::{WorldClock, GAME_SECONDS_PER_SECOND, SECS_PER_HOUR},
+        family::controller::{can_control, Controller},
+        spectator::Spectators,
+    },
+};
+
+/// Queues daily activities for idle actors according to their [`DailySchedule`].
+pub(super) struct SchedulePlugin;
+
+impl Plugin for SchedulePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<DailySchedule>()
+            .register_type::<ScheduledTask>()
+            .replicate::<DailySchedule>()
+            .replicate::<ScheduledTask>()
+            .add_mapped_client_event::<ScheduleChange>(ChannelKind::Ordered)
+            .add_systems(
+                PreUpdate,
+                Self::change
+                    .after(ClientSet::Receive)
+                    .run_if(server_or_singleplayer),
+            )
+            .add_systems(
+                Update,
+                (Self::defer, Self::queue.never_param_warn())
+                    .chain()
+                    .run_if(server_or_singleplayer)
+                    .run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+impl SchedulePlugin {
+    /// Queues the current activity for actors without tasks.
+    ///
+    /// Each occurrence of an activity is queued only once,
+    /// so cancelling it leaves the actor idle until the next one.
+    fn queue(
+        mut commands: Commands,
+        clock: Single<&WorldClock>,
+        mut actors: Query<(
+            Entity,
+            &DailySchedule,
+            &mut LastScheduled,
+            Option<&Children>,
+        )>,
+        tasks: Query<(), With<Task>>,
+    ) {
+        let hour = clock.hour();
+        for (actor_entity, schedule, mut last_scheduled, children) in &mut actors {
+            if children.is_some_and(|children| tasks.iter_many(children).next().is_some()) {
+                continue;
+            }
+
+            let Some(entry) = schedule.current(hour) else {
+                continue;
+            };
+
+            // Activities that started before midnight belong to the previous day.
+            let mut day = i64::from(clock.day());
+            if hour < f32::from(entry.start) {
+                day -= 1;
+            }
+            let occurrence = (day, entry.start);
+            if last_scheduled.0 == Some(occurrence) {
+                continue;
+            }
+            last_scheduled.0 = Some(occurrence);
+
+            let game_secs = entry.hours_left(hour) * SECS_PER_HOUR;
+            let duration = Duration::from_secs_f32(game_secs / GAME_SECONDS_PER_SECOND);
+            debug!(
+                "queuing scheduled `{:?}` for `{actor_entity}` for {duration:?}",
+                entry.activity
+            );
+            commands.entity(actor_entity).with_children(|parent| {
+                parent.spawn((
+                    Name::new(entry.activity.name()),
+                    ScheduledTask(entry.activity),
+                    TaskDuration(duration),
+                ));
+            });
+        }
+    }
+
+    /// Removes scheduled tasks when a player queues something else.
+    ///
+    /// The activity will be queued again once the actor becomes idle.
+    fn defer(
+        mut commands: Commands,
+        mut actors: Query<(Entity, &Children, &mut LastScheduled)>,
+        tasks: Query<(Entity, Has<ScheduledTask>), With<Task>>,
+    ) {
+        for (actor_entity, children, mut last_scheduled) in &mut actors {
+            let mut scheduled_entities = Vec::new();
+            let mut player_queued = false;
+            for (task_entity, scheduled) in tasks.iter_many(children) {
+                if scheduled {
+                    scheduled_entities.push(task_entity);
+                } else {
+                    player_queued = true;
+                }
+            }
+
+            if player_queued && !scheduled_entities.is_empty() {
+                debug!("deferring scheduled tasks for `{actor_entity}`");
+                for task_entity in scheduled_entities {
+                    commands.entity(task_entity).despawn();
+                }
+                last_scheduled.0 = None;
+            }
+        }
+    }
+
+    fn change(
+        mut change_events: EventReader<FromClient<ScheduleChange>>,
+        spectators: Res<Spectators>,
+        mut actors: Query<(&Actor, &mut DailySchedule)>,
+        controllers: Query<&Controller>,
+    ) {
+        for FromClient { client_id, event } in change_events.read() {
+            if spectators.contains(*client_id) {
+                warn!("ignoring schedule change from spectator `{client_id:?}`");
+                continue;
+            }
+            if !event.schedule.is_valid() {
+                error!("received invalid schedule from `{client_id:?}`");
+                continue;
+            }
+            let Ok((actor, mut schedule)) = actors.get_mut(event.actor_entity) else {
+                error!(
+                    "received schedule change for an invalid actor `{}`",
+                    event.actor_entity
+                );
+                continue;
+            };
+            let controller = controllers.get(actor.family_entity).ok();
+            if !can_control(controller, *client_id) {
+                warn!(
+                    "ignoring schedule change from `{client_id:?}` for `{}` controlled by another player",
+                    event.actor_entity
+                );
+                continue;
+            }
+
+            info!(
+                "`{client_id:?}` changes schedule for `{}`",
+                event.actor_entity
+            );
+            schedule.set_if_neq(event.schedule.clone());
+        }
+    }
+}
+
+/// Daily routine of an actor.
+///
+/// Activities are queued only when the actor has no other tasks.
+#[derive(Component, Clone, Debug, Deref, Deserialize, PartialEq, Reflect, Serialize)]
+#[reflect(Component)]
+#[require(LastScheduled)]
+pub struct DailySchedule(pub Vec<DailyScheduleEntry>);
+
+impl DailySchedule {
+    /// Returns the first entry that covers the hour.
+    fn current(&self, hour: f32) -> Option<DailyScheduleEntry> {
+        self.iter().copied().find(|entry| entry.contains(hour))
+    }
+
+    /// Returns `true` if all hours are within a day and no entry is empty.
+    pub fn is_valid(&self) -> bool {
+        self.iter()
+            .all(|entry| entry.start < 24 && entry.end < 24 && entry.start != entry.end)
+    }
+}
+
+impl Default for DailySchedule {
+    fn default() -> Self {
+        Self(vec![
+            DailyScheduleEntry::new(22, 6, Activity::Sleep),
+            DailyScheduleEntry::new(7, 8, Activity::Eat),
+            DailyScheduleEntry::new(9, 17, Activity::Work),
+            DailyScheduleEntry::new(18, 19, Activity::Eat),
+        ])
+    }
+}
+
+/// Activity between two hours of a day.
+///
+/// Wraps around midnight if the end is less than the start.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Reflect, Serialize)]
+pub struct DailyScheduleEntry {
+    pub start: u8,
+    pub end: u8,
+    pub activity: Activity,
+}
+
+impl DailyScheduleEntry {
+    pub fn new(start: u8, end: u8, activity: Activity) -> Self {
+        Self {
+            start,
+            end,
+            activity,
+        }
+    }
+
+    fn contains(self, hour: f32) -> bool {
+        let start = f32::from(self.start);
+        let end = f32::from(self.end);
+        if start <= end {
+            (start..end).contains(&hour)
+        } else {
+            hour >= start || hour < end
+        }
+    }
+
+    fn hours_left(self, hour: f32) -> f32 {
+        (f32::from(self.end) - hour).rem_euclid(24.0)
+    }
+}
+
+impl Default for DailyScheduleEntry {
+    fn default() -> Self {
+        Self::new(12, 13, Activity::Eat)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Reflect, Serialize)]
+pub enum Activity {
+    Sleep,
+    Eat,
+    Work,
+}
+
+impl Activity {
+    pub fn name(self) -> &'static str {
+        match self {
+            Activity::Sleep => "Sleep",
+            Activity::Eat => "Eat",
+            Activity::Work => "Work",
+        }
+    }
+
+    pub fn glyph(self) -> &'static str {
+        match self {
+            Activity::Sleep => "🛏",
+            Activity::Eat => "🍴",
+            Activity::Work => "💼",
+        }
+    }
+
+    /// Returns the next activity, wrapping around.
+    pub fn next(self) -> Self {
+        match self {
+            Activity::Sleep => Activity::Eat,
+            Activity::Eat => Activity::Work,
+            Activity::Work => Activity::Sleep,
+        }
+    }
+}
+
+/// Task queued from [`DailySchedule`].
+///
+/// Lasts until the end of its schedule entry.
+#[derive(Component, Clone, Copy, Deserialize, Reflect, Serialize)]
+#[reflect(Component)]
+#[require(Task, TaskGroups(|| TaskGroups::BOTH_HANDS | TaskGroups::LEGS))]
+pub struct ScheduledTask(pub Activity);
+
+/// Day and start hour of the last queued activity.
+#[derive(Component, Default)]
+struct LastScheduled(Option<(i64, u8)>);
+
+/// Replaces the schedule of an actor.
+#[derive(Clone, Deserialize, Event, Serialize)]
+pub struct ScheduleChange {
+    pub actor_entity: Entity,
+    pub schedule: DailySchedule,
+}
+
+impl MapEntities for ScheduleChange {
+    fn map_entities<T: EntityMapper>(&mut self, entity_mapper: &mut T) {
+        self.actor_entity = entity_mapper.map_entity(self.actor_entity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scheduled_sleep() {
+        let mut app = App::new();
+        app.add_systems(
+            Update,
+            (SchedulePlugin::defer, SchedulePlugin::queue).chain(),
+        );
+
+        let clock_entity = app.world_mut().spawn(WorldClock::at(0, 21.5)).id();
+        let actor_entity = app.world_mut().spawn(DailySchedule::default()).id();
+
+        app.update();
+
+        assert!(
+            app.world().get::<Children>(actor_entity).is_none(),
+            "nothing should be scheduled at this hour"
+        );
+
+        app.world_mut()
+            .entity_mut(clock_entity)
+            .insert(WorldClock::at(0, 22.5));
+        app.update();
+        app.update();
+
+        let children = app.world().get::<Children>(actor_entity).unwrap();
+        assert_eq!(children.len(), 1, "activity should be queued only once");
+        let task = app.world().get::<ScheduledTask>(children[0]).unwrap();
+        assert_eq!(task.0, Activity::Sleep);
+    }
+
+    #[test]
+    fn deferring() {
+        let mut app = App::new();
+        app.add_systems(
+            Update,
+            (SchedulePlugin::defer, SchedulePlugin::queue).chain(),
+        );
+
+        app.world_mut().spawn(WorldClock::at(0, 23.0));
+        let actor_entity = app.world_mut().spawn(DailySchedule::default()).id();
+
+        app.update();
+
+        let children = app.world().get::<Children>(actor_entity).unwrap();
+        let scheduled_entity = children[0];
+        let player_entity = app.world_mut().spawn(Task).set_parent(actor_entity).id();
+
+        app.update();
+
+        assert!(
+            app.world().get_entity(scheduled_entity).is_err(),
+            "scheduled task should be removed in favor of the player's task"
+        );
+        assert!(app.world().get_entity(player_entity).is_ok());
+    }
+
+    #[test]
+    fn controlled_change() {
+        let mut app = App::new();
+        app.add_event::<FromClient<ScheduleChange>>()
+            .init_resource::<Spectators>()
+            .add_systems(Update, SchedulePlugin::change);
+
+        let owner_id = ClientId::new(1);
+        let family_entity = app.world_mut().spawn(Controller(owner_id)).id();
+        let actor_entity = app
+            .world_mut()
+            .spawn((Actor { family_entity }, DailySchedule::default()))
+            .id();
+
+        for (client_id, hours) in [(ClientId::new(2), (1, 2)), (owner_id, (3, 4))] {
+            let (start, end) = hours;
+            app.world_mut().send_event(FromClient {
+                client_id,
+                event: ScheduleChange {
+                    actor_entity,
+                    schedule: DailySchedule(vec![DailyScheduleEntry::new(
+                        start,
+                        end,
+                        Activity::Sleep,
+                    )]),
+                },
+            });
+        }
+
+        app.update();
+
+        let schedule = app.world().get::<DailySchedule>(actor_entity).unwrap();
+        assert_eq!(
+            schedule.0,
+            [DailyScheduleEntry::new(3, 4, Activity::Sleep)],
+            "only the controlling player should be able to change the schedule"
+        );
+    }
+}
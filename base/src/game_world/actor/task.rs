@@ -2,15 +2,28 @@ mod friendly;
 mod linked_task;
 mod move_here;
 
-use std::any;
+use std::{any, time::Duration};
 
-use bevy::{ecs::entity::MapEntities, prelude::*, reflect::GetTypeRegistration};
+use bevy::{
+    ecs::entity::{EntityHashMap, MapEntities},
+    prelude::*,
+    reflect::GetTypeRegistration,
+};
 use bevy_replicon::prelude::*;
 use bitflags::bitflags;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use super::{animation_state::AnimationState, Actor, ActorTaskGroups, SelectedActor};
-use crate::game_world::{city::ActiveCity, family::FamilyMode, navigation::NavDestination};
+use crate::game_world::{
+    city::ActiveCity,
+    family::{
+        controller::{can_control, Controller},
+        FamilyMode,
+    },
+    navigation::{NavDestination, ROTATION_SPEED},
+    object::Usage,
+    spectator::Spectators,
+};
 use friendly::FriendlyPlugins;
 use linked_task::LinkedTaskPlugin;
 use move_here::MoveHerePlugin;
@@ -20,19 +33,33 @@ pub(super) struct TaskPlugin;
 impl Plugin for TaskPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins((FriendlyPlugins, LinkedTaskPlugin, MoveHerePlugin))
+            .register_type::<TaskProgress>()
+            .register_type::<QueueIndex>()
             .replicate::<ActiveTask>()
+            .replicate::<TaskProgress>()
+            .replicate::<TaskState>()
+            .replicate::<QueueIndex>()
             .add_client_event::<TaskCancel>(ChannelKind::Unordered)
+            .add_mapped_client_event::<TaskReorder>(ChannelKind::Ordered)
             .add_observer(Self::spawn_available.never_param_warn())
             .add_observer(Self::cleanup)
+            .add_observer(Self::release_usage)
             .add_systems(
                 PreUpdate,
-                Self::cancel
+                (Self::cancel, Self::reorder)
                     .after(ClientSet::Receive)
                     .run_if(server_or_singleplayer),
             )
+            .add_systems(
+                Update,
+                (Self::advance, Self::check_usage, Self::face_used_objects)
+                    .run_if(server_or_singleplayer),
+            )
             .add_systems(
                 PostUpdate,
-                Self::activate_queued.run_if(server_or_singleplayer),
+                (Self::complete, Self::assign_indices, Self::activate_queued)
+                    .chain()
+                    .run_if(server_or_singleplayer),
             );
     }
 }
@@ -76,30 +103,230 @@ impl TaskPlugin {
         });
     }
 
+    /// Puts new tasks at the end of their actor's queue.
+    fn assign_indices(
+        mut commands: Commands,
+        new_tasks: Query<(Entity, &Parent), (With<Task>, Without<QueueIndex>)>,
+        actors: Query<&Children>,
+        indices: Query<&QueueIndex>,
+    ) {
+        let mut next_indices = EntityHashMap::default();
+        for (task_entity, parent) in &new_tasks {
+            let next_index = next_indices.entry(**parent).or_insert_with(|| {
+                let children = actors.get(**parent).unwrap();
+                indices
+                    .iter_many(children)
+                    .map(|&index| *index + 1)
+                    .max()
+                    .unwrap_or_default()
+            });
+
+            debug!("assigning queue index {next_index} to `{task_entity}`");
+            commands.entity(task_entity).insert(QueueIndex(*next_index));
+            *next_index += 1;
+        }
+    }
+
+    /// Activates queued tasks whose groups are free in the order of [`QueueIndex`].
+    ///
+    /// Blocked tasks are skipped, so they don't stall tasks queued after them.
+    /// Tasks that use an object stay queued until the actor turns toward it,
+    /// and tasks queued after them can't take their groups meanwhile.
     fn activate_queued(
         mut commands: Commands,
-        tasks: Query<(Entity, &Name, &TaskGroups), Without<ActiveTask>>,
-        mut actors: Query<(&Children, &mut ActorTaskGroups)>,
+        tasks: Query<
+            (
+                Entity,
+                &Name,
+                &TaskGroups,
+                &TaskState,
+                Option<&UsedObject>,
+                Option<&QueueIndex>,
+            ),
+            Without<ActiveTask>,
+        >,
+        mut actors: Query<(
+            Entity,
+            &Children,
+            &mut ActorTaskGroups,
+            Option<&Transform>,
+            Has<FacingObject>,
+        )>,
+        mut objects: Query<&mut Usage>,
+        transforms: Query<&Transform>,
     ) {
-        for (children, mut actor_groups) in &mut actors {
-            for (entity, name, &groups) in tasks.iter_many(children) {
-                if !groups.intersects(**actor_groups) {
+        for (actor_entity, children, mut actor_groups, actor_transform, facing) in &mut actors {
+            let mut queued: Vec<_> = tasks.iter_many(children).collect();
+            queued.sort_by_key(|&(.., index)| index.map_or(u32::MAX, |index| **index));
+            let mut turning_groups = TaskGroups::empty();
+            let mut facing_object = None;
+            for (entity, name, &groups, state, used_object, _) in queued {
+                if let TaskState::Blocked { reason } = state {
+                    trace!("skipping blocked '{name}' for `{entity}`: {reason}");
+                    continue;
+                }
+
+                if !groups.intersects(**actor_groups | turning_groups) {
+                    if let Some(&object_entity) = used_object {
+                        // Could be taken by another actor in this frame.
+                        if objects
+                            .get(*object_entity)
+                            .is_ok_and(|usage| usage.is_full())
+                        {
+                            trace!("skipping '{name}' for `{entity}` with occupied object");
+                            continue;
+                        }
+
+                        let object_transform = transforms.get(*object_entity).ok();
+                        if let Some((actor_transform, object_transform)) =
+                            actor_transform.zip(object_transform)
+                        {
+                            if !is_facing(actor_transform, object_transform.translation) {
+                                trace!("waiting for `{actor_entity}` to face `{}`", *object_entity);
+                                turning_groups.insert(groups);
+                                facing_object.get_or_insert(*object_entity);
+                                continue;
+                            }
+                        }
+
+                        if let Ok(mut usage) = objects.get_mut(*object_entity) {
+                            debug!("reserving `{}` for `{actor_entity}`", *object_entity);
+                            usage.occupants.push(actor_entity);
+                        }
+                    }
+
                     debug!("activating '{name}' for `{entity}`");
                     actor_groups.insert(groups);
                     commands.entity(entity).insert(ActiveTask);
                 }
             }
+
+            if let Some(object_entity) = facing_object {
+                commands
+                    .entity(actor_entity)
+                    .insert(FacingObject(object_entity));
+            } else if facing {
+                commands.entity(actor_entity).remove::<FacingObject>();
+            }
+        }
+    }
+
+    /// Advances progress of active tasks with a fixed duration.
+    fn advance(
+        time: Res<Time>,
+        mut tasks: Query<(&TaskDuration, &mut TaskProgress), With<ActiveTask>>,
+    ) {
+        for (duration, mut progress) in &mut tasks {
+            **progress = (**progress + time.delta_secs() / duration.as_secs_f32()).min(1.0);
+        }
+    }
+
+    /// Turns actors toward objects that their queued tasks are waiting for.
+    ///
+    /// Skips actors that are still walking since navigation turns them along the path.
+    /// Only the yaw is changed to keep actors upright.
+    fn face_used_objects(
+        time: Res<Time>,
+        objects: Query<&Transform, Without<FacingObject>>,
+        mut actors: Query<(&mut Transform, &NavDestination, &FacingObject)>,
+    ) {
+        for (mut actor_transform, dest, facing_object) in &mut actors {
+            if dest.is_some() {
+                continue;
+            }
+            let Ok(object_transform) = objects.get(**facing_object) else {
+                continue;
+            };
+            let Some(target_rotation) =
+                facing_rotation(&actor_transform, object_transform.translation)
+            else {
+                continue;
+            };
+
+            let factor = (ROTATION_SPEED * time.delta_secs()).min(1.0);
+            actor_transform.rotation = actor_transform.rotation.slerp(target_rotation, factor);
+        }
+    }
+
+    /// Despawns active tasks that reached full progress.
+    fn complete(
+        mut commands: Commands,
+        tasks: Query<(Entity, &Parent, &Name, &TaskProgress), With<ActiveTask>>,
+    ) {
+        for (task_entity, parent, name, progress) in &tasks {
+            if **progress >= 1.0 {
+                debug!("completing '{name}' for `{}`", **parent);
+                commands.trigger(TaskCompleted {
+                    actor: **parent,
+                    task_kind: name.clone(),
+                });
+                commands.entity(task_entity).despawn();
+            }
+        }
+    }
+
+    /// Blocks queued tasks that use fully occupied objects.
+    fn check_usage(
+        objects: Query<&Usage>,
+        mut tasks: Query<(&UsedObject, &mut TaskState), Without<ActiveTask>>,
+    ) {
+        for (used_object, mut state) in &mut tasks {
+            let Ok(usage) = objects.get(**used_object) else {
+                continue;
+            };
+
+            let new_state = if usage.is_full() {
+                TaskState::Blocked {
+                    reason: "Object is in use".to_string(),
+                }
+            } else {
+                TaskState::Queued
+            };
+            state.set_if_neq(new_state);
+        }
+    }
+
+    /// Frees the reserved slot when an active task that uses an object is removed.
+    fn release_usage(
+        trigger: Trigger<OnRemove, UsedObject>,
+        tasks: Query<(&Parent, &UsedObject), With<ActiveTask>>,
+        mut objects: Query<&mut Usage>,
+    ) {
+        let Ok((parent, used_object)) = tasks.get(trigger.entity()) else {
+            return;
+        };
+        let Ok(mut usage) = objects.get_mut(**used_object) else {
+            return;
+        };
+
+        if let Some(index) = usage
+            .occupants
+            .iter()
+            .position(|&entity| entity == **parent)
+        {
+            debug!("releasing `{}` from `{}`", **used_object, **parent);
+            usage.occupants.swap_remove(index);
         }
     }
 
     fn cancel(
         mut commands: Commands,
         mut cancel_events: EventReader<FromClient<TaskCancel>>,
-        tasks: Query<(), With<Task>>,
+        spectators: Res<Spectators>,
+        tasks: Query<(&Parent, &Name), With<Task>>,
     ) {
         for FromClient { client_id, event } in cancel_events.read() {
-            if tasks.get(**event).is_ok() {
+            if spectators.contains(*client_id) {
+                warn!("ignoring task cancel from spectator `{client_id:?}`");
+                continue;
+            }
+
+            if let Ok((parent, name)) = tasks.get(**event) {
                 info!("`{client_id:?}` cancels task `{}`", **event);
+                commands.trigger(TaskCancelled {
+                    actor: **parent,
+                    task_kind: name.clone(),
+                });
                 commands.entity(**event).despawn();
             } else {
                 error!("task {:?} is not active", **event);
@@ -107,6 +334,62 @@ impl TaskPlugin {
         }
     }
 
+    /// Moves a queued task to the requested position.
+    ///
+    /// Indices past the end move the task to the end of the queue.
+    fn reorder(
+        mut reorder_events: EventReader<FromClient<TaskReorder>>,
+        spectators: Res<Spectators>,
+        tasks: Query<(&Parent, Has<ActiveTask>), With<Task>>,
+        actors: Query<&Children>,
+        mut indices: Query<(Entity, &mut QueueIndex), Without<ActiveTask>>,
+    ) {
+        for FromClient { client_id, event } in reorder_events.read() {
+            if spectators.contains(*client_id) {
+                warn!("ignoring task reorder from spectator `{client_id:?}`");
+                continue;
+            }
+
+            let Ok((parent, active)) = tasks.get(event.task_entity) else {
+                error!("`{}` is not a task", event.task_entity);
+                continue;
+            };
+            if active {
+                warn!("ignoring reorder of active task `{}`", event.task_entity);
+                continue;
+            }
+
+            let children = actors.get(**parent).unwrap();
+            let mut queued: Vec<_> = indices
+                .iter_many(children)
+                .map(|(entity, &index)| (entity, index))
+                .collect();
+            queued.sort_by_key(|&(_, index)| index);
+            let mut queued: Vec<_> = queued.into_iter().map(|(entity, _)| entity).collect();
+
+            let Some(position) = queued
+                .iter()
+                .position(|&entity| entity == event.task_entity)
+            else {
+                // The task was queued in this frame and doesn't have an index yet.
+                warn!("ignoring reorder of unindexed task `{}`", event.task_entity);
+                continue;
+            };
+
+            info!(
+                "`{client_id:?}` moves task `{}` to {}",
+                event.task_entity, event.index
+            );
+            queued.remove(position);
+            queued.insert(event.index.min(queued.len()), event.task_entity);
+
+            for (index, &entity) in queued.iter().enumerate() {
+                let (_, mut queue_index) = indices.get_mut(entity).unwrap();
+                queue_index.set_if_neq(QueueIndex(index as u32));
+            }
+        }
+    }
+
     fn cleanup(
         trigger: Trigger<OnRemove, TaskGroups>,
         tasks: Query<(&Parent, &TaskGroups), With<ActiveTask>>,
@@ -133,6 +416,34 @@ impl TaskPlugin {
     }
 }
 
+/// Maximum angle in radians between the actor's forward direction and an object to start using it.
+const FACING_TOLERANCE: f32 = 0.2;
+
+/// Returns `true` if the actor is turned toward the point within [`FACING_TOLERANCE`].
+fn is_facing(transform: &Transform, point: Vec3) -> bool {
+    facing_rotation(transform, point)
+        .is_none_or(|rotation| transform.rotation.angle_between(rotation) <= FACING_TOLERANCE)
+}
+
+/// Returns the upright rotation that turns the actor toward the point.
+///
+/// Returns [`None`] if the point is directly above or below the actor.
+fn facing_rotation(transform: &Transform, point: Vec3) -> Option<Quat> {
+    let mut direction = point - transform.translation;
+    direction.y = 0.0;
+    if direction.length_squared() <= f32::EPSILON {
+        return None;
+    }
+
+    Some(transform.looking_to(direction, Vec3::Y).rotation)
+}
+
+/// Object toward which the actor turns before activating a task that uses it.
+///
+/// Inserted on server by [`TaskPlugin::activate_queued`].
+#[derive(Component, Deref)]
+struct FacingObject(Entity);
+
 #[derive(Component)]
 /// Stores available tasks for an entity, triggered by picking.
 pub struct AvailableTasks {
@@ -142,12 +453,83 @@ pub struct AvailableTasks {
 }
 
 #[derive(Component, Default)]
-#[require(Name, TaskGroups, ParentSync, Replicated)]
+#[require(Name, TaskGroups, TaskProgress, TaskState, ParentSync, Replicated)]
 pub struct Task;
 
+/// Whether a queued task can be started.
+///
+/// Tasks update it on server for their queued instances and re-check each frame,
+/// so blocked tasks start automatically once the conditions change.
+#[derive(Component, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum TaskState {
+    #[default]
+    Queued,
+    Blocked {
+        reason: String,
+    },
+}
+
 #[derive(Component, Serialize, Deserialize)]
 pub struct ActiveTask;
 
+/// Position of a queued task in its actor's queue.
+///
+/// Tasks with lower values are activated first.
+/// Assigned on server when a task is queued and changed by [`TaskReorder`].
+#[derive(
+    Component,
+    Reflect,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Deref,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+)]
+#[reflect(Component)]
+pub struct QueueIndex(pub u32);
+
+/// Completion fraction of a task from 0 to 1.
+///
+/// Updated by each task on server while it's active.
+#[derive(
+    Component, Reflect, Clone, Copy, Debug, Default, Deref, DerefMut, Serialize, Deserialize,
+)]
+#[reflect(Component)]
+pub struct TaskProgress(pub f32);
+
+/// Object that a task interacts with.
+///
+/// If the object has [`Usage`], the task waits for a free slot before becoming active.
+#[derive(Component, Clone, Copy, Deref)]
+pub struct UsedObject(pub Entity);
+
+/// Simulation time needed to complete a task.
+///
+/// Tasks with this component advance their [`TaskProgress`] automatically.
+/// Other tasks update the progress themselves.
+#[derive(Component, Clone, Copy, Deref)]
+pub struct TaskDuration(pub Duration);
+
+/// Triggered on server when a task reaches full progress.
+#[derive(Event)]
+pub struct TaskCompleted {
+    pub actor: Entity,
+    pub task_kind: Name,
+}
+
+/// Triggered on server when a player cancels a task.
+#[derive(Event)]
+pub struct TaskCancelled {
+    pub actor: Entity,
+    pub task_kind: Name,
+}
+
 bitflags! {
     #[derive(Default, Component, Clone, Copy, Debug)]
     pub(super) struct TaskGroups: u8 {
@@ -168,6 +550,21 @@ pub struct TaskSelect;
 #[derive(Deserialize, Event, Serialize, Deref)]
 pub struct TaskCancel(pub Entity);
 
+/// An event of moving a queued task to the specified position in its actor's queue.
+///
+/// Emitted by players. Active tasks can't be reordered.
+#[derive(Deserialize, Event, Serialize, Clone, Copy)]
+pub struct TaskReorder {
+    pub task_entity: Entity,
+    pub index: usize,
+}
+
+impl MapEntities for TaskReorder {
+    fn map_entities<T: EntityMapper>(&mut self, entity_mapper: &mut T) {
+        self.task_entity = entity_mapper.map_entity(self.task_entity);
+    }
+}
+
 #[derive(Event, Clone, Copy, Serialize, Deserialize)]
 pub struct TaskRequest<C> {
     pub entity: Entity,
@@ -294,19 +691,409 @@ fn request<R, C>(
 fn queue<R, C>(
     mut commands: Commands,
     mut request_events: EventReader<FromClient<R>>,
-    actors: Query<(), With<Actor>>,
+    spectators: Res<Spectators>,
+    actors: Query<&Actor>,
+    controllers: Query<&Controller>,
 ) where
     R: Request<C> + Copy + Event,
     C: Component + Copy,
 {
     for FromClient { client_id, event } in request_events.read() {
-        if actors.get(event.entity()).is_ok() {
-            info!("`{client_id:?}` requests task `{}`", any::type_name::<C>());
-            commands.entity(event.entity()).with_children(|parent| {
-                parent.spawn(event.take_task());
-            });
-        } else {
+        if spectators.contains(*client_id) {
+            warn!("ignoring task request from spectator `{client_id:?}`");
+            continue;
+        }
+
+        let Ok(actor) = actors.get(event.entity()) else {
             error!("entity {:?} is not an actor", event.entity());
+            continue;
+        };
+        let controller = controllers.get(actor.family_entity).ok();
+        if !can_control(controller, *client_id) {
+            warn!(
+                "ignoring task request from `{client_id:?}` for `{}` controlled by another player",
+                event.entity()
+            );
+            continue;
+        }
+
+        info!("`{client_id:?}` requests task `{}`", any::type_name::<C>());
+        commands.entity(event.entity()).with_children(|parent| {
+            parent.spawn(event.take_task());
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::time::TimeUpdateStrategy;
+
+    use super::*;
+
+    #[test]
+    fn completion() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )))
+            .init_resource::<TaskEvents>()
+            .add_observer(record_completed)
+            .add_observer(record_cancelled)
+            .add_systems(Update, TaskPlugin::advance)
+            .add_systems(PostUpdate, TaskPlugin::complete);
+
+        let actor_entity = app.world_mut().spawn_empty().id();
+        let task_entity = app
+            .world_mut()
+            .spawn((
+                Name::new("Dummy"),
+                Task,
+                ActiveTask,
+                TaskDuration(Duration::from_secs(1)),
+            ))
+            .set_parent(actor_entity)
+            .id();
+
+        app.update();
+        app.update();
+
+        let progress = **app.world().get::<TaskProgress>(task_entity).unwrap();
+        assert!(progress > 0.0, "active task should advance");
+        assert!(
+            progress < 1.0,
+            "task shouldn't complete before its duration"
+        );
+
+        for _ in 0..20 {
+            app.update();
         }
+
+        assert!(
+            app.world().get_entity(task_entity).is_err(),
+            "completed task should be despawned"
+        );
+        let events = app.world().resource::<TaskEvents>();
+        assert_eq!(events.completed, [actor_entity]);
+        assert!(events.cancelled.is_empty());
+    }
+
+    #[test]
+    fn queued_doesnt_advance() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )))
+            .add_systems(Update, TaskPlugin::advance);
+
+        let task_entity = app
+            .world_mut()
+            .spawn((Task, TaskDuration(Duration::from_secs(1))))
+            .id();
+
+        app.update();
+        app.update();
+
+        let progress = **app.world().get::<TaskProgress>(task_entity).unwrap();
+        assert_eq!(progress, 0.0);
+    }
+
+    #[test]
+    fn controlled_requests() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_event::<FromClient<TaskRequest<DummyTask>>>()
+            .init_resource::<Spectators>()
+            .add_systems(PreUpdate, queue::<TaskRequest<DummyTask>, _>);
+
+        let owner_id = ClientId::new(1);
+        let family_entity = app.world_mut().spawn(Controller(owner_id)).id();
+        let actor_entity = app.world_mut().spawn(Actor { family_entity }).id();
+
+        for client_id in [ClientId::new(2), owner_id] {
+            app.world_mut().send_event(FromClient {
+                client_id,
+                event: TaskRequest {
+                    entity: actor_entity,
+                    task: DummyTask,
+                },
+            });
+        }
+
+        app.update();
+
+        let mut tasks = app.world_mut().query_filtered::<&Parent, With<DummyTask>>();
+        let parents: Vec<_> = tasks.iter(app.world()).map(|parent| **parent).collect();
+        assert_eq!(
+            parents,
+            [actor_entity],
+            "only the controlling player should be able to request tasks"
+        );
+    }
+
+    #[test]
+    fn cancellation() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_event::<FromClient<TaskCancel>>()
+            .init_resource::<Spectators>()
+            .init_resource::<TaskEvents>()
+            .add_observer(record_completed)
+            .add_observer(record_cancelled)
+            .add_systems(PreUpdate, TaskPlugin::cancel)
+            .add_systems(PostUpdate, TaskPlugin::complete);
+
+        let actor_entity = app.world_mut().spawn_empty().id();
+        let task_entity = app
+            .world_mut()
+            .spawn((Name::new("Dummy"), Task, ActiveTask))
+            .set_parent(actor_entity)
+            .id();
+
+        app.world_mut().send_event(FromClient {
+            client_id: ClientId::SERVER,
+            event: TaskCancel(task_entity),
+        });
+
+        app.update();
+
+        assert!(
+            app.world().get_entity(task_entity).is_err(),
+            "cancelled task should be despawned"
+        );
+        let events = app.world().resource::<TaskEvents>();
+        assert_eq!(events.cancelled, [actor_entity]);
+        assert!(events.completed.is_empty());
+    }
+
+    #[test]
+    fn reordering() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_event::<FromClient<TaskReorder>>()
+            .init_resource::<Spectators>()
+            .add_systems(PreUpdate, TaskPlugin::reorder)
+            .add_systems(PostUpdate, TaskPlugin::assign_indices);
+
+        let actor_entity = app.world_mut().spawn_empty().id();
+        let active_entity = app
+            .world_mut()
+            .spawn((Task, ActiveTask, QueueIndex(0)))
+            .set_parent(actor_entity)
+            .id();
+        let mut task_entities = Vec::new();
+        for _ in 0..3 {
+            let task_entity = app.world_mut().spawn(Task).set_parent(actor_entity).id();
+            task_entities.push(task_entity);
+        }
+
+        app.update();
+        assert_eq!(
+            queue(&mut app),
+            task_entities,
+            "new tasks should be queued in order"
+        );
+
+        send_reorder(&mut app, task_entities[2], 0);
+        assert_eq!(
+            queue(&mut app),
+            [task_entities[2], task_entities[0], task_entities[1]],
+            "task should be moved to the front"
+        );
+
+        send_reorder(&mut app, task_entities[2], 10);
+        assert_eq!(
+            queue(&mut app),
+            task_entities,
+            "index past the end should move the task to the end"
+        );
+
+        send_reorder(&mut app, active_entity, 2);
+        assert_eq!(queue(&mut app), task_entities);
+        assert_eq!(
+            *app.world().get::<QueueIndex>(active_entity).unwrap(),
+            QueueIndex(0),
+            "active task shouldn't be reordered"
+        );
+
+        let new_entity = app.world_mut().spawn(Task).set_parent(actor_entity).id();
+        app.update();
+        assert_eq!(
+            queue(&mut app).last(),
+            Some(&new_entity),
+            "task queued after reordering should be placed at the end"
+        );
+    }
+
+    #[test]
+    fn blocked_skipping() {
+        let mut app = App::new();
+        app.add_systems(Update, TaskPlugin::activate_queued);
+
+        let actor_entity = app.world_mut().spawn(ActorTaskGroups::default()).id();
+        let blocked_entity = app
+            .world_mut()
+            .spawn((
+                Task,
+                TaskGroups::LEGS,
+                TaskState::Blocked {
+                    reason: "Dummy".to_string(),
+                },
+            ))
+            .set_parent(actor_entity)
+            .id();
+        let queued_entity = app
+            .world_mut()
+            .spawn((Task, TaskGroups::LEGS))
+            .set_parent(actor_entity)
+            .id();
+
+        app.update();
+
+        assert!(!app.world().entity(blocked_entity).contains::<ActiveTask>());
+        assert!(
+            app.world().entity(queued_entity).contains::<ActiveTask>(),
+            "task after the blocked one should be activated"
+        );
+    }
+
+    #[test]
+    fn occupied_object() {
+        let mut app = App::new();
+        app.add_observer(TaskPlugin::release_usage)
+            .add_systems(Update, TaskPlugin::check_usage)
+            .add_systems(PostUpdate, TaskPlugin::activate_queued);
+
+        let object_entity = app.world_mut().spawn(Usage::new(1)).id();
+        let mut task_entities = Vec::new();
+        for _ in 0..2 {
+            let actor_entity = app.world_mut().spawn(ActorTaskGroups::default()).id();
+            let task_entity = app
+                .world_mut()
+                .spawn((Task, UsedObject(object_entity)))
+                .set_parent(actor_entity)
+                .id();
+            task_entities.push(task_entity);
+        }
+
+        app.update();
+
+        let (active, waiting): (Vec<_>, Vec<_>) = task_entities
+            .iter()
+            .partition(|&&entity| app.world().entity(entity).contains::<ActiveTask>());
+        assert_eq!(active.len(), 1, "only one actor should use the object");
+        assert_eq!(waiting.len(), 1);
+
+        app.update();
+
+        let waiting_entity = *waiting[0];
+        let state = app.world().get::<TaskState>(waiting_entity).unwrap();
+        assert!(
+            matches!(state, TaskState::Blocked { .. }),
+            "second actor should wait for the object"
+        );
+
+        app.world_mut().despawn(*active[0]);
+        app.update();
+
+        assert!(
+            app.world().entity(waiting_entity).contains::<ActiveTask>(),
+            "second actor should take the freed slot"
+        );
+        let usage = app.world().get::<Usage>(object_entity).unwrap();
+        assert_eq!(usage.occupants.len(), 1);
+    }
+
+    #[test]
+    fn facing_used_object() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )))
+            .add_systems(Update, TaskPlugin::face_used_objects)
+            .add_systems(PostUpdate, TaskPlugin::activate_queued);
+
+        let object_translation = Vec3::new(2.0, 1.0, 0.0);
+        let object_entity = app
+            .world_mut()
+            .spawn((
+                Usage::new(1),
+                Transform::from_translation(object_translation),
+            ))
+            .id();
+        let actor_entity = app
+            .world_mut()
+            .spawn((
+                ActorTaskGroups::default(),
+                Transform::default(),
+                NavDestination::default(),
+            ))
+            .id();
+        let task_entity = app
+            .world_mut()
+            .spawn((Task, UsedObject(object_entity)))
+            .set_parent(actor_entity)
+            .id();
+
+        app.update();
+
+        assert!(
+            !app.world().entity(task_entity).contains::<ActiveTask>(),
+            "task should wait for the actor to turn"
+        );
+
+        for _ in 0..20 {
+            app.update();
+        }
+
+        assert!(app.world().entity(task_entity).contains::<ActiveTask>());
+        assert!(!app.world().entity(actor_entity).contains::<FacingObject>());
+
+        let transform = app.world().get::<Transform>(actor_entity).unwrap();
+        let forward = transform.forward();
+        assert!(
+            forward.dot(Vec3::X) > FACING_TOLERANCE.cos(),
+            "actor should face the object, but faces `{forward}`"
+        );
+        assert!(forward.y.abs() < 0.001, "actor should stay upright");
+    }
+
+    fn send_reorder(app: &mut App, task_entity: Entity, index: usize) {
+        app.world_mut().send_event(FromClient {
+            client_id: ClientId::SERVER,
+            event: TaskReorder { task_entity, index },
+        });
+        app.update();
+    }
+
+    /// Returns queued tasks in the order of activation.
+    fn queue(app: &mut App) -> Vec<Entity> {
+        let mut queued: Vec<_> = app
+            .world_mut()
+            .query_filtered::<(Entity, &QueueIndex), Without<ActiveTask>>()
+            .iter(app.world())
+            .map(|(entity, &index)| (entity, index))
+            .collect();
+        queued.sort_by_key(|&(_, index)| index);
+        queued.into_iter().map(|(entity, _)| entity).collect()
+    }
+
+    #[derive(Clone, Copy, Component)]
+    struct DummyTask;
+
+    #[derive(Resource, Default)]
+    struct TaskEvents {
+        completed: Vec<Entity>,
+        cancelled: Vec<Entity>,
+    }
+
+    fn record_completed(trigger: Trigger<TaskCompleted>, mut events: ResMut<TaskEvents>) {
+        events.completed.push(trigger.actor);
+    }
+
+    fn record_cancelled(trigger: Trigger<TaskCancelled>, mut events: ResMut<TaskEvents>) {
+        events.cancelled.push(trigger.actor);
     }
 }
@@ -6,10 +6,12 @@ use crate::{
     game_world::{
         actor::{
             animation_state::{AnimationState, Montage, MontageFinished},
+            needs::{self, Need},
             task::{
                 linked_task::LinkedTask, ActiveTask, AvailableTasks, Task, TaskAppExt, TaskGroups,
+                TaskProgress,
             },
-            Actor, ActorAnimation, Movement,
+            Actor, ActorAnimation, Movement, MovementSpeed,
         },
         navigation::{following::Following, Navigation},
     },
@@ -49,17 +51,19 @@ impl TellSecretPlugin {
     fn activate(
         trigger: Trigger<OnAdd, ActiveTask>,
         mut commands: Commands,
-        mut actors: Query<&mut Navigation>,
+        mut actors: Query<(&MovementSpeed, Option<&Children>, &mut Navigation)>,
         tasks: Query<(&Parent, &TellSecret)>,
+        needs: Query<&Need>,
     ) {
         let Ok((parent, tell_secret)) = tasks.get(trigger.entity()) else {
             return;
         };
 
-        let mut navigation = actors
+        let (&speed, children, mut navigation) = actors
             .get_mut(**parent)
             .expect("actors should have navigation component");
-        *navigation = Navigation::new(Movement::Walk.speed()).with_offset(0.5);
+        let movement = Movement::Walk.with_urgency(needs::has_critical_need(children, &needs));
+        *navigation = Navigation::new(speed.get(movement)).with_offset(0.5);
 
         commands
             .entity(**parent)
@@ -125,16 +129,15 @@ impl TellSecretPlugin {
 
     fn finish(
         trigger: Trigger<MontageFinished>,
-        mut commands: Commands,
         children: Query<&Children>,
-        tasks: Query<Entity, (With<TellSecret>, With<ActiveTask>)>,
+        mut tasks: Query<&mut TaskProgress, (With<TellSecret>, With<ActiveTask>)>,
     ) {
         let Ok(children) = children.get(trigger.entity()) else {
             return;
         };
 
-        if let Some(task_entity) = tasks.iter_many(children).next() {
-            commands.entity(task_entity).despawn();
+        if let Some(mut progress) = tasks.iter_many_mut(children).fetch_next() {
+            **progress = 1.0;
         }
     }
 }
@@ -1,12 +1,18 @@
 use bevy::prelude::*;
+use bevy_replicon::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use super::{ActiveTask, AvailableTasks, Task, TaskAppExt, TaskGroups};
+use vleue_navigator::prelude::*;
+
+use super::{ActiveTask, AvailableTasks, Task, TaskAppExt, TaskGroups, TaskProgress, TaskState};
 use crate::{
     core::GameState,
     game_world::{
-        actor::Movement,
-        city::Ground,
+        actor::{
+            needs::{self, Need},
+            Movement, MovementSpeed,
+        },
+        city::{CityNavMesh, Ground},
         navigation::{NavDestination, Navigation},
     },
 };
@@ -18,7 +24,12 @@ impl Plugin for MoveHerePlugin {
         app.add_task::<MoveHere>()
             .add_observer(Self::add_to_list)
             .add_observer(Self::activate)
-            .add_systems(Update, Self::finish.run_if(in_state(GameState::InGame)));
+            .add_systems(
+                Update,
+                (Self::check_reachability, Self::finish)
+                    .run_if(in_state(GameState::InGame))
+                    .run_if(server_or_singleplayer),
+            );
     }
 }
 
@@ -54,33 +65,99 @@ impl MoveHerePlugin {
 
     fn activate(
         trigger: Trigger<OnAdd, ActiveTask>,
-        mut actors: Query<(&mut Navigation, &mut NavDestination)>,
+        mut commands: Commands,
+        mut actors: Query<(
+            &Transform,
+            &MovementSpeed,
+            Option<&Children>,
+            &mut Navigation,
+            &mut NavDestination,
+        )>,
         tasks: Query<(&Parent, &MoveHere)>,
+        needs: Query<&Need>,
     ) {
         let Ok((parent, move_here)) = tasks.get(trigger.entity()) else {
             return;
         };
 
         debug!("starting movement");
-        let (mut navigation, mut dest) = actors
+        let (transform, &speed, children, mut navigation, mut dest) = actors
             .get_mut(**parent)
             .expect("actors should have navigation component");
-        *navigation = Navigation::new(move_here.movement.speed());
+        let movement = move_here
+            .movement
+            .with_urgency(needs::has_critical_need(children, &needs));
+        *navigation = Navigation::new(speed.get(movement));
         **dest = Some(move_here.endpoint);
+
+        let distance = transform.translation.distance(move_here.endpoint);
+        commands
+            .entity(trigger.entity())
+            .insert(StartDistance(distance));
+    }
+
+    /// Blocks queued movement to points that can't be reached.
+    fn check_reachability(
+        navmeshes: Res<Assets<NavMesh>>,
+        cities: Query<&CityNavMesh>,
+        city_navmeshes: Query<&ManagedNavMesh>,
+        actors: Query<(&Parent, &Transform)>,
+        mut tasks: Query<(&Parent, &MoveHere, &mut TaskState), Without<ActiveTask>>,
+    ) {
+        for (parent, move_here, mut state) in &mut tasks {
+            let (city_entity, transform) = actors
+                .get(**parent)
+                .expect("tasks should be children of actors");
+            let navmesh_entity = cities
+                .get(**city_entity)
+                .expect("all actors should have city as parents");
+            let navmesh_handle = city_navmeshes
+                .get(**navmesh_entity)
+                .expect("city navmesh should always be valid");
+            let Some(navmesh) = navmeshes.get(navmesh_handle) else {
+                continue;
+            };
+
+            let new_state = if navmesh
+                .transformed_path(transform.translation, move_here.endpoint)
+                .is_some()
+            {
+                TaskState::Queued
+            } else {
+                TaskState::Blocked {
+                    reason: "Destination can't be reached".to_string(),
+                }
+            };
+            state.set_if_neq(new_state);
+        }
     }
 
     fn finish(
-        mut commands: Commands,
-        actors: Query<&NavDestination>,
-        tasks: Query<(Entity, &Parent), (With<MoveHere>, With<ActiveTask>)>,
+        actors: Query<(&Transform, &NavDestination)>,
+        mut tasks: Query<
+            (
+                Entity,
+                &Parent,
+                &MoveHere,
+                &StartDistance,
+                &mut TaskProgress,
+            ),
+            With<ActiveTask>,
+        >,
     ) {
-        for (task_entity, parent) in &tasks {
-            let dest = actors
+        for (task_entity, parent, move_here, start_distance, mut progress) in &mut tasks {
+            let (transform, dest) = actors
                 .get(**parent)
                 .expect("actors should have always have destination");
             if dest.is_none() {
-                debug!("ending movement");
-                commands.entity(task_entity).despawn();
+                debug!("ending movement for `{task_entity}`");
+                **progress = 1.0;
+            } else if **start_distance > 0.0 {
+                let distance = transform.translation.distance(move_here.endpoint);
+                let new_progress = (1.0 - distance / **start_distance).clamp(0.0, 1.0);
+                if **progress != new_progress {
+                    **progress = new_progress;
+                }
             }
         }
     }
@@ -93,3 +170,53 @@ struct MoveHere {
     endpoint: Vec3,
     movement: Movement,
 }
+
+/// Distance to the endpoint at the moment of activation.
+///
+/// Used to calculate [`TaskProgress`].
+#[derive(Component, Deref)]
+struct StartDistance(f32);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn critical_need_running() {
+        let mut app = App::new();
+        app.add_observer(MoveHerePlugin::activate);
+
+        let speed = MovementSpeed::default();
+        let actor_entity = app
+            .world_mut()
+            .spawn((Transform::default(), speed, Navigation::default()))
+            .id();
+        app.world_mut().spawn(Need(50.0)).set_parent(actor_entity);
+        let task_entity = app
+            .world_mut()
+            .spawn(MoveHere {
+                endpoint: Vec3::X,
+                movement: Movement::Walk,
+            })
+            .set_parent(actor_entity)
+            .id();
+
+        app.world_mut().entity_mut(task_entity).insert(ActiveTask);
+
+        let navigation = app.world().get::<Navigation>(actor_entity).unwrap();
+        assert_eq!(navigation.speed(), speed.walk);
+
+        app.world_mut().spawn(Need(5.0)).set_parent(actor_entity);
+        app.world_mut()
+            .entity_mut(task_entity)
+            .remove::<ActiveTask>();
+        app.world_mut().entity_mut(task_entity).insert(ActiveTask);
+
+        let navigation = app.world().get::<Navigation>(actor_entity).unwrap();
+        assert_eq!(
+            navigation.speed(),
+            speed.run,
+            "critical need should make the actor run"
+        );
+    }
+}
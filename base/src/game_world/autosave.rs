@@ -0,0 +1,132 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+use super::{spectator::Spectator, SaveKind, SaveState, WorldName};
+use crate::{
+    core::GameState, error_message::error_message, game_paths::GamePaths, settings::Settings,
+};
+
+/// Periodically saves the world into separate files.
+///
+/// Used to recover when the main world file is damaged.
+/// Autosaves rotate between several slots, see [`GamePaths::next_autosave_path`].
+pub(super) struct AutosavePlugin;
+
+impl Plugin for AutosavePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::InGame), Self::reset_timer)
+            .add_systems(
+                Update,
+                Self::save
+                    .pipe(error_message)
+                    .run_if(|settings: Res<Settings>| settings.autosave.enabled)
+                    .run_if(server_or_singleplayer)
+                    .run_if(not(resource_exists::<Spectator>))
+                    .run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+impl AutosavePlugin {
+    /// Counts the interval from entering a world, not from the previous one.
+    fn reset_timer(mut commands: Commands, time: Res<Time<Real>>) {
+        commands.insert_resource(LastAutosave(time.elapsed()));
+    }
+
+    fn save(
+        mut commands: Commands,
+        world: &World,
+        last_save: Res<LastAutosave>,
+        time: Res<Time<Real>>,
+        settings: Res<Settings>,
+        world_name: Res<WorldName>,
+        game_paths: Res<GamePaths>,
+    ) -> Result<()> {
+        // Postpone until the current save is written.
+        if time.elapsed() - **last_save < settings.autosave.interval()
+            || world.resource::<SaveState>().in_progress()
+        {
+            return Ok(());
+        }
+        commands.insert_resource(LastAutosave(time.elapsed()));
+
+        let autosave_path = game_paths.next_autosave_path(&world_name.0);
+        info!("autosaving world to {autosave_path:?}");
+
+        super::start_saving(
+            &mut commands,
+            world,
+            game_paths.worlds.clone(),
+            autosave_path,
+            SaveKind::Autosave,
+        )
+    }
+}
+
+/// Time of the last autosave or of entering the world.
+#[derive(Resource, Deref)]
+struct LastAutosave(Duration);
+
+#[cfg(test)]
+mod tests {
+    use bevy::{state::app::StatesPlugin, tasks::block_on, time::TimeUpdateStrategy};
+
+    use super::*;
+    use crate::{
+        game_paths::TempDir,
+        game_world::PendingSave,
+        settings::{AutosaveSettings, MIN_AUTOSAVE_INTERVAL},
+    };
+
+    #[test]
+    fn interval() -> Result<()> {
+        let dir = TempDir::new("autosave_interval");
+
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            RepliconPlugins,
+            AutosavePlugin,
+        ))
+        .init_state::<GameState>()
+        .init_resource::<SaveState>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs(
+            (MIN_AUTOSAVE_INTERVAL / 2).into(),
+        )))
+        .insert_resource(Settings {
+            autosave: AutosaveSettings {
+                enabled: true,
+                interval_secs: 0,
+            },
+            ..Default::default()
+        })
+        .insert_resource(dir.game_paths())
+        .insert_resource(WorldName("World".to_string()));
+
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::InGame);
+        app.update();
+        app.update();
+        assert!(
+            !app.world().contains_resource::<PendingSave>(),
+            "world shouldn't be saved before the interval"
+        );
+
+        app.update();
+        let mut pending_save = app
+            .world_mut()
+            .remove_resource::<PendingSave>()
+            .expect("world should be saved after the interval");
+        block_on(&mut pending_save.task)?;
+
+        let game_paths = app.world().resource::<GamePaths>();
+        assert!(game_paths.latest_autosave_path("World").is_some());
+
+        Ok(())
+    }
+}
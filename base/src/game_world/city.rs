@@ -4,7 +4,6 @@ use std::f32::consts::FRAC_PI_2;
 
 use avian3d::prelude::*;
 use bevy::{prelude::*, render::mesh::VertexAttributeValues};
-use bevy_atmosphere::prelude::*;
 use bevy_replicon::prelude::*;
 use serde::{Deserialize, Serialize};
 use strum::EnumIter;
@@ -13,7 +12,11 @@ use vleue_navigator::prelude::*;
 use super::{actor::SelectedActor, WorldState};
 use crate::{
     core::GameState,
-    game_world::{actor::ACTOR_RADIUS, player_camera::PlayerCamera, Layer},
+    game_world::{
+        actor::ACTOR_RADIUS,
+        player_camera::{DynamicSky, PlayerCamera},
+        Layer,
+    },
 };
 use road::RoadPlugin;
 
@@ -43,7 +46,7 @@ impl Plugin for CityPlugin {
 }
 
 /// City square side size.
-const CITY_SIZE: f32 = 500.0;
+pub(super) const CITY_SIZE: f32 = 500.0;
 pub(super) const HALF_CITY_SIZE: f32 = CITY_SIZE / 2.0;
 
 impl CityPlugin {
@@ -109,7 +112,7 @@ impl CityPlugin {
                 Sun,
                 Transform::from_xyz(4.0, 7.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
             ));
-            parent.spawn((PlayerCamera, AtmosphereCamera::default()));
+            parent.spawn((PlayerCamera, DynamicSky));
         });
     }
 
@@ -229,3 +232,57 @@ pub(super) struct Ground;
     }),
 )]
 struct Sun;
+
+#[cfg(test)]
+mod tests {
+    use bevy_replicon::test_app::ServerTestAppExt;
+
+    use super::*;
+
+    #[test]
+    fn rename_replication() {
+        let mut server_app = App::new();
+        let mut client_app = App::new();
+        for app in [&mut server_app, &mut client_app] {
+            app.add_plugins((
+                MinimalPlugins,
+                RepliconPlugins.set(ServerPlugin {
+                    tick_policy: TickPolicy::EveryFrame,
+                    ..Default::default()
+                }),
+            ))
+            .replicate_group::<(City, Name)>();
+        }
+
+        server_app.connect_client(&mut client_app);
+
+        let server_entity = server_app
+            .world_mut()
+            .spawn((City, Name::new("Old name")))
+            .id();
+
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+        server_app.exchange_with_client(&mut client_app);
+
+        let mut names = client_app.world_mut().query_filtered::<&Name, With<City>>();
+        assert_eq!(names.single(client_app.world()).as_str(), "Old name");
+
+        server_app
+            .world_mut()
+            .get_mut::<Name>(server_entity)
+            .unwrap()
+            .set("New name");
+
+        server_app.update();
+        server_app.exchange_with_client(&mut client_app);
+        client_app.update();
+
+        assert_eq!(
+            names.single(client_app.world()).as_str(),
+            "New name",
+            "rename on the server should update the client"
+        );
+    }
+}
@@ -18,6 +18,7 @@ use crate::{
             PendingCommand,
         },
         segment::{PointKind, Segment, SegmentConnections, SegmentPlugin},
+        spectator::Spectators,
         Layer,
     },
 };
@@ -113,9 +114,15 @@ impl RoadPlugin {
         mut commands: Commands,
         mut request_events: EventReader<FromClient<CommandRequest<RoadCommand>>>,
         mut confirm_events: EventWriter<ToClients<CommandConfirmation>>,
+        spectators: Res<Spectators>,
         mut roads: Query<&mut Segment, With<Road>>,
     ) {
         for FromClient { client_id, event } in request_events.read().cloned() {
+            if spectators.contains(client_id) {
+                warn!("ignoring road command from spectator `{client_id:?}`");
+                continue;
+            }
+
             // TODO: validate if command can be applied.
             let mut confirmation = CommandConfirmation::new(event.id);
             match event.command {
@@ -0,0 +1,160 @@
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::core::GameState;
+
+pub(super) struct ClockPlugin;
+
+impl Plugin for ClockPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<WorldClock>()
+            .replicate::<WorldClock>()
+            .add_client_event::<ClockSkip>(ChannelKind::Ordered)
+            .add_systems(
+                OnEnter(GameState::InGame),
+                Self::spawn.run_if(server_or_singleplayer),
+            )
+            .add_systems(
+                PreUpdate,
+                Self::skip
+                    .never_param_warn()
+                    .after(ClientSet::Receive)
+                    .run_if(server_or_singleplayer)
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(
+                Update,
+                Self::advance
+                    .never_param_warn()
+                    .run_if(server_or_singleplayer)
+                    .run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+impl ClockPlugin {
+    /// Spawns a clock for newly created worlds.
+    ///
+    /// Loaded worlds already contain it.
+    fn spawn(mut commands: Commands, clocks: Query<(), With<WorldClock>>) {
+        if clocks.is_empty() {
+            debug!("spawning world clock");
+            commands.spawn(WorldClock::default());
+        }
+    }
+
+    fn advance(time: Res<Time>, mut clock: Single<&mut WorldClock>) {
+        // Don't trigger change detection while paused.
+        if time.delta_secs() > 0.0 {
+            clock.elapsed_secs += time.delta_secs_f64() * f64::from(GAME_SECONDS_PER_SECOND);
+        }
+    }
+
+    fn skip(
+        mut commands: Commands,
+        mut skip_events: EventReader<FromClient<ClockSkip>>,
+        mut clock: Single<&mut WorldClock>,
+    ) {
+        for FromClient { client_id, event } in skip_events.read() {
+            if *client_id != ClientId::SERVER {
+                warn!("ignoring clock skip from client `{client_id:?}`");
+                continue;
+            }
+            if !(0.0..24.0).contains(&event.hour) {
+                error!("received invalid hour {} to skip to", event.hour);
+                continue;
+            }
+
+            let secs = clock.secs_until(event.hour);
+            info!("skipping {secs} game seconds to {} hour", event.hour);
+            clock.elapsed_secs += f64::from(secs);
+            commands.trigger(TimeSkipped(secs));
+        }
+    }
+}
+
+/// How many game seconds pass in one real second.
+pub const GAME_SECONDS_PER_SECOND: f32 = 60.0;
+
+pub(crate) const SECS_PER_HOUR: f32 = 60.0 * 60.0;
+const SECS_PER_DAY: f64 = 24.0 * SECS_PER_HOUR as f64;
+
+/// Tracks in-game time of the world.
+#[derive(Component, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Component)]
+#[require(
+    Name(|| Name::new("World clock")),
+    Replicated,
+    StateScoped::<GameState>(|| StateScoped(GameState::InGame))
+)]
+pub struct WorldClock {
+    /// Game seconds since the world creation.
+    ///
+    /// Stored in double precision, otherwise minutes start to drift after a few months of game time.
+    elapsed_secs: f64,
+}
+
+impl WorldClock {
+    /// Creates a clock at the hour of the day.
+    #[cfg(test)]
+    pub(crate) fn at(day: u32, hour: f32) -> Self {
+        Self {
+            elapsed_secs: f64::from(day) * SECS_PER_DAY + f64::from(hour * SECS_PER_HOUR),
+        }
+    }
+
+    pub fn day(&self) -> u32 {
+        (self.elapsed_secs / SECS_PER_DAY) as u32
+    }
+
+    /// Returns current hour with fraction.
+    pub fn hour(&self) -> f32 {
+        (self.elapsed_secs % SECS_PER_DAY / f64::from(SECS_PER_HOUR)) as f32
+    }
+
+    /// Returns game seconds until the next occurrence of the hour.
+    ///
+    /// Always moves forward, so the same hour means the next day.
+    fn secs_until(&self, hour: f32) -> f32 {
+        let secs = (hour - self.hour()) * SECS_PER_HOUR;
+        if secs <= 0.0 {
+            secs + SECS_PER_DAY as f32
+        } else {
+            secs
+        }
+    }
+}
+
+/// Requests the server to advance the clock to the next occurrence of the hour.
+///
+/// Accepted only from the host.
+#[derive(Clone, Copy, Deserialize, Event, Serialize)]
+pub struct ClockSkip {
+    pub hour: f32,
+}
+
+/// Triggered on server after the world clock skipped the specified number of game seconds.
+///
+/// Time-dependent systems should apply the missed changes.
+#[derive(Clone, Copy, Deref, Event)]
+pub(crate) struct TimeSkipped(pub(crate) f32);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn precision() {
+        let mut clock = WorldClock::at(1000, 12.5);
+        let hour = clock.hour();
+        assert_eq!(clock.day(), 1000);
+        assert_eq!(hour, 12.5);
+
+        clock.elapsed_secs += 1.0;
+        assert!(
+            clock.hour() > hour,
+            "a single second should be noticeable after many days"
+        );
+    }
+}
@@ -13,7 +13,11 @@ use bevy::{
 use bevy_replicon::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::core::GameState;
+use crate::{
+    core::GameState,
+    error_message::ErrorMessage,
+    settings::{InterfaceSettings, Settings},
+};
 
 pub(super) struct CommandHistoryPlugin;
 
@@ -22,12 +26,17 @@ impl Plugin for CommandHistoryPlugin {
         app.init_resource::<HistoryBuffer>()
             .init_resource::<CommandIds>()
             .add_server_event::<CommandConfirmation>(ChannelKind::Unordered)
+            .add_server_event::<CommandRejection>(ChannelKind::Unordered)
             .add_systems(
                 PreUpdate,
-                Self::confirm
+                (Self::confirm, Self::reject)
                     .after(ClientSet::Receive)
                     .run_if(in_state(GameState::InGame)),
             )
+            .add_systems(
+                PreUpdate,
+                Self::apply_depth.run_if(resource_exists_and_changed::<Settings>),
+            )
             .add_systems(OnExit(GameState::InGame), Self::cleanup);
     }
 }
@@ -52,6 +61,37 @@ impl CommandHistoryPlugin {
         }
     }
 
+    fn reject(
+        mut commands: Commands,
+        mut rejection_events: EventReader<CommandRejection>,
+        mut buffer: ResMut<HistoryBuffer>,
+        despawn_entities: Query<(Entity, &PendingDespawn)>,
+    ) {
+        for rejection in rejection_events.read() {
+            buffer.reject(rejection.id);
+
+            if let Some((entity, _)) = despawn_entities
+                .iter()
+                .find(|(_, despawn)| despawn.command_id == rejection.id)
+            {
+                debug!(
+                    "despawning entity `{entity}` for rejected `{:?}`",
+                    rejection.id
+                );
+                commands.entity(entity).despawn_recursive();
+            }
+
+            commands.trigger(ErrorMessage(format!(
+                "Server rejected the action: {}",
+                rejection.reason
+            )));
+        }
+    }
+
+    fn apply_depth(settings: Res<Settings>, mut buffer: ResMut<HistoryBuffer>) {
+        buffer.set_depth(settings.interface.history_depth() as usize);
+    }
+
     fn cleanup(mut buffer: ResMut<HistoryBuffer>) {
         buffer.clear();
     }
@@ -89,7 +129,7 @@ impl CommandsHistory<'_, '_> {
     ///
     /// See also [`CommandConfirmation`].
     pub(super) fn push_pending<C: PendingCommand + 'static>(&mut self, command: C) -> CommandId {
-        let id = self.ids.next();
+        let id = self.ids.next(false);
         self.commands.queue(move |world: &mut World| {
             world.resource_scope(|world, mut buffer: Mut<HistoryBuffer>| {
                 buffer.apply_pending(
@@ -135,20 +175,35 @@ struct CommandIds(AtomicU8);
 
 impl CommandIds {
     /// Generates a new ID for a command.
-    fn next(&self) -> CommandId {
-        CommandId(self.0.fetch_add(1, Ordering::Relaxed))
+    ///
+    /// `from_history` should be set for commands applied by undo or redo.
+    fn next(&self, from_history: bool) -> CommandId {
+        CommandId {
+            value: self.0.fetch_add(1, Ordering::Relaxed),
+            from_history,
+        }
     }
 }
 
-#[derive(Resource, Default)]
+#[derive(Resource)]
 struct HistoryBuffer {
     undo: LinkedList<CommandRecord>,
     redo: LinkedList<CommandRecord>,
     mapper: CommandEntityMapper,
     unconfirmed: Vec<UnconfirmedCommand>,
+    /// Maximum number of commands that can be undone.
+    depth: usize,
 }
 
 impl HistoryBuffer {
+    fn set_depth(&mut self, depth: usize) {
+        debug!("setting history depth to {depth}");
+        self.depth = depth;
+        while self.undo.len() > depth {
+            self.undo.pop_front();
+        }
+    }
+
     /// Applies the command for `stack` from the reverse one.
     fn apply_reverse(&mut self, stack: Stack, world: &mut World) {
         let record = match stack {
@@ -162,7 +217,7 @@ impl HistoryBuffer {
                     self.apply(command, record.entities, stack, world)
                 }
                 ReverseCommand::Pending(command) => {
-                    let id = world.resource::<CommandIds>().next();
+                    let id = world.resource::<CommandIds>().next(true);
                     self.apply_pending(id, command, record.entities, stack, world);
                 }
             }
@@ -227,13 +282,26 @@ impl HistoryBuffer {
         }
     }
 
+    /// Removes a command added by [`Self::apply_pending`] without adding it to the history.
+    fn reject(&mut self, id: CommandId) {
+        if let Some(index) = self
+            .unconfirmed
+            .iter()
+            .position(|unconfirmed| unconfirmed.id == id)
+        {
+            debug!("removing rejected `{id:?}`");
+            self.unconfirmed.swap_remove(index);
+        } else {
+            debug!("ignoring rejection for `{id:?}`");
+        }
+    }
+
     fn push(&mut self, record: CommandRecord, stack: Stack) {
         match stack {
             Stack::Undo { new } => {
                 self.undo.push_back(record);
 
-                const HISTORY_LEN: usize = 25;
-                if self.undo.len() > HISTORY_LEN {
+                if self.undo.len() > self.depth {
                     self.undo.pop_front();
                 }
 
@@ -279,6 +347,18 @@ impl HistoryBuffer {
     }
 }
 
+impl Default for HistoryBuffer {
+    fn default() -> Self {
+        Self {
+            undo: Default::default(),
+            redo: Default::default(),
+            mapper: Default::default(),
+            unconfirmed: Default::default(),
+            depth: InterfaceSettings::default().history_depth as usize,
+        }
+    }
+}
+
 /// Regular or confirmed command.
 struct CommandRecord {
     command: ReverseCommand,
@@ -401,9 +481,52 @@ impl CommandConfirmation {
     }
 }
 
+/// Server event to notify client that the command was rejected with the reason.
+///
+/// The command won't be added to the history.
+#[derive(Event, Serialize, Deserialize, Clone, Debug)]
+pub(super) struct CommandRejection {
+    pub(super) id: CommandId,
+    pub(super) reason: String,
+}
+
+impl CommandRejection {
+    pub(super) fn new(id: CommandId, reason: String) -> Self {
+        Self { id, reason }
+    }
+}
+
 /// ID for an unconfirmed command.
+///
+/// Also tells the server if the command was produced by undo or redo.
 #[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
-pub(super) struct CommandId(u8);
+pub(super) struct CommandId {
+    value: u8,
+    from_history: bool,
+}
+
+impl CommandId {
+    #[cfg(test)]
+    pub(super) fn new(value: u8, from_history: bool) -> Self {
+        Self {
+            value,
+            from_history,
+        }
+    }
+
+    /// Returns `true` if the command reverts or re-applies a previous one.
+    ///
+    /// Such commands should be checked with [`LastEditor`] to not override changes from other players.
+    pub(super) fn from_history(self) -> bool {
+        self.from_history
+    }
+}
+
+/// Client that was the last to change the entity.
+///
+/// Exists only on server.
+#[derive(Component, Clone, Copy, Deref)]
+pub(super) struct LastEditor(pub(super) ClientId);
 
 #[derive(Deref, DerefMut, Default)]
 pub(super) struct CommandEntityMapper(EntityHashMap<Entity>);
@@ -441,3 +564,89 @@ impl<C: MapEntities> MapEntities for CommandRequest<C> {
         self.command.map_entities(entity_mapper);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    #[test]
+    fn depth() {
+        let mut app = App::new();
+        app.init_resource::<HistoryBuffer>()
+            .init_resource::<CommandIds>();
+        app.world_mut().resource_mut::<HistoryBuffer>().set_depth(2);
+
+        for value in 0..3 {
+            push(&mut app, PlaceCommand::Place(value));
+        }
+
+        for _ in 0..3 {
+            history(&mut app, |mut history| history.undo());
+        }
+        assert_eq!(
+            placed(&mut app),
+            [0],
+            "commands beyond the depth shouldn't be undone"
+        );
+    }
+
+    fn push(app: &mut App, command: PlaceCommand) {
+        history(app, move |mut history| history.push(command));
+    }
+
+    fn history(app: &mut App, f: impl Fn(CommandsHistory) + Send + Sync + 'static) {
+        app.world_mut()
+            .run_system_once(move |history: CommandsHistory| f(history))
+            .unwrap();
+    }
+
+    fn placed(app: &mut App) -> Vec<u8> {
+        let mut values: Vec<_> = app
+            .world_mut()
+            .query::<&Placed>()
+            .iter(app.world())
+            .map(|placed| placed.0)
+            .collect();
+        values.sort();
+        values
+    }
+
+    #[derive(Component)]
+    struct Placed(u8);
+
+    #[derive(Clone, Copy)]
+    enum PlaceCommand {
+        Place(u8),
+        Remove { entity: Entity, value: u8 },
+    }
+
+    impl ReversibleCommand for PlaceCommand {
+        fn apply(
+            self: Box<Self>,
+            mut recorder: EntityRecorder,
+            world: &mut World,
+        ) -> Box<dyn ReversibleCommand> {
+            match *self {
+                PlaceCommand::Place(value) => {
+                    let entity = world.spawn(Placed(value)).id();
+                    recorder.record(entity);
+                    Box::new(PlaceCommand::Remove { entity, value })
+                }
+                PlaceCommand::Remove { entity, value } => {
+                    world.despawn(entity);
+                    Box::new(PlaceCommand::Place(value))
+                }
+            }
+        }
+    }
+
+    impl MapEntities for PlaceCommand {
+        fn map_entities<T: EntityMapper>(&mut self, entity_mapper: &mut T) {
+            if let PlaceCommand::Remove { entity, .. } = self {
+                *entity = entity_mapper.map_entity(*entity);
+            }
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+use serde::Deserialize;
+use strum::{EnumIter, IntoStaticStr};
+
+use crate::{
+    asset::difficulties::{DifficultyTable, DifficultyTableHandle},
+    core::GameState,
+};
+
+/// Scales gameplay tunables depending on the world difficulty.
+pub(super) struct DifficultyPlugin;
+
+impl Plugin for DifficultyPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Difficulty>()
+            .init_resource::<Difficulty>()
+            .add_systems(OnExit(GameState::InGame), Self::cleanup);
+    }
+}
+
+impl DifficultyPlugin {
+    fn cleanup(mut difficulty: ResMut<Difficulty>) {
+        *difficulty = Default::default();
+    }
+}
+
+/// Difficulty of the currently loaded world.
+///
+/// Selected on world creation and stored inside the world file.
+/// Available only on server, which applies the modifiers.
+#[derive(
+    Resource, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq, EnumIter, IntoStaticStr,
+)]
+#[reflect(Resource)]
+pub enum Difficulty {
+    Relaxed,
+    #[default]
+    Normal,
+    Hard,
+}
+
+/// Modifiers of the current [`Difficulty`].
+///
+/// Available only after the difficulty table is loaded.
+#[derive(SystemParam)]
+pub(crate) struct CurrentModifiers<'w> {
+    difficulty: Res<'w, Difficulty>,
+    table_handle: Res<'w, DifficultyTableHandle>,
+    tables: Res<'w, Assets<DifficultyTable>>,
+}
+
+impl CurrentModifiers<'_> {
+    pub(crate) fn get(&self) -> &DifficultyModifiers {
+        let table = self
+            .tables
+            .get(&**self.table_handle)
+            .expect("difficulty table should be loaded");
+        table.modifiers(*self.difficulty)
+    }
+}
+
+/// Inserts the default difficulty with the table from the assets directory.
+#[cfg(test)]
+pub(crate) fn insert_table(app: &mut App) {
+    let data = std::fs::read_to_string("../app/assets/base/difficulties.difficulties.ron").unwrap();
+    let table: DifficultyTable = bevy::scene::ron::from_str(&data).unwrap();
+    let mut tables = Assets::<DifficultyTable>::default();
+    let handle = tables.add(table);
+    app.init_resource::<Difficulty>()
+        .insert_resource(tables)
+        .insert_resource(DifficultyTableHandle(handle));
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct DifficultyModifiers {
+    /// Applied to negative need rates.
+    pub need_decay: f32,
+    /// Applied to budgets of newly created families.
+    pub starting_budget: f32,
+    /// Applied to prices from object manifests.
+    pub object_price: f32,
+}
+
+impl DifficultyModifiers {
+    pub fn object_price(&self, price: u32) -> u32 {
+        (price as f32 * self.object_price).round() as u32
+    }
+}
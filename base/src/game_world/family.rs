@@ -1,8 +1,11 @@
+pub mod bill;
 pub mod building;
+pub mod controller;
 pub mod editor;
 
 use std::io::Cursor;
 
+use anyhow::{Context, Result};
 use bevy::{
     ecs::{
         entity::{EntityMapper, MapEntities},
@@ -11,6 +14,7 @@ use bevy::{
     prelude::*,
     reflect::serde::{ReflectDeserializer, ReflectSerializer},
 };
+use bevy_enhanced_input::prelude::*;
 use bevy_replicon::{
     core::event_registry::ctx::{ClientSendCtx, ServerReceiveCtx},
     prelude::*,
@@ -21,22 +25,28 @@ use strum::EnumIter;
 
 use super::{
     actor::{Actor, SelectedActor},
+    difficulty::{CurrentModifiers, DifficultyModifiers},
+    spectator::Spectators,
     WorldState,
 };
-use crate::core::GameState;
+use crate::{core::GameState, error_message::ErrorMessage, settings::Settings};
+use bill::{BillPlugin, UnpaidBills};
 use building::BuildingPlugin;
+use controller::{Controller, ControllerPlugin};
 use editor::{EditorPlugin, FamilyScene, ReflectActorBundle};
 
 pub struct FamilyPlugin;
 
 impl Plugin for FamilyPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((EditorPlugin, BuildingPlugin))
+        app.add_plugins((EditorPlugin, BillPlugin, BuildingPlugin, ControllerPlugin))
             .add_sub_state::<FamilyMode>()
             .enable_state_scoped_entities::<FamilyMode>()
             .register_type::<Family>()
             .register_type::<Budget>()
+            .register_type::<BudgetPolicy>()
             .replicate::<Budget>()
+            .replicate::<BudgetPolicy>()
             .replicate_group::<(Family, Name)>()
             .add_client_event_with(
                 ChannelKind::Unordered,
@@ -44,9 +54,14 @@ impl Plugin for FamilyPlugin {
                 deserialize_family_spawn,
             )
             .add_mapped_client_event::<FamilyDelete>(ChannelKind::Unordered)
+            .add_mapped_client_event::<BudgetCheat>(ChannelKind::Ordered)
             .add_mapped_server_event::<SelectedFamilyCreated>(ChannelKind::Unordered)
+            .add_server_event::<FamilyRejected>(ChannelKind::Unordered)
+            .add_input_context::<SelectedFamily>()
             .add_observer(Self::record_new_members)
             .add_observer(Self::update_members)
+            .add_observer(Self::select_next)
+            .add_observer(Self::select_previous)
             .add_systems(OnEnter(WorldState::Family), Self::select)
             .add_systems(
                 OnExit(WorldState::Family),
@@ -54,8 +69,16 @@ impl Plugin for FamilyPlugin {
             )
             .add_systems(
                 PreUpdate,
-                (Self::create, Self::delete)
-                    .run_if(server_or_singleplayer)
+                (
+                    (
+                        Self::create,
+                        Self::delete,
+                        Self::cheat_budget,
+                        Self::transfer_budget,
+                    )
+                        .run_if(server_or_singleplayer),
+                    Self::receive_rejection,
+                )
                     .after(ClientSet::Receive)
                     .run_if(in_state(GameState::InGame)),
             );
@@ -80,14 +103,57 @@ impl FamilyPlugin {
         members.push(**trigger.event())
     }
 
+    fn select_next(
+        trigger: Trigger<Fired<NextMember>>,
+        mut commands: Commands,
+        families: Query<&FamilyMembers>,
+        selected_entity: Single<Entity, With<SelectedActor>>,
+    ) {
+        let members = families.get(trigger.entity()).unwrap();
+        cycle_selection(&mut commands, members, *selected_entity, 1);
+    }
+
+    fn select_previous(
+        trigger: Trigger<Fired<PreviousMember>>,
+        mut commands: Commands,
+        families: Query<&FamilyMembers>,
+        selected_entity: Single<Entity, With<SelectedActor>>,
+    ) {
+        let members = families.get(trigger.entity()).unwrap();
+        cycle_selection(&mut commands, members, *selected_entity, -1);
+    }
+
     fn create(
         mut commands: Commands,
         mut created_events: EventWriter<ToClients<SelectedFamilyCreated>>,
+        mut rejection_events: EventWriter<ToClients<FamilyRejected>>,
         mut create_events: ResMut<Events<FromClient<FamilyCreate>>>,
+        spectators: Res<Spectators>,
+        modifiers: CurrentModifiers,
     ) {
+        let budget = Budget::starting(modifiers.get());
         for FromClient { client_id, event } in create_events.drain() {
+            if spectators.contains(client_id) {
+                warn!("ignoring family creation from spectator `{client_id:?}`");
+                continue;
+            }
+            // Also checked on deserialization, but local events don't go through it.
+            if let Err(e) = check_family_size(event.scene.actors.len()) {
+                error!("rejecting family from `{client_id:?}`: {e}");
+                rejection_events.send(ToClients {
+                    mode: SendMode::Direct(client_id),
+                    event: FamilyRejected(e.to_string()),
+                });
+                continue;
+            }
+
             info!("creating new family");
-            let family_entity = commands.spawn((Family, Name::new(event.scene.name))).id();
+            let family_entity = commands
+                .spawn((Family, Name::new(event.scene.name), budget))
+                .id();
+            if event.select {
+                commands.entity(family_entity).insert(Controller(client_id));
+            }
             for actor in event.scene.actors {
                 commands.entity(event.city_entity).with_children(|parent| {
                     parent
@@ -104,12 +170,32 @@ impl FamilyPlugin {
         }
     }
 
+    fn receive_rejection(
+        mut commands: Commands,
+        mut rejection_events: EventReader<FamilyRejected>,
+    ) {
+        for reason in rejection_events.read() {
+            info!("family creation was rejected");
+            commands.trigger(ErrorMessage(format!(
+                "Unable to create family: {}",
+                **reason
+            )));
+        }
+    }
+
     fn delete(
         mut commands: Commands,
         mut delete_events: EventReader<FromClient<FamilyDelete>>,
+        spectators: Res<Spectators>,
         families: Query<&mut FamilyMembers>,
     ) {
-        for family_entity in delete_events.read().map(|event| event.event.0) {
+        for FromClient { client_id, event } in delete_events.read() {
+            if spectators.contains(*client_id) {
+                warn!("ignoring family deletion from spectator `{client_id:?}`");
+                continue;
+            }
+
+            let family_entity = event.0;
             match families.get(family_entity) {
                 Ok(members) => {
                     info!("deleting family `{family_entity}`");
@@ -123,6 +209,90 @@ impl FamilyPlugin {
         }
     }
 
+    /// Moves money between the family and its members when the [`BudgetPolicy`] changes.
+    ///
+    /// Switching to [`BudgetPolicy::PerActor`] splits the family budget evenly,
+    /// switching back to [`BudgetPolicy::Shared`] puts everything together.
+    fn transfer_budget(
+        families: Query<(Entity, Ref<BudgetPolicy>, &FamilyMembers), Changed<BudgetPolicy>>,
+        mut budgets: Query<&mut Budget>,
+    ) {
+        for (family_entity, policy, members) in &families {
+            if policy.is_added() || members.is_empty() {
+                continue;
+            }
+
+            let family_amount = budgets
+                .get(family_entity)
+                .map(|budget| budget.0)
+                .expect("families should always have a budget");
+            match *policy {
+                BudgetPolicy::Shared => {
+                    let mut total = family_amount;
+                    let mut iter = budgets.iter_many_mut(members.iter());
+                    while let Some(mut budget) = iter.fetch_next() {
+                        total = total.saturating_add(budget.0);
+                        budget.0 = 0;
+                    }
+                    info!("merging budgets of `{family_entity}` into {total}");
+                    budgets.get_mut(family_entity).unwrap().0 = total;
+                }
+                BudgetPolicy::PerActor => {
+                    info!("splitting {family_amount} of `{family_entity}` between members");
+                    budgets.get_mut(family_entity).unwrap().0 = 0;
+                    let count = members.len() as u32;
+                    let mut iter = budgets.iter_many_mut(members.iter());
+                    let mut index = 0;
+                    while let Some(mut budget) = iter.fetch_next() {
+                        // Give the remainder to the first members.
+                        let share =
+                            family_amount / count + u32::from(index < family_amount % count);
+                        budget.deposit(share);
+                        index += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    fn cheat_budget(
+        settings: Res<Settings>,
+        mut cheat_events: EventReader<FromClient<BudgetCheat>>,
+        mut families: Query<&mut Budget>,
+    ) {
+        for FromClient { client_id, event } in cheat_events.read() {
+            if *client_id != ClientId::SERVER {
+                warn!("ignoring budget cheat from client `{client_id:?}`");
+                continue;
+            }
+            if !settings.developer.cheats {
+                warn!("ignoring budget cheat because cheats are disabled");
+                continue;
+            }
+
+            let Ok(mut budget) = families.get_mut(event.family_entity) else {
+                error!(
+                    "received budget cheat for an invalid family `{}`",
+                    event.family_entity
+                );
+                continue;
+            };
+
+            let value = match event.change {
+                BudgetChange::Set(value) => value,
+                BudgetChange::Add(amount) => (i64::from(budget.0) + amount)
+                    .clamp(0, u32::MAX.into())
+                    .try_into()
+                    .unwrap(),
+            };
+            info!(
+                "cheating budget for `{}` from {} to {value}",
+                event.family_entity, budget.0
+            );
+            budget.0 = value;
+        }
+    }
+
     pub fn select(mut commands: Commands, selected_actor: Single<&Actor, With<SelectedActor>>) {
         info!("selecting `{}`", selected_actor.family_entity);
         commands
@@ -138,6 +308,42 @@ impl FamilyPlugin {
     }
 }
 
+/// Moves [`SelectedActor`] to the member at the offset from the currently selected.
+fn cycle_selection(
+    commands: &mut Commands,
+    members: &FamilyMembers,
+    selected_entity: Entity,
+    offset: isize,
+) {
+    let Some(next_entity) = members.cycle(selected_entity, offset) else {
+        error!("selected actor `{selected_entity}` doesn't belong to the selected family");
+        return;
+    };
+
+    if next_entity != selected_entity {
+        info!("selecting `{next_entity}`");
+        commands.entity(selected_entity).remove::<SelectedActor>();
+        commands.entity(next_entity).insert(SelectedActor);
+    }
+}
+
+/// Maximum number of actors in a family.
+pub const MAX_FAMILY_SIZE: usize = 8;
+
+/// Rejects imported families that are empty or exceed [`MAX_FAMILY_SIZE`].
+///
+/// Checked before reading actors to avoid allocating for the reported count.
+fn check_family_size(actors_count: usize) -> bincode::Result<()> {
+    if !(1..=MAX_FAMILY_SIZE).contains(&actors_count) {
+        return Err(ErrorKind::Custom(format!(
+            "family should have from 1 to {MAX_FAMILY_SIZE} actors, but has {actors_count}"
+        ))
+        .into());
+    }
+
+    Ok(())
+}
+
 fn serialize_family_spawn(
     ctx: &mut ClientSendCtx,
     event: &FamilyCreate,
@@ -162,6 +368,7 @@ fn deserialize_family_spawn(
     let city_entity = DefaultOptions::new().deserialize_from(&mut *cursor)?;
     let name = DefaultOptions::new().deserialize_from(&mut *cursor)?;
     let actors_count = DefaultOptions::new().deserialize_from(&mut *cursor)?;
+    check_family_size(actors_count)?;
     let mut actors = Vec::with_capacity(actors_count);
     for _ in 0..actors_count {
         let mut deserializer =
@@ -222,6 +429,8 @@ impl FamilyMode {
 #[require(
     Name,
     Budget,
+    BudgetPolicy,
+    UnpaidBills,
     Replicated,
     FamilyMembers,
     StateScoped<GameState>(|| StateScoped(GameState::InGame))
@@ -232,18 +441,84 @@ pub struct Family;
 #[reflect(Component)]
 pub struct Budget(u32);
 
+impl Budget {
+    /// Subtracts the amount if there is enough money.
+    pub(crate) fn withdraw(&mut self, amount: u32) -> Result<()> {
+        self.0 = self
+            .0
+            .checked_sub(amount)
+            .with_context(|| format!("not enough money to pay {amount}, only {} left", self.0))?;
+        Ok(())
+    }
+
+    /// Adds the amount, saturating at the maximum.
+    pub(crate) fn deposit(&mut self, amount: u32) {
+        self.0 = self.0.saturating_add(amount);
+    }
+}
+
+impl Budget {
+    /// Returns the budget for a newly created family.
+    fn starting(modifiers: &DifficultyModifiers) -> Self {
+        let amount = Self::default().0 as f32 * modifiers.starting_budget;
+        Self(amount as u32)
+    }
+}
+
+impl Budget {
+    /// Returns the budget for actors, they spend it only with [`BudgetPolicy::PerActor`].
+    pub(crate) fn empty() -> Self {
+        Self(0)
+    }
+}
+
 impl Default for Budget {
     fn default() -> Self {
         Self(20_000)
     }
 }
 
+/// Defines who pays for purchases when multiple players control the same family.
+///
+/// Purchases are charged on server one by one in the order of received requests,
+/// so concurrent purchases can't spend the same money twice.
+#[derive(Clone, Component, Copy, Debug, Default, Deserialize, PartialEq, Reflect, Serialize)]
+#[reflect(Component)]
+pub enum BudgetPolicy {
+    /// All members spend the [`Budget`] of the family.
+    #[default]
+    Shared,
+    /// Each member spends own [`Budget`].
+    PerActor,
+}
+
+impl BudgetPolicy {
+    /// Returns entity whose [`Budget`] should be used for purchases by the actor.
+    pub fn payer(self, actor_entity: Entity, actor: &Actor) -> Entity {
+        match self {
+            BudgetPolicy::Shared => actor.family_entity,
+            BudgetPolicy::PerActor => actor_entity,
+        }
+    }
+}
+
 /// Contains the entities of all the actors that belong to the family.
 ///
 /// Automatically created and updated based on [`Actor`].
 #[derive(Component, Default, Deref, DerefMut)]
 pub struct FamilyMembers(Vec<Entity>);
 
+impl FamilyMembers {
+    /// Returns the member at the offset from the given one, wrapping around at the ends.
+    ///
+    /// Returns [`None`] if the given entity isn't a member.
+    pub fn cycle(&self, entity: Entity, offset: isize) -> Option<Entity> {
+        let index = self.iter().position(|&member| member == entity)?;
+        let next_index = (index as isize + offset).rem_euclid(self.len() as isize);
+        Some(self[next_index as usize])
+    }
+}
+
 /// Emitted when an actor spawned.
 ///
 /// This additional level of indirection is needed because when an actor spawned from scene,
@@ -257,6 +532,27 @@ struct FamilyMemberAdded(Entity);
 #[derive(Component)]
 pub struct SelectedFamily;
 
+impl InputContext for SelectedFamily {
+    fn context_instance(world: &World, _entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+        let settings = world.resource::<Settings>();
+
+        ctx.bind::<NextMember>().to(&settings.keyboard.next_member);
+        ctx.bind::<PreviousMember>()
+            .to(&settings.keyboard.previous_member);
+
+        ctx
+    }
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct NextMember;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct PreviousMember;
+
 #[derive(Event)]
 pub struct FamilyCreate {
     pub city_entity: Entity,
@@ -279,6 +575,31 @@ impl MapEntities for FamilyDelete {
     }
 }
 
+/// Changes the budget of a family for testing.
+///
+/// Applied only by the server and only if [`DeveloperSettings::cheats`](crate::settings::DeveloperSettings::cheats) is enabled.
+#[derive(Clone, Copy, Deserialize, Event, Serialize)]
+pub struct BudgetCheat {
+    pub family_entity: Entity,
+    pub change: BudgetChange,
+}
+
+impl MapEntities for BudgetCheat {
+    fn map_entities<T: EntityMapper>(&mut self, entity_mapper: &mut T) {
+        self.family_entity = entity_mapper.map_entity(self.family_entity);
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub enum BudgetChange {
+    Set(u32),
+    Add(i64),
+}
+
+/// Sent to a client whose family wasn't created, contains the reason.
+#[derive(Deref, Deserialize, Event, Serialize)]
+struct FamilyRejected(String);
+
 /// An event from server which indicates spawn confirmation for the selected family.
 #[derive(Deserialize, Event, Serialize)]
 pub(super) struct SelectedFamilyCreated(pub(super) Entity);
@@ -288,3 +609,92 @@ impl MapEntities for SelectedFamilyCreated {
         self.0 = entity_mapper.map_entity(self.0);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn members_cycling() {
+        let first_entity = Entity::from_raw(0);
+        let second_entity = Entity::from_raw(1);
+        let third_entity = Entity::from_raw(2);
+        let members = FamilyMembers(vec![first_entity, second_entity, third_entity]);
+
+        assert_eq!(members.cycle(first_entity, 1), Some(second_entity));
+        assert_eq!(
+            members.cycle(third_entity, 1),
+            Some(first_entity),
+            "cycling past the last member should return to the first"
+        );
+        assert_eq!(
+            members.cycle(first_entity, -1),
+            Some(third_entity),
+            "cycling before the first member should return to the last"
+        );
+        assert_eq!(members.cycle(Entity::from_raw(3), 1), None);
+        assert_eq!(
+            FamilyMembers(vec![first_entity]).cycle(first_entity, 1),
+            Some(first_entity)
+        );
+    }
+
+    #[test]
+    fn family_size() {
+        assert!(check_family_size(1).is_ok());
+        assert!(check_family_size(MAX_FAMILY_SIZE).is_ok());
+        assert!(
+            check_family_size(0).is_err(),
+            "empty families should be rejected"
+        );
+
+        let error = check_family_size(MAX_FAMILY_SIZE + 1).unwrap_err();
+        assert!(
+            error.to_string().contains(&MAX_FAMILY_SIZE.to_string()),
+            "error should mention the limit"
+        );
+    }
+
+    #[test]
+    fn budget_transfer() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_systems(Update, FamilyPlugin::transfer_budget);
+
+        let first_entity = app.world_mut().spawn(Budget::empty()).id();
+        let second_entity = app.world_mut().spawn(Budget::empty()).id();
+        let family_entity = app
+            .world_mut()
+            .spawn((
+                Family,
+                Budget(101),
+                FamilyMembers(vec![first_entity, second_entity]),
+            ))
+            .id();
+
+        app.update();
+        assert_eq!(
+            app.world().get::<Budget>(family_entity).unwrap().0,
+            101,
+            "newly added policy shouldn't move money"
+        );
+
+        *app.world_mut()
+            .get_mut::<BudgetPolicy>(family_entity)
+            .unwrap() = BudgetPolicy::PerActor;
+        app.update();
+
+        assert_eq!(app.world().get::<Budget>(family_entity).unwrap().0, 0);
+        assert_eq!(app.world().get::<Budget>(first_entity).unwrap().0, 51);
+        assert_eq!(app.world().get::<Budget>(second_entity).unwrap().0, 50);
+
+        *app.world_mut()
+            .get_mut::<BudgetPolicy>(family_entity)
+            .unwrap() = BudgetPolicy::Shared;
+        app.update();
+
+        assert_eq!(app.world().get::<Budget>(family_entity).unwrap().0, 101);
+        assert_eq!(app.world().get::<Budget>(first_entity).unwrap().0, 0);
+        assert_eq!(app.world().get::<Budget>(second_entity).unwrap().0, 0);
+    }
+}
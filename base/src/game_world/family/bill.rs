@@ -0,0 +1,294 @@
+use bevy::{ecs::entity::MapEntities, prelude::*};
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{Budget, BudgetPolicy, Family, FamilyMembers};
+use crate::{
+    asset::{
+        bills::{BillRates, BillRatesHandle},
+        manifest::object_manifest::ObjectManifest,
+    },
+    core::GameState,
+    game_world::{clock::WorldClock, object::Object},
+};
+
+/// Periodically charges families for utilities.
+pub(super) struct BillPlugin;
+
+impl Plugin for BillPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<UnpaidBills>()
+            .replicate::<UnpaidBills>()
+            .init_resource::<LastBillDay>()
+            .add_mapped_server_event::<BillIssued>(ChannelKind::Ordered)
+            .add_systems(
+                Update,
+                Self::issue
+                    .never_param_warn()
+                    .run_if(server_or_singleplayer)
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(OnExit(GameState::InGame), Self::reset_day);
+    }
+}
+
+impl BillPlugin {
+    /// Charges families when a new billing day starts.
+    ///
+    /// The amount depends on the price of objects in the city where the family lives.
+    /// With [`BudgetPolicy::PerActor`] the bill is split between members.
+    /// Everything that can't be paid is added to [`UnpaidBills`].
+    fn issue(
+        mut last_day: ResMut<LastBillDay>,
+        mut issued_events: EventWriter<ToClients<BillIssued>>,
+        clock: Single<&WorldClock>,
+        asset_server: Res<AssetServer>,
+        rates_handle: Res<BillRatesHandle>,
+        rates: Res<Assets<BillRates>>,
+        manifests: Res<Assets<ObjectManifest>>,
+        mut families: Query<
+            (
+                Entity,
+                &FamilyMembers,
+                &BudgetPolicy,
+                &mut Budget,
+                &mut UnpaidBills,
+            ),
+            With<Family>,
+        >,
+        mut actor_budgets: Query<(Entity, &mut Budget), Without<Family>>,
+        parents: Query<&Parent>,
+        objects: Query<(&Parent, &Object)>,
+    ) {
+        let day = clock.day();
+        let previous_day = last_day.replace(day);
+        if previous_day.is_none_or(|previous_day| previous_day == day) {
+            return;
+        }
+
+        let Some(rates) = rates.get(&**rates_handle) else {
+            error!("unable to issue bills: bill rates are not loaded");
+            return;
+        };
+        if !rates.is_due(day) {
+            return;
+        }
+
+        for (family_entity, members, &policy, mut budget, mut unpaid) in &mut families {
+            let Some(city_entity) = members
+                .first()
+                .and_then(|&actor_entity| parents.get(actor_entity).ok())
+                .map(|parent| **parent)
+            else {
+                continue;
+            };
+
+            let objects_price = objects
+                .iter()
+                .filter(|(parent, _)| ***parent == city_entity)
+                .filter_map(|(_, object)| asset_server.get_handle::<ObjectManifest>(&**object))
+                .filter_map(|handle| manifests.get(&handle))
+                .fold(0, |price: u32, manifest| {
+                    price.saturating_add(manifest.price)
+                });
+
+            let amount = rates.amount(objects_price);
+            let paid = match policy {
+                BudgetPolicy::Shared => charge(&mut [&mut *budget], &mut unpaid, amount),
+                BudgetPolicy::PerActor => {
+                    let mut budgets: Vec<_> = actor_budgets
+                        .iter_mut()
+                        .filter(|(entity, _)| members.contains(entity))
+                        .map(|(_, budget)| budget.into_inner())
+                        .collect();
+                    charge(&mut budgets, &mut unpaid, amount)
+                }
+            };
+            info!("charging {paid} out of {amount} from `{family_entity}` for bills");
+
+            issued_events.send(ToClients {
+                mode: SendMode::Broadcast,
+                event: BillIssued {
+                    family_entity,
+                    amount,
+                    unpaid: **unpaid,
+                },
+            });
+        }
+    }
+
+    /// Prevents billing on the first frame of the next loaded world.
+    fn reset_day(mut last_day: ResMut<LastBillDay>) {
+        **last_day = None;
+    }
+}
+
+/// Pays the bill together with previously unpaid bills as much as the budgets allow.
+///
+/// The total is split evenly, payers that can't cover their share
+/// leave the rest to the following ones.
+///
+/// Returns the paid amount.
+fn charge(budgets: &mut [&mut Budget], unpaid: &mut UnpaidBills, amount: u32) -> u32 {
+    let total = unpaid.0.saturating_add(amount);
+    let mut remaining = total;
+    let payers = budgets.len();
+    for (index, budget) in budgets.iter_mut().enumerate() {
+        let payers_left = (payers - index) as u32;
+        let paid = remaining.div_ceil(payers_left).min(budget.0);
+        budget.0 -= paid;
+        remaining -= paid;
+    }
+    unpaid.0 = remaining;
+    total - remaining
+}
+
+/// Day on which [`BillPlugin::issue`] last checked for bills.
+///
+/// Reset on world exit, so the first day of a loaded world isn't billed.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct LastBillDay(Option<u32>);
+
+/// Money that the family wasn't able to pay for bills.
+#[derive(Clone, Component, Copy, Debug, Default, Deref, Deserialize, Reflect, Serialize)]
+#[reflect(Component)]
+pub struct UnpaidBills(u32);
+
+/// Sent to clients when a family is charged for bills.
+#[derive(Clone, Copy, Deserialize, Event, Serialize)]
+pub struct BillIssued {
+    pub family_entity: Entity,
+    pub amount: u32,
+
+    /// Remaining debt after the payment.
+    pub unpaid: u32,
+}
+
+impl MapEntities for BillIssued {
+    fn map_entities<T: EntityMapper>(&mut self, entity_mapper: &mut T) {
+        self.family_entity = entity_mapper.map_entity(self.family_entity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::state::app::StatesPlugin;
+
+    use super::*;
+
+    #[test]
+    fn charging() {
+        let mut budget = Budget(100);
+        let mut unpaid = UnpaidBills::default();
+
+        assert_eq!(charge(&mut [&mut budget], &mut unpaid, 60), 60);
+        assert_eq!(budget.0, 40);
+        assert_eq!(unpaid.0, 0);
+
+        assert_eq!(charge(&mut [&mut budget], &mut unpaid, 60), 40);
+        assert_eq!(budget.0, 0);
+        assert_eq!(unpaid.0, 20, "unpaid part should accumulate");
+
+        budget.0 = 100;
+        assert_eq!(
+            charge(&mut [&mut budget], &mut unpaid, 10),
+            30,
+            "debt should be paid with the next bill"
+        );
+        assert_eq!(budget.0, 70);
+        assert_eq!(unpaid.0, 0);
+    }
+
+    #[test]
+    fn splitting() {
+        let mut first = Budget(100);
+        let mut second = Budget(20);
+        let mut third = Budget(100);
+        let mut unpaid = UnpaidBills::default();
+
+        assert_eq!(
+            charge(&mut [&mut first, &mut second, &mut third], &mut unpaid, 90),
+            90
+        );
+        assert_eq!(first.0, 70);
+        assert_eq!(second.0, 0, "poor member should pay what it can");
+        assert_eq!(third.0, 60, "the rest should be paid by the next member");
+        assert_eq!(unpaid.0, 0);
+
+        assert_eq!(
+            charge(&mut [&mut first, &mut second, &mut third], &mut unpaid, 300),
+            130
+        );
+        assert_eq!(unpaid.0, 170);
+    }
+
+    #[test]
+    fn world_reload() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin, AssetPlugin::default()))
+            .init_state::<GameState>()
+            .init_asset::<BillRates>()
+            .init_asset::<ObjectManifest>()
+            .init_resource::<LastBillDay>()
+            .add_event::<ToClients<BillIssued>>()
+            .add_systems(
+                Update,
+                BillPlugin::issue.run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(OnExit(GameState::InGame), BillPlugin::reset_day);
+
+        let rates = BillRates {
+            interval_days: 1,
+            base_fee: 10,
+            object_rate: 0.0,
+        };
+        let rates_handle = app
+            .world_mut()
+            .resource_mut::<Assets<BillRates>>()
+            .add(rates);
+        app.insert_resource(BillRatesHandle(rates_handle));
+
+        let first_world = spawn_world(&mut app, 0);
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::InGame);
+        app.update();
+
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Menu);
+        app.update();
+        for entity in first_world {
+            app.world_mut().despawn(entity);
+        }
+
+        // Load a world on a different day.
+        let [.., family_entity] = spawn_world(&mut app, 2);
+        let budget = **app.world().get::<Budget>(family_entity).unwrap();
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::InGame);
+        app.update();
+
+        assert_eq!(
+            **app.world().get::<Budget>(family_entity).unwrap(),
+            budget,
+            "loading a world shouldn't be treated as a new day"
+        );
+    }
+
+    /// Spawns a clock, a city and a family with a single actor inside it.
+    ///
+    /// Returns the clock, city, actor and family entities.
+    fn spawn_world(app: &mut App, day: u32) -> [Entity; 4] {
+        let clock_entity = app.world_mut().spawn(WorldClock::at(day, 12.0)).id();
+        let city_entity = app.world_mut().spawn_empty().id();
+        let actor_entity = app.world_mut().spawn_empty().set_parent(city_entity).id();
+        let family_entity = app
+            .world_mut()
+            .spawn((Family, FamilyMembers(vec![actor_entity])))
+            .id();
+
+        [clock_entity, city_entity, actor_entity, family_entity]
+    }
+}
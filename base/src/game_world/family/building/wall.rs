@@ -19,6 +19,7 @@ use crate::{
         },
         navigation::Obstacle,
         segment::{PointKind, Segment, SegmentConnections, SegmentPlugin},
+        spectator::Spectators,
         Layer,
     },
 };
@@ -105,9 +106,15 @@ impl WallPlugin {
         mut commands: Commands,
         mut request_events: EventReader<FromClient<CommandRequest<WallCommand>>>,
         mut confirm_events: EventWriter<ToClients<CommandConfirmation>>,
+        spectators: Res<Spectators>,
         mut walls: Query<&mut Segment, With<Wall>>,
     ) {
         for FromClient { client_id, event } in request_events.read().copied() {
+            if spectators.contains(client_id) {
+                warn!("ignoring wall command from spectator `{client_id:?}`");
+                continue;
+            }
+
             // TODO: validate if command can be applied.
             let mut confirmation = CommandConfirmation::new(event.id);
             match event.command {
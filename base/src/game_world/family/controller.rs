@@ -0,0 +1,216 @@
+use bevy::{
+    ecs::entity::{EntityMapper, MapEntities},
+    prelude::*,
+};
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{Family, SelectedFamily};
+use crate::{
+    core::GameState,
+    error_message::ErrorMessage,
+    game_world::{spectator::Spectators, WorldState},
+};
+
+/// Assigns families to players, so they don't take each other's controls.
+pub(super) struct ControllerPlugin;
+
+impl Plugin for ControllerPlugin {
+    fn build(&self, app: &mut App) {
+        app.replicate::<Controller>()
+            .add_mapped_client_event::<ControlRequest>(ChannelKind::Ordered)
+            .add_server_event::<ControlRejected>(ChannelKind::Unordered)
+            .add_observer(Self::take)
+            .add_observer(Self::release)
+            .add_systems(
+                PreUpdate,
+                (
+                    Self::apply_requests
+                        .after(ClientSet::Receive)
+                        .run_if(server_or_singleplayer),
+                    Self::release_disconnected
+                        .after(ServerSet::Receive)
+                        .run_if(server_running),
+                    Self::receive_rejection
+                        .after(ClientSet::Receive)
+                        .run_if(in_state(GameState::InGame)),
+                ),
+            );
+    }
+}
+
+impl ControllerPlugin {
+    fn take(
+        trigger: Trigger<OnAdd, SelectedFamily>,
+        mut request_events: EventWriter<ControlRequest>,
+    ) {
+        debug!("requesting control over `{}`", trigger.entity());
+        request_events.send(ControlRequest::Take(trigger.entity()));
+    }
+
+    fn release(
+        trigger: Trigger<OnRemove, SelectedFamily>,
+        mut request_events: EventWriter<ControlRequest>,
+    ) {
+        debug!("releasing control over `{}`", trigger.entity());
+        request_events.send(ControlRequest::Release(trigger.entity()));
+    }
+
+    fn apply_requests(
+        mut commands: Commands,
+        mut request_events: EventReader<FromClient<ControlRequest>>,
+        mut rejection_events: EventWriter<ToClients<ControlRejected>>,
+        spectators: Res<Spectators>,
+        families: Query<Option<&Controller>, With<Family>>,
+    ) {
+        for &FromClient { client_id, event } in request_events.read() {
+            match event {
+                ControlRequest::Take(family_entity) => {
+                    let Ok(controller) = families.get(family_entity) else {
+                        error!("`{client_id:?}` requested control over invalid `{family_entity}`");
+                        continue;
+                    };
+
+                    if spectators.contains(client_id) || !can_control(controller, client_id) {
+                        info!("rejecting control over `{family_entity}` for `{client_id:?}`");
+                        rejection_events.send(ToClients {
+                            mode: SendMode::Direct(client_id),
+                            event: ControlRejected,
+                        });
+                        continue;
+                    }
+
+                    info!("`{client_id:?}` takes control over `{family_entity}`");
+                    commands.entity(family_entity).insert(Controller(client_id));
+                }
+                ControlRequest::Release(family_entity) => {
+                    // Could be already despawned.
+                    if let Ok(Some(controller)) = families.get(family_entity) {
+                        if **controller == client_id {
+                            info!("`{client_id:?}` releases control over `{family_entity}`");
+                            commands.entity(family_entity).remove::<Controller>();
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn release_disconnected(
+        mut commands: Commands,
+        mut server_events: EventReader<ServerEvent>,
+        families: Query<(Entity, &Controller)>,
+    ) {
+        for event in server_events.read() {
+            if let ServerEvent::ClientDisconnected { client_id, .. } = *event {
+                for (family_entity, controller) in &families {
+                    if **controller == client_id {
+                        debug!("releasing `{family_entity}` from disconnected `{client_id:?}`");
+                        commands.entity(family_entity).remove::<Controller>();
+                    }
+                }
+            }
+        }
+    }
+
+    fn receive_rejection(
+        mut commands: Commands,
+        mut rejection_events: EventReader<ControlRejected>,
+    ) {
+        for _ in rejection_events.read() {
+            info!("family control was rejected");
+            commands.set_state(WorldState::World);
+            commands.trigger(ErrorMessage(
+                "This family is controlled by another player".to_string(),
+            ));
+        }
+    }
+}
+
+/// Client that currently plays the family.
+///
+/// Assigned by server. Families without it can be taken by any player.
+#[derive(Component, Clone, Copy, Debug, Deref, Deserialize, Serialize)]
+pub struct Controller(pub ClientId);
+
+/// Returns `true` if the client is allowed to control the family.
+pub fn can_control(controller: Option<&Controller>, client_id: ClientId) -> bool {
+    controller.is_none_or(|controller| **controller == client_id)
+}
+
+#[derive(Clone, Copy, Deserialize, Event, Serialize)]
+enum ControlRequest {
+    Take(Entity),
+    Release(Entity),
+}
+
+impl MapEntities for ControlRequest {
+    fn map_entities<T: EntityMapper>(&mut self, entity_mapper: &mut T) {
+        match self {
+            Self::Take(entity) | Self::Release(entity) => {
+                *entity = entity_mapper.map_entity(*entity)
+            }
+        }
+    }
+}
+
+/// Sent to a client whose control request was rejected.
+#[derive(Deserialize, Event, Serialize)]
+struct ControlRejected;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ownership() {
+        let mut app = App::new();
+        app.add_event::<FromClient<ControlRequest>>()
+            .add_event::<ToClients<ControlRejected>>()
+            .init_resource::<Spectators>()
+            .add_systems(Update, ControllerPlugin::apply_requests);
+
+        let family_entity = app.world_mut().spawn(Family).id();
+        let owner_id = ClientId::new(1);
+        let other_id = ClientId::new(2);
+
+        app.world_mut().send_event(FromClient {
+            client_id: owner_id,
+            event: ControlRequest::Take(family_entity),
+        });
+        app.update();
+
+        let controller = app.world().get::<Controller>(family_entity).unwrap();
+        assert_eq!(**controller, owner_id);
+
+        app.world_mut().send_event(FromClient {
+            client_id: other_id,
+            event: ControlRequest::Take(family_entity),
+        });
+        app.update();
+
+        let controller = app.world().get::<Controller>(family_entity).unwrap();
+        assert_eq!(**controller, owner_id, "non-owner shouldn't take control");
+        let rejections = app.world().resource::<Events<ToClients<ControlRejected>>>();
+        assert_eq!(rejections.len(), 1, "non-owner should be notified");
+
+        app.world_mut().send_event(FromClient {
+            client_id: other_id,
+            event: ControlRequest::Release(family_entity),
+        });
+        app.update();
+
+        assert!(
+            app.world().get::<Controller>(family_entity).is_some(),
+            "non-owner shouldn't release control"
+        );
+
+        app.world_mut().send_event(FromClient {
+            client_id: owner_id,
+            event: ControlRequest::Release(family_entity),
+        });
+        app.update();
+
+        assert!(app.world().get::<Controller>(family_entity).is_none());
+    }
+}
@@ -0,0 +1,125 @@
+use std::time::SystemTime;
+
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+use crate::core::GameState;
+
+/// Initializes the deterministic random number generator for gameplay.
+pub(super) struct GameRngPlugin;
+
+impl Plugin for GameRngPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<WorldSeed>()
+            .register_type::<GameRng>()
+            .add_systems(
+                OnEnter(GameState::InGame),
+                Self::init.run_if(server_or_singleplayer),
+            )
+            .add_systems(OnExit(GameState::InGame), Self::cleanup);
+    }
+}
+
+impl GameRngPlugin {
+    /// Creates a seed and generator for newly created worlds.
+    ///
+    /// Loaded worlds already contain them.
+    fn init(mut commands: Commands, seed: Option<Res<WorldSeed>>, rng: Option<Res<GameRng>>) {
+        let seed = match seed {
+            Some(seed) => *seed,
+            None => {
+                let seed = WorldSeed::from_time();
+                info!("generating world seed {}", seed.0);
+                commands.insert_resource(seed);
+                seed
+            }
+        };
+
+        if rng.is_none() {
+            commands.insert_resource(GameRng::new(seed.0));
+        }
+    }
+
+    fn cleanup(mut commands: Commands) {
+        commands.remove_resource::<WorldSeed>();
+        commands.remove_resource::<GameRng>();
+    }
+}
+
+/// Seed from which all gameplay randomness of the world originates.
+///
+/// Stored inside the world file.
+#[derive(Resource, Reflect, Clone, Copy, Debug, Default)]
+#[reflect(Resource)]
+pub struct WorldSeed(pub u64);
+
+impl WorldSeed {
+    fn from_time() -> Self {
+        let current_time = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default();
+        Self(current_time.as_nanos() as u64)
+    }
+}
+
+/// Random number generator that all gameplay systems should draw from.
+///
+/// Available only on server, which advances it authoritatively.
+/// The state is stored inside the world file, so reloading reproduces outcomes.
+/// Uses `SplitMix64`, which is fast and has a single-word state.
+#[derive(Resource, Reflect, Clone, Debug, Default)]
+#[reflect(Resource)]
+pub struct GameRng {
+    state: u64,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut value = self.state;
+        value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        value ^ (value >> 31)
+    }
+
+    /// Returns a value in `[0, 1)`.
+    pub fn next_f32(&mut self) -> f32 {
+        // Use the upper bits that fit into the mantissa.
+        (self.next_u64() >> 40) as f32 / (1 << 24) as f32
+    }
+
+    /// Picks an item with probability proportional to its weight.
+    ///
+    /// Returns `None` if there are no items with a positive weight.
+    pub fn choose_weighted<'a, T>(
+        &mut self,
+        items: &'a [T],
+        weight: impl Fn(&T) -> f32,
+    ) -> Option<&'a T> {
+        let total: f32 = items.iter().map(&weight).filter(|&w| w > 0.0).sum();
+        if total <= 0.0 {
+            return None;
+        }
+
+        let mut target = self.next_f32() * total;
+        let mut last_positive = None;
+        for item in items {
+            let item_weight = weight(item);
+            if item_weight <= 0.0 {
+                continue;
+            }
+            if target < item_weight {
+                return Some(item);
+            }
+            target -= item_weight;
+            last_positive = Some(item);
+        }
+
+        // Guard against floating point errors.
+        last_positive
+    }
+}
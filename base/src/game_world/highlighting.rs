@@ -13,6 +13,7 @@ pub(super) struct HighlightingPlugin;
 impl Plugin for HighlightingPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<Highlighting>()
+            .init_resource::<HighlightingStyle>()
             .add_observer(Self::show)
             .add_observer(Self::hide)
             .add_observer(Self::init_scene)
@@ -26,7 +27,11 @@ impl Plugin for HighlightingPlugin {
             )
             .add_systems(OnExit(BuildingMode::Objects), Self::disable_highlighting)
             .add_systems(OnExit(CityMode::Objects), Self::disable_highlighting)
-            .add_systems(OnExit(FamilyMode::Life), Self::disable_highlighting);
+            .add_systems(OnExit(FamilyMode::Life), Self::disable_highlighting)
+            .add_systems(
+                PostUpdate,
+                Self::apply_style.run_if(resource_changed::<HighlightingStyle>),
+            );
     }
 }
 
@@ -63,8 +68,17 @@ impl HighlightingPlugin {
         }
     }
 
+    fn apply_style(style: Res<HighlightingStyle>, mut volumes: Query<&mut OutlineVolume>) {
+        debug!("applying highlighting style");
+        for mut outline in &mut volumes {
+            outline.width = style.width;
+            outline.colour = style.hovered_color;
+        }
+    }
+
     fn show(
         trigger: Trigger<Pointer<Over>>,
+        style: Res<HighlightingStyle>,
         mut highlighting: ResMut<Highlighting>,
         disabler: Query<(), With<HighlightDisabler>>,
         mut volumes: Query<(&mut OutlineVolume, &CollisionLayers)>,
@@ -85,6 +99,8 @@ impl HighlightingPlugin {
         if disabler.is_empty() {
             debug!("showing highlighting for `{}`", trigger.entity());
             outline.visible = true;
+            outline.width = style.width;
+            outline.colour = style.hovered_color;
         }
     }
 
@@ -106,6 +122,7 @@ impl HighlightingPlugin {
 
     fn disable(
         _trigger: Trigger<OnAdd, HighlightDisabler>,
+        style: Res<HighlightingStyle>,
         mut volumes: Query<&mut OutlineVolume>,
         mut highlighting: ResMut<Highlighting>,
     ) {
@@ -113,6 +130,7 @@ impl HighlightingPlugin {
             if let Ok(mut outline) = volumes.get_mut(entity) {
                 debug!("disabling highlighting for `{entity}`");
                 outline.visible = true;
+                outline.colour = style.selected_color;
             } else {
                 highlighting.last_hovered = None;
             }
@@ -121,6 +139,7 @@ impl HighlightingPlugin {
 
     fn enable(
         _trigger: Trigger<OnRemove, HighlightDisabler>,
+        style: Res<HighlightingStyle>,
         mut highlighting: ResMut<Highlighting>,
         mut volumes: Query<&mut OutlineVolume>,
     ) {
@@ -128,6 +147,7 @@ impl HighlightingPlugin {
             if let Ok(mut outline) = volumes.get_mut(entity) {
                 debug!("enabling highlighting for `{entity}`");
                 outline.visible = true;
+                outline.colour = style.hovered_color;
             } else {
                 highlighting.last_hovered = None;
             }
@@ -141,6 +161,25 @@ pub(super) const HIGHLIGHTING_VOLUME: OutlineVolume = OutlineVolume {
     width: 3.0,
 };
 
+/// Appearance of outlines, mirrors the UI theme.
+#[derive(Resource, Clone, Copy, PartialEq)]
+pub struct HighlightingStyle {
+    pub width: f32,
+    pub hovered_color: Color,
+    /// Color for the last hovered entity while highlighting is disabled by [`HighlightDisabler`].
+    pub selected_color: Color,
+}
+
+impl Default for HighlightingStyle {
+    fn default() -> Self {
+        Self {
+            width: HIGHLIGHTING_VOLUME.width,
+            hovered_color: HIGHLIGHTING_VOLUME.colour,
+            selected_color: Color::srgba(0.35, 0.75, 0.35, 0.8),
+        }
+    }
+}
+
 #[derive(Resource)]
 struct Highlighting {
     mask: LayerMask,
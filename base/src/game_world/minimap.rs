@@ -0,0 +1,272 @@
+use std::time::Duration;
+
+use bevy::{
+    prelude::*,
+    render::{
+        camera::{RenderTarget, ScalingMode},
+        render_resource::{Extent3d, TextureUsages},
+    },
+};
+
+use super::{
+    city::{ActiveCity, CITY_SIZE, HALF_CITY_SIZE},
+    player_camera::{OrbitOrigin, PlayerCamera},
+    WorldState,
+};
+use crate::settings::{Settings, SettingsApply};
+
+/// Renders a top-down view of the active city into [`MinimapImage`].
+///
+/// To keep the cost low, the camera renders a single frame each [`REFRESH_INTERVAL`].
+pub(super) struct MinimapPlugin;
+
+impl Plugin for MinimapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MinimapImage>()
+            .add_observer(Self::spawn)
+            .add_observer(Self::apply_settings)
+            .add_observer(Self::recenter)
+            .add_systems(
+                Update,
+                (Self::refresh, Self::update_view.never_param_warn())
+                    .run_if(any_with_component::<MinimapCamera>),
+            )
+            .add_systems(OnExit(WorldState::City), Self::despawn)
+            .add_systems(OnExit(WorldState::Family), Self::despawn);
+    }
+}
+
+/// Real time between minimap updates.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Width and height of the minimap image.
+const RESOLUTION: u32 = 256;
+
+/// Height from which the city is captured.
+const CAMERA_HEIGHT: f32 = 100.0;
+
+impl MinimapPlugin {
+    fn spawn(
+        trigger: Trigger<OnAdd, ActiveCity>,
+        mut commands: Commands,
+        settings: Res<Settings>,
+        minimap_image: Res<MinimapImage>,
+    ) {
+        if settings.interface.minimap {
+            spawn_camera(&mut commands, trigger.entity(), &minimap_image);
+        }
+    }
+
+    fn apply_settings(
+        _trigger: Trigger<SettingsApply>,
+        mut commands: Commands,
+        settings: Res<Settings>,
+        minimap_image: Res<MinimapImage>,
+        active_city: Option<Single<Entity, With<ActiveCity>>>,
+        cameras: Query<Entity, With<MinimapCamera>>,
+    ) {
+        let Some(city_entity) = active_city else {
+            return;
+        };
+
+        if !settings.interface.minimap {
+            for camera_entity in &cameras {
+                debug!("despawning minimap camera");
+                commands.entity(camera_entity).despawn();
+            }
+        } else if cameras.is_empty() {
+            spawn_camera(&mut commands, *city_entity, &minimap_image);
+        }
+    }
+
+    /// Activates the camera only for a single frame each interval.
+    ///
+    /// Inactive cameras keep the last rendered image.
+    fn refresh(
+        time: Res<Time<Real>>,
+        camera: Single<(&mut Camera, &mut RefreshTimer), With<MinimapCamera>>,
+    ) {
+        let (mut camera, mut timer) = camera.into_inner();
+        timer.tick(time.delta());
+        camera.is_active = timer.just_finished();
+    }
+
+    /// Projects corners of the player camera viewport onto the ground.
+    ///
+    /// Corners above the horizon are limited by the render distance.
+    fn update_view(
+        settings: Res<Settings>,
+        player_camera: Single<(&Parent, &Camera, &GlobalTransform), With<PlayerCamera>>,
+        cities: Query<&GlobalTransform>,
+        mut minimap_view: Single<&mut MinimapView>,
+    ) {
+        let (parent, camera, &camera_transform) = *player_camera;
+        let Some(viewport_size) = camera.logical_viewport_size() else {
+            return;
+        };
+        let city_transform = cities.get(**parent).unwrap();
+        let inverse_affine = city_transform.affine().inverse();
+        let render_distance = settings.video.render_distance();
+
+        let mut min = Vec2::ONE;
+        let mut max = Vec2::ZERO;
+        for corner in [Vec2::ZERO, Vec2::X, Vec2::Y, Vec2::ONE] {
+            let Ok(ray) = camera.viewport_to_world(&camera_transform, corner * viewport_size)
+            else {
+                continue;
+            };
+            let distance = ray
+                .intersect_plane(city_transform.translation(), InfinitePlane3d::new(Vec3::Y))
+                .unwrap_or(render_distance)
+                .min(render_distance);
+            let local_point = inverse_affine.transform_point3(ray.get_point(distance));
+            let minimap_point = to_minimap(local_point.xz()).clamp(Vec2::ZERO, Vec2::ONE);
+            min = min.min(minimap_point);
+            max = max.max(minimap_point);
+        }
+
+        minimap_view.set_if_neq(MinimapView(Rect { min, max }));
+    }
+
+    fn recenter(
+        trigger: Trigger<MinimapClick>,
+        mut orbit_origin: Single<&mut OrbitOrigin, With<PlayerCamera>>,
+    ) {
+        let point = from_minimap(trigger.0.clamp(Vec2::ZERO, Vec2::ONE));
+        info!("moving camera to `{point}` from minimap");
+        orbit_origin.x = point.x;
+        orbit_origin.z = point.y;
+    }
+
+    fn despawn(mut commands: Commands, cameras: Query<Entity, With<MinimapCamera>>) {
+        for camera_entity in &cameras {
+            debug!("despawning minimap camera");
+            commands.entity(camera_entity).despawn();
+        }
+    }
+}
+
+fn spawn_camera(commands: &mut Commands, city_entity: Entity, minimap_image: &MinimapImage) {
+    debug!("spawning minimap camera for city `{city_entity}`");
+    commands.entity(city_entity).with_children(|parent| {
+        parent.spawn((
+            MinimapCamera,
+            Camera {
+                target: RenderTarget::Image(minimap_image.0.clone()),
+                ..Default::default()
+            },
+        ));
+    });
+}
+
+/// Converts a city-local point on the ground into normalized minimap coordinates.
+fn to_minimap(point: Vec2) -> Vec2 {
+    (point + HALF_CITY_SIZE) / CITY_SIZE
+}
+
+/// Converts normalized minimap coordinates into a city-local point on the ground.
+fn from_minimap(point: Vec2) -> Vec2 {
+    point * CITY_SIZE - HALF_CITY_SIZE
+}
+
+/// Image into which the minimap camera renders.
+#[derive(Resource)]
+pub struct MinimapImage(pub Handle<Image>);
+
+impl FromWorld for MinimapImage {
+    fn from_world(world: &mut World) -> Self {
+        let mut image = Image::default();
+        image.texture_descriptor.usage |= TextureUsages::RENDER_ATTACHMENT;
+        image.resize(Extent3d {
+            width: RESOLUTION,
+            height: RESOLUTION,
+            ..Default::default()
+        });
+
+        let mut images = world.resource_mut::<Assets<Image>>();
+        Self(images.add(image))
+    }
+}
+
+/// Camera that looks at the whole city from above.
+///
+/// Oriented with `-Z` at the top of the image.
+#[derive(Component)]
+#[require(
+    Name(|| Name::new("Minimap camera")),
+    Camera3d,
+    Projection(top_down_projection),
+    Transform(|| Transform::from_xyz(0.0, CAMERA_HEIGHT, 0.0).looking_at(Vec3::ZERO, Vec3::NEG_Z)),
+    MinimapView,
+    RefreshTimer
+)]
+pub struct MinimapCamera;
+
+fn top_down_projection() -> Projection {
+    OrthographicProjection {
+        scaling_mode: ScalingMode::Fixed {
+            width: CITY_SIZE,
+            height: CITY_SIZE,
+        },
+        ..OrthographicProjection::default_3d()
+    }
+    .into()
+}
+
+/// Area visible from the player camera in normalized minimap coordinates.
+///
+/// `(0, 0)` corresponds to the top left corner of the image.
+#[derive(Component, Clone, Copy, Default, Deref, PartialEq)]
+pub struct MinimapView(Rect);
+
+#[derive(Component, Deref, DerefMut)]
+struct RefreshTimer(Timer);
+
+impl Default for RefreshTimer {
+    fn default() -> Self {
+        let mut timer = Timer::new(REFRESH_INTERVAL, TimerMode::Repeating);
+        // Render the first frame immediately.
+        timer.set_elapsed(REFRESH_INTERVAL);
+        Self(timer)
+    }
+}
+
+/// Moves the player camera to a point in normalized minimap coordinates.
+#[derive(Event)]
+pub struct MinimapClick(pub Vec2);
+
+#[cfg(test)]
+mod tests {
+    use bevy::time::TimeUpdateStrategy;
+
+    use super::*;
+
+    #[test]
+    fn refresh_rate() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(REFRESH_INTERVAL / 2))
+            .add_systems(Update, MinimapPlugin::refresh);
+
+        let camera_entity = app.world_mut().spawn(MinimapCamera).id();
+
+        let mut active_frames = Vec::new();
+        for _ in 0..5 {
+            app.update();
+            let camera = app.world().get::<Camera>(camera_entity).unwrap();
+            active_frames.push(camera.is_active);
+        }
+
+        // The first update doesn't advance the time.
+        assert_eq!(active_frames, [true, false, true, false, true]);
+    }
+
+    #[test]
+    fn coordinates() {
+        assert_eq!(to_minimap(Vec2::splat(-HALF_CITY_SIZE)), Vec2::ZERO);
+        assert_eq!(to_minimap(Vec2::ZERO), Vec2::splat(0.5));
+
+        let point = Vec2::new(-30.0, 120.0);
+        assert_eq!(from_minimap(to_minimap(point)), point);
+    }
+}
@@ -0,0 +1,197 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, utils::HashMap};
+use bevy_replicon::prelude::*;
+#[cfg(feature = "networking")]
+use bevy_replicon_renet::{
+    netcode::{NetcodeClientTransport, NetcodeServerTransport},
+    renet::{RenetClient, RenetServer},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::core::GameState;
+#[cfg(feature = "networking")]
+use crate::{error_message::ErrorMessage, network::ConnectionInfo};
+
+/// Host tools to remove players from the session.
+pub(super) struct ModerationPlugin;
+
+impl Plugin for ModerationPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<BannedPlayers>()
+            .init_resource::<BannedPlayers>()
+            .init_resource::<PlayerIds>()
+            .init_resource::<PendingKicks>()
+            .add_server_event::<KickNotice>(ChannelKind::Ordered)
+            .add_observer(Self::kick)
+            .add_systems(OnExit(GameState::InGame), Self::cleanup);
+
+        #[cfg(feature = "networking")]
+        app.add_systems(
+            PreUpdate,
+            (
+                Self::check_connections
+                    .after(ServerSet::Receive)
+                    .run_if(server_running),
+                Self::receive_notice
+                    .after(ClientSet::Receive)
+                    .run_if(client_connected),
+            ),
+        )
+        .add_systems(
+            PostUpdate,
+            Self::disconnect_kicked
+                .never_param_warn()
+                .run_if(server_running),
+        );
+    }
+}
+
+/// Time for the kick notice to reach the client before disconnecting.
+const NOTICE_DELAY: Duration = Duration::from_millis(500);
+
+impl ModerationPlugin {
+    /// Remembers player IDs of connected clients and refuses banned ones.
+    #[cfg(feature = "networking")]
+    fn check_connections(
+        mut server_events: EventReader<ServerEvent>,
+        mut notice_events: EventWriter<ToClients<KickNotice>>,
+        time: Res<Time<Real>>,
+        transport: Option<Res<NetcodeServerTransport>>,
+        banned: Res<BannedPlayers>,
+        mut player_ids: ResMut<PlayerIds>,
+        mut pending_kicks: ResMut<PendingKicks>,
+    ) {
+        for event in server_events.read() {
+            match *event {
+                ServerEvent::ClientConnected { client_id } => {
+                    let Some(user_data) = transport
+                        .as_ref()
+                        .and_then(|transport| transport.user_data(client_id.get()))
+                    else {
+                        continue;
+                    };
+
+                    let info = ConnectionInfo::from_user_data(&user_data);
+                    if banned.contains(&info.player_id) {
+                        info!(
+                            "refusing `{client_id:?}` with banned player ID {}",
+                            info.player_id
+                        );
+                        notice_events.send(ToClients {
+                            mode: SendMode::Direct(client_id),
+                            event: KickNotice("You are banned from this world".to_string()),
+                        });
+                        pending_kicks.push((client_id, time.elapsed() + NOTICE_DELAY));
+                    }
+                    player_ids.insert(client_id, info.player_id);
+                }
+                ServerEvent::ClientDisconnected { client_id, .. } => {
+                    player_ids.remove(&client_id);
+                }
+            }
+        }
+    }
+
+    fn kick(
+        trigger: Trigger<KickPlayer>,
+        mut notice_events: EventWriter<ToClients<KickNotice>>,
+        time: Res<Time<Real>>,
+        player_ids: Res<PlayerIds>,
+        mut banned: ResMut<BannedPlayers>,
+        mut pending_kicks: ResMut<PendingKicks>,
+    ) {
+        let event = trigger.event();
+        let Some(&player_id) = player_ids.get(&event.client_id) else {
+            error!("unable to kick unknown `{:?}`", event.client_id);
+            return;
+        };
+
+        let reason = if event.ban {
+            info!("banning `{:?}` with player ID {player_id}", event.client_id);
+            if !banned.contains(&player_id) {
+                banned.push(player_id);
+            }
+            "You were banned by the host"
+        } else {
+            info!("kicking `{:?}`", event.client_id);
+            "You were kicked by the host"
+        };
+
+        notice_events.send(ToClients {
+            mode: SendMode::Direct(event.client_id),
+            event: KickNotice(reason.to_string()),
+        });
+        pending_kicks.push((event.client_id, time.elapsed() + NOTICE_DELAY));
+    }
+
+    #[cfg(feature = "networking")]
+    fn disconnect_kicked(
+        time: Res<Time<Real>>,
+        mut server: ResMut<RenetServer>,
+        mut pending_kicks: ResMut<PendingKicks>,
+    ) {
+        pending_kicks.retain(|&(client_id, disconnect_time)| {
+            if time.elapsed() < disconnect_time {
+                return true;
+            }
+
+            debug!("disconnecting kicked `{client_id:?}`");
+            server.disconnect(client_id.get());
+            false
+        });
+    }
+
+    #[cfg(feature = "networking")]
+    fn receive_notice(mut commands: Commands, mut notice_events: EventReader<KickNotice>) {
+        for notice in notice_events.read() {
+            info!("disconnected by the host: {}", **notice);
+            commands.remove_resource::<RenetClient>();
+            commands.remove_resource::<NetcodeClientTransport>();
+            commands.set_state(GameState::Menu);
+            commands.trigger(ErrorMessage(format!("Disconnected: {}", **notice)));
+        }
+    }
+
+    fn cleanup(
+        mut banned: ResMut<BannedPlayers>,
+        mut player_ids: ResMut<PlayerIds>,
+        mut pending_kicks: ResMut<PendingKicks>,
+    ) {
+        banned.clear();
+        player_ids.clear();
+        pending_kicks.clear();
+    }
+}
+
+/// Disconnects a client with a notice.
+///
+/// Should be triggered only on server.
+#[derive(Event)]
+pub struct KickPlayer {
+    pub client_id: ClientId,
+
+    /// Also refuse future connections from this player.
+    pub ban: bool,
+}
+
+/// Persistent player IDs that aren't allowed to join.
+///
+/// Stored inside the world file.
+#[derive(Resource, Reflect, Default, Deref, DerefMut)]
+#[reflect(Resource)]
+pub struct BannedPlayers(Vec<u64>);
+
+/// Persistent player IDs of connected clients.
+///
+/// Available only on server.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct PlayerIds(HashMap<ClientId, u64>);
+
+/// Clients that will be disconnected after the specified time.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct PendingKicks(Vec<(ClientId, Duration)>);
+
+/// Sent to a client before disconnecting it.
+#[derive(Clone, Deref, Deserialize, Event, Serialize)]
+struct KickNotice(String);
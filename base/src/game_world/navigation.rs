@@ -162,6 +162,9 @@ impl NavigationPlugin {
     }
 }
 
+/// How fast agents turn, used as a slerp factor per second.
+pub(super) const ROTATION_SPEED: f32 = 10.0;
+
 /// Marks an entity with [`Collider`] as a navigation mesh affector.
 #[derive(Component, Default)]
 pub struct Obstacle;
@@ -196,7 +199,6 @@ fn move_agent(
 
     let disp = target_point - transform.translation;
     let target_rotation = transform.looking_to(disp, Vec3::Y).rotation;
-    const ROTATION_SPEED: f32 = 10.0;
     transform.translation += disp.normalize() * movement_step;
     transform.rotation = transform
         .rotation
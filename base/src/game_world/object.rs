@@ -1,7 +1,9 @@
 pub(crate) mod door;
 pub mod placing_object;
+pub mod reachability;
 pub(crate) mod wall_mount;
 
+use anyhow::{anyhow, ensure, Context, Result};
 use avian3d::prelude::*;
 use bevy::{
     asset::AssetPath,
@@ -13,33 +15,50 @@ use bevy_replicon::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use super::{
+    actor::Actor,
     city::{City, HALF_CITY_SIZE},
     commands_history::{
-        CommandConfirmation, CommandId, CommandRequest, ConfirmableCommand, EntityRecorder,
-        PendingCommand,
+        CommandConfirmation, CommandId, CommandRejection, CommandRequest, ConfirmableCommand,
+        EntityRecorder, LastEditor, PendingCommand,
+    },
+    difficulty::CurrentModifiers,
+    family::{
+        controller::{can_control, Controller},
+        Budget, BudgetPolicy,
     },
     highlighting::HIGHLIGHTING_VOLUME,
+    spectator::Spectators,
+};
+use crate::{
+    asset::manifest::object_manifest::ObjectManifest, combined_scene_collider::GeneratedCollider,
+    game_world::Layer,
 };
-use crate::{asset::manifest::object_manifest::ObjectManifest, game_world::Layer};
 use door::DoorPlugin;
 use placing_object::PlacingObjectPlugin;
+use reachability::ReachabilityPlugin;
 use wall_mount::WallMountPlugin;
 
 pub(super) struct ObjectPlugin;
 
 impl Plugin for ObjectPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((DoorPlugin, PlacingObjectPlugin, WallMountPlugin))
-            .register_type::<Object>()
-            .replicate_group::<(Object, Transform)>()
-            .add_mapped_client_event::<CommandRequest<ObjectCommand>>(ChannelKind::Unordered)
-            .add_observer(Self::init)
-            .add_systems(
-                PostUpdate,
-                Self::apply_command
-                    .before(ServerSet::StoreHierarchy)
-                    .run_if(server_or_singleplayer),
-            );
+        app.add_plugins((
+            DoorPlugin,
+            PlacingObjectPlugin,
+            ReachabilityPlugin,
+            WallMountPlugin,
+        ))
+        .register_type::<Object>()
+        .register_type::<Usage>()
+        .replicate_group::<(Object, Transform)>()
+        .add_mapped_client_event::<CommandRequest<ObjectCommand>>(ChannelKind::Unordered)
+        .add_observer(Self::init)
+        .add_systems(
+            PostUpdate,
+            Self::apply_command
+                .before(ServerSet::StoreHierarchy)
+                .run_if(server_or_singleplayer),
+        );
     }
 }
 
@@ -81,59 +100,252 @@ impl ObjectPlugin {
 
     fn apply_command(
         mut commands: Commands,
+        asset_server: Res<AssetServer>,
+        manifests: Res<Assets<ObjectManifest>>,
         mut request_events: EventReader<FromClient<CommandRequest<ObjectCommand>>>,
         mut confirm_events: EventWriter<ToClients<CommandConfirmation>>,
-        mut objects: Query<&mut Transform, Without<City>>,
+        mut reject_events: EventWriter<ToClients<CommandRejection>>,
+        spectators: Res<Spectators>,
+        modifiers: CurrentModifiers,
+        spatial_query: SpatialQuery,
+        cities: Query<&GlobalTransform, With<City>>,
+        colliders: Query<(&Object, &Collider), With<GeneratedCollider>>,
+        actors: Query<&Actor>,
+        policies: Query<&BudgetPolicy>,
+        controllers: Query<&Controller>,
+        mut budgets: Query<&mut Budget>,
+        mut objects: Query<(&Object, &Parent, &mut Transform, Option<&LastEditor>)>,
     ) {
         for FromClient { client_id, event } in request_events.read().cloned() {
-            // TODO: validate if command can be applied.
+            if spectators.contains(client_id) {
+                warn!("rejecting object command from spectator `{client_id:?}`");
+                reject_events.send(ToClients {
+                    mode: SendMode::Direct(client_id),
+                    event: CommandRejection::new(
+                        event.id,
+                        "spectators can't edit the world".into(),
+                    ),
+                });
+                continue;
+            }
+
             let mut confirmation = CommandConfirmation::new(event.id);
-            match event.command {
+            // Undo and redo shouldn't override changes from other players.
+            let check_editor = |editor: Option<&LastEditor>| {
+                ensure!(
+                    !event.id.from_history() || editor.is_none_or(|editor| **editor == client_id),
+                    "the object was changed by another player"
+                );
+                Ok(())
+            };
+            let result = match event.command {
                 ObjectCommand::Buy {
                     manifest_path,
                     city_entity,
                     translation,
                     rotation,
-                } => {
-                    if translation.y.abs() > HALF_CITY_SIZE {
-                        error!("received translation {translation} with 'y' outside of city size");
-                        continue;
-                    }
-
-                    info!("`{client_id:?}` buys object {manifest_path:?}");
-                    commands.entity(city_entity).with_children(|parent| {
-                        let transform =
-                            Transform::from_translation(translation).with_rotation(rotation);
-                        let entity = parent.spawn((Object(manifest_path), transform)).id();
-                        confirmation.entity = Some(entity);
-                    });
-                }
+                    buyer_entity,
+                } => validate_translation(translation)
+                    .and_then(|()| {
+                        let city_transform = cities
+                            .get(city_entity)
+                            .map_err(|_| anyhow!("city doesn't exist"))?;
+                        let manifest = asset_server
+                            .get_handle::<ObjectManifest>(&manifest_path)
+                            .and_then(|handle| manifests.get(&handle))
+                            .with_context(|| format!("object {manifest_path:?} doesn't exist"))?;
+
+                        // All objects with the same manifest have the same collider.
+                        let collider = colliders
+                            .iter()
+                            .find(|(object, _)| ***object == manifest_path)
+                            .map(|(_, collider)| collider);
+                        validate_collision(
+                            &spatial_query,
+                            collider,
+                            city_transform,
+                            Transform::from_translation(translation).with_rotation(rotation),
+                            None,
+                        )?;
+
+                        if let Some(buyer_entity) = buyer_entity {
+                            // Requests are processed sequentially and the budget is modified in place,
+                            // so the next purchase in the same frame will see the updated value.
+                            let (payer_entity, mut budget) = payer_budget(
+                                &actors,
+                                &policies,
+                                &controllers,
+                                &mut budgets,
+                                buyer_entity,
+                                client_id,
+                            )?;
+                            let price = modifiers.get().object_price(manifest.price);
+                            budget.withdraw(price)?;
+                            debug!("charging {price} from `{payer_entity}`");
+                        }
+
+                        Ok(())
+                    })
+                    .map(|()| {
+                        info!("`{client_id:?}` buys object {manifest_path:?}");
+                        commands.entity(city_entity).with_children(|parent| {
+                            let transform =
+                                Transform::from_translation(translation).with_rotation(rotation);
+                            let entity = parent
+                                .spawn((Object(manifest_path), transform, LastEditor(client_id)))
+                                .id();
+                            confirmation.entity = Some(entity);
+                        });
+                    }),
                 ObjectCommand::Move {
                     entity,
                     translation,
                     rotation,
-                } => match objects.get_mut(entity) {
-                    Ok(mut transform) => {
-                        info!("`{client_id:?}` moves object `{entity}`");
-                        transform.translation = translation;
-                        transform.rotation = rotation;
-                    }
-                    Err(e) => error!("unable to move object `{entity}`: {e}"),
-                },
-                ObjectCommand::Sell { entity } => {
-                    info!("`{client_id:?}` sells object `{entity}`");
-                    commands.entity(entity).despawn_recursive();
+                } => validate_translation(translation).and_then(|()| {
+                    let (_, parent, mut transform, editor) = objects
+                        .get_mut(entity)
+                        .with_context(|| format!("object `{entity}` doesn't exist"))?;
+                    check_editor(editor)?;
+                    let city_transform = cities
+                        .get(**parent)
+                        .context("object should belong to a city")?;
+                    let collider = colliders.get(entity).ok().map(|(_, collider)| collider);
+                    validate_collision(
+                        &spatial_query,
+                        collider,
+                        city_transform,
+                        Transform::from_translation(translation).with_rotation(rotation),
+                        Some(entity),
+                    )?;
+                    info!("`{client_id:?}` moves object `{entity}`");
+                    transform.translation = translation;
+                    transform.rotation = rotation;
+                    commands.entity(entity).insert(LastEditor(client_id));
+                    Ok(())
+                }),
+                ObjectCommand::Sell {
+                    entity,
+                    seller_entity,
+                } => objects
+                    .get(entity)
+                    .with_context(|| format!("object `{entity}` doesn't exist"))
+                    .and_then(|(object, _, _, editor)| {
+                        check_editor(editor)?;
+                        if let Some(seller_entity) = seller_entity {
+                            let manifest = asset_server
+                                .get_handle::<ObjectManifest>(&**object)
+                                .and_then(|handle| manifests.get(&handle))
+                                .with_context(|| format!("object {:?} doesn't exist", **object))?;
+                            let (payer_entity, mut budget) = payer_budget(
+                                &actors,
+                                &policies,
+                                &controllers,
+                                &mut budgets,
+                                seller_entity,
+                                client_id,
+                            )?;
+                            let price = modifiers.get().object_price(manifest.price);
+                            budget.deposit(price);
+                            debug!("refunding {price} to `{payer_entity}`");
+                        }
+                        Ok(())
+                    })
+                    .map(|()| {
+                        info!("`{client_id:?}` sells object `{entity}`");
+                        commands.entity(entity).despawn_recursive();
+                    }),
+            };
+
+            match result {
+                Ok(()) => {
+                    confirm_events.send(ToClients {
+                        mode: SendMode::Direct(client_id),
+                        event: confirmation,
+                    });
+                }
+                Err(e) => {
+                    error!("rejecting object command from `{client_id:?}`: {e:#}");
+                    reject_events.send(ToClients {
+                        mode: SendMode::Direct(client_id),
+                        event: CommandRejection::new(event.id, format!("{e:#}")),
+                    });
                 }
             }
-
-            confirm_events.send(ToClients {
-                mode: SendMode::Direct(client_id),
-                event: confirmation,
-            });
         }
     }
 }
 
+/// Returns the budget that the actor uses according to the [`BudgetPolicy`] of its family.
+///
+/// Fails if the family is controlled by another client.
+fn payer_budget<'a>(
+    actors: &Query<&Actor>,
+    policies: &Query<&BudgetPolicy>,
+    controllers: &Query<&Controller>,
+    budgets: &'a mut Query<&mut Budget>,
+    actor_entity: Entity,
+    client_id: ClientId,
+) -> Result<(Entity, Mut<'a, Budget>)> {
+    let actor = actors
+        .get(actor_entity)
+        .with_context(|| format!("actor `{actor_entity}` doesn't exist"))?;
+    ensure!(
+        can_control(controllers.get(actor.family_entity).ok(), client_id),
+        "the family is controlled by another player"
+    );
+    let policy = policies
+        .get(actor.family_entity)
+        .context("actor should belong to a family")?;
+    let payer_entity = policy.payer(actor_entity, actor);
+    let budget = budgets
+        .get_mut(payer_entity)
+        .expect("actors and families should always have a budget");
+
+    Ok((payer_entity, budget))
+}
+
+/// Checks if the translation relative to the city is inside its bounds.
+fn validate_translation(translation: Vec3) -> Result<()> {
+    ensure!(
+        translation.x.abs() <= HALF_CITY_SIZE
+            && translation.y.abs() <= HALF_CITY_SIZE
+            && translation.z.abs() <= HALF_CITY_SIZE,
+        "translation {translation} is outside of the city"
+    );
+    Ok(())
+}
+
+/// Checks that an object placed with the transform relative to the city doesn't overlap
+/// other objects or walls.
+///
+/// Skipped if the collider isn't generated yet, clients perform the same check while placing.
+fn validate_collision(
+    spatial_query: &SpatialQuery,
+    collider: Option<&Collider>,
+    city_transform: &GlobalTransform,
+    transform: Transform,
+    object_entity: Option<Entity>,
+) -> Result<()> {
+    let Some(collider) = collider else {
+        debug!("skipping collision check for an object without a generated collider");
+        return Ok(());
+    };
+
+    let (_, rotation, translation) = city_transform
+        .mul_transform(transform)
+        .to_scale_rotation_translation();
+    let filter = SpatialQueryFilter::from_mask([Layer::Object, Layer::Wall])
+        .with_excluded_entities(object_entity);
+    let intersections = spatial_query.shape_intersections(collider, translation, rotation, &filter);
+    ensure!(
+        intersections.is_empty(),
+        "the object overlaps with {} other objects",
+        intersections.len()
+    );
+
+    Ok(())
+}
+
 /// Contains path to the object info.
 #[derive(Clone, Component, Debug, Default, Reflect, Serialize, Deserialize, Deref)]
 #[reflect(Component)]
@@ -149,7 +361,39 @@ impl ObjectPlugin {
         [Layer::PlacingObject, Layer::Wall, Layer::PlacingWall],
     ))
 )]
-pub(crate) struct Object(pub(crate) AssetPath<'static>);
+pub struct Object(pub(crate) AssetPath<'static>);
+
+impl Object {
+    /// Creates an object from the path to its manifest.
+    pub fn new(manifest_path: AssetPath<'static>) -> Self {
+        Self(manifest_path)
+    }
+}
+
+/// Limits how many actors can use an object at once.
+///
+/// Declared in object manifests. Objects without it are unlimited.
+/// Slots are reserved on server when a task using the object becomes active.
+#[derive(Component, Reflect, Default, Clone, Debug)]
+#[reflect(Component)]
+pub struct Usage {
+    pub capacity: u8,
+    #[reflect(ignore)]
+    pub occupants: Vec<Entity>,
+}
+
+impl Usage {
+    pub fn new(capacity: u8) -> Self {
+        Self {
+            capacity,
+            occupants: Default::default(),
+        }
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.occupants.len() >= self.capacity.into()
+    }
+}
 
 #[derive(Clone, Deserialize, Serialize)]
 enum ObjectCommand {
@@ -158,6 +402,10 @@ enum ObjectCommand {
         city_entity: Entity,
         translation: Vec3,
         rotation: Quat,
+        /// Actor that pays for the object according to its [`BudgetPolicy`].
+        ///
+        /// Objects placed outside of family mode are free.
+        buyer_entity: Option<Entity>,
     },
     Move {
         entity: Entity,
@@ -166,6 +414,10 @@ enum ObjectCommand {
     },
     Sell {
         entity: Entity,
+        /// Actor that receives the refund according to its [`BudgetPolicy`].
+        ///
+        /// Objects removed outside of family mode aren't refunded.
+        seller_entity: Option<Entity>,
     },
 }
 
@@ -177,9 +429,10 @@ impl PendingCommand for ObjectCommand {
         world: &mut World,
     ) -> Box<dyn ConfirmableCommand> {
         let reverse_command = match *self {
-            Self::Buy { .. } => Self::Sell {
+            Self::Buy { buyer_entity, .. } => Self::Sell {
                 // Correct entity will be set after the server confirmation.
                 entity: Entity::PLACEHOLDER,
+                seller_entity: buyer_entity,
             },
             Self::Move { entity, .. } => {
                 let transform = world.get::<Transform>(entity).unwrap();
@@ -189,17 +442,22 @@ impl PendingCommand for ObjectCommand {
                     rotation: transform.rotation,
                 }
             }
-            Self::Sell { entity } => {
+            Self::Sell {
+                entity,
+                seller_entity,
+            } => {
                 recorder.record(entity);
                 let entity = world.entity(entity);
                 let manifest_path = entity.get::<Object>().unwrap().0.clone();
                 let parent = entity.get::<Parent>().unwrap();
                 let transform = entity.get::<Transform>().unwrap();
+                // Restoring charges back the refund.
                 Self::Buy {
                     manifest_path,
                     city_entity: **parent,
                     translation: transform.translation,
                     rotation: transform.rotation,
+                    buyer_entity: seller_entity,
                 }
             }
         };
@@ -216,7 +474,7 @@ impl ConfirmableCommand for ObjectCommand {
         mut recorder: EntityRecorder,
         confirmation: CommandConfirmation,
     ) -> Box<dyn PendingCommand> {
-        if let Self::Sell { entity } = &mut *self {
+        if let Self::Sell { entity, .. } = &mut *self {
             *entity = confirmation
                 .entity
                 .expect("confirmation for object buying should contain an entity");
@@ -230,9 +488,541 @@ impl ConfirmableCommand for ObjectCommand {
 impl MapEntities for ObjectCommand {
     fn map_entities<T: EntityMapper>(&mut self, entity_mapper: &mut T) {
         match self {
-            Self::Buy { .. } => (),
+            Self::Buy { buyer_entity, .. } => {
+                if let Some(entity) = buyer_entity {
+                    *entity = entity_mapper.map_entity(*entity);
+                }
+            }
             Self::Move { entity, .. } => *entity = entity_mapper.map_entity(*entity),
-            Self::Sell { entity } => *entity = entity_mapper.map_entity(*entity),
+            Self::Sell {
+                entity,
+                seller_entity,
+            } => {
+                *entity = entity_mapper.map_entity(*entity);
+                if let Some(seller_entity) = seller_entity {
+                    *seller_entity = entity_mapper.map_entity(*seller_entity);
+                }
+            }
         };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::system::RunSystemOnce, state::app::StatesPlugin};
+
+    use super::*;
+    use crate::{
+        asset::manifest::{object_manifest::ObjectCategory, GeneralManifest},
+        core::GameState,
+        game_world::{
+            commands_history::{CommandHistoryPlugin, CommandsHistory},
+            difficulty,
+            family::Family,
+        },
+    };
+
+    #[test]
+    fn command_validation() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<ObjectManifest>()
+            .add_event::<FromClient<CommandRequest<ObjectCommand>>>()
+            .add_event::<ToClients<CommandConfirmation>>()
+            .add_event::<ToClients<CommandRejection>>()
+            .init_resource::<Spectators>()
+            .init_resource::<SpatialQueryPipeline>()
+            .add_systems(Update, ObjectPlugin::apply_command);
+        difficulty::insert_table(&mut app);
+
+        let city_entity = app.world_mut().spawn(City).id();
+        let object_entity = app
+            .world_mut()
+            .spawn((Object::default(), Transform::default()))
+            .set_parent(city_entity)
+            .id();
+
+        let move_id = CommandId::new(0, false);
+        let outside_id = CommandId::new(1, false);
+        let missing_city_id = CommandId::new(2, false);
+        let translation = Vec3::X;
+        for (id, command) in [
+            (
+                move_id,
+                ObjectCommand::Move {
+                    entity: object_entity,
+                    translation,
+                    rotation: Quat::IDENTITY,
+                },
+            ),
+            (
+                outside_id,
+                ObjectCommand::Move {
+                    entity: object_entity,
+                    translation: Vec3::X * (HALF_CITY_SIZE + 1.0),
+                    rotation: Quat::IDENTITY,
+                },
+            ),
+            (
+                missing_city_id,
+                ObjectCommand::Buy {
+                    manifest_path: "missing.object.ron".into(),
+                    city_entity: Entity::PLACEHOLDER,
+                    translation,
+                    rotation: Quat::IDENTITY,
+                    buyer_entity: None,
+                },
+            ),
+        ] {
+            app.world_mut().send_event(FromClient {
+                client_id: ClientId::SERVER,
+                event: CommandRequest { id, command },
+            });
+        }
+
+        app.update();
+
+        let confirmations = app
+            .world()
+            .resource::<Events<ToClients<CommandConfirmation>>>();
+        let confirmed: Vec<_> = confirmations
+            .iter_current_update_events()
+            .map(|event| event.event.id)
+            .collect();
+        assert_eq!(confirmed, [move_id]);
+
+        let rejections = app
+            .world()
+            .resource::<Events<ToClients<CommandRejection>>>();
+        let rejected: Vec<_> = rejections
+            .iter_current_update_events()
+            .map(|event| event.event.id)
+            .collect();
+        assert_eq!(rejected, [outside_id, missing_city_id]);
+
+        let transform = app.world().get::<Transform>(object_entity).unwrap();
+        assert_eq!(
+            transform.translation, translation,
+            "only valid movement should be applied"
+        );
+    }
+
+    #[test]
+    fn history_conflict() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<ObjectManifest>()
+            .add_event::<FromClient<CommandRequest<ObjectCommand>>>()
+            .add_event::<ToClients<CommandConfirmation>>()
+            .add_event::<ToClients<CommandRejection>>()
+            .init_resource::<Spectators>()
+            .init_resource::<SpatialQueryPipeline>()
+            .add_systems(Update, ObjectPlugin::apply_command);
+        difficulty::insert_table(&mut app);
+
+        let client_id = ClientId::new(1);
+        let city_entity = app.world_mut().spawn(City).id();
+        let own_entity = app
+            .world_mut()
+            .spawn((Object::default(), LastEditor(client_id)))
+            .set_parent(city_entity)
+            .id();
+        let other_entity = app
+            .world_mut()
+            .spawn((Object::default(), LastEditor(ClientId::new(2))))
+            .set_parent(city_entity)
+            .id();
+
+        let own_id = CommandId::new(0, true);
+        let other_id = CommandId::new(1, true);
+        let new_id = CommandId::new(2, false);
+        for (id, entity) in [
+            (own_id, own_entity),
+            (other_id, other_entity),
+            (new_id, other_entity),
+        ] {
+            app.world_mut().send_event(FromClient {
+                client_id,
+                event: CommandRequest {
+                    id,
+                    command: ObjectCommand::Move {
+                        entity,
+                        translation: Vec3::X,
+                        rotation: Quat::IDENTITY,
+                    },
+                },
+            });
+        }
+
+        app.update();
+
+        let confirmations = app
+            .world()
+            .resource::<Events<ToClients<CommandConfirmation>>>();
+        let confirmed: Vec<_> = confirmations
+            .iter_current_update_events()
+            .map(|event| event.event.id)
+            .collect();
+        assert_eq!(
+            confirmed,
+            [own_id, new_id],
+            "new commands should be allowed for any object"
+        );
+
+        let rejections = app
+            .world()
+            .resource::<Events<ToClients<CommandRejection>>>();
+        let rejected: Vec<_> = rejections
+            .iter_current_update_events()
+            .map(|event| event.event.id)
+            .collect();
+        assert_eq!(
+            rejected,
+            [other_id],
+            "undo shouldn't override changes from other players"
+        );
+    }
+
+    #[test]
+    fn shared_budget() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<ObjectManifest>()
+            .add_event::<FromClient<CommandRequest<ObjectCommand>>>()
+            .add_event::<ToClients<CommandConfirmation>>()
+            .add_event::<ToClients<CommandRejection>>()
+            .init_resource::<Spectators>()
+            .init_resource::<SpatialQueryPipeline>()
+            .add_systems(Update, ObjectPlugin::apply_command);
+        difficulty::insert_table(&mut app);
+
+        let manifest_path = insert_manifest(&mut app, 600);
+        let price = app
+            .world_mut()
+            .run_system_once(|modifiers: CurrentModifiers| modifiers.get().object_price(600))
+            .unwrap();
+
+        let city_entity = app.world_mut().spawn(City).id();
+        let family_entity = app.world_mut().spawn(Family).id();
+        let first_entity = app.world_mut().spawn(Actor { family_entity }).id();
+        let second_entity = app.world_mut().spawn(Actor { family_entity }).id();
+        let budget = **app.world().get::<Budget>(family_entity).unwrap();
+        let purchases = budget / price;
+
+        // Simulate purchases from different players received in the same frame.
+        for index in 0..=purchases {
+            let buyer_entity = if index % 2 == 0 {
+                first_entity
+            } else {
+                second_entity
+            };
+            app.world_mut().send_event(FromClient {
+                client_id: ClientId::new(index.into()),
+                event: CommandRequest {
+                    id: CommandId::new(index as u8, false),
+                    command: ObjectCommand::Buy {
+                        manifest_path: manifest_path.clone(),
+                        city_entity,
+                        translation: Vec3::ZERO,
+                        rotation: Quat::IDENTITY,
+                        buyer_entity: Some(buyer_entity),
+                    },
+                },
+            });
+        }
+
+        app.update();
+
+        let confirmations = app
+            .world()
+            .resource::<Events<ToClients<CommandConfirmation>>>();
+        let bought_entity = confirmations
+            .iter_current_update_events()
+            .next()
+            .and_then(|event| event.event.entity)
+            .expect("purchase should be confirmed");
+        assert_eq!(
+            confirmations.iter_current_update_events().count(),
+            purchases as usize
+        );
+        let rejections = app
+            .world()
+            .resource::<Events<ToClients<CommandRejection>>>();
+        assert_eq!(
+            rejections.iter_current_update_events().count(),
+            1,
+            "the last purchase should see the updated budget"
+        );
+        let remaining = **app.world().get::<Budget>(family_entity).unwrap();
+        assert_eq!(remaining, budget - purchases * price);
+
+        app.world_mut().send_event(FromClient {
+            client_id: ClientId::SERVER,
+            event: CommandRequest {
+                id: CommandId::new(0, false),
+                command: ObjectCommand::Sell {
+                    entity: bought_entity,
+                    seller_entity: Some(second_entity),
+                },
+            },
+        });
+
+        app.update();
+
+        assert_eq!(
+            **app.world().get::<Budget>(family_entity).unwrap(),
+            remaining + price,
+            "selling should refund the family budget"
+        );
+
+        app.world_mut()
+            .entity_mut(family_entity)
+            .insert(Controller(ClientId::new(1)));
+        let foreign_id = CommandId::new(1, false);
+        app.world_mut().send_event(FromClient {
+            client_id: ClientId::new(2),
+            event: CommandRequest {
+                id: foreign_id,
+                command: ObjectCommand::Buy {
+                    manifest_path,
+                    city_entity,
+                    translation: Vec3::ZERO,
+                    rotation: Quat::IDENTITY,
+                    buyer_entity: Some(first_entity),
+                },
+            },
+        });
+
+        app.update();
+
+        let rejections = app
+            .world()
+            .resource::<Events<ToClients<CommandRejection>>>();
+        let rejected: Vec<_> = rejections
+            .iter_current_update_events()
+            .map(|event| event.event.id)
+            .collect();
+        assert_eq!(
+            rejected,
+            [foreign_id],
+            "only the controlling player should be able to buy for the family"
+        );
+    }
+
+    #[test]
+    fn collision() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<ObjectManifest>()
+            .add_event::<FromClient<CommandRequest<ObjectCommand>>>()
+            .add_event::<ToClients<CommandConfirmation>>()
+            .add_event::<ToClients<CommandRejection>>()
+            .init_resource::<Spectators>()
+            .init_resource::<SpatialQueryPipeline>()
+            .add_systems(Update, ObjectPlugin::apply_command);
+        difficulty::insert_table(&mut app);
+
+        let manifest_path = insert_manifest(&mut app, 0);
+        let city_entity = app.world_mut().spawn(City).id();
+        let object_entity = app
+            .world_mut()
+            .spawn((
+                Object::new(manifest_path.clone()),
+                Collider::cuboid(1.0, 1.0, 1.0),
+                GeneratedCollider,
+                Position::default(),
+                Rotation::default(),
+            ))
+            .set_parent(city_entity)
+            .id();
+
+        // Physics plugins aren't added, so update the pipeline manually.
+        app.world_mut()
+            .run_system_once(|mut spatial_query: SpatialQuery| spatial_query.update_pipeline())
+            .unwrap();
+
+        let overlap_id = CommandId::new(0, false);
+        let free_id = CommandId::new(1, false);
+        let move_id = CommandId::new(2, false);
+        for (id, command) in [
+            (
+                overlap_id,
+                ObjectCommand::Buy {
+                    manifest_path: manifest_path.clone(),
+                    city_entity,
+                    translation: Vec3::ZERO,
+                    rotation: Quat::IDENTITY,
+                    buyer_entity: None,
+                },
+            ),
+            (
+                free_id,
+                ObjectCommand::Buy {
+                    manifest_path,
+                    city_entity,
+                    translation: Vec3::X * 5.0,
+                    rotation: Quat::IDENTITY,
+                    buyer_entity: None,
+                },
+            ),
+            (
+                move_id,
+                ObjectCommand::Move {
+                    entity: object_entity,
+                    translation: Vec3::X * 0.5,
+                    rotation: Quat::IDENTITY,
+                },
+            ),
+        ] {
+            app.world_mut().send_event(FromClient {
+                client_id: ClientId::SERVER,
+                event: CommandRequest { id, command },
+            });
+        }
+
+        app.update();
+
+        let confirmations = app
+            .world()
+            .resource::<Events<ToClients<CommandConfirmation>>>();
+        let confirmed: Vec<_> = confirmations
+            .iter_current_update_events()
+            .map(|event| event.event.id)
+            .collect();
+        assert_eq!(
+            confirmed,
+            [free_id, move_id],
+            "object shouldn't collide with itself"
+        );
+
+        let rejections = app
+            .world()
+            .resource::<Events<ToClients<CommandRejection>>>();
+        let rejected: Vec<_> = rejections
+            .iter_current_update_events()
+            .map(|event| event.event.id)
+            .collect();
+        assert_eq!(rejected, [overlap_id]);
+    }
+
+    #[test]
+    fn undo_redo() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            AssetPlugin::default(),
+            RepliconPlugins,
+            CommandHistoryPlugin,
+        ))
+        .init_state::<GameState>()
+        .init_asset::<ObjectManifest>()
+        .add_mapped_client_event::<CommandRequest<ObjectCommand>>(ChannelKind::Unordered)
+        .init_resource::<Spectators>()
+        .init_resource::<SpatialQueryPipeline>()
+        .add_systems(Update, ObjectPlugin::apply_command);
+        difficulty::insert_table(&mut app);
+
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::InGame);
+        app.update();
+
+        let manifest_path = insert_manifest(&mut app, 0);
+        let city_entity = app.world_mut().spawn(City).id();
+        let translations = [Vec3::ZERO, Vec3::X, Vec3::X * 2.0];
+        for translation in translations {
+            let command = ObjectCommand::Buy {
+                manifest_path: manifest_path.clone(),
+                city_entity,
+                translation,
+                rotation: Quat::IDENTITY,
+                buyer_entity: None,
+            };
+            history(&mut app, move |mut history| {
+                history.push_pending(command.clone());
+            });
+        }
+        assert_eq!(object_translations(&mut app), translations);
+
+        history(&mut app, |mut history| history.undo());
+        assert_eq!(object_translations(&mut app), translations[..2]);
+
+        history(&mut app, |mut history| history.undo());
+        assert_eq!(
+            object_translations(&mut app),
+            translations[..1],
+            "the most recent placements should be undone first"
+        );
+
+        history(&mut app, |mut history| history.redo());
+        assert_eq!(
+            object_translations(&mut app),
+            translations[..2],
+            "only the last undone placement should be redone"
+        );
+
+        history(&mut app, |mut history| history.undo());
+        assert_eq!(
+            object_translations(&mut app),
+            translations[..1],
+            "redone placement should be undoable"
+        );
+    }
+
+    /// Runs the function with history and waits for the server to confirm the command.
+    fn history(app: &mut App, f: impl Fn(CommandsHistory) + Send + Sync + 'static) {
+        app.world_mut()
+            .run_system_once(move |history: CommandsHistory| f(history))
+            .unwrap();
+
+        // Sending the request, applying it on server and receiving the confirmation happen in different frames.
+        for _ in 0..3 {
+            app.update();
+        }
+    }
+
+    /// Returns translations of all objects sorted by X to identify them.
+    fn object_translations(app: &mut App) -> Vec<Vec3> {
+        let mut translations: Vec<_> = app
+            .world_mut()
+            .query_filtered::<&Transform, With<Object>>()
+            .iter(app.world())
+            .map(|transform| transform.translation)
+            .collect();
+        translations.sort_by(|a, b| a.x.total_cmp(&b.x));
+        translations
+    }
+
+    /// Registers the path without a loader, so the manifest can be inserted manually.
+    fn insert_manifest(app: &mut App, price: u32) -> AssetPath<'static> {
+        let manifest_path: AssetPath = "chair.object.ron".into();
+        let manifest_handle = app
+            .world()
+            .resource::<AssetServer>()
+            .load::<ObjectManifest>(manifest_path.clone());
+        app.world_mut()
+            .resource_mut::<Assets<ObjectManifest>>()
+            .insert(&manifest_handle, manifest(price));
+
+        manifest_path
+    }
+
+    fn manifest(price: u32) -> ObjectManifest {
+        ObjectManifest {
+            general: GeneralManifest {
+                name: "Chair".to_string(),
+                author: Default::default(),
+                license: Default::default(),
+            },
+            scene: Default::default(),
+            category: ObjectCategory("furniture".to_string()),
+            subcategory: None,
+            price,
+            preview_translation: Vec3::ZERO,
+            components: Vec::new(),
+            place_components: Vec::new(),
+            spawn_components: Vec::new(),
+        }
+    }
+}
@@ -18,6 +18,7 @@ use crate::{
     alpha_color::{AlphaColor, AlphaColorPlugin},
     asset::manifest::object_manifest::ObjectManifest,
     game_world::{
+        actor::SelectedActor,
         city::CityMode,
         commands_history::{CommandsHistory, PendingDespawn},
         family::building::BuildingMode,
@@ -34,14 +35,19 @@ use wall_snap::WallSnapPlugin;
 
 pub(super) struct PlacingObjectPlugin;
 
+/// Distance for a single arrow key press in keyboard placement mode.
+const KEYBOARD_STEP: f32 = 0.5;
+
 impl Plugin for PlacingObjectPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(WallSnapPlugin)
             .add_plugins(SideSnapPlugin)
             .add_input_context::<PlacingObject>()
+            .init_resource::<RecentObjects>()
             .add_observer(Self::pick)
             .add_observer(Self::init)
             .add_observer(Self::rotate)
+            .add_observer(Self::step)
             .add_observer(Self::sell)
             .add_observer(Self::cancel.never_param_warn())
             .add_observer(Self::confirm)
@@ -99,6 +105,7 @@ impl PlacingObjectPlugin {
         camera_caster: CameraCaster,
         objects_manifests: Res<Assets<ObjectManifest>>,
         asset_server: Res<AssetServer>,
+        settings: Res<Settings>,
         camera_transform: Single<&Transform, With<PlayerCamera>>,
         mut placing_objects: Query<
             (
@@ -119,7 +126,7 @@ impl PlacingObjectPlugin {
             trigger.entity()
         );
 
-        let (manifest, cursor_offset, rotation) = match placing_object {
+        let (manifest, cursor_offset, rotation, translation) = match placing_object {
             PlacingObject::Spawning(id) => {
                 let manifest = objects_manifests.get(id).expect("info should be preloaded");
 
@@ -128,7 +135,18 @@ impl PlacingObjectPlugin {
                 let rounded_angle = (y / FRAC_PI_2).round() * FRAC_PI_2 - PI;
                 let rotation = Quat::from_rotation_y(rounded_angle);
 
-                (manifest, Vec3::ZERO, rotation)
+                // Start at the point the camera looks at.
+                let translation =
+                    Ray3d::new(camera_transform.translation, camera_transform.forward())
+                        .intersect_plane(Vec3::ZERO, InfinitePlane3d::new(Vec3::Y))
+                        .map(|distance| {
+                            let point = camera_transform.translation
+                                + camera_transform.forward() * distance;
+                            (point / KEYBOARD_STEP).round() * KEYBOARD_STEP
+                        })
+                        .unwrap_or_default();
+
+                (manifest, Vec3::ZERO, rotation, translation)
             }
             PlacingObject::Moving(object_entity) => {
                 let (object, &transform) = objects
@@ -145,13 +163,22 @@ impl PlacingObjectPlugin {
                     .map(|point| transform.translation - point)
                     .unwrap_or(transform.translation);
 
-                (manifest, cursor_offset, transform.rotation)
+                (
+                    manifest,
+                    cursor_offset,
+                    transform.rotation,
+                    transform.translation,
+                )
             }
         };
 
         scene_root.0 = asset_server.load(manifest.scene.clone());
         transform.rotation = rotation;
         state.cursor_offset = cursor_offset;
+        state.keyboard_translation = translation;
+        if settings.keyboard.keyboard_placement {
+            transform.translation = translation;
+        }
 
         let mut placing_entity = commands.entity(trigger.entity());
 
@@ -182,10 +209,35 @@ impl PlacingObjectPlugin {
         );
     }
 
+    fn step(
+        trigger: Trigger<Started<StepObject>>,
+        camera_transform: Single<&Transform, With<PlayerCamera>>,
+        mut state: Single<&mut PlacingObjectState>,
+    ) {
+        // Move along the world axis closest to the camera direction.
+        let forward = camera_transform.forward();
+        let forward = if forward.x.abs() > forward.z.abs() {
+            Vec3::X * forward.x.signum()
+        } else {
+            Vec3::Z * forward.z.signum()
+        };
+        let right = forward.cross(Vec3::Y);
+
+        let event = trigger.event();
+        let step = (right * event.value.x + forward * event.value.y) * KEYBOARD_STEP;
+        state.keyboard_translation += step;
+
+        debug!(
+            "stepping placing object to `{}`",
+            state.keyboard_translation
+        );
+    }
+
     fn sell(
         trigger: Trigger<Completed<SellObject>>,
         mut commands: Commands,
         mut history: CommandsHistory,
+        seller_entity: Option<Single<Entity, With<SelectedActor>>>,
         placing_object: Single<(&PlacingObject, &mut Transform)>,
         objects: Query<&Transform, Without<PlacingObject>>,
     ) {
@@ -195,7 +247,10 @@ impl PlacingObjectPlugin {
             // Set original position until the deletion is confirmed.
             *transform = *objects.get(entity).expect("moving object should exist");
 
-            let command_id = history.push_pending(ObjectCommand::Sell { entity });
+            let command_id = history.push_pending(ObjectCommand::Sell {
+                entity,
+                seller_entity: seller_entity.map(|entity| *entity),
+            });
             commands
                 .entity(trigger.entity())
                 .insert(PendingDespawn { command_id })
@@ -214,7 +269,10 @@ impl PlacingObjectPlugin {
         trigger: Trigger<Completed<ConfirmObject>>,
         mut commands: Commands,
         mut history: CommandsHistory,
+        mut recent_objects: ResMut<RecentObjects>,
         asset_server: Res<AssetServer>,
+        settings: Res<Settings>,
+        buyer_entity: Option<Single<Entity, With<SelectedActor>>>,
         placing_object: Single<(
             &Parent,
             &Transform,
@@ -231,6 +289,7 @@ impl PlacingObjectPlugin {
 
         let command_id = match placing_object {
             PlacingObject::Spawning(id) => {
+                recent_objects.push(id, settings.interface.recent_objects as usize);
                 let manifest_path = asset_server
                     .get_path(id)
                     .expect("manifest should always come from file");
@@ -239,6 +298,7 @@ impl PlacingObjectPlugin {
                     city_entity: **parent,
                     translation: translation.translation,
                     rotation: translation.rotation,
+                    buyer_entity: buyer_entity.map(|entity| *entity),
                 })
             }
             PlacingObject::Moving(entity) => history.push_pending(ObjectCommand::Move {
@@ -258,10 +318,14 @@ impl PlacingObjectPlugin {
 
     fn apply_position(
         camera_caster: CameraCaster,
+        settings: Res<Settings>,
         placing_object: Single<(&mut Transform, &PlacingObjectState)>,
     ) {
         let (mut transform, state) = placing_object.into_inner();
-        if let Some(point) = camera_caster.intersect_ground() {
+        if settings.keyboard.keyboard_placement {
+            // Applied every frame to let snapping plugins modify the position.
+            transform.translation = state.keyboard_translation;
+        } else if let Some(point) = camera_caster.intersect_ground() {
             transform.translation = point + state.cursor_offset;
         }
     }
@@ -309,6 +373,20 @@ pub enum PlacingObject {
     Moving(Entity),
 }
 
+/// Objects recently placed by the local player, the most recent first.
+///
+/// Limited by [`InterfaceSettings::recent_objects`](crate::settings::InterfaceSettings::recent_objects).
+#[derive(Resource, Default, Deref)]
+pub struct RecentObjects(Vec<AssetId<ObjectManifest>>);
+
+impl RecentObjects {
+    fn push(&mut self, id: AssetId<ObjectManifest>, max_len: usize) {
+        self.0.retain(|&recent_id| recent_id != id);
+        self.0.insert(0, id);
+        self.0.truncate(max_len);
+    }
+}
+
 impl InputContext for PlacingObject {
     const PRIORITY: isize = 1;
 
@@ -331,6 +409,17 @@ impl InputContext for PlacingObject {
         ctx.bind::<ConfirmObject>()
             .to((MouseButton::Left, GamepadButton::South));
 
+        if settings.keyboard.keyboard_placement {
+            ctx.bind::<StepObject>().to(Cardinal {
+                north: KeyCode::ArrowUp,
+                east: KeyCode::ArrowRight,
+                south: KeyCode::ArrowDown,
+                west: KeyCode::ArrowLeft,
+            });
+            ctx.bind::<ConfirmObject>()
+                .to((KeyCode::Enter, KeyCode::NumpadEnter));
+        }
+
         ctx
     }
 }
@@ -339,6 +428,10 @@ impl InputContext for PlacingObject {
 #[input_action(output = f32)]
 struct RotateObject;
 
+#[derive(Debug, InputAction)]
+#[input_action(output = Vec2)]
+struct StepObject;
+
 #[derive(Debug, InputAction)]
 #[input_action(output = bool)]
 struct SellObject;
@@ -362,6 +455,9 @@ struct PlacingObjectState {
     /// An offset between cursor position on first creation and object origin.
     cursor_offset: Vec3,
 
+    /// Position controlled by arrow keys when keyboard placement is enabled.
+    keyboard_translation: Vec3,
+
     /// Additional object condition for placing.
     ///
     /// For example, a door can be placed only on a wall. Controlled by other plugins.
@@ -372,6 +468,7 @@ impl Default for PlacingObjectState {
     fn default() -> Self {
         Self {
             cursor_offset: Default::default(),
+            keyboard_translation: Default::default(),
             allowed_place: true,
         }
     }
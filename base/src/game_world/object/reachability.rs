@@ -0,0 +1,189 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use bevy_mod_billboard::prelude::*;
+use vleue_navigator::prelude::*;
+
+use super::Object;
+use crate::game_world::actor::Actor;
+
+/// Flags objects that actors can't walk to.
+///
+/// Objects stay placed, the flag only warns the player.
+pub(super) struct ReachabilityPlugin;
+
+impl Plugin for ReachabilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(Self::spawn_badge)
+            .add_observer(Self::despawn_badge)
+            .add_systems(PostUpdate, Self::update);
+    }
+}
+
+/// Distance from the object origin to the points from which actors interact with it.
+const INTERACTION_DISTANCE: f32 = 0.8;
+
+/// Number of points around the object to check.
+const INTERACTION_POINTS: usize = 8;
+
+/// Height of the warning badge above the object origin.
+const BADGE_HEIGHT: f32 = 1.5;
+
+impl ReachabilityPlugin {
+    /// Re-checks all objects in a city after its navmesh rebuild and newly placed objects.
+    fn update(
+        mut commands: Commands,
+        navmeshes: Res<Assets<NavMesh>>,
+        city_navmeshes: Query<(Entity, &ManagedNavMesh, &Parent, &NavMeshStatus)>,
+        children: Query<&Children>,
+        actors: Query<&Transform, With<Actor>>,
+        objects: Query<(Entity, &Transform, Has<Unreachable>), With<Object>>,
+        changed_navmeshes: Query<(), Changed<NavMeshStatus>>,
+        added_objects: Query<(), Added<Object>>,
+    ) {
+        for (navmesh_entity, navmesh_handle, parent, status) in &city_navmeshes {
+            if !matches!(status, NavMeshStatus::Built) {
+                continue;
+            }
+
+            let Some(navmesh) = navmeshes.get(navmesh_handle) else {
+                continue;
+            };
+
+            let rebuilt = changed_navmeshes.get(navmesh_entity).is_ok();
+            let city_children = children.get(**parent).unwrap();
+            if !rebuilt
+                && !city_children
+                    .iter()
+                    .any(|&entity| added_objects.get(entity).is_ok())
+            {
+                continue;
+            }
+
+            let starts: Vec<_> = actors
+                .iter_many(city_children)
+                .map(|transform| transform.translation)
+                .collect();
+            if starts.is_empty() {
+                // Nothing to check against.
+                continue;
+            }
+
+            for (object_entity, transform, unreachable) in objects.iter_many(city_children) {
+                let reachable = is_reachable(navmesh, &starts, transform.translation);
+                if reachable && unreachable {
+                    debug!("object `{object_entity}` is now reachable");
+                    commands.entity(object_entity).remove::<Unreachable>();
+                } else if !reachable && !unreachable {
+                    debug!("object `{object_entity}` is unreachable");
+                    commands.entity(object_entity).insert(Unreachable);
+                }
+            }
+        }
+    }
+
+    fn spawn_badge(
+        trigger: Trigger<OnAdd, Unreachable>,
+        mut commands: Commands,
+        asset_server: Res<AssetServer>,
+    ) {
+        commands.entity(trigger.entity()).with_children(|parent| {
+            parent.spawn((
+                UnreachableBadge,
+                BillboardText::new("⚠"),
+                TextFont {
+                    font: asset_server.load("base/fonts/NotoEmoji-Regular.ttf"),
+                    font_size: 60.0,
+                    ..Default::default()
+                },
+                TextColor(Color::srgb(1.0, 0.8, 0.0)),
+                Transform::from_translation(Vec3::Y * BADGE_HEIGHT).with_scale(Vec3::splat(0.005)),
+            ));
+        });
+    }
+
+    fn despawn_badge(
+        trigger: Trigger<OnRemove, Unreachable>,
+        mut commands: Commands,
+        children: Query<&Children>,
+        badges: Query<Entity, With<UnreachableBadge>>,
+    ) {
+        let Ok(children) = children.get(trigger.entity()) else {
+            return;
+        };
+
+        for entity in badges.iter_many(children) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// Returns `true` if any interaction point around the object can be reached from any start.
+fn is_reachable(navmesh: &NavMesh, starts: &[Vec3], translation: Vec3) -> bool {
+    (0..INTERACTION_POINTS)
+        .map(|index| {
+            let angle = index as f32 / INTERACTION_POINTS as f32 * TAU;
+            let (sin, cos) = angle.sin_cos();
+            translation + Vec3::new(cos, 0.0, sin) * INTERACTION_DISTANCE
+        })
+        .any(|point| {
+            starts
+                .iter()
+                .any(|&start| navmesh.transformed_path(start, point).is_some())
+        })
+}
+
+/// Marks an object that actors can't reach.
+///
+/// Calculated locally from the navmesh.
+#[derive(Component)]
+pub struct Unreachable;
+
+#[derive(Component)]
+#[require(Name(|| Name::new("Unreachable badge")))]
+struct UnreachableBadge;
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use super::*;
+
+    #[test]
+    fn walled_off() {
+        // Square room with walls on every side.
+        let wall = |min: Vec2, max: Vec2| {
+            vec![
+                Vec2::new(min.x, min.y),
+                Vec2::new(max.x, min.y),
+                Vec2::new(max.x, max.y),
+                Vec2::new(min.x, max.y),
+            ]
+        };
+        let obstacles = vec![
+            wall(Vec2::new(-3.0, -3.0), Vec2::new(3.0, -2.8)),
+            wall(Vec2::new(-3.0, 2.8), Vec2::new(3.0, 3.0)),
+            wall(Vec2::new(-3.0, -2.8), Vec2::new(-2.8, 2.8)),
+            wall(Vec2::new(2.8, -2.8), Vec2::new(3.0, 2.8)),
+        ];
+        let edges = vec![
+            Vec2::new(-10.0, -10.0),
+            Vec2::new(10.0, -10.0),
+            Vec2::new(10.0, 10.0),
+            Vec2::new(-10.0, 10.0),
+        ];
+        let mut navmesh = NavMesh::from_edge_and_obstacles(edges, obstacles);
+        // Same orientation as city navmeshes.
+        navmesh.set_transform(Transform::from_rotation(Quat::from_rotation_x(FRAC_PI_2)));
+
+        let starts = [Vec3::new(-8.0, 0.0, -8.0)];
+        assert!(
+            is_reachable(&navmesh, &starts, Vec3::new(6.0, 0.0, 6.0)),
+            "object in the open should be reachable"
+        );
+        assert!(
+            !is_reachable(&navmesh, &starts, Vec3::ZERO),
+            "object inside the walled-off room shouldn't be reachable"
+        );
+    }
+}
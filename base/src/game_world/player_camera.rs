@@ -4,15 +4,18 @@ use bevy::{
     asset::AssetPath, core_pipeline::experimental::taa::TemporalAntiAliasing,
     ecs::system::SystemParam, pbr::ScreenSpaceAmbientOcclusion, prelude::*,
 };
+use bevy_atmosphere::prelude::*;
 use bevy_enhanced_input::prelude::*;
 use num_enum::IntoPrimitive;
 use strum::EnumIter;
 
 use crate::{
     asset::collection::{AssetCollection, Collection},
+    combined_scene_collider::ColliderFocus,
     common_conditions::in_any_state,
     game_world::WorldState,
-    settings::Settings,
+    gpu_support::GpuSupport,
+    settings::{AntiAliasing, Settings, SettingsApply, VideoSettings},
 };
 
 pub(super) struct PlayerCameraPlugin;
@@ -22,6 +25,7 @@ impl Plugin for PlayerCameraPlugin {
         app.init_resource::<Collection<EnvironmentMap>>()
             .add_input_context::<PlayerCamera>()
             .add_observer(Self::init)
+            .add_observer(Self::apply_settings)
             .add_observer(Self::pan)
             .add_observer(Self::zoom)
             .add_observer(Self::rotate)
@@ -39,19 +43,49 @@ impl Plugin for PlayerCameraPlugin {
 impl PlayerCameraPlugin {
     fn init(
         trigger: Trigger<OnAdd, PlayerCamera>,
-        mut cameras: Query<&mut EnvironmentMapLight>,
+        mut commands: Commands,
+        mut cameras: Query<(&mut EnvironmentMapLight, &mut Camera, Has<DynamicSky>)>,
         environment_map: Res<Collection<EnvironmentMap>>,
+        settings: Res<Settings>,
+        gpu_support: Res<GpuSupport>,
     ) {
         debug!("initializing player camera");
-        let mut env_light = cameras.get_mut(trigger.entity()).unwrap();
+        let (mut env_light, mut camera, dynamic_sky) = cameras.get_mut(trigger.entity()).unwrap();
         env_light.diffuse_map = environment_map.handle(EnvironmentMap::Diffuse);
         env_light.specular_map = environment_map.handle(EnvironmentMap::Specular);
         env_light.intensity = 800.0;
+
+        apply_video_settings(
+            &mut commands.entity(trigger.entity()),
+            &mut camera,
+            dynamic_sky,
+            *gpu_support,
+            &settings.video,
+        );
+    }
+
+    fn apply_settings(
+        _trigger: Trigger<SettingsApply>,
+        mut commands: Commands,
+        settings: Res<Settings>,
+        gpu_support: Res<GpuSupport>,
+        mut cameras: Query<(Entity, &mut Camera, Has<DynamicSky>), With<PlayerCamera>>,
+    ) {
+        for (camera_entity, mut camera, dynamic_sky) in &mut cameras {
+            apply_video_settings(
+                &mut commands.entity(camera_entity),
+                &mut camera,
+                dynamic_sky,
+                *gpu_support,
+                &settings.video,
+            );
+        }
     }
 
     fn pan(
         trigger: Trigger<Fired<PanCamera>>,
         world_state: Res<State<WorldState>>,
+        settings: Res<Settings>,
         camera: Single<(&mut OrbitOrigin, &Transform, &SpringArm)>,
     ) {
         if *world_state == WorldState::FamilyEditor {
@@ -72,13 +106,18 @@ impl PlayerCameraPlugin {
         // Make speed dependent on camera distance.
         let arm_multiplier = **spring_arm * 0.02;
 
-        **orbit_origin += rotation * movement * arm_multiplier;
+        **orbit_origin += rotation * movement * arm_multiplier * settings.camera.pan_multiplier();
     }
 
-    fn zoom(trigger: Trigger<Fired<ZoomCamera>>, mut spring_arm: Single<&mut SpringArm>) {
+    fn zoom(
+        trigger: Trigger<Fired<ZoomCamera>>,
+        settings: Res<Settings>,
+        mut spring_arm: Single<&mut SpringArm>,
+    ) {
         let event = trigger.event();
+        let delta = event.value * settings.camera.zoom_multiplier();
         // Limit to prevent clipping into the ground.
-        ***spring_arm = (***spring_arm - event.value).max(0.2);
+        ***spring_arm = (***spring_arm - delta).max(0.2);
     }
 
     fn rotate(
@@ -87,7 +126,7 @@ impl PlayerCameraPlugin {
         mut rotation: Single<&mut OrbitRotation>,
     ) {
         let event = trigger.event();
-        ***rotation += event.value;
+        ***rotation += event.value * settings.camera.rotation_axes();
 
         let max_y = if settings.developer.free_camera_rotation {
             PI // To avoid flipping when the camera is under ground.
@@ -105,6 +144,65 @@ impl PlayerCameraPlugin {
     }
 }
 
+/// Reconfigures the camera for the anti-aliasing mode, render distance and sky.
+///
+/// TAA and SSAO require MSAA to be off, so they are removed for MSAA modes.
+/// Modes unsupported by the GPU fall back to no anti-aliasing.
+fn apply_video_settings(
+    commands: &mut EntityCommands,
+    camera: &mut Camera,
+    dynamic_sky: bool,
+    gpu_support: GpuSupport,
+    video: &VideoSettings,
+) {
+    if dynamic_sky && video.dynamic_sky {
+        commands.insert(AtmosphereCamera::default());
+        camera.clear_color = ClearColorConfig::Default;
+    } else {
+        // Atmosphere removes its skybox together with the component.
+        commands.remove::<AtmosphereCamera>();
+        if dynamic_sky {
+            camera.clear_color = ClearColorConfig::Custom(SKY_COLOR);
+        }
+    }
+
+    commands.insert(Projection::Perspective(PerspectiveProjection {
+        far: video.render_distance(),
+        ..Default::default()
+    }));
+
+    let mut anti_aliasing = video.anti_aliasing;
+    if !gpu_support.anti_aliasing(anti_aliasing) {
+        warn!("disabling `{anti_aliasing:?}` since it's unsupported by the GPU");
+        anti_aliasing = AntiAliasing::None;
+    }
+    debug!("setting anti-aliasing to `{anti_aliasing:?}`");
+    if anti_aliasing == AntiAliasing::Taa {
+        commands.insert(TemporalAntiAliasing::default());
+    } else {
+        commands.remove::<TemporalAntiAliasing>();
+    }
+
+    if !gpu_support.ssao {
+        commands.remove::<ScreenSpaceAmbientOcclusion>();
+    } else if anti_aliasing.supports_ssao() {
+        commands.insert(ScreenSpaceAmbientOcclusion::default());
+    } else {
+        warn!("disabling ambient occlusion since it's incompatible with `{anti_aliasing:?}`");
+        commands.remove::<ScreenSpaceAmbientOcclusion>();
+    }
+
+    // Applied last to avoid frames with MSAA enabled together with TAA or SSAO.
+    commands.insert(anti_aliasing.msaa());
+}
+
+/// Color used instead of the atmosphere when [`VideoSettings::dynamic_sky`] is disabled.
+const SKY_COLOR: Color = Color::srgb(0.53, 0.71, 0.89);
+
+/// Shows the atmosphere on the camera if enabled in settings.
+#[derive(Component)]
+pub(super) struct DynamicSky;
+
 #[derive(Component)]
 #[require(
     OrbitOrigin,
@@ -112,11 +210,9 @@ impl PlayerCameraPlugin {
     SpringArm,
     Name(|| Name::new("Player camera")),
     Camera3d,
-    Msaa(|| Msaa::Off),
     Camera(|| Camera { hdr: true, ..Default::default() }),
-    TemporalAntiAliasing,
     EnvironmentMapLight,
-    ScreenSpaceAmbientOcclusion
+    ColliderFocus
 )]
 pub(super) struct PlayerCamera;
 
@@ -231,7 +327,7 @@ impl AssetCollection for EnvironmentMap {
 
 /// The origin of a camera.
 #[derive(Component, Default, Deref, DerefMut)]
-struct OrbitOrigin(Vec3);
+pub(super) struct OrbitOrigin(Vec3);
 
 /// Camera rotation in `X` and `Z`.
 #[derive(Component, Deref, DerefMut)]
@@ -0,0 +1,239 @@
+use std::time::Duration;
+
+use bevy::{color::palettes::css::DEEP_SKY_BLUE, ecs::entity::MapEntities, prelude::*};
+use bevy_mod_billboard::prelude::*;
+use bevy_replicon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    city::{ActiveCity, City},
+    player_camera::CameraCaster,
+};
+use crate::{core::GameState, network};
+
+/// Shows where other players point in the world.
+///
+/// Purely cosmetic, so positions are throttled and sent over an unreliable channel.
+pub(super) struct RemoteCursorPlugin;
+
+impl Plugin for RemoteCursorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CursorAssets>()
+            .add_mapped_client_event::<CursorMove>(ChannelKind::Unreliable)
+            .add_mapped_server_event::<RemoteCursorMove>(ChannelKind::Unreliable)
+            .add_systems(
+                PreUpdate,
+                (
+                    Self::forward
+                        .after(ServerSet::Receive)
+                        .run_if(server_running),
+                    Self::apply.after(ClientSet::Receive),
+                )
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    Self::send
+                        .never_param_warn()
+                        .run_if(server_running.or(client_connected)),
+                    Self::despawn_stale,
+                )
+                    .run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+/// Minimal delay between sending cursor positions.
+const SEND_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Delay between sending an unchanged position, to keep the remote cursor alive.
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Cursors without updates for this duration will be removed.
+///
+/// Covers disconnected players and players that no longer point at the ground.
+const STALE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Offset of the label from the cursor.
+const LABEL_OFFSET: Vec3 = Vec3::new(0.0, 0.4, 0.0);
+
+impl RemoteCursorPlugin {
+    fn send(
+        time: Res<Time<Real>>,
+        mut last_sent: Local<Option<(Duration, Entity, Vec3)>>,
+        mut move_events: EventWriter<CursorMove>,
+        camera_caster: CameraCaster,
+        city_entity: Single<Entity, With<ActiveCity>>,
+    ) {
+        let Some(position) = camera_caster.intersect_ground() else {
+            return;
+        };
+
+        if let Some((sent_time, sent_city, sent_position)) = *last_sent {
+            let elapsed = time.elapsed() - sent_time;
+            let unchanged = sent_city == *city_entity && sent_position == position;
+            if elapsed < SEND_INTERVAL || (unchanged && elapsed < KEEPALIVE_INTERVAL) {
+                return;
+            }
+        }
+
+        *last_sent = Some((time.elapsed(), *city_entity, position));
+        move_events.send(CursorMove {
+            city_entity: *city_entity,
+            position,
+        });
+    }
+
+    fn forward(
+        mut move_events: EventReader<FromClient<CursorMove>>,
+        mut remote_events: EventWriter<ToClients<RemoteCursorMove>>,
+        cities: Query<(), With<City>>,
+    ) {
+        for &FromClient { client_id, event } in move_events.read() {
+            if cities.get(event.city_entity).is_err() {
+                debug!("ignoring cursor from `{client_id:?}` outside of a city");
+                continue;
+            }
+
+            remote_events.send(ToClients {
+                mode: SendMode::BroadcastExcept(client_id),
+                event: RemoteCursorMove {
+                    client_id,
+                    city_entity: event.city_entity,
+                    position: event.position,
+                },
+            });
+        }
+    }
+
+    fn apply(
+        mut commands: Commands,
+        time: Res<Time<Real>>,
+        assets: Res<CursorAssets>,
+        mut move_events: EventReader<RemoteCursorMove>,
+        mut cursors: Query<(Entity, &mut RemoteCursor, &Parent, &mut Transform)>,
+    ) {
+        for event in move_events.read() {
+            if let Some((entity, mut cursor, parent, mut transform)) = cursors
+                .iter_mut()
+                .find(|(_, cursor, ..)| cursor.client_id == event.client_id)
+            {
+                if **parent != event.city_entity {
+                    commands.entity(entity).set_parent(event.city_entity);
+                }
+                cursor.last_update = time.elapsed();
+                transform.translation = event.position;
+            } else {
+                debug!("spawning remote cursor for `{:?}`", event.client_id);
+                commands.entity(event.city_entity).with_children(|parent| {
+                    parent
+                        .spawn((
+                            RemoteCursor {
+                                client_id: event.client_id,
+                                last_update: time.elapsed(),
+                            },
+                            Transform::from_translation(event.position),
+                            Mesh3d(assets.mesh.clone()),
+                            MeshMaterial3d(assets.material.clone()),
+                        ))
+                        .with_child((
+                            BillboardText(network::player_label(event.client_id)),
+                            TextFont {
+                                font: assets.font.clone(),
+                                font_size: 40.0,
+                                ..Default::default()
+                            },
+                            TextColor(DEEP_SKY_BLUE.into()),
+                            Transform::from_translation(LABEL_OFFSET)
+                                .with_scale(Vec3::splat(0.005)),
+                        ));
+                });
+            }
+        }
+    }
+
+    fn despawn_stale(
+        mut commands: Commands,
+        time: Res<Time<Real>>,
+        cursors: Query<(Entity, &RemoteCursor)>,
+    ) {
+        for (entity, cursor) in &cursors {
+            if time.elapsed() - cursor.last_update > STALE_TIMEOUT {
+                debug!(
+                    "despawning stale remote cursor for `{:?}`",
+                    cursor.client_id
+                );
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+    }
+}
+
+#[derive(Resource)]
+struct CursorAssets {
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+    font: Handle<Font>,
+}
+
+impl FromWorld for CursorAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mesh = world.resource_mut::<Assets<Mesh>>().add(Sphere::new(0.08));
+        let material = world
+            .resource_mut::<Assets<StandardMaterial>>()
+            .add(StandardMaterial {
+                base_color: DEEP_SKY_BLUE.into(),
+                unlit: true,
+                ..Default::default()
+            });
+        let font = world
+            .resource::<AssetServer>()
+            .load("base/fonts/FiraSans-Bold.ttf");
+
+        Self {
+            mesh,
+            material,
+            font,
+        }
+    }
+}
+
+/// Cursor position of a remote player.
+#[derive(Component)]
+#[require(
+    Name(|| Name::new("Remote cursor")),
+    Visibility,
+    StateScoped::<GameState>(|| StateScoped(GameState::InGame)),
+)]
+struct RemoteCursor {
+    client_id: ClientId,
+    last_update: Duration,
+}
+
+/// Sent by clients with the ground position under their cursor.
+#[derive(Clone, Copy, Deserialize, Event, Serialize)]
+struct CursorMove {
+    city_entity: Entity,
+    position: Vec3,
+}
+
+impl MapEntities for CursorMove {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.city_entity = entity_mapper.map_entity(self.city_entity);
+    }
+}
+
+/// Forwarded by server to all other clients.
+#[derive(Clone, Copy, Deserialize, Event, Serialize)]
+struct RemoteCursorMove {
+    client_id: ClientId,
+    city_entity: Entity,
+    position: Vec3,
+}
+
+impl MapEntities for RemoteCursorMove {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.city_entity = entity_mapper.map_entity(self.city_entity);
+    }
+}
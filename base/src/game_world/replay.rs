@@ -0,0 +1,301 @@
+use std::{fs, path::PathBuf, time::Duration};
+
+use anyhow::{ensure, Context, Result};
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+use bincode::{DefaultOptions, Options};
+use serde::{Deserialize, Serialize};
+
+use super::{deserialize_world, serialize_world, WorldName};
+use crate::{
+    common_conditions::in_multiplayer, core::GameState, error_message::error_message,
+    game_paths::GamePaths, stepping::SetSimulationPaused,
+};
+
+/// Records periodic world snapshots and plays them back for debugging.
+///
+/// A replay file is a bincode-encoded [`Replay`]: format version,
+/// interval between snapshots and the list of [`ReplayFrame`].
+/// Each frame stores the virtual time of the capture and the bytes produced by [`serialize_world`],
+/// so snapshots are validated by [`deserialize_world`] on playback.
+pub(super) struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(Self::start_recording.pipe(error_message))
+            .add_observer(Self::stop_recording.pipe(error_message))
+            .add_observer(Self::open.pipe(error_message))
+            .add_observer(Self::seek.pipe(error_message))
+            .add_systems(
+                Update,
+                Self::record
+                    .run_if(resource_exists::<ReplayRecorder>)
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(OnExit(GameState::InGame), Self::cleanup);
+    }
+}
+
+/// Version of the replay format, increased on incompatible changes.
+const REPLAY_VERSION: u32 = 1;
+
+/// Virtual time between snapshots.
+const RECORD_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Maximum number of snapshots, recording stops automatically after reaching it.
+///
+/// With [`RECORD_INTERVAL`] it's one hour of the simulation.
+const MAX_FRAMES: usize = 720;
+
+impl ReplayPlugin {
+    fn start_recording(
+        _trigger: Trigger<StartRecording>,
+        mut commands: Commands,
+        recorder: Option<Res<ReplayRecorder>>,
+        playback: Option<Res<ReplayPlayback>>,
+    ) -> Result<()> {
+        ensure!(recorder.is_none(), "recording is already in progress");
+        ensure!(playback.is_none(), "replay is being played");
+
+        info!("starting recording");
+        commands.init_resource::<ReplayRecorder>();
+
+        Ok(())
+    }
+
+    /// Captures a snapshot each [`RECORD_INTERVAL`].
+    ///
+    /// Exclusive because the recorder needs to be modified while the world is serialized.
+    fn record(world: &mut World) {
+        let elapsed = world.resource::<Time<Virtual>>().elapsed();
+        let finished = world.resource_scope(|world, mut recorder: Mut<ReplayRecorder>| {
+            if recorder
+                .frames
+                .last()
+                .is_some_and(|frame| elapsed - frame.elapsed < RECORD_INTERVAL)
+            {
+                return false;
+            }
+
+            match serialize_world(world) {
+                Ok(snapshot) => {
+                    debug!("capturing snapshot {}", recorder.frames.len());
+                    recorder.frames.push(ReplayFrame { elapsed, snapshot });
+                }
+                Err(e) => error!("unable to capture snapshot: {e:#}"),
+            }
+
+            recorder.frames.len() >= MAX_FRAMES
+        });
+
+        if finished {
+            info!("reached the maximum replay length");
+            world.trigger(StopRecording);
+        }
+    }
+
+    fn stop_recording(
+        _trigger: Trigger<StopRecording>,
+        mut commands: Commands,
+        recorder: Option<ResMut<ReplayRecorder>>,
+        world_name: Option<Res<WorldName>>,
+        game_paths: Res<GamePaths>,
+    ) -> Result<()> {
+        let mut recorder = recorder.context("recording isn't in progress")?;
+        commands.remove_resource::<ReplayRecorder>();
+
+        let replay = Replay {
+            version: REPLAY_VERSION,
+            interval: RECORD_INTERVAL,
+            frames: recorder.frames.drain(..).collect(),
+        };
+        let world_name = world_name.map(|name| name.0.clone()).unwrap_or_default();
+        let path = game_paths.replay_path(&world_name);
+        info!("writing {} snapshots to {path:?}", replay.frames.len());
+
+        fs::create_dir_all(&game_paths.replays)
+            .with_context(|| format!("unable to create {:?}", game_paths.replays))?;
+        let bytes = DefaultOptions::new()
+            .serialize(&replay)
+            .context("unable to serialize replay")?;
+        fs::write(&path, bytes).with_context(|| format!("unable to write {path:?}"))
+    }
+
+    /// Reads a replay and shows its first snapshot.
+    ///
+    /// The simulation is paused to keep the snapshot intact.
+    fn open(
+        trigger: Trigger<ReplayOpen>,
+        mut commands: Commands,
+        recorder: Option<Res<ReplayRecorder>>,
+        server: Option<Res<RepliconServer>>,
+        client: Res<RepliconClient>,
+    ) -> Result<()> {
+        ensure!(
+            !in_multiplayer(server, client),
+            "replays are unavailable in multiplayer"
+        );
+        ensure!(recorder.is_none(), "recording is in progress");
+
+        let path = &trigger.0;
+        info!("opening replay {path:?}");
+        let bytes = fs::read(path).with_context(|| format!("unable to read {path:?}"))?;
+        let replay: Replay = DefaultOptions::new()
+            .deserialize(&bytes)
+            .with_context(|| format!("unable to deserialize {path:?}"))?;
+        ensure!(
+            replay.version == REPLAY_VERSION,
+            "replay version {} is not supported, expected {REPLAY_VERSION}",
+            replay.version
+        );
+        ensure!(!replay.frames.is_empty(), "replay is empty");
+
+        commands.insert_resource(ReplayPlayback { replay, current: 0 });
+        commands.trigger(SetSimulationPaused(true));
+        commands.trigger(ReplaySeek(0));
+
+        Ok(())
+    }
+
+    /// Replaces the world with the requested snapshot.
+    fn seek(
+        trigger: Trigger<ReplaySeek>,
+        mut commands: Commands,
+        mut scene_spawner: ResMut<SceneSpawner>,
+        mut scenes: ResMut<Assets<DynamicScene>>,
+        registry: Res<AppTypeRegistry>,
+        playback: Option<ResMut<ReplayPlayback>>,
+        replicated: Query<Entity, (With<Replicated>, Without<Parent>)>,
+    ) -> Result<()> {
+        let mut playback = playback.context("no replay is opened")?;
+        let index = **trigger;
+        let frame = playback.replay.frames.get(index).with_context(|| {
+            format!(
+                "snapshot {index} doesn't exist, replay contains {}",
+                playback.replay.frames.len()
+            )
+        })?;
+        let scene = deserialize_world(&frame.snapshot, &registry.read())
+            .with_context(|| format!("unable to deserialize snapshot {index}"))?;
+
+        info!("showing snapshot {index} captured at {:?}", frame.elapsed);
+        for entity in &replicated {
+            commands.entity(entity).despawn_recursive();
+        }
+        scene_spawner.spawn_dynamic(scenes.add(scene));
+        playback.current = index;
+
+        Ok(())
+    }
+
+    fn cleanup(mut commands: Commands) {
+        commands.remove_resource::<ReplayRecorder>();
+        commands.remove_resource::<ReplayPlayback>();
+    }
+}
+
+/// Content of a replay file.
+#[derive(Deserialize, Serialize)]
+struct Replay {
+    version: u32,
+    interval: Duration,
+    frames: Vec<ReplayFrame>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct ReplayFrame {
+    /// Virtual time at the moment of the capture.
+    elapsed: Duration,
+
+    /// World serialized with [`serialize_world`].
+    snapshot: Vec<u8>,
+}
+
+/// Snapshots captured since the recording started.
+///
+/// Exists only while recording.
+#[derive(Resource, Default)]
+struct ReplayRecorder {
+    frames: Vec<ReplayFrame>,
+}
+
+/// Opened replay.
+#[derive(Resource)]
+pub struct ReplayPlayback {
+    replay: Replay,
+    current: usize,
+}
+
+impl ReplayPlayback {
+    /// Returns the index of the shown snapshot.
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    pub fn snapshots_count(&self) -> usize {
+        self.replay.frames.len()
+    }
+}
+
+/// Starts capturing world snapshots.
+#[derive(Event)]
+pub struct StartRecording;
+
+/// Stops capturing and writes the replay into [`GamePaths::replays`] with the world name.
+#[derive(Event)]
+pub struct StopRecording;
+
+/// Opens a replay file and shows its first snapshot.
+#[derive(Event)]
+pub struct ReplayOpen(pub PathBuf);
+
+/// Shows the snapshot with the index from the opened replay.
+#[derive(Event, Deref, Clone, Copy)]
+pub struct ReplaySeek(pub usize);
+
+#[cfg(test)]
+mod tests {
+    use bevy::time::TimeUpdateStrategy;
+
+    use super::*;
+
+    #[test]
+    fn recording_cap() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, RepliconPlugins))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(RECORD_INTERVAL))
+            .init_resource::<ReplayRecorder>()
+            .add_observer(|_trigger: Trigger<StopRecording>, mut commands: Commands| {
+                commands.remove_resource::<ReplayRecorder>();
+            })
+            .add_systems(
+                Update,
+                ReplayPlugin::record.run_if(resource_exists::<ReplayRecorder>),
+            );
+
+        app.world_mut()
+            .resource_mut::<Time<Virtual>>()
+            .set_max_delta(RECORD_INTERVAL);
+
+        // The first update doesn't advance the time, but captures the initial snapshot.
+        for _ in 0..MAX_FRAMES - 1 {
+            app.update();
+        }
+
+        let recorder = app.world().resource::<ReplayRecorder>();
+        assert_eq!(recorder.frames.len(), MAX_FRAMES - 1);
+        let snapshot = &recorder.frames[0].snapshot;
+        let registry = app.world().resource::<AppTypeRegistry>();
+        assert!(
+            deserialize_world(snapshot, &registry.read()).is_ok(),
+            "snapshots should be readable as worlds"
+        );
+
+        app.update();
+
+        assert!(
+            !app.world().contains_resource::<ReplayRecorder>(),
+            "recording should stop after reaching the limit"
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use bevy::{prelude::*, utils::HashSet};
+use bevy_replicon::prelude::*;
+#[cfg(feature = "networking")]
+use bevy_replicon_renet::netcode::NetcodeServerTransport;
+
+use crate::core::GameState;
+#[cfg(feature = "networking")]
+use crate::network::ConnectionInfo;
+
+pub(super) struct SpectatorPlugin;
+
+impl Plugin for SpectatorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Spectators>()
+            .add_systems(OnExit(GameState::InGame), Self::cleanup);
+
+        #[cfg(feature = "networking")]
+        app.add_systems(
+            PreUpdate,
+            Self::update_spectators
+                .after(ServerSet::Receive)
+                .run_if(server_running),
+        );
+    }
+}
+
+impl SpectatorPlugin {
+    #[cfg(feature = "networking")]
+    fn update_spectators(
+        mut server_events: EventReader<ServerEvent>,
+        transport: Option<Res<NetcodeServerTransport>>,
+        mut spectators: ResMut<Spectators>,
+    ) {
+        for event in server_events.read() {
+            match *event {
+                ServerEvent::ClientConnected { client_id } => {
+                    let spectator = transport
+                        .as_ref()
+                        .and_then(|transport| transport.user_data(client_id.get()))
+                        .is_some_and(|user_data| {
+                            ConnectionInfo::from_user_data(&user_data).spectator
+                        });
+                    if spectator {
+                        info!("`{client_id:?}` joined as spectator");
+                        spectators.0.insert(client_id);
+                    }
+                }
+                ServerEvent::ClientDisconnected { client_id, .. } => {
+                    spectators.0.remove(&client_id);
+                }
+            }
+        }
+    }
+
+    fn cleanup(mut commands: Commands, mut spectators: ResMut<Spectators>) {
+        commands.remove_resource::<Spectator>();
+        spectators.0.clear();
+    }
+}
+
+/// Marks the local client as a spectator.
+///
+/// Inserted before connecting. Spectators can move the camera,
+/// but can't place objects, control actors or save the world.
+#[derive(Resource)]
+pub struct Spectator;
+
+/// Clients that joined as spectators.
+///
+/// Available only on server, used to reject gameplay requests from them.
+#[derive(Resource, Default)]
+pub(crate) struct Spectators(HashSet<ClientId>);
+
+impl Spectators {
+    pub(crate) fn contains(&self, client_id: ClientId) -> bool {
+        self.0.contains(&client_id)
+    }
+}
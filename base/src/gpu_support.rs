@@ -0,0 +1,150 @@
+use bevy::{
+    prelude::*,
+    render::{
+        render_resource::{TextureFormat, TextureUsages},
+        renderer::{RenderAdapter, RenderDevice},
+        settings::WgpuFeatures,
+        view::ViewTarget,
+    },
+};
+
+use crate::settings::{AntiAliasing, Settings};
+
+/// Detects rendering features that the GPU lacks.
+///
+/// Settings that depend on missing features are ignored instead of breaking rendering.
+pub(super) struct GpuSupportPlugin;
+
+impl Plugin for GpuSupportPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GpuSupport>();
+    }
+
+    fn finish(&self, app: &mut App) {
+        // Inserted by the render plugin during its own `finish`.
+        let world = app.world();
+        let (Some(device), Some(adapter)) = (
+            world.get_resource::<RenderDevice>(),
+            world.get_resource::<RenderAdapter>(),
+        ) else {
+            return;
+        };
+
+        let gpu_support = GpuSupport::new(device, adapter);
+        for name in gpu_support.unsupported_options() {
+            warn!("{name} isn't supported by the GPU");
+        }
+        app.insert_resource(gpu_support);
+    }
+}
+
+/// Rendering options available on the current GPU.
+///
+/// Everything is supported when rendering is unavailable.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct GpuSupport {
+    pub wireframe: bool,
+    pub msaa2: bool,
+    pub msaa4: bool,
+    pub taa: bool,
+    pub ssao: bool,
+}
+
+impl GpuSupport {
+    fn new(device: &RenderDevice, adapter: &RenderAdapter) -> Self {
+        // Cameras render into HDR textures.
+        let hdr_features = adapter.get_texture_format_features(ViewTarget::TEXTURE_FORMAT_HDR);
+        // Same requirements as checked by Bevy before enabling SSAO.
+        let ssao_features = adapter.get_texture_format_features(TextureFormat::R16Float);
+
+        Self {
+            wireframe: device.features().contains(WgpuFeatures::POLYGON_MODE_LINE),
+            msaa2: hdr_features.flags.sample_count_supported(2),
+            msaa4: hdr_features.flags.sample_count_supported(4),
+            // Doesn't work on WebGL2-class hardware.
+            taa: adapter.get_downlevel_capabilities().is_webgpu_compliant(),
+            ssao: ssao_features
+                .allowed_usages
+                .contains(TextureUsages::STORAGE_BINDING)
+                && device.limits().max_storage_textures_per_shader_stage >= 5,
+        }
+    }
+
+    pub fn anti_aliasing(self, anti_aliasing: AntiAliasing) -> bool {
+        match anti_aliasing {
+            AntiAliasing::None => true,
+            AntiAliasing::Msaa2 => self.msaa2,
+            AntiAliasing::Msaa4 => self.msaa4,
+            AntiAliasing::Taa => self.taa,
+        }
+    }
+
+    /// Returns names of options that can't be used.
+    pub fn unsupported_options(self) -> Vec<&'static str> {
+        let mut options = Vec::new();
+        if !self.wireframe {
+            options.push("Wireframe");
+        }
+        for anti_aliasing in [AntiAliasing::Msaa2, AntiAliasing::Msaa4, AntiAliasing::Taa] {
+            if !self.anti_aliasing(anti_aliasing) {
+                options.push(anti_aliasing.text());
+            }
+        }
+        if !self.ssao {
+            options.push("Ambient occlusion");
+        }
+
+        options
+    }
+
+    /// Returns names of options enabled in settings that will be ignored.
+    pub fn disabled_settings(self, settings: &Settings) -> Vec<&'static str> {
+        let mut options = Vec::new();
+        if settings.developer.wireframe && !self.wireframe {
+            options.push("Wireframe");
+        }
+        let anti_aliasing = settings.video.anti_aliasing;
+        if !self.anti_aliasing(anti_aliasing) {
+            options.push(anti_aliasing.text());
+        }
+
+        options
+    }
+}
+
+impl Default for GpuSupport {
+    fn default() -> Self {
+        Self {
+            wireframe: true,
+            msaa2: true,
+            msaa4: true,
+            taa: true,
+            ssao: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabled_settings() {
+        let gpu_support = GpuSupport {
+            wireframe: false,
+            taa: false,
+            ..Default::default()
+        };
+
+        let mut settings = Settings::default();
+        settings.video.anti_aliasing = AntiAliasing::Msaa4;
+        assert!(gpu_support.disabled_settings(&settings).is_empty());
+
+        settings.video.anti_aliasing = AntiAliasing::Taa;
+        settings.developer.wireframe = true;
+        assert_eq!(
+            gpu_support.disabled_settings(&settings),
+            ["Wireframe", "TAA"]
+        );
+    }
+}
@@ -1,15 +1,20 @@
 mod alpha_color;
 pub mod asset;
+pub mod build_info;
 mod combined_scene_collider;
 pub mod common_conditions;
 pub mod core;
+pub mod crash_report;
 mod dynamic_mesh;
 pub mod error_message;
+pub mod file_log;
 pub mod game_paths;
 pub mod game_world;
 mod ghost;
+pub mod gpu_support;
 pub mod network;
 pub mod settings;
+pub mod stepping;
 
 use bevy::{app::PluginGroupBuilder, prelude::*};
 
@@ -17,10 +22,13 @@ use alpha_color::AlphaColorPlugin;
 use asset::AssetPlugin;
 use combined_scene_collider::SceneColliderConstructorPlugin;
 use core::CorePlugin;
+use crash_report::CrashReportPlugin;
 use game_paths::GamePathsPlugin;
 use game_world::GameWorldPlugin;
 use ghost::GhostPlugin;
+use gpu_support::GpuSupportPlugin;
 use settings::SettingsPlugin;
+use stepping::SteppingPlugin;
 
 pub struct CorePlugins;
 
@@ -33,7 +41,10 @@ impl PluginGroup for CorePlugins {
             .add(SceneColliderConstructorPlugin)
             .add(GameWorldPlugin)
             .add(GamePathsPlugin)
+            .add(CrashReportPlugin)
+            .add(GpuSupportPlugin)
             .add(SettingsPlugin)
             .add(GhostPlugin)
+            .add(SteppingPlugin)
     }
 }
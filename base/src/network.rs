@@ -1,18 +1,29 @@
-use std::{
-    net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket},
-    time::SystemTime,
-};
+#[cfg(feature = "networking")]
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket};
+use std::{fs, path::Path, time::SystemTime};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+#[cfg(feature = "networking")]
 use bevy_replicon_renet::netcode::{
     ClientAuthentication, NetcodeClientTransport, NetcodeServerTransport, ServerAuthentication,
-    ServerConfig,
+    ServerConfig, NETCODE_USER_DATA_BYTES,
 };
 
 pub const DEFAULT_PORT: u16 = 4761;
+#[cfg(feature = "networking")]
 const PROTOCOL_ID: u64 = 7;
 
+/// Index of the spectator flag inside the user data sent on connect.
+#[cfg(feature = "networking")]
+const SPECTATOR_INDEX: usize = 0;
+
+/// Range of the persistent player ID inside the user data sent on connect.
+#[cfg(feature = "networking")]
+const PLAYER_ID_RANGE: std::ops::Range<usize> = 1..9;
+
+#[cfg(feature = "networking")]
 pub fn create_server(port: u16) -> Result<NetcodeServerTransport> {
     info!("creating server transport for port {port}");
 
@@ -31,7 +42,12 @@ pub fn create_server(port: u16) -> Result<NetcodeServerTransport> {
     Ok(transport)
 }
 
-pub fn create_client(ip: IpAddr, port: u16) -> Result<NetcodeClientTransport> {
+#[cfg(feature = "networking")]
+pub fn create_client(
+    ip: IpAddr,
+    port: u16,
+    info: ConnectionInfo,
+) -> Result<NetcodeClientTransport> {
     info!("creating client transport for {ip}:{port}");
 
     let current_time = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH)?;
@@ -42,9 +58,66 @@ pub fn create_client(ip: IpAddr, port: u16) -> Result<NetcodeClientTransport> {
         client_id,
         protocol_id: PROTOCOL_ID,
         server_addr,
-        user_data: None,
+        user_data: Some(info.to_user_data()),
     };
     let transport = NetcodeClientTransport::new(current_time, authentication, socket)?;
 
     Ok(transport)
 }
+
+/// Reads the persistent player ID or generates a new one.
+///
+/// Unlike client IDs, it stays the same between connections,
+/// so hosts can use it to ban players.
+pub fn load_player_id(path: &Path) -> Result<u64> {
+    if let Ok(content) = fs::read_to_string(path) {
+        return content
+            .trim()
+            .parse()
+            .with_context(|| format!("unable to parse player ID from {path:?}"));
+    }
+
+    let current_time = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH)?;
+    let player_id = current_time.as_nanos() as u64;
+    info!("generating new player ID {player_id}");
+    fs::write(path, player_id.to_string())
+        .with_context(|| format!("unable to write player ID to {path:?}"))?;
+
+    Ok(player_id)
+}
+
+/// Client information sent to the server on connect.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ConnectionInfo {
+    pub player_id: u64,
+    pub spectator: bool,
+}
+
+#[cfg(feature = "networking")]
+impl ConnectionInfo {
+    fn to_user_data(self) -> [u8; NETCODE_USER_DATA_BYTES] {
+        let mut user_data = [0; NETCODE_USER_DATA_BYTES];
+        user_data[SPECTATOR_INDEX] = self.spectator.into();
+        user_data[PLAYER_ID_RANGE].copy_from_slice(&self.player_id.to_le_bytes());
+        user_data
+    }
+
+    pub fn from_user_data(user_data: &[u8; NETCODE_USER_DATA_BYTES]) -> Self {
+        let player_id = user_data[PLAYER_ID_RANGE]
+            .try_into()
+            .expect("range should match the ID size");
+        Self {
+            player_id: u64::from_le_bytes(player_id),
+            spectator: user_data[SPECTATOR_INDEX] != 0,
+        }
+    }
+}
+
+/// Returns a display name for a client.
+pub fn player_label(client_id: ClientId) -> String {
+    if client_id == ClientId::SERVER {
+        "Host".to_string()
+    } else {
+        format!("Player {}", client_id.get())
+    }
+}
@@ -1,22 +1,40 @@
-use std::{fs, path::Path};
+use std::{
+    fmt::{self, Formatter},
+    fs,
+    path::Path,
+    time::Duration,
+};
 
 use anyhow::{Context, Result};
 use avian3d::prelude::*;
 use bevy::{
-    color::palettes::css::DARK_RED, pbr::wireframe::WireframeConfig, prelude::*, scene::ron,
+    color::palettes::css::DARK_RED,
+    pbr::{
+        wireframe::WireframeConfig, CascadeShadowConfig, CascadeShadowConfigBuilder,
+        DirectionalLightShadowMap,
+    },
+    prelude::*,
+    scene::ron,
+    utils::HashMap,
     window::WindowMode,
 };
 use bevy_enhanced_input::prelude::*;
-use serde::{Deserialize, Serialize};
+use ron::value::RawValue;
+use serde::{
+    de::{DeserializeOwned, MapAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+use strum::EnumIter;
 use vleue_navigator::prelude::*;
 
-use super::{error_message::error_message, game_paths::GamePaths};
+use super::{error_message::error_message, game_paths::GamePaths, gpu_support::GpuSupport};
 
 pub(super) struct SettingsPlugin;
 
 impl Plugin for SettingsPlugin {
     fn build(&self, app: &mut App) {
         app.add_observer(Self::apply.pipe(error_message))
+            .add_observer(Self::init_light)
             .add_systems(Startup, Self::load);
     }
 }
@@ -27,6 +45,7 @@ impl SettingsPlugin {
         mut config_store: ResMut<GizmoConfigStore>,
         mut wireframe_config: ResMut<WireframeConfig>,
         game_paths: Res<GamePaths>,
+        gpu_support: Res<GpuSupport>,
         mut window: Single<&mut Window>,
     ) {
         info!("loading settings");
@@ -38,6 +57,7 @@ impl SettingsPlugin {
             &mut config_store,
             &mut wireframe_config,
             &mut window,
+            *gpu_support,
             &settings,
         );
 
@@ -51,20 +71,36 @@ impl SettingsPlugin {
         mut wireframe_config: ResMut<WireframeConfig>,
         settings: Res<Settings>,
         game_paths: Res<GamePaths>,
+        gpu_support: Res<GpuSupport>,
         mut window: Single<&mut Window>,
+        mut lights: Query<&mut CascadeShadowConfig>,
     ) -> Result<()> {
         info!("applying settings");
 
+        for mut cascade_config in &mut lights {
+            *cascade_config = settings.video.cascade_config();
+        }
+
         apply_settings(
             &mut commands,
             &mut config_store,
             &mut wireframe_config,
             &mut window,
+            *gpu_support,
             &settings,
         );
 
         settings.write(&game_paths.settings)
     }
+
+    fn init_light(
+        trigger: Trigger<OnAdd, DirectionalLight>,
+        settings: Res<Settings>,
+        mut lights: Query<&mut CascadeShadowConfig>,
+    ) {
+        let mut cascade_config = lights.get_mut(trigger.entity()).unwrap();
+        *cascade_config = settings.video.cascade_config();
+    }
 }
 
 fn apply_settings(
@@ -72,6 +108,7 @@ fn apply_settings(
     config_store: &mut GizmoConfigStore,
     wireframe_config: &mut WireframeConfig,
     window: &mut Window,
+    gpu_support: GpuSupport,
     settings: &Settings,
 ) {
     if settings.video.fullscreen {
@@ -80,7 +117,13 @@ fn apply_settings(
         window.mode = WindowMode::Windowed;
     }
 
-    wireframe_config.global = settings.developer.wireframe;
+    commands.insert_resource(DirectionalLightShadowMap {
+        size: settings.video.shadow_map_size() as usize,
+    });
+
+    commands.insert_resource(GlobalVolume::new(settings.audio.master));
+
+    wireframe_config.global = settings.developer.wireframe && gpu_support.wireframe;
     config_store.config_mut::<PhysicsGizmos>().0.enabled = settings.developer.colliders;
     if settings.developer.nav_mesh {
         commands.insert_resource(NavMeshesDebug(DARK_RED.into()))
@@ -99,21 +142,37 @@ pub struct SettingsApply;
 #[serde(default)]
 pub struct Settings {
     pub video: VideoSettings,
+    pub audio: AudioSettings,
+    pub interface: InterfaceSettings,
+    pub autosave: AutosaveSettings,
+    pub camera: CameraSettings,
     pub keyboard: KeyboardSettings,
     pub developer: DeveloperSettings,
+    pub language: LanguageSettings,
 }
 
 impl Settings {
     /// Creates [`Settings`] from the application settings file.
     /// Will be initialed with defaults if the file does not exist.
-    fn read(file_name: &Path) -> Result<Settings> {
+    ///
+    /// Files from older versions are migrated. Fields that can't be read
+    /// are reset to defaults without affecting the others.
+    pub fn read(file_name: &Path) -> Result<Settings> {
         info!("reading settings from {file_name:?}");
 
-        match fs::read_to_string(file_name) {
-            Ok(content) => ron::from_str::<Settings>(&content)
-                .with_context(|| format!("unable to read settings from {file_name:?}")),
-            Err(_) => Ok(Settings::default()),
-        }
+        let Ok(content) = fs::read_to_string(file_name) else {
+            return Ok(Settings::default());
+        };
+
+        let mut raw = RawSettings::parse(&content)
+            .with_context(|| format!("unable to read settings from {file_name:?}"))?;
+        raw.migrate();
+
+        let mut settings = raw.into_settings();
+        settings.video.shadow_map_size = settings.video.shadow_map_size();
+        settings.interface.history_depth = settings.interface.history_depth();
+
+        Ok(settings)
     }
 
     /// Saves settings on disk under.
@@ -122,7 +181,18 @@ impl Settings {
     fn write(&self, file_name: &Path) -> Result<()> {
         info!("writing settings to {file_name:?}");
 
-        let content = ron::ser::to_string_pretty(&self, Default::default())
+        let file = SettingsFile {
+            version: SETTINGS_VERSION,
+            video: &self.video,
+            audio: &self.audio,
+            interface: &self.interface,
+            autosave: &self.autosave,
+            camera: &self.camera,
+            keyboard: &self.keyboard,
+            developer: &self.developer,
+            language: &self.language,
+        };
+        let content = ron::ser::to_string_pretty(&file, Default::default())
             .context("unable to serialize settings")?;
 
         let parent_folder = file_name
@@ -137,11 +207,418 @@ impl Settings {
     }
 }
 
-#[derive(Clone, Default, Deserialize, PartialEq, Reflect, Serialize)]
+/// Current layout version of the settings file.
+///
+/// Should be incremented with a new migration when a field is renamed or moved.
+const SETTINGS_VERSION: u32 = 1;
+
+/// Functions that upgrade the layout from the version at their index to the next one.
+const MIGRATIONS: [fn(&mut RawSettings); SETTINGS_VERSION as usize] = [migrate_unversioned];
+
+/// Files before versioning have the same layout and only lack the version field.
+fn migrate_unversioned(_raw: &mut RawSettings) {}
+
+/// Layout of the settings file on disk.
+#[derive(Serialize)]
+struct SettingsFile<'a> {
+    version: u32,
+    video: &'a VideoSettings,
+    audio: &'a AudioSettings,
+    interface: &'a InterfaceSettings,
+    autosave: &'a AutosaveSettings,
+    camera: &'a CameraSettings,
+    keyboard: &'a KeyboardSettings,
+    developer: &'a DeveloperSettings,
+    language: &'a LanguageSettings,
+}
+
+/// Settings file with fields kept as raw RON.
+///
+/// Allows migrating and reading each field separately.
+struct RawSettings {
+    version: u32,
+    sections: HashMap<String, RawStruct>,
+}
+
+impl RawSettings {
+    fn parse(content: &str) -> Result<Self> {
+        let RawStruct(fields) = ron::from_str(content)?;
+
+        let mut version = 0;
+        let mut sections = HashMap::new();
+        for (name, value) in fields {
+            if name == "version" {
+                version = value
+                    .into_rust()
+                    .context("unable to read settings version")?;
+            } else {
+                match value.into_rust::<RawStruct>() {
+                    Ok(section) => {
+                        sections.insert(name, section);
+                    }
+                    Err(e) => warn!("ignoring invalid settings section `{name}`: {e}"),
+                }
+            }
+        }
+
+        Ok(Self { version, sections })
+    }
+
+    fn migrate(&mut self) {
+        if self.version > SETTINGS_VERSION {
+            warn!(
+                "settings version {} is newer than {SETTINGS_VERSION}, reading as is",
+                self.version
+            );
+            return;
+        }
+
+        for (version, migration) in MIGRATIONS.iter().enumerate().skip(self.version as usize) {
+            info!("migrating settings from version {version}");
+            migration(self);
+        }
+        self.version = SETTINGS_VERSION;
+    }
+
+    fn into_settings(mut self) -> Settings {
+        Settings {
+            video: self.take_section("video"),
+            audio: self.take_section("audio"),
+            interface: self.take_section("interface"),
+            autosave: self.take_section("autosave"),
+            camera: self.take_section("camera"),
+            keyboard: self.take_section("keyboard"),
+            developer: self.take_section("developer"),
+            language: self.take_section("language"),
+        }
+    }
+
+    /// Deserializes a section, skipping fields that fail to deserialize.
+    ///
+    /// Skipped and missing fields are initialized with defaults.
+    fn take_section<T: DeserializeOwned + Default>(&mut self, name: &str) -> T {
+        let Some(RawStruct(fields)) = self.sections.remove(name) else {
+            return Default::default();
+        };
+
+        let mut valid_fields = Vec::new();
+        for (field, value) in fields {
+            let entry = format!("{field}: {}", value.get_ron());
+            match ron::from_str::<T>(&format!("({entry})")) {
+                Ok(_) => valid_fields.push(entry),
+                Err(e) => warn!("resetting invalid setting `{name}.{field}`: {e}"),
+            }
+        }
+
+        match ron::from_str(&format!("({})", valid_fields.join(", "))) {
+            Ok(section) => section,
+            Err(e) => {
+                warn!("resetting settings section `{name}`: {e}");
+                Default::default()
+            }
+        }
+    }
+}
+
+/// Struct with fields in their original RON representation.
+struct RawStruct(Vec<(String, Box<RawValue>)>);
+
+impl<'de> Deserialize<'de> for RawStruct {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(RawStructVisitor)
+    }
+}
+
+struct RawStructVisitor;
+
+impl<'de> Visitor<'de> for RawStructVisitor {
+    type Value = RawStruct;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("a struct")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut fields = Vec::new();
+        while let Some(field) = map.next_entry()? {
+            fields.push(field);
+        }
+
+        Ok(RawStruct(fields))
+    }
+}
+
+#[derive(Clone, Deserialize, PartialEq, Reflect, Serialize)]
 #[serde(default)]
 pub struct VideoSettings {
     /// TODO: Replace with combobox for all window modes.
     pub fullscreen: bool,
+    pub nameplates: bool,
+    pub selection_ring: bool,
+    /// Disable to save resources on low-end hardware.
+    pub menu_background: bool,
+    /// Replaces animations with instant changes.
+    ///
+    /// See `ReduceMotion` in widgets for the list of affected effects.
+    pub reduce_motion: bool,
+    pub anti_aliasing: AntiAliasing,
+    /// Resolution of each shadow cascade.
+    pub shadow_map_size: u32,
+    pub shadow_cascades: u32,
+    /// Distance in meters after which objects and shadows aren't rendered.
+    pub render_distance: u32,
+    /// Renders the atmosphere instead of a solid sky color.
+    pub dynamic_sky: bool,
+}
+
+impl VideoSettings {
+    /// Returns render distance in meters, clamped to the supported minimum.
+    pub fn render_distance(&self) -> f32 {
+        self.render_distance.max(MIN_RENDER_DISTANCE) as f32
+    }
+
+    /// Returns shadow map size rounded up to a power of two within the supported range.
+    pub fn shadow_map_size(&self) -> u32 {
+        self.shadow_map_size
+            .clamp(MIN_SHADOW_MAP_SIZE, MAX_SHADOW_MAP_SIZE)
+            .next_power_of_two()
+    }
+
+    fn cascade_config(&self) -> CascadeShadowConfig {
+        // Values from the file could be out of range, and the builder panics on them.
+        CascadeShadowConfigBuilder {
+            num_cascades: self.shadow_cascades.clamp(1, MAX_SHADOW_CASCADES) as usize,
+            maximum_distance: self.render_distance(),
+            ..Default::default()
+        }
+        .build()
+    }
+
+    /// Sets all performance-related values at once.
+    pub fn set_preset(&mut self, preset: GraphicsPreset) {
+        let (shadow_map_size, shadow_cascades, render_distance, dynamic_sky) = match preset {
+            GraphicsPreset::Low => (1024, 1, 200, false),
+            GraphicsPreset::Medium => (2048, 2, 500, true),
+            GraphicsPreset::High => (2048, 4, 1000, true),
+        };
+        self.shadow_map_size = shadow_map_size;
+        self.shadow_cascades = shadow_cascades;
+        self.render_distance = render_distance;
+        self.dynamic_sky = dynamic_sky;
+    }
+}
+
+impl Default for VideoSettings {
+    fn default() -> Self {
+        Self {
+            fullscreen: false,
+            nameplates: true,
+            selection_ring: true,
+            menu_background: true,
+            reduce_motion: false,
+            anti_aliasing: Default::default(),
+            shadow_map_size: 2048,
+            shadow_cascades: 4,
+            render_distance: 1000,
+            dynamic_sky: true,
+        }
+    }
+}
+
+pub const MAX_SHADOW_CASCADES: u32 = 4;
+pub const MIN_SHADOW_MAP_SIZE: u32 = 512;
+pub const MAX_SHADOW_MAP_SIZE: u32 = 4096;
+pub const MIN_RENDER_DISTANCE: u32 = 50;
+
+#[derive(Clone, Copy, EnumIter)]
+pub enum GraphicsPreset {
+    Low,
+    Medium,
+    High,
+}
+
+impl GraphicsPreset {
+    pub fn text(self) -> &'static str {
+        match self {
+            GraphicsPreset::Low => "Low",
+            GraphicsPreset::Medium => "Medium",
+            GraphicsPreset::High => "High",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, EnumIter, PartialEq, Reflect, Serialize)]
+pub enum AntiAliasing {
+    None,
+    Msaa2,
+    Msaa4,
+    #[default]
+    Taa,
+}
+
+impl AntiAliasing {
+    pub fn text(self) -> &'static str {
+        match self {
+            AntiAliasing::None => "None",
+            AntiAliasing::Msaa2 => "MSAA 2x",
+            AntiAliasing::Msaa4 => "MSAA 4x",
+            AntiAliasing::Taa => "TAA",
+        }
+    }
+
+    pub fn msaa(self) -> Msaa {
+        match self {
+            AntiAliasing::None | AntiAliasing::Taa => Msaa::Off,
+            AntiAliasing::Msaa2 => Msaa::Sample2,
+            AntiAliasing::Msaa4 => Msaa::Sample4,
+        }
+    }
+
+    /// Returns `true` if ambient occlusion can be used with this mode.
+    ///
+    /// Screen space ambient occlusion requires MSAA to be off.
+    pub fn supports_ssao(self) -> bool {
+        self.msaa() == Msaa::Off
+    }
+}
+
+/// Volumes from 0 to 1.
+///
+/// Only the master volume is applied for now since the game doesn't play any music or sounds yet.
+#[derive(Clone, Deserialize, PartialEq, Reflect, Serialize)]
+#[serde(default)]
+pub struct AudioSettings {
+    pub master: f32,
+    pub music: f32,
+    pub sfx: f32,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            master: 1.0,
+            music: 1.0,
+            sfx: 1.0,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, PartialEq, Reflect, Serialize)]
+#[serde(default)]
+pub struct InterfaceSettings {
+    /// Number of recently placed objects to show in the build palette.
+    pub recent_objects: u32,
+    /// Shows a top-down view of the active city in the corner.
+    pub minimap: bool,
+    /// Number of actions that can be undone.
+    pub history_depth: u32,
+}
+
+impl InterfaceSettings {
+    /// Returns history depth, clamped to the supported range.
+    ///
+    /// Zero is not allowed since it would silently disable undo.
+    pub fn history_depth(&self) -> u32 {
+        self.history_depth
+            .clamp(MIN_HISTORY_DEPTH, MAX_HISTORY_DEPTH)
+    }
+}
+
+impl Default for InterfaceSettings {
+    fn default() -> Self {
+        Self {
+            recent_objects: 8,
+            minimap: true,
+            history_depth: 25,
+        }
+    }
+}
+
+pub const MIN_HISTORY_DEPTH: u32 = 1;
+pub const MAX_HISTORY_DEPTH: u32 = 100;
+
+#[derive(Clone, Deserialize, PartialEq, Reflect, Serialize)]
+#[serde(default)]
+pub struct AutosaveSettings {
+    pub enabled: bool,
+    /// Time between autosaves in seconds.
+    pub interval_secs: u32,
+}
+
+impl AutosaveSettings {
+    /// Returns the interval, clamped to the supported minimum.
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_secs.max(MIN_AUTOSAVE_INTERVAL).into())
+    }
+}
+
+impl Default for AutosaveSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_secs: 5 * 60,
+        }
+    }
+}
+
+/// Minimum value for [`AutosaveSettings::interval_secs`] in seconds.
+pub const MIN_AUTOSAVE_INTERVAL: u32 = 30;
+
+/// Camera controls.
+///
+/// Speeds are in percents of the default speed.
+/// They scale already smoothed input, so smoothing behaves the same at any speed.
+#[derive(Clone, Deserialize, PartialEq, Reflect, Serialize)]
+#[serde(default)]
+pub struct CameraSettings {
+    pub pan_speed: u32,
+    pub rotation_speed: u32,
+    pub zoom_speed: u32,
+    pub invert_x: bool,
+    pub invert_y: bool,
+}
+
+impl CameraSettings {
+    pub fn pan_multiplier(&self) -> f32 {
+        speed_multiplier(self.pan_speed)
+    }
+
+    pub fn rotation_multiplier(&self) -> f32 {
+        speed_multiplier(self.rotation_speed)
+    }
+
+    pub fn zoom_multiplier(&self) -> f32 {
+        speed_multiplier(self.zoom_speed)
+    }
+
+    /// Returns multipliers for rotation axes with inversion applied.
+    pub fn rotation_axes(&self) -> Vec2 {
+        let x = if self.invert_x { -1.0 } else { 1.0 };
+        let y = if self.invert_y { -1.0 } else { 1.0 };
+        Vec2::new(x, y) * self.rotation_multiplier()
+    }
+}
+
+impl Default for CameraSettings {
+    fn default() -> Self {
+        Self {
+            pan_speed: 100,
+            rotation_speed: 100,
+            zoom_speed: 100,
+            invert_x: false,
+            invert_y: false,
+        }
+    }
+}
+
+pub const MIN_CAMERA_SPEED: u32 = 10;
+pub const MAX_CAMERA_SPEED: u32 = 300;
+
+/// Converts speed in percents into a multiplier.
+///
+/// Clamped since the file could be edited manually.
+fn speed_multiplier(speed: u32) -> f32 {
+    speed.clamp(MIN_CAMERA_SPEED, MAX_CAMERA_SPEED) as f32 / 100.0
 }
 
 #[derive(Clone, Deserialize, PartialEq, Reflect, Serialize)]
@@ -158,6 +635,14 @@ pub struct KeyboardSettings {
     pub delete: Vec<KeyCode>,
     pub free_placement: Vec<KeyCode>,
     pub ordinal_placement: Vec<KeyCode>,
+    pub next_member: Vec<KeyCode>,
+    pub previous_member: Vec<KeyCode>,
+    /// Pressed together with Ctrl.
+    pub undo: Vec<KeyCode>,
+    /// Pressed together with Ctrl and Shift.
+    pub redo: Vec<KeyCode>,
+    /// Move placing objects with arrow keys instead of the cursor.
+    pub keyboard_placement: bool,
 }
 
 impl KeyboardSettings {
@@ -172,6 +657,11 @@ impl KeyboardSettings {
         self.zoom_out.clear();
         self.delete.clear();
         self.free_placement.clear();
+        self.ordinal_placement.clear();
+        self.next_member.clear();
+        self.previous_member.clear();
+        self.undo.clear();
+        self.redo.clear();
     }
 }
 
@@ -189,6 +679,11 @@ impl Default for KeyboardSettings {
             delete: vec![KeyCode::Delete, KeyCode::Backspace],
             free_placement: vec![KeyCode::AltLeft, KeyCode::AltRight],
             ordinal_placement: vec![KeyCode::ShiftLeft, KeyCode::ShiftRight],
+            next_member: vec![KeyCode::BracketRight],
+            previous_member: vec![KeyCode::BracketLeft],
+            undo: vec![KeyCode::KeyZ],
+            redo: vec![KeyCode::KeyZ],
+            keyboard_placement: false,
         }
     }
 }
@@ -201,4 +696,97 @@ pub struct DeveloperSettings {
     pub colliders: bool,
     pub paths: bool,
     pub nav_mesh: bool,
+    /// Generates scene colliders only near the camera and actors.
+    ///
+    /// Speeds up loading of large worlds, but distant objects can't be picked
+    /// and are ignored by navigation until something comes close.
+    pub lazy_colliders: bool,
+    /// Enables cheats, like changing the family budget.
+    ///
+    /// Has no effect on multiplayer clients.
+    pub cheats: bool,
+    /// Logs debug messages in addition to info messages.
+    ///
+    /// Applied after restart since logging is initialized before settings.
+    pub debug_logs: bool,
+}
+
+#[derive(Clone, Deserialize, PartialEq, Reflect, Serialize)]
+#[serde(default)]
+pub struct LanguageSettings {
+    /// ID of the locale used for UI strings.
+    pub locale: String,
+}
+
+impl Default for LanguageSettings {
+    fn default() -> Self {
+        Self {
+            locale: "en".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_paths::TempDir;
+
+    #[test]
+    fn migration() -> Result<()> {
+        let dir = TempDir::new("settings_migration");
+
+        // Written before versioning, with a field of a wrong type and a removed field.
+        let path = dir.join("settings.ron");
+        fs::write(
+            &path,
+            "(
+                video: (
+                    fullscreen: true,
+                    nameplates: \"yes\",
+                    shadow_map_size: 100000,
+                ),
+                interface: (
+                    history_depth: 0,
+                ),
+                keyboard: (
+                    camera_forward: [KeyI],
+                    removed_binding: [KeyX],
+                ),
+                developer: (
+                    cheats: true,
+                ),
+            )",
+        )?;
+
+        let settings = Settings::read(&path)?;
+        assert!(settings.video.fullscreen);
+        assert_eq!(
+            settings.video.nameplates,
+            VideoSettings::default().nameplates,
+            "invalid field should be reset"
+        );
+        assert_eq!(
+            settings.video.shadow_map_size, MAX_SHADOW_MAP_SIZE,
+            "out of range value should be clamped"
+        );
+        assert_eq!(
+            settings.interface.history_depth, MIN_HISTORY_DEPTH,
+            "zero depth should be clamped to keep undo working"
+        );
+        assert_eq!(settings.keyboard.camera_forward, [KeyCode::KeyI]);
+        assert_eq!(
+            settings.keyboard.camera_left,
+            KeyboardSettings::default().camera_left,
+            "missing field should be initialized with default"
+        );
+        assert!(settings.developer.cheats);
+
+        settings.write(&path)?;
+        assert!(
+            Settings::read(&path)? == settings,
+            "settings should be preserved after writing in the current version"
+        );
+
+        Ok(())
+    }
 }
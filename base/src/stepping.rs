@@ -0,0 +1,119 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, time::TimeSystem};
+use bevy_replicon::prelude::*;
+
+use crate::common_conditions::in_multiplayer;
+
+/// Allows pausing the simulation and advancing it one fixed tick at a time for debugging.
+///
+/// Rendering continues while paused, since only [`Time<Virtual>`] is affected.
+pub(super) struct SteppingPlugin;
+
+impl Plugin for SteppingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PendingStep>()
+            .add_observer(Self::set_paused)
+            .add_observer(Self::step)
+            .add_systems(First, Self::start_step.after(TimeSystem))
+            .add_systems(Last, Self::finish_step);
+    }
+}
+
+/// Relative speed used to guarantee that the virtual delta reaches the clamped maximum.
+const STEP_SPEED: f32 = 1000.0;
+
+impl SteppingPlugin {
+    fn set_paused(
+        trigger: Trigger<SetSimulationPaused>,
+        mut time: ResMut<Time<Virtual>>,
+        server: Option<Res<RepliconServer>>,
+        client: Res<RepliconClient>,
+    ) {
+        if in_multiplayer(server, client) {
+            warn!("ignoring pause in multiplayer");
+            return;
+        }
+
+        if **trigger {
+            info!("pausing simulation");
+            time.pause();
+        } else {
+            info!("resuming simulation");
+            time.unpause();
+        }
+    }
+
+    /// Unpauses virtual time with a delta clamped to exactly one fixed timestep.
+    ///
+    /// The delta is applied at the next frame, see [`Self::start_step`].
+    fn step(
+        _trigger: Trigger<StepSimulation>,
+        mut pending_step: ResMut<PendingStep>,
+        mut virtual_time: ResMut<Time<Virtual>>,
+        fixed_time: Res<Time<Fixed>>,
+        server: Option<Res<RepliconServer>>,
+        client: Res<RepliconClient>,
+    ) {
+        if in_multiplayer(server, client) {
+            warn!("ignoring simulation step in multiplayer");
+            return;
+        }
+        if !virtual_time.is_paused() {
+            warn!("ignoring simulation step because it's not paused");
+            return;
+        }
+        if *pending_step != PendingStep::None {
+            debug!("ignoring simulation step because another step is pending");
+            return;
+        }
+
+        debug!("stepping simulation by {:?}", fixed_time.timestep());
+        *pending_step = PendingStep::Requested {
+            speed: virtual_time.relative_speed(),
+            max_delta: virtual_time.max_delta(),
+        };
+        virtual_time.set_relative_speed(STEP_SPEED);
+        virtual_time.set_max_delta(fixed_time.timestep());
+        virtual_time.unpause();
+    }
+
+    fn start_step(mut pending_step: ResMut<PendingStep>) {
+        if let PendingStep::Requested { speed, max_delta } = *pending_step {
+            *pending_step = PendingStep::Active { speed, max_delta };
+        }
+    }
+
+    /// Restores virtual time after the frame that performed the step.
+    fn finish_step(mut pending_step: ResMut<PendingStep>, mut virtual_time: ResMut<Time<Virtual>>) {
+        if let PendingStep::Active { speed, max_delta } = *pending_step {
+            virtual_time.pause();
+            virtual_time.set_relative_speed(speed);
+            virtual_time.set_max_delta(max_delta);
+            *pending_step = PendingStep::None;
+        }
+    }
+}
+
+/// Pauses or resumes the simulation.
+///
+/// Ignored in multiplayer.
+#[derive(Event, Deref, Clone, Copy)]
+pub struct SetSimulationPaused(pub bool);
+
+/// Advances the paused simulation by exactly one fixed timestep.
+///
+/// [`Update`] systems will receive the same delta for this frame.
+/// Ignored in multiplayer since it would desync clients.
+#[derive(Event, Clone, Copy)]
+pub struct StepSimulation;
+
+#[derive(Resource, Default, Clone, Copy, PartialEq)]
+enum PendingStep {
+    #[default]
+    None,
+    /// Step was requested, virtual time will advance at the next frame.
+    Requested { speed: f32, max_delta: Duration },
+    /// Virtual time advanced at this frame.
+    Active { speed: f32, max_delta: Duration },
+}
@@ -0,0 +1,105 @@
+use std::fmt::Write;
+
+use bevy::prelude::*;
+
+use project_harmonia_base::game_world::actor::{
+    needs::{Need, NeedGlyph, NeedRate},
+    task::{ActiveTask, Task, TaskProgress, TaskState},
+    FirstName, LastName, SelectedActor,
+};
+use project_harmonia_widgets::{label::LabelKind, theme::Theme};
+
+/// Developer panel with raw values of the selected actor.
+///
+/// Toggled with F3. Read-only and updated every frame.
+pub(super) struct ActorInspectorPlugin;
+
+impl Plugin for ActorInspectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                Self::toggle.never_param_warn(),
+                Self::update.never_param_warn(),
+            )
+                .chain(),
+        );
+    }
+}
+
+impl ActorInspectorPlugin {
+    fn toggle(
+        mut commands: Commands,
+        keys: Res<ButtonInput<KeyCode>>,
+        theme: Res<Theme>,
+        root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+        inspectors: Query<Entity, With<ActorInspector>>,
+    ) {
+        if !keys.just_pressed(KeyCode::F3) {
+            return;
+        }
+
+        if let Ok(inspector_entity) = inspectors.get_single() {
+            info!("closing actor inspector");
+            commands.entity(inspector_entity).despawn_recursive();
+            return;
+        }
+
+        info!("opening actor inspector");
+        commands.entity(*root_entity).with_children(|parent| {
+            parent
+                .spawn((
+                    ActorInspector,
+                    Node {
+                        position_type: PositionType::Absolute,
+                        top: Val::Px(0.0),
+                        right: Val::Px(0.0),
+                        padding: theme.padding.normal,
+                        ..Default::default()
+                    },
+                    theme.panel_background,
+                ))
+                .with_child((InspectorLabel, LabelKind::Normal, Text::default()));
+        });
+    }
+
+    fn update(
+        mut text: Single<&mut Text, With<InspectorLabel>>,
+        actors: Query<(Entity, &FirstName, &LastName, &Children), With<SelectedActor>>,
+        needs: Query<(&NeedGlyph, &Need, &NeedRate)>,
+        tasks: Query<(&Name, &TaskProgress, &TaskState, Has<ActiveTask>), With<Task>>,
+    ) {
+        let Ok((actor_entity, first_name, last_name, children)) = actors.get_single() else {
+            text.0 = "No actor selected".to_string();
+            return;
+        };
+
+        let mut output = format!("{} {} `{actor_entity}`", first_name.0, last_name.0);
+
+        output.push_str("\n\nNeeds:");
+        for (glyph, need, rate) in needs.iter_many(children) {
+            write!(output, "\n  {} {:.1} ({:+.2}/s)", glyph.0, need.0, rate.0).unwrap();
+        }
+
+        output.push_str("\n\nTasks:");
+        for (name, progress, state, active) in tasks.iter_many(children) {
+            let status = match state {
+                _ if active => "active".to_string(),
+                TaskState::Queued => "queued".to_string(),
+                TaskState::Blocked { reason } => format!("blocked: {reason}"),
+            };
+            write!(output, "\n  {name} [{status}] {:.0}%", **progress * 100.0).unwrap();
+        }
+
+        if text.0 != output {
+            text.0 = output;
+        }
+    }
+}
+
+#[derive(Component)]
+#[require(Name(|| Name::new("Actor inspector")), Node)]
+struct ActorInspector;
+
+#[derive(Component)]
+struct InspectorLabel;
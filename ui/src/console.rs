@@ -0,0 +1,589 @@
+use std::{collections::BTreeMap, fmt::Write, str::FromStr};
+
+use anyhow::{anyhow, ensure, Context, Result};
+use bevy::{ecs::system::SystemId, prelude::*};
+use bevy_enhanced_input::prelude::*;
+use bevy_replicon::prelude::*;
+use bevy_simple_text_input::{TextInputSubmitEvent, TextInputValue};
+
+use project_harmonia_base::{
+    asset::manifest::object_manifest::{self, ObjectManifest},
+    common_conditions::in_multiplayer,
+    game_paths::GamePaths,
+    game_world::{
+        actor::{needs::Need, SelectedActor},
+        city::ActiveCity,
+        clock::ClockSkip,
+        family::{BudgetChange, BudgetCheat, BudgetPolicy, SelectedFamily},
+        object::Object,
+        replay::{ReplayOpen, ReplayPlayback, ReplaySeek, StartRecording, StopRecording},
+    },
+    settings::Settings,
+    stepping::{SetSimulationPaused, StepSimulation},
+};
+use project_harmonia_widgets::{label::LabelKind, text_edit::TextEdit, theme::Theme};
+
+/// Developer console with commands for debugging.
+///
+/// Opened with the backtick key.
+pub(super) struct ConsolePlugin;
+
+impl Plugin for ConsolePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_input_context::<ConsoleToggle>()
+            .init_resource::<ConsoleCommands>()
+            .init_resource::<ConsoleLog>()
+            .add_console_command("help", "help", Self::help)
+            .add_console_command("set_budget", "set_budget <amount>", Self::set_budget)
+            .add_console_command(
+                "budget_policy",
+                "budget_policy <shared|per_actor>",
+                Self::set_budget_policy,
+            )
+            .add_console_command("give_need", "give_need <value>", Self::give_need)
+            .add_console_command("teleport", "teleport <x> <z>", Self::teleport)
+            .add_console_command("time", "time <hour>", Self::skip_time)
+            .add_console_command("pause", "pause", Self::pause)
+            .add_console_command("resume", "resume", Self::resume)
+            .add_console_command("step", "step", Self::step)
+            .add_console_command("replay_record", "replay_record", Self::record_replay)
+            .add_console_command("replay_stop", "replay_stop", Self::stop_replay)
+            .add_console_command("replay_open", "replay_open <name>", Self::open_replay)
+            .add_console_command("replay_seek", "replay_seek <index>", Self::seek_replay)
+            .add_console_command(
+                "spawn_object",
+                "spawn_object <id> [x y z]",
+                Self::spawn_object,
+            )
+            .add_observer(Self::toggle)
+            .add_systems(Startup, Self::setup)
+            .add_systems(
+                Update,
+                (Self::remove_backticks, Self::submit, Self::update_log),
+            );
+    }
+}
+
+/// Maximum number of lines kept in the log.
+const MAX_LOG_LINES: usize = 100;
+
+impl ConsolePlugin {
+    fn setup(mut commands: Commands) {
+        commands.spawn(ConsoleToggle);
+    }
+
+    fn toggle(
+        _trigger: Trigger<Started<ToggleConsole>>,
+        mut commands: Commands,
+        theme: Res<Theme>,
+        root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+        consoles: Query<Entity, With<Console>>,
+    ) {
+        if let Ok(console_entity) = consoles.get_single() {
+            info!("closing console");
+            commands.entity(console_entity).despawn_recursive();
+            return;
+        }
+
+        info!("opening console");
+        commands.entity(*root_entity).with_children(|parent| {
+            parent
+                .spawn((
+                    Console,
+                    Node {
+                        position_type: PositionType::Absolute,
+                        flex_direction: FlexDirection::Column,
+                        width: Val::Percent(100.0),
+                        height: Val::Percent(40.0),
+                        padding: theme.padding.normal,
+                        row_gap: theme.gap.normal,
+                        ..Default::default()
+                    },
+                    theme.panel_background,
+                ))
+                .with_children(|parent| {
+                    parent
+                        .spawn(Node {
+                            flex_grow: 1.0,
+                            flex_direction: FlexDirection::ColumnReverse,
+                            overflow: Overflow::scroll_y(),
+                            ..Default::default()
+                        })
+                        .with_child(ConsoleLogLabel);
+                    parent.spawn((
+                        ConsoleEdit,
+                        TextEdit::default(),
+                        Node {
+                            width: Val::Percent(100.0),
+                            ..Default::default()
+                        },
+                    ));
+                });
+        });
+    }
+
+    /// Removes the key that opened the console from the input.
+    fn remove_backticks(
+        mut edits: Query<&mut TextInputValue, (Changed<TextInputValue>, With<ConsoleEdit>)>,
+    ) {
+        for mut text in &mut edits {
+            if text.0.contains('`') {
+                text.0.retain(|c| c != '`');
+            }
+        }
+    }
+
+    fn submit(
+        mut commands: Commands,
+        mut submit_events: EventReader<TextInputSubmitEvent>,
+        mut edits: Query<&mut TextInputValue, With<ConsoleEdit>>,
+    ) {
+        for event in submit_events.read() {
+            let Ok(mut text) = edits.get_mut(event.entity) else {
+                continue;
+            };
+
+            let line = event.value.trim().to_string();
+            text.0.clear();
+            if !line.is_empty() {
+                commands.queue(move |world: &mut World| execute(world, line));
+            }
+        }
+    }
+
+    fn update_log(
+        log: Res<ConsoleLog>,
+        mut labels: Query<&mut Text, With<ConsoleLogLabel>>,
+        added_labels: Query<(), Added<ConsoleLogLabel>>,
+    ) {
+        if !log.is_changed() && added_labels.is_empty() {
+            return;
+        }
+
+        for mut text in &mut labels {
+            text.0 = log.0.join("\n");
+        }
+    }
+
+    fn help(_args: In<Vec<String>>, console_commands: Res<ConsoleCommands>) -> Result<String> {
+        let mut output = String::from("Available commands:");
+        for command in console_commands.values() {
+            write!(output, "\n  {}", command.usage).unwrap();
+        }
+
+        Ok(output)
+    }
+
+    fn set_budget(
+        In(args): In<Vec<String>>,
+        settings: Res<Settings>,
+        mut cheat_events: EventWriter<BudgetCheat>,
+        family_entity: Option<Single<Entity, With<SelectedFamily>>>,
+    ) -> Result<String> {
+        ensure!(
+            settings.developer.cheats,
+            "cheats should be enabled in developer settings"
+        );
+        let amount = parse_arg(&args, 0, "amount")?;
+        let family_entity = family_entity.context("no family is selected")?;
+
+        cheat_events.send(BudgetCheat {
+            family_entity: *family_entity,
+            change: BudgetChange::Set(amount),
+        });
+
+        Ok(format!("requested budget change to {amount}"))
+    }
+
+    fn set_budget_policy(
+        In(args): In<Vec<String>>,
+        client: Res<RepliconClient>,
+        policy: Option<Single<&mut BudgetPolicy, With<SelectedFamily>>>,
+    ) -> Result<String> {
+        ensure!(!client.is_connected(), "only available on server");
+        let new_policy = match args.first().map(String::as_str) {
+            Some("shared") => BudgetPolicy::Shared,
+            Some("per_actor") => BudgetPolicy::PerActor,
+            _ => return Err(anyhow!("policy should be `shared` or `per_actor`")),
+        };
+        let mut policy = policy.context("no family is selected")?;
+        **policy = new_policy;
+
+        Ok(format!("set budget policy to `{new_policy:?}`"))
+    }
+
+    fn give_need(
+        In(args): In<Vec<String>>,
+        client: Res<RepliconClient>,
+        actor_children: Option<Single<&Children, With<SelectedActor>>>,
+        mut needs: Query<&mut Need>,
+    ) -> Result<String> {
+        ensure!(!client.is_connected(), "only available on server");
+        let value: f32 = parse_arg(&args, 0, "value")?;
+        ensure!(
+            (0.0..=100.0).contains(&value),
+            "value should be in range from 0 to 100"
+        );
+        let actor_children = actor_children.context("no actor is selected")?;
+
+        let mut iter = needs.iter_many_mut(*actor_children);
+        let mut count = 0;
+        while let Some(mut need) = iter.fetch_next() {
+            need.0 = value;
+            count += 1;
+        }
+
+        Ok(format!("set {count} needs to {value}"))
+    }
+
+    fn teleport(
+        In(args): In<Vec<String>>,
+        client: Res<RepliconClient>,
+        actor_transform: Option<Single<&mut Transform, With<SelectedActor>>>,
+    ) -> Result<String> {
+        ensure!(!client.is_connected(), "only available on server");
+        let x = parse_arg(&args, 0, "x")?;
+        let z = parse_arg(&args, 1, "z")?;
+        let mut actor_transform = actor_transform.context("no actor is selected")?;
+
+        actor_transform.translation.x = x;
+        actor_transform.translation.z = z;
+
+        Ok(format!("teleported actor to ({x}, {z})"))
+    }
+
+    fn skip_time(
+        In(args): In<Vec<String>>,
+        client: Res<RepliconClient>,
+        mut skip_events: EventWriter<ClockSkip>,
+    ) -> Result<String> {
+        ensure!(!client.is_connected(), "only available on server");
+        let hour = parse_arg(&args, 0, "hour")?;
+        ensure!(
+            (0.0..24.0).contains(&hour),
+            "hour should be in range from 0 to 24"
+        );
+
+        skip_events.send(ClockSkip { hour });
+
+        Ok(format!("skipping to {hour} hour"))
+    }
+
+    fn pause(
+        _args: In<Vec<String>>,
+        mut commands: Commands,
+        server: Option<Res<RepliconServer>>,
+        client: Res<RepliconClient>,
+    ) -> Result<String> {
+        ensure!(
+            !in_multiplayer(server, client),
+            "unavailable in multiplayer"
+        );
+        commands.trigger(SetSimulationPaused(true));
+
+        Ok("simulation paused".to_string())
+    }
+
+    fn resume(_args: In<Vec<String>>, mut commands: Commands) -> Result<String> {
+        commands.trigger(SetSimulationPaused(false));
+
+        Ok("simulation resumed".to_string())
+    }
+
+    fn step(
+        _args: In<Vec<String>>,
+        mut commands: Commands,
+        time: Res<Time<Virtual>>,
+        fixed_time: Res<Time<Fixed>>,
+        server: Option<Res<RepliconServer>>,
+        client: Res<RepliconClient>,
+    ) -> Result<String> {
+        ensure!(
+            !in_multiplayer(server, client),
+            "unavailable in multiplayer since it would desync clients"
+        );
+        ensure!(time.is_paused(), "simulation should be paused first");
+        commands.trigger(StepSimulation);
+
+        Ok(format!("stepping by {:?}", fixed_time.timestep()))
+    }
+
+    fn record_replay(_args: In<Vec<String>>, mut commands: Commands) -> Result<String> {
+        commands.trigger(StartRecording);
+
+        Ok("requested recording start".to_string())
+    }
+
+    fn stop_replay(_args: In<Vec<String>>, mut commands: Commands) -> Result<String> {
+        commands.trigger(StopRecording);
+
+        Ok("requested recording stop".to_string())
+    }
+
+    fn open_replay(
+        In(args): In<Vec<String>>,
+        mut commands: Commands,
+        game_paths: Res<GamePaths>,
+    ) -> Result<String> {
+        let name: String = parse_arg(&args, 0, "name")?;
+        let path = game_paths.replay_path(&name);
+        ensure!(path.exists(), "{path:?} doesn't exist");
+        commands.trigger(ReplayOpen(path));
+
+        Ok(format!("opening replay '{name}'"))
+    }
+
+    fn seek_replay(
+        In(args): In<Vec<String>>,
+        mut commands: Commands,
+        playback: Option<Res<ReplayPlayback>>,
+    ) -> Result<String> {
+        let index: usize = parse_arg(&args, 0, "index")?;
+        let playback = playback.context("no replay is opened")?;
+        ensure!(
+            index < playback.snapshots_count(),
+            "index should be less than {}",
+            playback.snapshots_count()
+        );
+        commands.trigger(ReplaySeek(index));
+
+        Ok(format!(
+            "seeking from snapshot {} to {index}",
+            playback.current()
+        ))
+    }
+
+    /// Spawns an object by its manifest file name without extensions.
+    ///
+    /// Spawns at the point the camera looks at if position is not specified.
+    fn spawn_object(
+        In(args): In<Vec<String>>,
+        mut commands: Commands,
+        client: Res<RepliconClient>,
+        asset_server: Res<AssetServer>,
+        manifests: Res<Assets<ObjectManifest>>,
+        city_entity: Option<Single<Entity, With<ActiveCity>>>,
+        camera_transform: Option<Single<&GlobalTransform, With<Camera3d>>>,
+    ) -> Result<String> {
+        ensure!(!client.is_connected(), "only available on server");
+        let id: String = parse_arg(&args, 0, "id")?;
+        let city_entity = city_entity.context("no city is active")?;
+
+        let translation = if args.len() > 1 {
+            Vec3::new(
+                parse_arg(&args, 1, "x")?,
+                parse_arg(&args, 2, "y")?,
+                parse_arg(&args, 3, "z")?,
+            )
+        } else {
+            let camera_transform = camera_transform.context("no camera to spawn in front of")?;
+            let ray = Ray3d::new(camera_transform.translation(), camera_transform.forward());
+            let distance = ray
+                .intersect_plane(Vec3::ZERO, InfinitePlane3d::new(Vec3::Y))
+                .context("camera should look at the ground")?;
+            ray.get_point(distance)
+        };
+
+        let paths: Vec<_> = manifests
+            .ids()
+            .filter_map(|id| asset_server.get_path(id))
+            .collect();
+        let Some(manifest_path) = paths
+            .iter()
+            .find(|path| object_manifest::object_id(path) == id)
+        else {
+            let suggestions = closest_matches(paths.iter().map(object_manifest::object_id), &id);
+            return Err(anyhow!(
+                "unknown object `{id}`, closest matches: {}",
+                suggestions.join(", ")
+            ));
+        };
+
+        commands.entity(*city_entity).with_children(|parent| {
+            parent.spawn((
+                Object::new(manifest_path.clone_owned()),
+                Transform::from_translation(translation),
+            ));
+        });
+
+        Ok(format!("spawned `{id}` at {translation}"))
+    }
+}
+
+/// Returns up to 3 candidates sorted by their edit distance to the string.
+fn closest_matches<'a>(candidates: impl Iterator<Item = &'a str>, id: &str) -> Vec<&'a str> {
+    let mut suggestions: Vec<_> = candidates.collect();
+    suggestions.sort_by_key(|suggestion| edit_distance(suggestion, id));
+    suggestions.truncate(3);
+    suggestions
+}
+
+/// Number of single-character edits to turn one string into another.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<_> = b.chars().collect();
+    let mut row: Vec<_> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut prev_diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &b_char) in b.iter().enumerate() {
+            let substitution = prev_diagonal + usize::from(a_char != b_char);
+            prev_diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+/// Parses and runs a console line, writing the result into [`ConsoleLog`].
+fn execute(world: &mut World, line: String) {
+    let mut args: Vec<_> = line.split_whitespace().map(ToString::to_string).collect();
+    let name = args.remove(0);
+
+    info!("executing console command `{line}`");
+    let result = match world.resource::<ConsoleCommands>().get(&*name) {
+        Some(command) => {
+            let id = command.id;
+            world
+                .run_system_with_input(id, args)
+                .map_err(|e| anyhow!("unable to run `{name}`: {e}"))
+                .and_then(|result| result)
+        }
+        None => Err(anyhow!(
+            "unknown command `{name}`, type `help` for the list"
+        )),
+    };
+
+    let mut log = world.resource_mut::<ConsoleLog>();
+    log.push(format!("> {line}"));
+    match result {
+        Ok(output) => log.push(output),
+        Err(e) => {
+            error!("console command failed: {e:#}");
+            log.push(format!("error: {e:#}"));
+        }
+    }
+}
+
+/// Parses an argument at the given index.
+fn parse_arg<T: FromStr>(args: &[String], index: usize, name: &str) -> Result<T>
+where
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    let arg = args
+        .get(index)
+        .with_context(|| format!("missing argument `{name}`"))?;
+
+    arg.parse()
+        .with_context(|| format!("invalid argument `{name}`: '{arg}'"))
+}
+
+pub(super) trait ConsoleCommandsExt {
+    /// Registers a system that will be executed with whitespace-separated arguments
+    /// when a line starts with the given name.
+    ///
+    /// The returned string or error is displayed in the console.
+    fn add_console_command<M>(
+        &mut self,
+        name: &'static str,
+        usage: &'static str,
+        system: impl IntoSystem<In<Vec<String>>, Result<String>, M> + 'static,
+    ) -> &mut Self;
+}
+
+impl ConsoleCommandsExt for App {
+    fn add_console_command<M>(
+        &mut self,
+        name: &'static str,
+        usage: &'static str,
+        system: impl IntoSystem<In<Vec<String>>, Result<String>, M> + 'static,
+    ) -> &mut Self {
+        let id = self.world_mut().register_system(system);
+        self.world_mut()
+            .get_resource_or_insert_with(ConsoleCommands::default)
+            .insert(name, ConsoleCommand { usage, id });
+
+        self
+    }
+}
+
+/// Commands available in the console by their names.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct ConsoleCommands(BTreeMap<&'static str, ConsoleCommand>);
+
+struct ConsoleCommand {
+    usage: &'static str,
+    id: SystemId<In<Vec<String>>, Result<String>>,
+}
+
+/// Entered lines and their results.
+#[derive(Resource, Default)]
+struct ConsoleLog(Vec<String>);
+
+impl ConsoleLog {
+    fn push(&mut self, line: String) {
+        if self.0.len() >= MAX_LOG_LINES {
+            self.0.remove(0);
+        }
+        self.0.push(line);
+    }
+}
+
+/// Context for opening and closing the console.
+///
+/// Separate from [`Console`] since it should be active while the console is closed.
+#[derive(Component)]
+#[require(Name(|| Name::new("Console toggle")))]
+struct ConsoleToggle;
+
+impl InputContext for ConsoleToggle {
+    fn context_instance(_world: &World, _entity: Entity) -> ContextInstance {
+        let mut ctx = ContextInstance::default();
+        ctx.bind::<ToggleConsole>().to(KeyCode::Backquote);
+        ctx
+    }
+}
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct ToggleConsole;
+
+#[derive(Component)]
+#[require(Name(|| Name::new("Console")))]
+struct Console;
+
+#[derive(Component)]
+#[require(LabelKind(|| LabelKind::Normal), Text)]
+struct ConsoleLogLabel;
+
+#[derive(Component)]
+struct ConsoleEdit;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distance() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("chair", "chair"), 0);
+        assert_eq!(edit_distance("", "chair"), 5);
+        assert_eq!(edit_distance("chair", ""), 5);
+        assert_eq!(edit_distance("chair", "chairs"), 1, "insertion");
+        assert_eq!(edit_distance("chair", "hair"), 1, "deletion");
+        assert_eq!(edit_distance("chair", "cheir"), 1, "substitution");
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(
+            edit_distance("sitting", "kitten"),
+            3,
+            "distance should be symmetric"
+        );
+    }
+
+    #[test]
+    fn suggestions() {
+        let candidates = ["table", "armchair", "chairs", "chair", "lamp"];
+        let suggestions = closest_matches(candidates.into_iter(), "chair");
+        assert_eq!(suggestions, ["chair", "chairs", "armchair"]);
+    }
+}
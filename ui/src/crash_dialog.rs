@@ -0,0 +1,110 @@
+use std::{path::PathBuf, process::Command};
+
+use anyhow::{Context, Result};
+use bevy::prelude::*;
+
+use project_harmonia_base::{
+    core::GameState, crash_report::PendingCrashReport, error_message::error_message,
+};
+use project_harmonia_widgets::{
+    button::ButtonKind, dialog::Dialog, label::LabelKind, theme::Theme,
+};
+
+/// Offers to open the crash report from the previous launch.
+pub(super) struct CrashDialogPlugin;
+
+impl Plugin for CrashDialogPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            Self::show
+                .run_if(resource_exists::<PendingCrashReport>)
+                .run_if(in_state(GameState::Menu)),
+        );
+    }
+}
+
+impl CrashDialogPlugin {
+    fn show(
+        mut commands: Commands,
+        theme: Res<Theme>,
+        report: Res<PendingCrashReport>,
+        root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+    ) {
+        info!("showing crash dialog");
+        commands.remove_resource::<PendingCrashReport>();
+        commands.entity(*root_entity).with_children(|parent| {
+            parent
+                .spawn(CrashDialog(report.0.clone()))
+                .with_children(|parent| {
+                    parent
+                        .spawn((
+                            Node {
+                                flex_direction: FlexDirection::Column,
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                padding: theme.padding.normal,
+                                row_gap: theme.gap.normal,
+                                ..Default::default()
+                            },
+                            theme.panel_background,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                LabelKind::Normal,
+                                Text::new(format!(
+                                    "The game crashed last time, report saved to\n{}",
+                                    report.display()
+                                )),
+                            ));
+
+                            parent
+                                .spawn(Node {
+                                    column_gap: theme.gap.normal,
+                                    ..Default::default()
+                                })
+                                .with_children(|parent| {
+                                    parent
+                                        .spawn(ButtonKind::Normal)
+                                        .with_child(Text::new("Open report"))
+                                        .observe(Self::open.pipe(error_message));
+                                    parent
+                                        .spawn(ButtonKind::Normal)
+                                        .with_child(Text::new("Ok"))
+                                        .observe(Self::close);
+                                });
+                        });
+                });
+        });
+    }
+
+    fn open(_trigger: Trigger<Pointer<Click>>, dialog: Single<&CrashDialog>) -> Result<()> {
+        info!("opening crash report {:?}", dialog.0);
+        let program = if cfg!(target_os = "windows") {
+            "explorer"
+        } else if cfg!(target_os = "macos") {
+            "open"
+        } else {
+            "xdg-open"
+        };
+        Command::new(program)
+            .arg(&dialog.0)
+            .spawn()
+            .with_context(|| format!("unable to open {:?}", dialog.0))?;
+
+        Ok(())
+    }
+
+    fn close(
+        _trigger: Trigger<Pointer<Click>>,
+        mut commands: Commands,
+        dialog_entity: Single<Entity, With<CrashDialog>>,
+    ) {
+        info!("closing crash dialog");
+        commands.entity(*dialog_entity).despawn_recursive();
+    }
+}
+
+#[derive(Component)]
+#[require(Name(|| Name::new("Crash dialog")), Dialog)]
+struct CrashDialog(PathBuf);
@@ -1,6 +1,8 @@
 use bevy::prelude::*;
 
-use project_harmonia_base::error_message::ErrorMessage;
+use project_harmonia_base::{
+    build_info::BuildInfo, error_message::ErrorMessage, game_paths::GamePaths,
+};
 use project_harmonia_widgets::{
     button::ButtonKind, dialog::Dialog, label::LabelKind, theme::Theme,
 };
@@ -18,6 +20,8 @@ impl ErrorDialogPlugin {
         trigger: Trigger<ErrorMessage>,
         mut commands: Commands,
         theme: Res<Theme>,
+        game_paths: Res<GamePaths>,
+        build_info: Res<BuildInfo>,
         root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
     ) {
         info!("showing error dialog");
@@ -37,6 +41,14 @@ impl ErrorDialogPlugin {
                     ))
                     .with_children(|parent| {
                         parent.spawn((LabelKind::Normal, Text::new(&**trigger)));
+                        parent.spawn((
+                            LabelKind::Small,
+                            Text::new(format!(
+                                "Logs for bug reports are in {}\nVersion {}",
+                                game_paths.logs.display(),
+                                *build_info
+                            )),
+                        ));
                         parent.spawn(ButtonKind::Normal).with_child(Text::new("Ok"));
                     })
                     .observe(Self::close);
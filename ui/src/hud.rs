@@ -1,13 +1,17 @@
 mod city_hud;
 mod family_hud;
+mod minimap_node;
 mod objects_node;
 pub(super) mod task_menu;
 mod tools_node;
 
 use bevy::prelude::*;
+use project_harmonia_base::game_world::highlighting::HighlightingStyle;
+use project_harmonia_widgets::theme::Theme;
 
 use city_hud::CityHudPlugin;
 use family_hud::FamilyHudPlugin;
+use minimap_node::MinimapNodePlugin;
 use objects_node::ObjectsNodePlugin;
 use task_menu::TaskMenuPlugin;
 use tools_node::ToolsNodePlugin;
@@ -20,8 +24,23 @@ impl Plugin for HudPlugin {
             CityHudPlugin,
             ObjectsNodePlugin,
             FamilyHudPlugin,
+            MinimapNodePlugin,
             TaskMenuPlugin,
             ToolsNodePlugin,
-        ));
+        ))
+        .add_systems(
+            Update,
+            Self::apply_outline_theme.run_if(resource_changed::<Theme>),
+        );
+    }
+}
+
+impl HudPlugin {
+    fn apply_outline_theme(theme: Res<Theme>, mut style: ResMut<HighlightingStyle>) {
+        style.set_if_neq(HighlightingStyle {
+            width: theme.outline.width,
+            hovered_color: theme.outline.hovered_color,
+            selected_color: theme.outline.selected_color,
+        });
     }
 }
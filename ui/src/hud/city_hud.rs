@@ -3,13 +3,14 @@ mod roads_node;
 use bevy::prelude::*;
 use project_harmonia_base::{
     asset::manifest::{
-        object_manifest::{ObjectCategory, ObjectManifest},
+        category_manifest::{CategoryGroup, ObjectCategories},
+        object_manifest::ObjectManifest,
         road_manifest::RoadManifest,
     },
-    game_world::{city::CityMode, WorldState},
+    game_world::{city::CityMode, spectator::Spectator, WorldState},
 };
 use project_harmonia_widgets::{
-    button::{ButtonKind, TabContent, Toggled},
+    button::{ButtonKind, RadioChanged, RadioGroup, TabContent, Toggled},
     theme::Theme,
 };
 use strum::IntoEnumIterator;
@@ -22,11 +23,7 @@ pub(super) struct CityHudPlugin;
 impl Plugin for CityHudPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(RoadsNodePlugin)
-            .add_systems(OnEnter(WorldState::City), Self::setup)
-            .add_systems(
-                Update,
-                Self::set_city_mode.run_if(in_state(WorldState::City)),
-            );
+            .add_systems(OnEnter(WorldState::City), Self::setup);
     }
 }
 
@@ -37,7 +34,9 @@ impl CityHudPlugin {
         theme: Res<Theme>,
         asset_server: Res<AssetServer>,
         object_manifests: Res<Assets<ObjectManifest>>,
+        categories: Res<ObjectCategories>,
         road_manifests: Res<Assets<RoadManifest>>,
+        spectator: Option<Res<Spectator>>,
         root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
     ) {
         debug!("showing city HUD");
@@ -53,10 +52,16 @@ impl CityHudPlugin {
                     },
                 ))
                 .with_children(|parent| {
+                    if spectator.is_some() {
+                        // Spectators can only look around.
+                        return;
+                    }
+
                     tools_node::setup(parent, &theme);
 
                     let tabs_entity = parent
                         .spawn((
+                            RadioGroup,
                             Node {
                                 flex_direction: FlexDirection::Column,
                                 align_self: AlignSelf::FlexEnd,
@@ -65,6 +70,7 @@ impl CityHudPlugin {
                             },
                             theme.panel_background,
                         ))
+                        .observe(Self::set_city_mode)
                         .id();
 
                     for mode in CityMode::iter() {
@@ -85,7 +91,10 @@ impl CityHudPlugin {
                                         &mut tab_commands,
                                         &theme,
                                         &object_manifests,
-                                        ObjectCategory::CITY_CATEGORIES,
+                                        &categories,
+                                        CategoryGroup::City,
+                                        None,
+                                        &Default::default(),
                                     );
                                 }
                                 CityMode::Roads => roads_node::setup(
@@ -113,14 +122,17 @@ impl CityHudPlugin {
     }
 
     fn set_city_mode(
+        trigger: Trigger<RadioChanged>,
         mut commands: Commands,
-        buttons: Query<(Ref<Toggled>, &CityMode), Changed<Toggled>>,
+        tabs: Query<&Children>,
+        buttons: Query<&CityMode>,
     ) {
-        for (toggled, &mode) in &buttons {
-            if toggled.0 && !toggled.is_added() {
-                info!("changing city mode to `{mode:?}`");
-                commands.set_state(mode);
-            }
-        }
+        let children = tabs.get(trigger.entity()).unwrap();
+        let mode = *buttons
+            .iter_many(children)
+            .nth(**trigger)
+            .expect("tabs should be spawned for each mode");
+        info!("changing city mode to `{mode:?}`");
+        commands.set_state(mode);
     }
 }
@@ -1,46 +1,63 @@
 mod building_hud;
+mod clock_node;
 mod info_node;
 mod members_node;
 mod portrait_node;
+mod selection_ring;
 mod tasks_node;
 
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashMap};
 use project_harmonia_base::{
-    asset::manifest::object_manifest::ObjectManifest,
+    asset::manifest::{
+        category_manifest::ObjectCategories,
+        object_manifest::{ObjectCategory, ObjectManifest},
+    },
     game_world::{
         actor::{
             task::{ActiveTask, Task},
             SelectedActor,
         },
-        family::{Budget, FamilyMembers, FamilyMode, FamilyPlugin, SelectedFamily},
+        family::{
+            building::BuildingMode, Budget, BudgetPolicy, FamilyMembers, FamilyMode, FamilyPlugin,
+            SelectedFamily,
+        },
+        spectator::Spectator,
         WorldState,
     },
+    settings::Settings,
 };
 use project_harmonia_widgets::{
-    button::{ButtonKind, TabContent, Toggled},
+    button::{ButtonKind, RadioChanged, RadioGroup, TabContent, Toggled},
     theme::Theme,
 };
 use strum::IntoEnumIterator;
 
 use building_hud::BuildingHudPlugin;
-use info_node::InfoNodePlugin;
+use clock_node::ClockNodePlugin;
+use info_node::{InfoNodePlugin, InfoTab};
+use members_node::MembersNodePlugin;
 use portrait_node::PortraitNodePlugin;
+use selection_ring::SelectionRingPlugin;
 use tasks_node::TasksNodePlugin;
 
 pub(super) struct FamilyHudPlugin;
 
 impl Plugin for FamilyHudPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((
-            TasksNodePlugin,
-            InfoNodePlugin,
-            PortraitNodePlugin,
-            BuildingHudPlugin,
-        ))
-        .add_systems(
-            OnEnter(WorldState::Family),
-            Self::setup.after(FamilyPlugin::select),
-        );
+        app.init_resource::<LastTabs>()
+            .add_plugins((
+                TasksNodePlugin,
+                InfoNodePlugin,
+                MembersNodePlugin,
+                PortraitNodePlugin,
+                BuildingHudPlugin,
+                ClockNodePlugin,
+                SelectionRingPlugin,
+            ))
+            .add_systems(
+                OnEnter(WorldState::Family),
+                Self::setup.after(FamilyPlugin::select),
+            );
     }
 }
 
@@ -49,11 +66,15 @@ impl FamilyHudPlugin {
         mut commands: Commands,
         mut tab_commands: Commands,
         theme: Res<Theme>,
+        last_tabs: Res<LastTabs>,
+        settings: Res<Settings>,
         object_manifests: Res<Assets<ObjectManifest>>,
+        categories: Res<ObjectCategories>,
+        spectator: Option<Res<Spectator>>,
         root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
         actor_children: Single<&Children, With<SelectedActor>>,
-        selected_family: Single<(&Budget, &FamilyMembers), With<SelectedFamily>>,
-        selected_entity: Single<Entity, With<SelectedActor>>,
+        selected_family: Single<(&Budget, &BudgetPolicy, &FamilyMembers), With<SelectedFamily>>,
+        selected_actor: Single<(Entity, &Budget), With<SelectedActor>>,
         tasks: Query<(Entity, Has<ActiveTask>), With<Task>>,
     ) {
         debug!("showing family hud");
@@ -71,6 +92,7 @@ impl FamilyHudPlugin {
                 .with_children(|parent| {
                     let tabs_entity = parent
                         .spawn((
+                            RadioGroup,
                             Node {
                                 position_type: PositionType::Absolute,
                                 right: Val::Px(0.0),
@@ -79,9 +101,13 @@ impl FamilyHudPlugin {
                             },
                             theme.panel_background,
                         ))
+                        .observe(Self::set_family_mode)
                         .id();
 
-                    for mode in FamilyMode::iter() {
+                    // Spectators can't build, so only life mode is available for them.
+                    let modes = FamilyMode::iter()
+                        .filter(|&mode| spectator.is_none() || mode == FamilyMode::Life);
+                    for mode in modes {
                         let content_entity = parent
                             .spawn((
                                 Node {
@@ -92,18 +118,31 @@ impl FamilyHudPlugin {
                             ))
                             .with_children(|parent| match mode {
                                 FamilyMode::Life => {
-                                    tasks_node::setup(parent, &theme, *actor_children, &tasks);
+                                    if spectator.is_none() {
+                                        tasks_node::setup(parent, &theme, *actor_children, &tasks);
+                                    }
+                                    clock_node::setup(parent, &theme);
 
-                                    let (&budget, members) = *selected_family;
-                                    portrait_node::setup(parent, &theme, budget);
-                                    members_node::setup(parent, &theme, members, *selected_entity);
-                                    info_node::setup(parent, &mut tab_commands, &theme);
+                                    let (&family_budget, &policy, members) = *selected_family;
+                                    let (selected_entity, &actor_budget) = *selected_actor;
+                                    portrait_node::setup(
+                                        parent,
+                                        &theme,
+                                        family_budget,
+                                        actor_budget,
+                                        policy,
+                                        settings.developer.cheats,
+                                    );
+                                    members_node::setup(parent, &theme, members, selected_entity);
+                                    info_node::setup(parent, &mut tab_commands, &theme, &last_tabs);
                                 }
                                 FamilyMode::Building => building_hud::setup(
                                     parent,
                                     &mut tab_commands,
                                     &theme,
                                     &object_manifests,
+                                    &categories,
+                                    &last_tabs,
                                 ),
                             })
                             .id();
@@ -116,20 +155,34 @@ impl FamilyHudPlugin {
                                 Toggled(mode == Default::default()),
                             ))
                             .with_child(Text::new(mode.glyph()))
-                            .set_parent(tabs_entity)
-                            .observe(Self::set_family_mode);
+                            .set_parent(tabs_entity);
                     }
                 });
         });
     }
 
     fn set_family_mode(
-        trigger: Trigger<Pointer<Click>>,
+        trigger: Trigger<RadioChanged>,
         mut commands: Commands,
+        tabs: Query<&Children>,
         buttons: Query<&FamilyMode>,
     ) {
-        let mode = *buttons.get(trigger.entity()).unwrap();
+        let children = tabs.get(trigger.entity()).unwrap();
+        let mode = *buttons
+            .iter_many(children)
+            .nth(**trigger)
+            .expect("tabs should be spawned for each mode");
         info!("changing family mode to `{mode:?}`");
         commands.set_state(mode);
     }
 }
+
+/// Tabs selected during the session to restore them when the HUD is spawned again.
+#[derive(Resource, Default)]
+struct LastTabs {
+    building_mode: BuildingMode,
+    object_category: Option<ObjectCategory>,
+    /// Selected subcategory for each category, absent for "All".
+    subcategories: HashMap<ObjectCategory, String>,
+    info_tab: InfoTab,
+}
@@ -2,16 +2,23 @@ mod walls_node;
 
 use bevy::prelude::*;
 use project_harmonia_base::{
-    asset::manifest::object_manifest::{ObjectCategory, ObjectManifest},
+    asset::manifest::{
+        category_manifest::{CategoryGroup, ObjectCategories},
+        object_manifest::{ObjectCategory, ObjectManifest},
+    },
     game_world::family::{building::BuildingMode, FamilyMode},
 };
 use project_harmonia_widgets::{
-    button::{ButtonKind, TabContent, Toggled},
+    button::{ButtonKind, RadioChanged, RadioGroup, TabContent, Toggled},
     theme::Theme,
 };
 use strum::IntoEnumIterator;
 
-use crate::hud::{objects_node, tools_node};
+use super::LastTabs;
+use crate::hud::{
+    objects_node::{self, SubcategoryTab},
+    tools_node,
+};
 use walls_node::WallsNodePlugin;
 
 pub(super) struct BuildingHudPlugin;
@@ -19,19 +26,63 @@ pub(super) struct BuildingHudPlugin;
 impl Plugin for BuildingHudPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(WallsNodePlugin)
-            .add_systems(OnEnter(FamilyMode::Building), Self::sync_building_mode);
+            .add_systems(OnEnter(FamilyMode::Building), Self::sync_building_mode)
+            .add_systems(
+                Update,
+                (Self::remember_category, Self::remember_subcategory)
+                    .run_if(in_state(FamilyMode::Building)),
+            );
     }
 }
 
 impl BuildingHudPlugin {
     fn set_building_mode(
-        trigger: Trigger<Pointer<Click>>,
+        trigger: Trigger<RadioChanged>,
         mut commands: Commands,
+        mut last_tabs: ResMut<LastTabs>,
+        tabs: Query<&Children>,
         buttons: Query<&BuildingMode>,
     ) {
-        let mode = *buttons.get(trigger.entity()).unwrap();
+        let children = tabs.get(trigger.entity()).unwrap();
+        let mode = *buttons
+            .iter_many(children)
+            .nth(**trigger)
+            .expect("tabs should be spawned for each mode");
         info!("changing building mode to `{mode:?}`");
         commands.set_state(mode);
+        last_tabs.building_mode = mode;
+    }
+
+    fn remember_category(
+        mut last_tabs: ResMut<LastTabs>,
+        buttons: Query<(&ObjectCategory, &Toggled), Changed<Toggled>>,
+    ) {
+        for (category, toggled) in &buttons {
+            if toggled.0 {
+                debug!("remembering object category `{category}`");
+                last_tabs.object_category = Some(category.clone());
+            }
+        }
+    }
+
+    fn remember_subcategory(
+        mut last_tabs: ResMut<LastTabs>,
+        buttons: Query<(&SubcategoryTab, &Toggled), Changed<Toggled>>,
+    ) {
+        for (tab, toggled) in &buttons {
+            if toggled.0 {
+                let category = tab.category().clone();
+                if let Some(subcategory) = tab.subcategory() {
+                    debug!("remembering subcategory `{subcategory}` for `{category}`");
+                    last_tabs
+                        .subcategories
+                        .insert(category, subcategory.clone());
+                } else {
+                    debug!("remembering all objects for `{category}`");
+                    last_tabs.subcategories.remove(&category);
+                }
+            }
+        }
     }
 
     /// Sets building mode to the last selected.
@@ -52,11 +103,14 @@ pub(super) fn setup(
     tab_commands: &mut Commands,
     theme: &Theme,
     object_manifests: &Assets<ObjectManifest>,
+    categories: &ObjectCategories,
+    last_tabs: &LastTabs,
 ) {
     tools_node::setup(parent, theme);
 
     let tabs_entity = parent
         .spawn((
+            RadioGroup,
             Node {
                 flex_direction: FlexDirection::Column,
                 align_self: AlignSelf::FlexEnd,
@@ -65,6 +119,7 @@ pub(super) fn setup(
             },
             theme.panel_background,
         ))
+        .observe(BuildingHudPlugin::set_building_mode)
         .id();
 
     for mode in BuildingMode::iter() {
@@ -85,7 +140,10 @@ pub(super) fn setup(
                         tab_commands,
                         theme,
                         object_manifests,
-                        ObjectCategory::FAMILY_CATEGORIES,
+                        categories,
+                        CategoryGroup::Family,
+                        last_tabs.object_category.as_ref(),
+                        &last_tabs.subcategories,
                     );
                 }
                 BuildingMode::Walls => walls_node::setup(parent),
@@ -97,10 +155,9 @@ pub(super) fn setup(
                 mode,
                 ButtonKind::Symbol,
                 TabContent(content_entity),
-                Toggled(mode == Default::default()),
+                Toggled(mode == last_tabs.building_mode),
             ))
             .with_child(Text::new(mode.glyph()))
-            .set_parent(tabs_entity)
-            .observe(BuildingHudPlugin::set_building_mode);
+            .set_parent(tabs_entity);
     }
 }
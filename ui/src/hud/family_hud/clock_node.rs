@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+use project_harmonia_base::{
+    core::localization::LocalizedText,
+    game_world::{
+        clock::{ClockSkip, WorldClock},
+        WorldState,
+    },
+};
+use project_harmonia_widgets::{button::ButtonKind, label::LabelKind, theme::Theme};
+
+pub(super) struct ClockNodePlugin;
+
+impl Plugin for ClockNodePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            Self::update_time
+                .never_param_warn()
+                .run_if(in_state(WorldState::Family)),
+        );
+    }
+}
+
+/// Hour to skip to with the button.
+const MORNING_HOUR: f32 = 8.0;
+
+impl ClockNodePlugin {
+    fn update_time(
+        clock: Single<Ref<WorldClock>>,
+        time_label: Single<(&mut LocalizedText, Ref<TimeLabel>)>,
+    ) {
+        let (mut text, label) = time_label.into_inner();
+        if !clock.is_changed() && !label.is_added() {
+            return;
+        }
+
+        let hour = clock.hour();
+        let minutes = (hour.fract() * 60.0) as u32;
+        text.set_if_neq(
+            LocalizedText::new("hud.clock.time")
+                .with_arg("day", clock.day() + 1)
+                .with_arg("time", format!("{:02}:{minutes:02}", hour as u32)),
+        );
+    }
+
+    fn skip_to_morning(_trigger: Trigger<Pointer<Click>>, mut skip_events: EventWriter<ClockSkip>) {
+        info!("requesting skip to {MORNING_HOUR} hour");
+        skip_events.send(ClockSkip { hour: MORNING_HOUR });
+    }
+}
+
+pub(super) fn setup(parent: &mut ChildBuilder, theme: &Theme) {
+    parent
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                align_items: AlignItems::Center,
+                column_gap: theme.gap.normal,
+                padding: theme.padding.normal,
+                ..Default::default()
+            },
+            theme.panel_background,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TimeLabel);
+            parent
+                .spawn(ButtonKind::Symbol)
+                .with_child(Text::new("⏩"))
+                .observe(ClockNodePlugin::skip_to_morning);
+        });
+}
+
+#[derive(Component)]
+#[require(
+    LabelKind(|| LabelKind::Normal),
+    LocalizedText(|| LocalizedText::new("hud.clock.time"))
+)]
+struct TimeLabel;
@@ -1,26 +1,42 @@
 use bevy::prelude::*;
-use project_harmonia_base::game_world::{
-    actor::{
-        needs::{Need, NeedGlyph},
-        SelectedActor,
+use project_harmonia_base::{
+    asset::jobs::{JobList, JobListHandle},
+    core::localization::LocalizedText,
+    game_world::{
+        actor::{
+            job::{Job, JobChange},
+            needs::{Need, NeedGlyph},
+            schedule::{DailySchedule, DailyScheduleEntry, ScheduleChange},
+            SelectedActor,
+        },
+        WorldState,
     },
-    WorldState,
 };
 use project_harmonia_widgets::{
     button::{ButtonKind, TabContent, Toggled},
     label::LabelKind,
+    number_input::NumberInput,
     progress_bar::ProgressBar,
     theme::Theme,
 };
 use strum::{EnumIter, IntoEnumIterator};
 
+use super::LastTabs;
+
 pub(super) struct InfoNodePlugin;
 
 impl Plugin for InfoNodePlugin {
     fn build(&self, app: &mut App) {
         app.add_observer(Self::cleanup_need_bars).add_systems(
             Update,
-            Self::update_need_bars.run_if(in_state(WorldState::Family)),
+            (
+                Self::update_need_bars,
+                Self::update_schedule,
+                Self::change_hours,
+                Self::update_job,
+                Self::remember_tab,
+            )
+                .run_if(in_state(WorldState::Family)),
         );
     }
 }
@@ -63,6 +79,243 @@ impl InfoNodePlugin {
         }
     }
 
+    fn update_schedule(
+        mut commands: Commands,
+        selected_actor: Single<(Ref<DailySchedule>, Ref<SelectedActor>)>,
+        tabs: Query<(&TabContent, &InfoTab)>,
+    ) {
+        let (schedule, selected_actor) = selected_actor.into_inner();
+        if !schedule.is_changed() && !selected_actor.is_added() {
+            return;
+        }
+
+        let (tab_content, _) = tabs
+            .iter()
+            .find(|(_, &tab)| tab == InfoTab::Schedule)
+            .expect("tab with schedule should be spawned on state enter");
+
+        debug!("updating schedule entries");
+        commands
+            .entity(tab_content.0)
+            .despawn_descendants()
+            .with_children(|parent| {
+                for (index, entry) in schedule.iter().enumerate() {
+                    parent
+                        .spawn((ScheduleRow(index), ButtonKind::Normal))
+                        .with_child(Text::new(format!(
+                            "{} {}",
+                            entry.activity.glyph(),
+                            entry.activity.name()
+                        )))
+                        .observe(Self::cycle_activity);
+                    parent.spawn((ScheduleHour::Start(index), hour_input(entry.start)));
+                    parent.spawn((ScheduleHour::End(index), hour_input(entry.end)));
+                    parent
+                        .spawn((ScheduleRow(index), ButtonKind::Symbol))
+                        .with_child(Text::new("❌"))
+                        .observe(Self::remove_entry);
+                }
+
+                parent
+                    .spawn((
+                        ButtonKind::Normal,
+                        Node {
+                            grid_column: GridPlacement::span(4),
+                            ..Default::default()
+                        },
+                    ))
+                    .with_child(LocalizedText::new("hud.schedule.add"))
+                    .observe(Self::add_entry);
+            });
+    }
+
+    fn cycle_activity(
+        trigger: Trigger<Pointer<Click>>,
+        mut change_events: EventWriter<ScheduleChange>,
+        rows: Query<&ScheduleRow>,
+        selected_actor: Single<(Entity, &DailySchedule), With<SelectedActor>>,
+    ) {
+        let row = rows.get(trigger.entity()).unwrap();
+        let (actor_entity, schedule) = *selected_actor;
+        let mut schedule = schedule.clone();
+        let entry = &mut schedule.0[row.0];
+        entry.activity = entry.activity.next();
+
+        info!("changing scheduled activity to `{:?}`", entry.activity);
+        change_events.send(ScheduleChange {
+            actor_entity,
+            schedule,
+        });
+    }
+
+    fn remove_entry(
+        trigger: Trigger<Pointer<Click>>,
+        mut change_events: EventWriter<ScheduleChange>,
+        rows: Query<&ScheduleRow>,
+        selected_actor: Single<(Entity, &DailySchedule), With<SelectedActor>>,
+    ) {
+        let row = rows.get(trigger.entity()).unwrap();
+        let (actor_entity, schedule) = *selected_actor;
+        let mut schedule = schedule.clone();
+        let entry = schedule.0.remove(row.0);
+
+        info!("removing scheduled `{:?}`", entry.activity);
+        change_events.send(ScheduleChange {
+            actor_entity,
+            schedule,
+        });
+    }
+
+    fn add_entry(
+        _trigger: Trigger<Pointer<Click>>,
+        mut change_events: EventWriter<ScheduleChange>,
+        selected_actor: Single<(Entity, &DailySchedule), With<SelectedActor>>,
+    ) {
+        let (actor_entity, schedule) = *selected_actor;
+        let mut schedule = schedule.clone();
+        schedule.0.push(DailyScheduleEntry::default());
+
+        info!("adding schedule entry");
+        change_events.send(ScheduleChange {
+            actor_entity,
+            schedule,
+        });
+    }
+
+    fn change_hours(
+        mut change_events: EventWriter<ScheduleChange>,
+        inputs: Query<(&NumberInput, &ScheduleHour), Changed<NumberInput>>,
+        selected_actor: Single<(Entity, &DailySchedule), With<SelectedActor>>,
+    ) {
+        let (actor_entity, schedule) = *selected_actor;
+        let mut new_schedule = schedule.clone();
+        for (input, &hour) in &inputs {
+            let value = input
+                .value
+                .try_into()
+                .expect("hour input should be limited to a day");
+            // Could be outdated if the schedule was replaced in this frame.
+            match hour {
+                ScheduleHour::Start(index) => {
+                    if let Some(entry) = new_schedule.0.get_mut(index) {
+                        entry.start = value;
+                    }
+                }
+                ScheduleHour::End(index) => {
+                    if let Some(entry) = new_schedule.0.get_mut(index) {
+                        entry.end = value;
+                    }
+                }
+            }
+        }
+
+        // Inputs are also changed on spawn, so skip if nothing is edited.
+        if new_schedule != *schedule && new_schedule.is_valid() {
+            info!("changing schedule hours");
+            change_events.send(ScheduleChange {
+                actor_entity,
+                schedule: new_schedule,
+            });
+        }
+    }
+
+    fn update_job(
+        mut commands: Commands,
+        mut removed_jobs: RemovedComponents<Job>,
+        mut asset_events: EventReader<AssetEvent<JobList>>,
+        job_list_handle: Res<JobListHandle>,
+        job_lists: Res<Assets<JobList>>,
+        selected_actor: Single<(Entity, Option<Ref<Job>>, Ref<SelectedActor>)>,
+        tabs: Query<(&TabContent, &InfoTab)>,
+    ) {
+        let (actor_entity, job, selected_actor) = selected_actor.into_inner();
+        let job_removed = removed_jobs.read().any(|entity| entity == actor_entity);
+        let list_changed = asset_events.read().count() > 0;
+        if !selected_actor.is_added()
+            && !job.as_ref().is_some_and(|job| job.is_changed())
+            && !job_removed
+            && !list_changed
+        {
+            return;
+        }
+
+        let (tab_content, _) = tabs
+            .iter()
+            .find(|(_, &tab)| tab == InfoTab::Job)
+            .expect("tab with job should be spawned on state enter");
+
+        debug!("updating job list");
+        commands
+            .entity(tab_content.0)
+            .despawn_descendants()
+            .with_children(|parent| {
+                let current = match &job {
+                    Some(job) => LocalizedText::new("hud.job.current")
+                        .with_arg("title", &job.title)
+                        .with_arg("income", job.income_per_day)
+                        .with_arg("start", job.hours.0)
+                        .with_arg("end", job.hours.1),
+                    None => LocalizedText::new("hud.job.unemployed"),
+                };
+                parent.spawn((LabelKind::Normal, current));
+
+                if let Some(job_list) = job_lists.get(&**job_list_handle) {
+                    for available in &job_list.jobs {
+                        let is_current =
+                            job.as_ref().is_some_and(|job| job.title == available.title);
+                        parent
+                            .spawn((
+                                JobButton(Some(available.title.clone())),
+                                ButtonKind::Normal,
+                                Toggled(is_current),
+                                Node {
+                                    width: Val::Percent(100.0),
+                                    ..Default::default()
+                                },
+                            ))
+                            .with_child(Text::new(format!(
+                                "{} (${})",
+                                available.title, available.income_per_day
+                            )))
+                            .observe(Self::change_job);
+                    }
+                }
+
+                if job.is_some() {
+                    parent
+                        .spawn((JobButton(None), ButtonKind::Normal))
+                        .with_child(LocalizedText::new("hud.job.quit"))
+                        .observe(Self::change_job);
+                }
+            });
+    }
+
+    fn change_job(
+        trigger: Trigger<Pointer<Click>>,
+        mut change_events: EventWriter<JobChange>,
+        buttons: Query<&JobButton>,
+        actor_entity: Single<Entity, With<SelectedActor>>,
+    ) {
+        let button = buttons.get(trigger.entity()).unwrap();
+        info!("changing job to `{:?}`", button.0);
+        change_events.send(JobChange {
+            actor_entity: *actor_entity,
+            title: button.0.clone(),
+        });
+    }
+
+    fn remember_tab(
+        mut last_tabs: ResMut<LastTabs>,
+        tabs: Query<(&InfoTab, &Toggled), Changed<Toggled>>,
+    ) {
+        for (&tab, toggled) in &tabs {
+            if toggled.0 {
+                debug!("remembering info tab `{tab:?}`");
+                last_tabs.info_tab = tab;
+            }
+        }
+    }
+
     fn cleanup_need_bars(
         trigger: Trigger<OnRemove, Need>,
         mut commands: Commands,
@@ -78,7 +331,12 @@ impl InfoNodePlugin {
     }
 }
 
-pub(super) fn setup(parent: &mut ChildBuilder, tab_commands: &mut Commands, theme: &Theme) {
+pub(super) fn setup(
+    parent: &mut ChildBuilder,
+    tab_commands: &mut Commands,
+    theme: &Theme,
+    last_tabs: &LastTabs,
+) {
     parent
         .spawn(Node {
             flex_direction: FlexDirection::ColumnReverse,
@@ -99,9 +357,35 @@ pub(super) fn setup(parent: &mut ChildBuilder, tab_commands: &mut Commands, them
                 ))
                 .id();
 
-            for (index, tab) in InfoTab::iter().enumerate() {
+            for tab in InfoTab::iter() {
                 let content_entity = match tab {
                     InfoTab::Skills => parent.spawn(Node::default()).id(),
+                    InfoTab::Job => parent
+                        .spawn((
+                            Node {
+                                flex_direction: FlexDirection::Column,
+                                width: Val::Px(300.0),
+                                row_gap: theme.gap.normal,
+                                padding: theme.padding.normal,
+                                ..Default::default()
+                            },
+                            theme.panel_background,
+                        ))
+                        .id(),
+                    InfoTab::Schedule => parent
+                        .spawn((
+                            Node {
+                                display: Display::Grid,
+                                column_gap: theme.gap.normal,
+                                row_gap: theme.gap.normal,
+                                padding: theme.padding.normal,
+                                align_items: AlignItems::Center,
+                                grid_template_columns: vec![GridTrack::auto(); 4],
+                                ..Default::default()
+                            },
+                            theme.panel_background,
+                        ))
+                        .id(),
                     InfoTab::Needs => parent
                         .spawn((
                             Node {
@@ -128,7 +412,7 @@ pub(super) fn setup(parent: &mut ChildBuilder, tab_commands: &mut Commands, them
                         tab,
                         ButtonKind::Symbol,
                         TabContent(content_entity),
-                        Toggled(index == 0),
+                        Toggled(tab == last_tabs.info_tab),
                     ))
                     .with_child(Text::new(tab.glyph()))
                     .set_parent(tabs_entity);
@@ -136,13 +420,40 @@ pub(super) fn setup(parent: &mut ChildBuilder, tab_commands: &mut Commands, them
         });
 }
 
+fn hour_input(hour: u8) -> NumberInput {
+    NumberInput {
+        value: hour.into(),
+        min: 0,
+        max: 23,
+        step: 1,
+    }
+}
+
 #[derive(Component)]
 struct BarNeed(Entity);
 
-#[derive(Component, EnumIter, Clone, Copy, PartialEq)]
-enum InfoTab {
+/// Job title that the button assigns or `None` to quit the current job.
+#[derive(Component)]
+struct JobButton(Option<String>);
+
+/// Index of the schedule entry that the button edits.
+#[derive(Component, Clone, Copy)]
+struct ScheduleRow(usize);
+
+/// Hour of the schedule entry at the index that the input edits.
+#[derive(Component, Clone, Copy)]
+enum ScheduleHour {
+    Start(usize),
+    End(usize),
+}
+
+#[derive(Component, EnumIter, Clone, Copy, Debug, Default, PartialEq)]
+pub(super) enum InfoTab {
+    #[default]
     Skills,
     Needs,
+    Schedule,
+    Job,
 }
 
 impl InfoTab {
@@ -150,6 +461,8 @@ impl InfoTab {
         match self {
             InfoTab::Skills => "💡",
             InfoTab::Needs => "📈",
+            InfoTab::Schedule => "📅",
+            InfoTab::Job => "💼",
         }
     }
 }
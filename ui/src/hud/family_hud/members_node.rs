@@ -7,6 +7,42 @@ use project_harmonia_widgets::{
 
 use crate::preview::Preview;
 
+pub(super) struct MembersNodePlugin;
+
+impl Plugin for MembersNodePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(Self::toggle_selected);
+    }
+}
+
+impl MembersNodePlugin {
+    fn select(
+        trigger: Trigger<Pointer<Click>>,
+        mut commands: Commands,
+        buttons: Query<&MemberButton>,
+        selected_entity: Single<Entity, With<SelectedActor>>,
+    ) {
+        let member_button = buttons.get(trigger.entity()).unwrap();
+        commands.entity(*selected_entity).remove::<SelectedActor>();
+        commands.entity(**member_button).insert(SelectedActor);
+    }
+
+    /// Keeps buttons in sync when the actor is selected without clicking on them.
+    ///
+    /// Also untoggles other buttons to avoid relying on exclusivity, which is applied only in [`PostUpdate`].
+    fn toggle_selected(
+        trigger: Trigger<OnAdd, SelectedActor>,
+        mut buttons: Query<(&MemberButton, &mut Toggled)>,
+    ) {
+        for (member_button, mut toggled) in &mut buttons {
+            let selected = **member_button == trigger.entity();
+            if **toggled != selected {
+                **toggled = selected;
+            }
+        }
+    }
+}
+
 pub(super) fn setup(
     parent: &mut ChildBuilder,
     theme: &Theme,
@@ -26,18 +62,72 @@ pub(super) fn setup(
         .with_children(|parent| {
             for &entity in members.iter() {
                 parent
-                    .spawn((
-                        ButtonKind::Image,
-                        ExclusiveButton,
-                        Toggled(entity == active_entity),
-                    ))
+                    .spawn((MemberButton(entity), Toggled(entity == active_entity)))
                     .with_child(Preview::Actor(entity))
-                    .observe(
-                        move |_trigger: Trigger<Pointer<Click>>, mut commands: Commands, selected_entity: Single<Entity, With<SelectedActor>>| {
-                            commands.entity(*selected_entity).remove::<SelectedActor>();
-                            commands.entity(entity).insert(SelectedActor);
-                        },
-                    );
+                    .observe(MembersNodePlugin::select);
             }
         });
 }
+
+/// Selects the stored actor on click.
+#[derive(Component, Deref)]
+#[require(ButtonKind(|| ButtonKind::Image), ExclusiveButton)]
+struct MemberButton(Entity);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cycling() {
+        let mut app = App::new();
+        app.add_plugins(MembersNodePlugin);
+
+        let mut members = FamilyMembers::default();
+        for _ in 0..3 {
+            members.push(app.world_mut().spawn_empty().id());
+        }
+        let mut selected_entity = members[0];
+        app.world_mut()
+            .entity_mut(selected_entity)
+            .insert(SelectedActor);
+
+        let button_entities: Vec<_> = members
+            .iter()
+            .map(|&entity| {
+                app.world_mut()
+                    .spawn((MemberButton(entity), Toggled(entity == selected_entity)))
+                    .id()
+            })
+            .collect();
+
+        for (offset, expected) in [
+            (1, [false, true, false]),
+            (1, [false, false, true]),
+            (1, [true, false, false]),
+            (-1, [false, false, true]),
+        ] {
+            let next_entity = members.cycle(selected_entity, offset).unwrap();
+            app.world_mut()
+                .entity_mut(selected_entity)
+                .remove::<SelectedActor>();
+            app.world_mut()
+                .entity_mut(next_entity)
+                .insert(SelectedActor);
+            selected_entity = next_entity;
+
+            assert_eq!(
+                toggled_buttons(app.world(), &button_entities),
+                expected,
+                "only the button of the selected member should be toggled"
+            );
+        }
+    }
+
+    fn toggled_buttons(world: &World, entities: &[Entity]) -> Vec<bool> {
+        entities
+            .iter()
+            .map(|&entity| world.get::<Toggled>(entity).unwrap().0)
+            .collect()
+    }
+}
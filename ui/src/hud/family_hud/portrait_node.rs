@@ -1,9 +1,10 @@
 use bevy::prelude::*;
 use project_harmonia_base::game_world::{
-    family::{Budget, SelectedFamily},
+    actor::SelectedActor,
+    family::{Budget, BudgetChange, BudgetCheat, BudgetPolicy, SelectedFamily},
     WorldState,
 };
-use project_harmonia_widgets::{label::LabelKind, theme::Theme};
+use project_harmonia_widgets::{button::ButtonKind, label::LabelKind, theme::Theme};
 
 pub(super) struct PortraitNodePlugin;
 
@@ -20,15 +21,49 @@ impl Plugin for PortraitNodePlugin {
 
 impl PortraitNodePlugin {
     fn update_budget(
-        current_budget: Single<&Budget, (With<SelectedFamily>, Changed<Budget>)>,
+        family: Single<(&Budget, &BudgetPolicy), With<SelectedFamily>>,
+        actor_budget: Single<&Budget, With<SelectedActor>>,
         mut budget_label: Single<&mut Text, With<BudgetLabel>>,
     ) {
-        debug!("changing budget to `{:?}`", **current_budget);
-        ***budget_label = current_budget.to_string();
+        let (&family_budget, &policy) = *family;
+        let text = budget_text(family_budget, **actor_budget, policy);
+        if budget_label.0 != text {
+            debug!("changing budget to '{text}'");
+            budget_label.0 = text;
+        }
+    }
+
+    fn add_budget(
+        _trigger: Trigger<Pointer<Click>>,
+        mut cheat_events: EventWriter<BudgetCheat>,
+        family_entity: Single<Entity, With<SelectedFamily>>,
+    ) {
+        info!("requesting budget cheat for `{}`", *family_entity);
+        cheat_events.send(BudgetCheat {
+            family_entity: *family_entity,
+            change: BudgetChange::Add(CHEAT_AMOUNT),
+        });
+    }
+}
+
+const CHEAT_AMOUNT: i64 = 10_000;
+
+/// Displays the budget that will be used for purchases.
+fn budget_text(family_budget: Budget, actor_budget: Budget, policy: BudgetPolicy) -> String {
+    match policy {
+        BudgetPolicy::Shared => format!("👪 {}", *family_budget),
+        BudgetPolicy::PerActor => format!("👤 {}", *actor_budget),
     }
 }
 
-pub(super) fn setup(parent: &mut ChildBuilder, theme: &Theme, budget: Budget) {
+pub(super) fn setup(
+    parent: &mut ChildBuilder,
+    theme: &Theme,
+    family_budget: Budget,
+    actor_budget: Budget,
+    policy: BudgetPolicy,
+    cheats: bool,
+) {
     parent
         .spawn((
             Node {
@@ -41,7 +76,16 @@ pub(super) fn setup(parent: &mut ChildBuilder, theme: &Theme, budget: Budget) {
             theme.panel_background,
         ))
         .with_children(|parent| {
-            parent.spawn((BudgetLabel, Text::new(budget.to_string())));
+            parent.spawn((
+                BudgetLabel,
+                Text::new(budget_text(family_budget, actor_budget, policy)),
+            ));
+            if cheats {
+                parent
+                    .spawn(ButtonKind::Symbol)
+                    .with_child(Text::new("➕"))
+                    .observe(PortraitNodePlugin::add_budget);
+            }
         });
 }
 
@@ -0,0 +1,134 @@
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::{pbr::NotShadowCaster, prelude::*};
+use project_harmonia_base::{
+    game_world::{
+        actor::{Actor, SelectedActor},
+        family::FamilyMode,
+    },
+    settings::Settings,
+};
+use project_harmonia_widgets::theme::Theme;
+
+/// Displays rings on the ground under the selected and hovered actors.
+pub(super) struct SelectionRingPlugin;
+
+impl Plugin for SelectionRingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(Self::hover)
+            .add_observer(Self::unhover)
+            .add_systems(OnEnter(FamilyMode::Life), Self::spawn)
+            .add_systems(
+                PostUpdate,
+                Self::update_transforms
+                    .before(TransformSystem::TransformPropagate)
+                    .run_if(in_state(FamilyMode::Life)),
+            );
+    }
+}
+
+/// Small offset to avoid Z-fighting with the ground.
+const GROUND_OFFSET: f32 = 0.01;
+
+impl SelectionRingPlugin {
+    fn spawn(
+        mut commands: Commands,
+        theme: Res<Theme>,
+        mut meshes: ResMut<Assets<Mesh>>,
+        mut materials: ResMut<Assets<StandardMaterial>>,
+    ) {
+        debug!("spawning selection rings");
+        let mesh = meshes.add(Annulus::new(0.45, 0.55));
+        for (ring, color) in [
+            (SelectionRing::Selected, theme.selection_ring.selected_color),
+            (
+                SelectionRing::Hovered(None),
+                theme.selection_ring.hovered_color,
+            ),
+        ] {
+            commands.spawn((
+                ring,
+                Mesh3d(mesh.clone()),
+                MeshMaterial3d(materials.add(StandardMaterial {
+                    base_color: color,
+                    alpha_mode: AlphaMode::Blend,
+                    unlit: true,
+                    ..Default::default()
+                })),
+            ));
+        }
+    }
+
+    fn hover(
+        trigger: Trigger<Pointer<Over>>,
+        actors: Query<(), With<Actor>>,
+        mut rings: Query<&mut SelectionRing>,
+    ) {
+        if actors.get(trigger.entity()).is_err() {
+            return;
+        }
+
+        for mut ring in &mut rings {
+            if let SelectionRing::Hovered(hovered_entity) = &mut *ring {
+                trace!("showing hover ring for `{}`", trigger.entity());
+                *hovered_entity = Some(trigger.entity());
+            }
+        }
+    }
+
+    fn unhover(trigger: Trigger<Pointer<Out>>, mut rings: Query<&mut SelectionRing>) {
+        for mut ring in &mut rings {
+            if let SelectionRing::Hovered(hovered_entity) = &mut *ring {
+                if *hovered_entity == Some(trigger.entity()) {
+                    trace!("hiding hover ring for `{}`", trigger.entity());
+                    *hovered_entity = None;
+                }
+            }
+        }
+    }
+
+    fn update_transforms(
+        settings: Res<Settings>,
+        selected_entity: Option<Single<Entity, With<SelectedActor>>>,
+        actors: Query<&GlobalTransform, With<Actor>>,
+        mut rings: Query<(&SelectionRing, &mut Transform, &mut Visibility)>,
+    ) {
+        let selected_entity = selected_entity.map(|entity| *entity);
+        for (&ring, mut transform, mut visibility) in &mut rings {
+            let target_entity = match ring {
+                SelectionRing::Selected => selected_entity,
+                // Don't draw both rings under the same actor.
+                SelectionRing::Hovered(hovered_entity) => {
+                    hovered_entity.filter(|&entity| Some(entity) != selected_entity)
+                }
+            };
+
+            let actor_transform = target_entity
+                .filter(|_| settings.video.selection_ring)
+                .and_then(|entity| actors.get(entity).ok());
+            let Some(actor_transform) = actor_transform else {
+                visibility.set_if_neq(Visibility::Hidden);
+                continue;
+            };
+
+            visibility.set_if_neq(Visibility::Inherited);
+            let mut translation = actor_transform.translation();
+            translation.y += GROUND_OFFSET;
+            *transform = Transform::from_translation(translation)
+                .with_rotation(Quat::from_rotation_x(-FRAC_PI_2));
+        }
+    }
+}
+
+#[derive(Component, Clone, Copy)]
+#[require(
+    Name(|| Name::new("Selection ring")),
+    StateScoped::<FamilyMode>(|| StateScoped(FamilyMode::Life)),
+    Visibility(|| Visibility::Hidden),
+    NotShadowCaster,
+    PickingBehavior(|| PickingBehavior::IGNORE)
+)]
+enum SelectionRing {
+    Selected,
+    Hovered(Option<Entity>),
+}
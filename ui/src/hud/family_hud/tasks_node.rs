@@ -1,10 +1,12 @@
 use bevy::prelude::*;
 
 use project_harmonia_base::game_world::actor::{
-    task::{ActiveTask, Task, TaskCancel},
+    task::{ActiveTask, QueueIndex, Task, TaskCancel, TaskProgress, TaskReorder, TaskState},
     SelectedActor,
 };
-use project_harmonia_widgets::{button::ButtonKind, theme::Theme};
+use project_harmonia_widgets::{
+    button::ButtonKind, label::LabelKind, popup::Popup, progress_bar::ProgressBar, theme::Theme,
+};
 
 pub(super) struct TasksNodePlugin;
 
@@ -13,7 +15,16 @@ impl Plugin for TasksNodePlugin {
         app.add_observer(Self::change_actor.never_param_warn())
             .add_observer(Self::add_task.never_param_warn())
             .add_observer(Self::activate_task.never_param_warn())
-            .add_observer(Self::cleanup);
+            .add_observer(Self::cleanup)
+            .add_systems(
+                Update,
+                (
+                    Self::update_progress,
+                    Self::update_blocked,
+                    Self::show_blocked_popup,
+                    Self::sort_queued.never_param_warn(),
+                ),
+            );
     }
 }
 
@@ -38,7 +49,7 @@ impl TasksNodePlugin {
         commands
             .entity(*queued_node_entity)
             .with_children(|parent| {
-                spawn_button(parent, trigger.entity());
+                spawn_button(parent, trigger.entity(), false);
             });
     }
 
@@ -58,7 +69,86 @@ impl TasksNodePlugin {
             );
             commands
                 .entity(button_entity)
-                .set_parent(*active_node_entity);
+                .set_parent(*active_node_entity)
+                .with_child(TaskProgressBar);
+        }
+    }
+
+    fn update_progress(
+        buttons: Query<(&TaskButton, &Children)>,
+        tasks: Query<&TaskProgress>,
+        mut progress_bars: Query<&mut ProgressBar, With<TaskProgressBar>>,
+    ) {
+        for (task_button, children) in &buttons {
+            let Ok(&progress) = tasks.get(task_button.task_entity) else {
+                continue;
+            };
+
+            let Some(mut progress_bar) = progress_bars.iter_many_mut(children).fetch_next() else {
+                continue;
+            };
+
+            let percent = *progress * 100.0;
+            if progress_bar.0 != percent {
+                trace!(
+                    "updating progress to {} for `{}`",
+                    *progress,
+                    task_button.task_entity
+                );
+                progress_bar.0 = percent;
+            }
+        }
+    }
+
+    fn update_blocked(
+        buttons: Query<(&TaskButton, &Children)>,
+        tasks: Query<&TaskState>,
+        mut images: Query<&mut ImageNode>,
+        mut glyphs: Query<&mut Visibility, With<BlockedGlyph>>,
+    ) {
+        for (task_button, children) in &buttons {
+            let Ok(state) = tasks.get(task_button.task_entity) else {
+                continue;
+            };
+
+            let blocked = matches!(state, TaskState::Blocked { .. });
+            let (color, visibility) = if blocked {
+                (BLOCKED_TINT, Visibility::Inherited)
+            } else {
+                (Color::WHITE, Visibility::Hidden)
+            };
+
+            if let Some(mut image) = images.iter_many_mut(children).fetch_next() {
+                if image.color != color {
+                    image.color = color;
+                }
+            }
+            if let Some(mut glyph_visibility) = glyphs.iter_many_mut(children).fetch_next() {
+                glyph_visibility.set_if_neq(visibility);
+            }
+        }
+    }
+
+    fn show_blocked_popup(
+        mut commands: Commands,
+        root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+        buttons: Query<(Entity, &Interaction, &TaskButton), Changed<Interaction>>,
+        tasks: Query<&TaskState>,
+    ) {
+        for (button_entity, &interaction, task_button) in &buttons {
+            if interaction != Interaction::Hovered {
+                continue;
+            }
+            let Ok(TaskState::Blocked { reason }) = tasks.get(task_button.task_entity) else {
+                continue;
+            };
+
+            debug!("showing blocked popup for `{}`", task_button.task_entity);
+            commands.entity(*root_entity).with_children(|parent| {
+                parent
+                    .spawn(Popup { button_entity })
+                    .with_child((LabelKind::Normal, Text::new(reason.clone())));
+            });
         }
     }
 
@@ -83,7 +173,7 @@ impl TasksNodePlugin {
             };
 
             commands.entity(node_entity).with_children(|parent| {
-                spawn_button(parent, task_entity);
+                spawn_button(parent, task_entity, active);
             });
         }
     }
@@ -97,6 +187,58 @@ impl TasksNodePlugin {
         cancel_events.send(TaskCancel(task_button.task_entity));
     }
 
+    /// Moves the dragged queued task to the position of the task it was dropped on.
+    fn reorder(
+        trigger: Trigger<Pointer<DragDrop>>,
+        mut reorder_events: EventWriter<TaskReorder>,
+        queued_children: Single<&Children, With<QueuedTasksNode>>,
+        buttons: Query<&TaskButton>,
+    ) {
+        let dropped_entity = trigger.event().dropped;
+        if !queued_children.contains(&dropped_entity) {
+            return;
+        }
+        let Some(index) = queued_children
+            .iter()
+            .position(|&entity| entity == trigger.entity())
+        else {
+            return;
+        };
+
+        let task_button = buttons.get(dropped_entity).unwrap();
+        debug!("moving `{}` to {index}", task_button.task_entity);
+        reorder_events.send(TaskReorder {
+            task_entity: task_button.task_entity,
+            index,
+        });
+    }
+
+    /// Keeps queued buttons in the order of [`QueueIndex`].
+    fn sort_queued(
+        mut queued_children: Single<&mut Children, With<QueuedTasksNode>>,
+        buttons: Query<&TaskButton>,
+        tasks: Query<&QueueIndex>,
+    ) {
+        let index = |entity: &Entity| {
+            buttons
+                .get(*entity)
+                .ok()
+                .and_then(|task_button| tasks.get(task_button.task_entity).ok())
+                // Tasks that were just queued don't have an index yet.
+                .map_or(u32::MAX, |index| **index)
+        };
+
+        if queued_children
+            .windows(2)
+            .all(|pair| index(&pair[0]) <= index(&pair[1]))
+        {
+            return;
+        }
+
+        trace!("sorting queued task buttons");
+        queued_children.sort_by_key(index);
+    }
+
     fn cleanup(
         trigger: Trigger<OnRemove, Task>,
         mut commands: Commands,
@@ -139,7 +281,7 @@ pub(super) fn setup(
                 .with_children(|parent| {
                     for (task_entity, active) in tasks.iter_many(actor_children) {
                         if !active {
-                            spawn_button(parent, task_entity);
+                            spawn_button(parent, task_entity, false);
                         }
                     }
                 });
@@ -180,18 +322,23 @@ pub(super) fn setup(
                 .with_children(|parent| {
                     for (task_entity, active) in tasks.iter_many(actor_children) {
                         if active {
-                            spawn_button(parent, task_entity);
+                            spawn_button(parent, task_entity, true);
                         }
                     }
                 });
         });
 }
 
-fn spawn_button(parent: &mut ChildBuilder, task_entity: Entity) {
-    parent
-        .spawn(TaskButton { task_entity })
+fn spawn_button(parent: &mut ChildBuilder, task_entity: Entity, active: bool) {
+    let mut button = parent.spawn(TaskButton { task_entity });
+    button
         .with_child(ImageNode::default())
-        .observe(TasksNodePlugin::cancel);
+        .with_child(BlockedGlyph)
+        .observe(TasksNodePlugin::cancel)
+        .observe(TasksNodePlugin::reorder);
+    if active {
+        button.with_child(TaskProgressBar);
+    }
 }
 
 #[derive(Component)]
@@ -207,3 +354,41 @@ struct QueuedTasksNode;
 struct TaskButton {
     task_entity: Entity,
 }
+
+const BLOCKED_TINT: Color = Color::srgb(1.0, 0.4, 0.4);
+
+/// Symbol at the corner of a queued task button that can't be started.
+#[derive(Component)]
+#[require(
+    Name(|| Name::new("Blocked glyph")),
+    LabelKind(|| LabelKind::Symbol),
+    Text(|| Text::new("⛔")),
+    Visibility(|| Visibility::Hidden),
+    PickingBehavior(|| PickingBehavior::IGNORE),
+    Node(|| Node {
+        position_type: PositionType::Absolute,
+        top: Val::Px(0.0),
+        right: Val::Px(0.0),
+        ..Default::default()
+    })
+)]
+struct BlockedGlyph;
+
+/// Overlay at the bottom of an active task button.
+///
+/// Starts empty and reflects [`TaskProgress`] of the task.
+#[derive(Component)]
+#[require(
+    Name(|| Name::new("Task progress bar")),
+    ProgressBar(|| ProgressBar(0.0)),
+    PickingBehavior(|| PickingBehavior::IGNORE),
+    Node(|| Node {
+        position_type: PositionType::Absolute,
+        left: Val::Px(0.0),
+        right: Val::Px(0.0),
+        bottom: Val::Px(0.0),
+        height: Val::Px(4.0),
+        ..Default::default()
+    })
+)]
+struct TaskProgressBar;
@@ -0,0 +1,101 @@
+use bevy::prelude::*;
+use project_harmonia_base::game_world::minimap::{
+    MinimapCamera, MinimapClick, MinimapImage, MinimapView,
+};
+
+/// Shows the minimap while its camera exists.
+pub(super) struct MinimapNodePlugin;
+
+impl Plugin for MinimapNodePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(Self::setup)
+            .add_observer(Self::cleanup)
+            .add_systems(Update, Self::update_view.never_param_warn());
+    }
+}
+
+/// Width and height of the minimap on the screen.
+const MINIMAP_SIZE: f32 = 200.0;
+
+impl MinimapNodePlugin {
+    fn setup(
+        _trigger: Trigger<OnAdd, MinimapCamera>,
+        mut commands: Commands,
+        minimap_image: Res<MinimapImage>,
+        root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+    ) {
+        debug!("showing minimap");
+        commands.entity(*root_entity).with_children(|parent| {
+            parent
+                .spawn((
+                    MinimapNode,
+                    ImageNode::new(minimap_image.0.clone()),
+                    Node {
+                        position_type: PositionType::Absolute,
+                        // Below mode tabs.
+                        top: Val::Px(50.0),
+                        right: Val::Px(0.0),
+                        width: Val::Px(MINIMAP_SIZE),
+                        height: Val::Px(MINIMAP_SIZE),
+                        ..Default::default()
+                    },
+                ))
+                .with_child(ViewFrame)
+                .observe(Self::recenter);
+        });
+    }
+
+    fn cleanup(
+        _trigger: Trigger<OnRemove, MinimapCamera>,
+        mut commands: Commands,
+        nodes: Query<Entity, With<MinimapNode>>,
+    ) {
+        for entity in &nodes {
+            debug!("hiding minimap");
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+
+    fn update_view(
+        minimap_view: Single<&MinimapView, Changed<MinimapView>>,
+        mut frame_node: Single<&mut Node, With<ViewFrame>>,
+    ) {
+        frame_node.left = Val::Percent(minimap_view.min.x * 100.0);
+        frame_node.top = Val::Percent(minimap_view.min.y * 100.0);
+        frame_node.width = Val::Percent(minimap_view.width() * 100.0);
+        frame_node.height = Val::Percent(minimap_view.height() * 100.0);
+    }
+
+    fn recenter(
+        trigger: Trigger<Pointer<Click>>,
+        mut commands: Commands,
+        window: Single<&Window>,
+        nodes: Query<(&ComputedNode, &GlobalTransform)>,
+    ) {
+        let Some(cursor_pos) = window.physical_cursor_position() else {
+            return;
+        };
+
+        let (node, transform) = nodes.get(trigger.entity()).unwrap();
+        let rect = Rect::from_center_size(transform.translation().truncate(), node.size());
+        commands.trigger(MinimapClick((cursor_pos - rect.min) / rect.size()));
+    }
+}
+
+#[derive(Component)]
+#[require(Name(|| Name::new("Minimap")))]
+struct MinimapNode;
+
+/// Outlines the area visible from the player camera.
+#[derive(Component)]
+#[require(
+    Name(|| Name::new("View frame")),
+    Node(|| Node {
+        position_type: PositionType::Absolute,
+        border: UiRect::all(Val::Px(2.0)),
+        ..Default::default()
+    }),
+    BorderColor(|| BorderColor(Color::WHITE)),
+    PickingBehavior(|| PickingBehavior::IGNORE)
+)]
+struct ViewFrame;
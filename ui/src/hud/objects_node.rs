@@ -1,18 +1,26 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashMap};
+use bevy_enhanced_input::prelude::*;
+use bevy_simple_text_input::{TextInputInactive, TextInputSubmitEvent, TextInputValue};
 
 use crate::preview::Preview;
 use project_harmonia_base::{
-    asset::manifest::object_manifest::{ObjectCategory, ObjectManifest},
+    asset::manifest::{
+        category_manifest::{CategoryGroup, ObjectCategories},
+        object_manifest::{CatalogIndex, ObjectCategory, ObjectManifest},
+    },
+    core::localization::LocalizedText,
     game_world::{
         city::{ActiveCity, CityMode},
         family::FamilyMode,
-        object::placing_object::PlacingObject,
+        object::placing_object::{PlacingObject, RecentObjects},
     },
+    settings::Settings,
 };
 use project_harmonia_widgets::{
     button::{ButtonKind, ExclusiveButton, TabContent, Toggled},
     label::LabelKind,
     popup::Popup,
+    text_edit::TextEdit,
     theme::Theme,
 };
 
@@ -20,11 +28,38 @@ pub(super) struct ObjectsNodePlugin;
 
 impl Plugin for ObjectsNodePlugin {
     fn build(&self, app: &mut App) {
-        app.add_observer(Self::untoggle).add_systems(
-            Update,
-            (Self::show_popup, Self::reload_buttons)
-                .run_if(in_state(CityMode::Objects).or(in_state(FamilyMode::Building))),
-        );
+        app.add_observer(Self::untoggle)
+            .add_observer(Self::restore_input)
+            .add_systems(OnExit(CityMode::Objects), Self::unfocus_search)
+            .add_systems(OnExit(FamilyMode::Building), Self::unfocus_search)
+            .add_systems(
+                Update,
+                (
+                    Self::unfocus_search.run_if(
+                        on_event::<TextInputSubmitEvent>.or(any_match_filter::<Added<SearchEdit>>),
+                    ),
+                    Self::block_input,
+                )
+                    .chain(),
+            )
+            .add_systems(
+                Update,
+                (
+                    Self::show_popup,
+                    Self::reload_buttons,
+                    Self::update_recent.run_if(
+                        resource_changed::<RecentObjects>
+                            .or(resource_changed::<Settings>)
+                            .or(any_match_filter::<Added<RecentPalette>>),
+                    ),
+                    Self::filter_buttons.never_param_warn().run_if(
+                        any_match_filter::<(Changed<TextInputValue>, With<SearchEdit>)>
+                            .or(any_match_filter::<Added<ObjectButton>>),
+                    ),
+                )
+                    .chain()
+                    .run_if(in_state(CityMode::Objects).or(in_state(FamilyMode::Building))),
+            );
     }
 }
 
@@ -35,11 +70,17 @@ impl ObjectsNodePlugin {
         city_entity: Single<Entity, With<ActiveCity>>,
         placing_entity: Option<Single<Entity, With<PlacingObject>>>,
         buttons: Query<&ObjectButton>,
+        mut searches: Query<&mut TextInputInactive, With<SearchEdit>>,
     ) {
         let id = **buttons.get(trigger.entity()).unwrap();
 
         debug!("starting spawning object `{id:?}`");
 
+        // Return keyboard to placing controls.
+        for mut inactive in &mut searches {
+            inactive.set_if_neq(TextInputInactive(true));
+        }
+
         if let Some(placing_entity) = placing_entity {
             commands.entity(*placing_entity).despawn_recursive();
         }
@@ -90,21 +131,21 @@ impl ObjectsNodePlugin {
         mut commands: Commands,
         mut change_events: EventReader<AssetEvent<ObjectManifest>>,
         manifests: Res<Assets<ObjectManifest>>,
+        mut recent_objects: ResMut<RecentObjects>,
         buttons: Query<(Entity, &ObjectButton)>,
-        categories: Query<(&ObjectCategory, &TabContent)>,
+        grids: Query<(Entity, &ObjectGrid)>,
     ) {
         for &event in change_events.read() {
             let AssetEvent::Modified { id } = event else {
                 continue;
             };
 
-            debug!("recreating button for asset {id}");
+            debug!("recreating buttons for asset {id}");
 
-            // Fully remove the button because category may change.
+            // Fully remove the buttons because category may change.
             for (entity, &button) in &buttons {
                 if id == *button {
                     commands.entity(entity).despawn_recursive();
-                    break;
                 }
             }
 
@@ -112,22 +153,109 @@ impl ObjectsNodePlugin {
                 .get(id)
                 .expect("manifest should always come from file");
 
-            let tab_content = categories.iter().find_map(|(&category, &tab_content)| {
-                if category == manifest.category {
-                    Some(tab_content)
-                } else {
-                    None
+            if recent_objects.contains(&id) {
+                // Rebuild the palette to restore the removed button.
+                recent_objects.set_changed();
+            }
+
+            for (grid_entity, grid) in &grids {
+                if grid.contains(manifest) {
+                    commands.entity(grid_entity).with_children(|parent| {
+                        parent
+                            .spawn(ObjectButton(id))
+                            .with_child(Preview::Object(id))
+                            .observe(Self::start_placing);
+                    });
                 }
-            });
+            }
+        }
+    }
 
-            if let Some(tab_content) = tab_content {
-                commands.entity(tab_content.0).with_children(|parent| {
-                    parent
-                        .spawn(ObjectButton(id))
-                        .with_child(Preview::Object(id))
-                        .observe(Self::start_placing);
+    fn update_recent(
+        mut commands: Commands,
+        settings: Res<Settings>,
+        manifests: Res<Assets<ObjectManifest>>,
+        categories: Res<ObjectCategories>,
+        recent_objects: Res<RecentObjects>,
+        palettes: Query<(Entity, &RecentPalette)>,
+    ) {
+        for (palette_entity, &palette) in &palettes {
+            debug!("updating recent objects for `{palette_entity}`");
+            commands
+                .entity(palette_entity)
+                .despawn_descendants()
+                .with_children(|parent| {
+                    let ids = recent_objects
+                        .iter()
+                        .filter(|&&id| {
+                            manifests
+                                .get(id)
+                                .and_then(|manifest| categories.get(&manifest.category))
+                                .is_some_and(|info| info.groups.contains(&palette.0))
+                        })
+                        .take(settings.interface.recent_objects as usize);
+                    for &id in ids {
+                        parent
+                            .spawn(ObjectButton(id))
+                            .with_child(Preview::Object(id))
+                            .observe(Self::start_placing);
+                    }
                 });
+        }
+    }
+
+    /// Deactivates the search on spawn, submission or mode exit.
+    ///
+    /// Edits are activated by default when they are the only ones,
+    /// but in HUD it would prevent keyboard actions from triggering.
+    fn unfocus_search(mut searches: Query<&mut TextInputInactive, With<SearchEdit>>) {
+        for mut inactive in &mut searches {
+            inactive.set_if_neq(TextInputInactive(true));
+        }
+    }
+
+    /// Prevents keyboard actions from triggering while typing in the search.
+    fn block_input(
+        mut sources: ResMut<ActionSources>,
+        searches: Query<&TextInputInactive, (With<SearchEdit>, Changed<TextInputInactive>)>,
+    ) {
+        for inactive in &searches {
+            debug!("setting keyboard actions to `{}`", inactive.0);
+            sources.keyboard = inactive.0;
+        }
+    }
+
+    fn restore_input(
+        trigger: Trigger<OnRemove, SearchEdit>,
+        mut sources: ResMut<ActionSources>,
+        searches: Query<&TextInputInactive>,
+    ) {
+        let inactive = searches.get(trigger.entity()).unwrap();
+        if !inactive.0 {
+            debug!("restoring keyboard actions");
+            sources.keyboard = true;
+        }
+    }
+
+    /// Hides grid buttons that don't match the search query.
+    fn filter_buttons(
+        catalog_index: Res<CatalogIndex>,
+        search_text: Single<&TextInputValue, With<SearchEdit>>,
+        palettes: Query<(), With<RecentPalette>>,
+        mut buttons: Query<(&ObjectButton, &Parent, &mut Node)>,
+    ) {
+        let query = search_text.0.to_lowercase();
+        debug!("filtering objects by `{query}`");
+        for (&button, parent, mut node) in &mut buttons {
+            if palettes.get(**parent).is_ok() {
+                continue;
             }
+
+            node.display = if catalog_index.matches(*button, &query) {
+                Display::Flex
+            } else {
+                Display::None
+            };
         }
     }
 
@@ -153,8 +281,21 @@ pub(super) fn setup(
     tab_commands: &mut Commands,
     theme: &Theme,
     manifests: &Assets<ObjectManifest>,
-    categories: &[ObjectCategory],
+    categories: &ObjectCategories,
+    group: CategoryGroup,
+    last_category: Option<&ObjectCategory>,
+    last_subcategories: &HashMap<ObjectCategory, String>,
 ) {
+    let categories: Vec<_> = categories.group(group).collect();
+
+    parent.spawn(SearchEdit);
+
+    // Fallback to the first category if the last one is not available.
+    let selected_index = categories
+        .iter()
+        .position(|info| Some(&info.id) == last_category)
+        .unwrap_or_default();
+
     let tabs_entity = parent
         .spawn(Node {
             flex_direction: FlexDirection::Column,
@@ -162,39 +303,150 @@ pub(super) fn setup(
         })
         .id();
 
-    for (index, &category) in categories.iter().enumerate() {
+    for (index, info) in categories.into_iter().enumerate() {
         let content_entity = parent
             .spawn(Node {
-                display: Display::Grid,
-                column_gap: theme.gap.normal,
+                flex_direction: FlexDirection::Column,
                 row_gap: theme.gap.normal,
-                padding: theme.padding.normal,
-                grid_template_columns: vec![GridTrack::auto(); 8],
                 ..Default::default()
             })
-            .with_children(|parent| {
-                for (id, _) in manifests
-                    .iter()
-                    .filter(|(_, manifest)| manifest.category == category)
-                {
-                    parent
-                        .spawn(ObjectButton(id))
-                        .with_child(Preview::Object(id))
-                        .observe(ObjectsNodePlugin::start_placing);
-                }
+            .id();
+
+        let last_subcategory = last_subcategories
+            .get(&info.id)
+            .filter(|subcategory| info.has_subcategory(subcategory));
+
+        // "All" goes first and shows every object from the category.
+        let subcategories = [None]
+            .into_iter()
+            .chain(info.subcategories.iter().map(Some));
+
+        let subtabs_entity = tab_commands
+            .spawn(Node {
+                column_gap: theme.gap.normal,
+                padding: theme.padding.normal,
+                // Hide the row when there is only "All".
+                display: if info.subcategories.is_empty() {
+                    Display::None
+                } else {
+                    Display::Flex
+                },
+                ..Default::default()
             })
+            .set_parent(content_entity)
             .id();
 
+        for subcategory in subcategories {
+            let subcategory_id = subcategory.map(|subcategory| subcategory.id.clone());
+            let grid = ObjectGrid {
+                category: info.id.clone(),
+                subcategory: subcategory_id.clone(),
+            };
+            let grid_entity = tab_commands
+                .spawn((
+                    Node {
+                        display: Display::Grid,
+                        column_gap: theme.gap.normal,
+                        row_gap: theme.gap.normal,
+                        padding: theme.padding.normal,
+                        grid_template_columns: vec![GridTrack::auto(); 8],
+                        ..Default::default()
+                    },
+                    grid.clone(),
+                ))
+                .with_children(|parent| {
+                    for (id, _) in manifests
+                        .iter()
+                        .filter(|(_, manifest)| grid.contains(manifest))
+                    {
+                        parent
+                            .spawn(ObjectButton(id))
+                            .with_child(Preview::Object(id))
+                            .observe(ObjectsNodePlugin::start_placing);
+                    }
+                })
+                .set_parent(content_entity)
+                .id();
+
+            let mut tab = tab_commands.spawn((
+                SubcategoryTab(grid),
+                ButtonKind::Normal,
+                TabContent(grid_entity),
+                Toggled(subcategory_id.as_ref() == last_subcategory),
+            ));
+            match subcategory {
+                Some(subcategory) => tab.with_child(Text::new(subcategory.name.clone())),
+                None => tab.with_child(LocalizedText::new("hud.objects.all")),
+            };
+            tab.set_parent(subtabs_entity);
+        }
+
         tab_commands
             .spawn((
-                category,
+                info.id.clone(),
                 ButtonKind::Symbol,
                 TabContent(content_entity),
-                Toggled(index == 0),
+                Toggled(index == selected_index),
             ))
-            .with_child(Text::new(category.glyph()))
+            .with_child(Text::new(info.glyph.clone()))
             .set_parent(tabs_entity);
     }
+
+    parent.spawn((
+        RecentPalette(group),
+        Node {
+            flex_direction: FlexDirection::Column,
+            row_gap: theme.gap.normal,
+            padding: theme.padding.normal,
+            ..Default::default()
+        },
+    ));
+}
+
+/// Filters objects in grids by name, category or subcategory.
+#[derive(Component)]
+#[require(
+    Name(|| Name::new("Search edit")),
+    TextEdit,
+    Node(|| Node {
+        align_self: AlignSelf::FlexStart,
+        ..Default::default()
+    })
+)]
+struct SearchEdit;
+
+/// Quick-select buttons for [`RecentObjects`] available in the group.
+#[derive(Component, Clone, Copy)]
+struct RecentPalette(CategoryGroup);
+
+/// Node with object buttons from a category.
+#[derive(Component, Clone)]
+struct ObjectGrid {
+    category: ObjectCategory,
+
+    /// Subcategory ID or [`None`] to include all objects from the category.
+    subcategory: Option<String>,
+}
+
+impl ObjectGrid {
+    fn contains(&self, manifest: &ObjectManifest) -> bool {
+        manifest.category == self.category
+            && (self.subcategory.is_none() || self.subcategory == manifest.subcategory)
+    }
+}
+
+/// Tab that shows [`ObjectGrid`] for a subcategory.
+#[derive(Component)]
+pub(super) struct SubcategoryTab(ObjectGrid);
+
+impl SubcategoryTab {
+    pub(super) fn category(&self) -> &ObjectCategory {
+        &self.0.category
+    }
+
+    pub(super) fn subcategory(&self) -> Option<&String> {
+        self.0.subcategory.as_ref()
+    }
 }
 
 #[derive(Component, Clone, Copy, Deref)]
@@ -4,6 +4,7 @@ use bevy_enhanced_input::prelude::*;
 use project_harmonia_base::game_world::{
     actor::task::{AvailableTasks, TaskSelect},
     family::FamilyMode,
+    spectator::Spectator,
 };
 use project_harmonia_widgets::{button::ButtonKind, label::LabelKind, theme::Theme};
 
@@ -23,6 +24,7 @@ impl TaskMenuPlugin {
         trigger: Trigger<OnAdd, Parent>,
         mut commands: Commands,
         theme: Res<Theme>,
+        spectator: Option<Res<Spectator>>,
         menu_entity: Option<Single<Entity, With<TaskMenu>>>,
         window: Single<&Window>,
         root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
@@ -34,6 +36,11 @@ impl TaskMenuPlugin {
             return;
         };
 
+        if spectator.is_some() {
+            debug!("ignoring available tasks for spectator");
+            return;
+        }
+
         if let Some(menu_entity) = menu_entity {
             info!("closing previous task menu");
             commands.entity(*menu_entity).despawn_recursive();
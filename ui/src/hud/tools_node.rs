@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 use bevy_enhanced_input::prelude::*;
 
-use project_harmonia_base::game_world::commands_history::CommandsHistory;
+use project_harmonia_base::{game_world::commands_history::CommandsHistory, settings::Settings};
 use project_harmonia_widgets::{button::ButtonKind, theme::Theme};
 
 pub(super) struct ToolsNodePlugin;
@@ -61,16 +61,22 @@ pub(super) fn setup(parent: &mut ChildBuilder, theme: &Theme) {
 struct ToolsNode;
 
 impl InputContext for ToolsNode {
-    fn context_instance(_world: &World, _entity: Entity) -> ContextInstance {
+    fn context_instance(world: &World, _entity: Entity) -> ContextInstance {
         let mut ctx = ContextInstance::default();
+        let settings = world.resource::<Settings>();
 
-        ctx.bind::<Redo>()
-            .to(KeyCode::KeyZ.with_mod_keys(ModKeys::CONTROL | ModKeys::SHIFT))
-            .to(GamepadButton::RightTrigger)
+        let redo = ctx.bind::<Redo>();
+        for &key in &settings.keyboard.redo {
+            redo.to(key.with_mod_keys(ModKeys::CONTROL | ModKeys::SHIFT));
+        }
+        redo.to(GamepadButton::RightTrigger)
             .with_conditions(Pulse::new(0.3));
-        ctx.bind::<Undo>()
-            .to(KeyCode::KeyZ.with_mod_keys(ModKeys::CONTROL))
-            .to(GamepadButton::LeftTrigger)
+
+        let undo = ctx.bind::<Undo>();
+        for &key in &settings.keyboard.undo {
+            undo.to(key.with_mod_keys(ModKeys::CONTROL));
+        }
+        undo.to(GamepadButton::LeftTrigger)
             .with_conditions(Pulse::new(0.3));
 
         ctx
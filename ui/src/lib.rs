@@ -1,29 +1,51 @@
+#[cfg(feature = "dev")]
+mod actor_inspector;
 mod camera_2d;
+#[cfg(feature = "dev")]
+mod console;
+mod crash_dialog;
 mod error_dialog;
 mod hud;
 mod menu;
-mod preview;
+pub mod preview;
 mod root;
+mod toast;
+mod transition;
 
 use bevy::{app::PluginGroupBuilder, prelude::*};
 
+#[cfg(feature = "dev")]
+use actor_inspector::ActorInspectorPlugin;
 use camera_2d::Camera2dPlugin;
+#[cfg(feature = "dev")]
+use console::ConsolePlugin;
+use crash_dialog::CrashDialogPlugin;
 use error_dialog::ErrorDialogPlugin;
 use hud::HudPlugin;
 use menu::MenuPlugin;
 use preview::PreviewPlugin;
 use root::RootPlugin;
+use toast::ToastPlugin;
+use transition::TransitionPlugin;
 
 pub struct UiPlugins;
 
 impl PluginGroup for UiPlugins {
     fn build(self) -> PluginGroupBuilder {
-        PluginGroupBuilder::start::<Self>()
+        let group = PluginGroupBuilder::start::<Self>()
             .add(Camera2dPlugin)
+            .add(CrashDialogPlugin)
             .add(MenuPlugin)
             .add(ErrorDialogPlugin)
             .add(HudPlugin)
             .add(PreviewPlugin)
             .add(RootPlugin)
+            .add(ToastPlugin)
+            .add(TransitionPlugin);
+
+        #[cfg(feature = "dev")]
+        let group = group.add(ConsolePlugin).add(ActorInspectorPlugin);
+
+        group
     }
 }
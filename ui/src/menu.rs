@@ -1,17 +1,23 @@
+#[cfg(feature = "networking")]
 mod connection_dialog;
+mod credits_menu;
 mod editor_menu;
 mod ingame_menu;
 mod main_menu;
+mod players_menu;
 mod settings_menu;
 mod world_browser;
 mod world_menu;
 
 use bevy::prelude::*;
 
+#[cfg(feature = "networking")]
 use connection_dialog::ConnectionDialogPlugin;
+use credits_menu::CreditsMenuPlugin;
 use editor_menu::EditorMenuPlugin;
 use ingame_menu::InGameMenuPlugin;
 use main_menu::MainMenuPlugin;
+use players_menu::PlayersMenuPlugin;
 use project_harmonia_base::core::GameState;
 use settings_menu::SettingsMenuPlugin;
 use world_browser::WorldBrowserPlugin;
@@ -24,14 +30,18 @@ impl Plugin for MenuPlugin {
         app.add_sub_state::<MenuState>()
             .enable_state_scoped_entities::<MenuState>()
             .add_plugins((
-                ConnectionDialogPlugin,
+                CreditsMenuPlugin,
                 EditorMenuPlugin,
                 InGameMenuPlugin,
                 MainMenuPlugin,
+                PlayersMenuPlugin,
                 SettingsMenuPlugin,
                 WorldBrowserPlugin,
                 WorldMenuPlugin,
             ));
+
+        #[cfg(feature = "networking")]
+        app.add_plugins(ConnectionDialogPlugin);
     }
 }
 
@@ -41,4 +51,5 @@ pub(super) enum MenuState {
     #[default]
     MainMenu,
     WorldBrowser,
+    Credits,
 }
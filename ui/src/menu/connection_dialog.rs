@@ -3,7 +3,10 @@ use bevy_replicon::prelude::*;
 use bevy_replicon_renet::renet::RenetClient;
 
 use project_harmonia_widgets::{
-    button::ButtonKind, dialog::Dialog, label::LabelKind, theme::Theme,
+    button::ButtonKind,
+    dialog::{Dialog, Layer},
+    label::LabelKind,
+    theme::Theme,
 };
 
 pub(super) struct ConnectionDialogPlugin;
@@ -67,5 +70,6 @@ impl ConnectionDialogPlugin {
 }
 
 #[derive(Component)]
-#[require(Dialog)]
+// Closed automatically after connection or cancellation.
+#[require(Dialog, Layer(|| Layer::Manual))]
 struct ConnectionDialog;
@@ -0,0 +1,161 @@
+use bevy::prelude::*;
+
+use super::MenuState;
+use project_harmonia_base::asset::credits::Credits;
+use project_harmonia_widgets::{button::ButtonKind, label::LabelKind, theme::Theme};
+
+pub(super) struct CreditsMenuPlugin;
+
+impl Plugin for CreditsMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CreditsHandle>()
+            .add_systems(OnEnter(MenuState::Credits), Self::setup)
+            .add_systems(
+                Update,
+                Self::scroll
+                    .never_param_warn()
+                    .run_if(in_state(MenuState::Credits)),
+            );
+    }
+}
+
+/// Scroll speed in logical pixels per second.
+const SCROLL_SPEED: f32 = 40.0;
+
+impl CreditsMenuPlugin {
+    fn setup(
+        mut commands: Commands,
+        theme: Res<Theme>,
+        credits_handle: Res<CreditsHandle>,
+        credits: Res<Assets<Credits>>,
+        root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+    ) {
+        info!("entering credits");
+        commands.entity(*root_entity).with_children(|parent| {
+            parent
+                .spawn((
+                    StateScoped(MenuState::Credits),
+                    Node {
+                        width: Val::Percent(100.0),
+                        height: Val::Percent(100.0),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        padding: theme.padding.global,
+                        row_gap: theme.gap.normal,
+                        ..Default::default()
+                    },
+                ))
+                .with_children(|parent| {
+                    parent.spawn((LabelKind::Large, Text::new("Credits")));
+                    parent.spawn(CreditsViewport).with_children(|parent| {
+                        parent
+                            .spawn(Node {
+                                flex_direction: FlexDirection::Column,
+                                align_items: AlignItems::Center,
+                                row_gap: theme.gap.large,
+                                ..Default::default()
+                            })
+                            .with_children(|parent| {
+                                let Some(credits) = credits.get(&credits_handle.0) else {
+                                    error!("credits are not loaded");
+                                    return;
+                                };
+
+                                for section in &credits.sections {
+                                    parent
+                                        .spawn(Node {
+                                            flex_direction: FlexDirection::Column,
+                                            align_items: AlignItems::Center,
+                                            row_gap: theme.gap.normal,
+                                            ..Default::default()
+                                        })
+                                        .with_children(|parent| {
+                                            parent.spawn((
+                                                LabelKind::Large,
+                                                Text::new(section.title.clone()),
+                                            ));
+                                            for name in &section.names {
+                                                parent.spawn((
+                                                    LabelKind::Normal,
+                                                    Text::new(name.clone()),
+                                                ));
+                                            }
+                                        });
+                                }
+                            });
+                    });
+
+                    parent
+                        .spawn(Node {
+                            width: Val::Percent(100.0),
+                            justify_content: JustifyContent::FlexStart,
+                            ..Default::default()
+                        })
+                        .with_children(|parent| {
+                            parent
+                                .spawn(ButtonKind::Normal)
+                                .with_child(Text::new("Back"))
+                                .observe(Self::back);
+                        });
+                });
+        });
+    }
+
+    /// Scrolls credits automatically and starts over after reaching the end.
+    ///
+    /// Pauses while hovered to let the user read.
+    fn scroll(
+        time: Res<Time>,
+        viewport: Single<
+            (&mut ScrollPosition, &Interaction, &ComputedNode, &Children),
+            With<CreditsViewport>,
+        >,
+        nodes: Query<&ComputedNode>,
+    ) {
+        let (mut scroll_position, &interaction, viewport_node, children) = viewport.into_inner();
+        if interaction != Interaction::None {
+            return;
+        }
+
+        let content_node = nodes
+            .get(children[0])
+            .expect("viewport should contain credits");
+        let max_offset =
+            (content_node.size().y - viewport_node.size().y) * viewport_node.inverse_scale_factor();
+        if scroll_position.offset_y >= max_offset {
+            scroll_position.offset_y = 0.0;
+        } else {
+            scroll_position.offset_y += SCROLL_SPEED * time.delta_secs();
+        }
+    }
+
+    fn back(_trigger: Trigger<Pointer<Click>>, mut commands: Commands) {
+        commands.set_state(MenuState::MainMenu);
+    }
+}
+
+/// Keeps credits loaded.
+#[derive(Resource)]
+struct CreditsHandle(Handle<Credits>);
+
+impl FromWorld for CreditsHandle {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        Self(asset_server.load("base/credits.credits.ron"))
+    }
+}
+
+#[derive(Component)]
+#[require(
+    Node(|| Node {
+        width: Val::Percent(100.0),
+        height: Val::Percent(100.0),
+        flex_direction: FlexDirection::Column,
+        align_items: AlignItems::Center,
+        overflow: Overflow::scroll_y(),
+        ..Default::default()
+    }),
+    ScrollPosition,
+    Interaction
+)]
+struct CreditsViewport;
@@ -3,21 +3,28 @@ use std::mem;
 use bevy::prelude::*;
 use bevy_simple_text_input::TextInputValue;
 
-use crate::preview::{Preview, PreviewProcessed};
-use project_harmonia_base::game_world::{
-    city::City,
-    family::{
-        editor::{
-            EditorActor, EditorFamily, EditorFamilyReset, EditorFirstName, EditorLastName,
-            EditorSelectedActor, EditorSex, FamilyScene,
+use crate::{
+    preview::{Preview, PreviewProcessed},
+    transition::TransitionTo,
+};
+use project_harmonia_base::{
+    core::localization::{Localization, LocalizedText},
+    game_world::{
+        city::City,
+        family::{
+            editor::{
+                EditorActor, EditorFamily, EditorFamilyReset, EditorFirstName, EditorLastName,
+                EditorSelectedActor, EditorSex, FamilyScene,
+            },
+            FamilyCreate, MAX_FAMILY_SIZE,
         },
-        FamilyCreate,
+        WorldState,
     },
-    WorldState,
 };
 use project_harmonia_widgets::{
-    button::{ButtonKind, ExclusiveButton, Toggled},
-    dialog::Dialog,
+    button::{ButtonKind, ExclusiveButton, RadioChanged, RadioGroup, Toggled},
+    combo_box::ComboBox,
+    dialog::{Dialog, Resizable},
     label::LabelKind,
     text_edit::TextEdit,
     theme::Theme,
@@ -37,6 +44,7 @@ impl Plugin for EditorMenuPlugin {
                     Self::apply_first_name.never_param_warn(),
                     Self::apply_last_name.never_param_warn(),
                     Self::update_previews,
+                    Self::update_add_button,
                 )
                     .run_if(in_state(WorldState::FamilyEditor)),
             );
@@ -126,7 +134,13 @@ impl EditorMenuPlugin {
         mut commands: Commands,
         family_entity: Single<Entity, With<EditorFamily>>,
         selected_entity: Single<Entity, With<EditorSelectedActor>>,
+        actors: Query<(), With<EditorActor>>,
     ) {
+        if actors.iter().len() >= MAX_FAMILY_SIZE {
+            warn!("ignoring new actor because the family is full");
+            return;
+        }
+
         info!("adding new actor");
         commands
             .entity(*selected_entity)
@@ -151,11 +165,16 @@ impl EditorMenuPlugin {
     }
 
     fn apply_sex(
-        trigger: Trigger<Pointer<Click>>,
+        trigger: Trigger<RadioChanged>,
         mut actor_sex: Single<&mut EditorSex, With<EditorSelectedActor>>,
+        groups: Query<&Children>,
         buttons: Query<&EditorSex, Without<EditorSelectedActor>>,
     ) {
-        let button_sex = *buttons.get(trigger.entity()).unwrap();
+        let children = groups.get(trigger.entity()).unwrap();
+        let button_sex = *buttons
+            .iter_many(children)
+            .nth(**trigger)
+            .expect("sex buttons should be spawned for each variant");
         info!("changing sex to '{button_sex:?}'");
         **actor_sex = button_sex;
     }
@@ -184,6 +203,24 @@ impl EditorMenuPlugin {
         }
     }
 
+    /// Replaces the add button with a hint when the family is full.
+    fn update_add_button(
+        actors: Query<(), With<EditorActor>>,
+        mut add_node: Single<&mut Node, With<AddActorButton>>,
+        mut limit_node: Single<&mut Node, (With<FamilyLimitLabel>, Without<AddActorButton>)>,
+    ) {
+        let (add_display, limit_display) = if actors.iter().len() < MAX_FAMILY_SIZE {
+            (Display::Flex, Display::None)
+        } else {
+            (Display::None, Display::Flex)
+        };
+
+        if add_node.display != add_display {
+            add_node.display = add_display;
+            limit_node.display = limit_display;
+        }
+    }
+
     fn update_previews(
         mut commands: Commands,
         actors: Query<(Entity, Ref<EditorSex>), With<EditorActor>>,
@@ -211,15 +248,16 @@ impl EditorMenuPlugin {
         _trigger: Trigger<Pointer<Click>>,
         mut commands: Commands,
         theme: Res<Theme>,
+        localization: Res<Localization>,
         root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
     ) {
         commands.entity(*root_entity).with_children(|parent| {
-            setup_save_family_dialog(parent, &theme);
+            setup_save_family_dialog(parent, &theme, &localization);
         });
     }
 
     fn cancel_family(_trigger: Trigger<Pointer<Click>>, mut commands: Commands) {
-        commands.set_state(WorldState::World);
+        commands.queue(TransitionTo(WorldState::World));
     }
 
     fn save_family(
@@ -266,32 +304,32 @@ impl EditorMenuPlugin {
     }
 
     fn place_and_play(
-        trigger: Trigger<Pointer<Click>>,
+        _trigger: Trigger<Pointer<Click>>,
         mut spawn_events: EventWriter<FamilyCreate>,
         mut family_scene: ResMut<FamilyScene>,
-        buttons: Query<&PlaceCityButton>,
+        city_combo_box: Single<(&ComboBox, &CityComboBox)>,
     ) {
         info!("placing family with select");
-        let city_button = buttons.get(trigger.entity()).unwrap();
+        let (combo_box, city_combo_box) = *city_combo_box;
         spawn_events.send(FamilyCreate {
-            city_entity: city_button.city_entity,
+            city_entity: city_combo_box[combo_box.selected],
             scene: mem::take(&mut family_scene),
             select: true,
         });
     }
 
     fn place(
-        trigger: Trigger<Pointer<Click>>,
+        _trigger: Trigger<Pointer<Click>>,
         mut commands: Commands,
         mut spawn_events: EventWriter<FamilyCreate>,
         mut family_scene: ResMut<FamilyScene>,
-        buttons: Query<&PlaceCityButton>,
+        city_combo_box: Single<(&ComboBox, &CityComboBox)>,
         dialog_entity: Single<Entity, With<Dialog>>,
     ) {
         info!("placing family");
-        let city_button = buttons.get(trigger.entity()).unwrap();
+        let (combo_box, city_combo_box) = *city_combo_box;
         spawn_events.send(FamilyCreate {
-            city_entity: city_button.city_entity,
+            city_entity: city_combo_box[combo_box.selected],
             scene: mem::take(&mut family_scene),
             select: false,
         });
@@ -322,27 +360,34 @@ fn setup_personality_node(parent: &mut ChildBuilder, theme: &Theme) {
                     ..Default::default()
                 },))
                 .with_children(|parent| {
-                    parent.spawn((LabelKind::Normal, Text::new("First name")));
+                    parent.spawn((
+                        LabelKind::Normal,
+                        LocalizedText::new("menu.editor.first_name"),
+                    ));
                     parent.spawn(FirstNameEdit);
-                    parent.spawn((LabelKind::Normal, Text::new("Last name")));
+                    parent.spawn((
+                        LabelKind::Normal,
+                        LocalizedText::new("menu.editor.last_name"),
+                    ));
                     parent.spawn(LastNameEdit);
                 });
 
-            parent.spawn(Node::default()).with_children(|parent| {
-                parent
-                    .spawn((
-                        EditorSex::Male,
-                        ButtonKind::Normal,
-                        ExclusiveButton,
-                        Toggled(true),
-                    ))
-                    .with_child(Text::new("Male"))
-                    .observe(EditorMenuPlugin::apply_sex);
-                parent
-                    .spawn((EditorSex::Female, ButtonKind::Normal, ExclusiveButton))
-                    .with_child(Text::new("Female"))
-                    .observe(EditorMenuPlugin::apply_sex);
-            });
+            parent
+                .spawn(RadioGroup)
+                .with_children(|parent| {
+                    parent
+                        .spawn((
+                            EditorSex::Male,
+                            ButtonKind::Normal,
+                            ExclusiveButton,
+                            Toggled(true),
+                        ))
+                        .with_child(LocalizedText::new("menu.editor.male"));
+                    parent
+                        .spawn((EditorSex::Female, ButtonKind::Normal, ExclusiveButton))
+                        .with_child(LocalizedText::new("menu.editor.female"));
+                })
+                .observe(EditorMenuPlugin::apply_sex);
         });
 }
 
@@ -368,9 +413,18 @@ fn setup_actors_node(parent: &mut ChildBuilder, theme: &Theme) {
                 },
             ));
             parent
-                .spawn(ButtonKind::Symbol)
+                .spawn(AddActorButton)
                 .with_child(Text::new("➕"))
                 .observe(EditorMenuPlugin::add_actor);
+            parent.spawn((
+                FamilyLimitLabel,
+                LabelKind::Small,
+                LocalizedText::new("menu.editor.family_limit").with_arg("count", MAX_FAMILY_SIZE),
+                Node {
+                    display: Display::None,
+                    ..Default::default()
+                },
+            ));
         });
 }
 
@@ -387,16 +441,16 @@ fn setup_family_menu_buttons(parent: &mut ChildBuilder, theme: &Theme) {
         .with_children(|parent| {
             parent
                 .spawn(ButtonKind::Normal)
-                .with_child(Text::new("Confirm"))
+                .with_child(LocalizedText::new("menu.confirm"))
                 .observe(EditorMenuPlugin::confirm_family);
             parent
                 .spawn(ButtonKind::Normal)
-                .with_child(Text::new("Cancel"))
+                .with_child(LocalizedText::new("menu.cancel"))
                 .observe(EditorMenuPlugin::cancel_family);
         });
 }
 
-fn setup_save_family_dialog(parent: &mut ChildBuilder, theme: &Theme) {
+fn setup_save_family_dialog(parent: &mut ChildBuilder, theme: &Theme, localization: &Localization) {
     info!("showing save family dialog");
     parent.spawn(Dialog).with_children(|parent| {
         parent
@@ -412,12 +466,15 @@ fn setup_save_family_dialog(parent: &mut ChildBuilder, theme: &Theme) {
                 theme.panel_background,
             ))
             .with_children(|parent| {
-                parent.spawn((LabelKind::Normal, Text::new("Save family")));
+                parent.spawn((
+                    LabelKind::Normal,
+                    LocalizedText::new("menu.editor.save_family"),
+                ));
                 parent.spawn((
                     FamilyNameEdit,
                     // HACK: For some reason it can't be required component, it messes the edit.
-                    TextEdit,
-                    TextInputValue("New family".to_string()),
+                    TextEdit::default(),
+                    TextInputValue(localization.tr("menu.editor.new_family")),
                 ));
                 parent
                     .spawn(Node {
@@ -427,11 +484,11 @@ fn setup_save_family_dialog(parent: &mut ChildBuilder, theme: &Theme) {
                     .with_children(|parent| {
                         parent
                             .spawn(ButtonKind::Normal)
-                            .with_child(Text::new("Save"))
+                            .with_child(LocalizedText::new("menu.editor.save"))
                             .observe(EditorMenuPlugin::save_family);
                         parent
                             .spawn(ButtonKind::Normal)
-                            .with_child(Text::new("Cancel"))
+                            .with_child(LocalizedText::new("menu.cancel"))
                             .observe(EditorMenuPlugin::cancel_saving);
                     });
             });
@@ -454,39 +511,58 @@ fn setup_place_family_dialog(
                         align_items: AlignItems::Center,
                         padding: theme.padding.normal,
                         row_gap: theme.gap.normal,
+                        overflow: Overflow::clip(),
                         ..Default::default()
                     },
+                    Resizable {
+                        id: "place_family",
+                        min_size: Vec2::new(400.0, 200.0),
+                        max_size: Vec2::new(1200.0, 900.0),
+                    },
                     theme.panel_background,
                 ))
                 .with_children(|parent| {
-                    parent.spawn((LabelKind::Normal, Text::new("Place family")));
+                    parent.spawn((
+                        LabelKind::Normal,
+                        LocalizedText::new("menu.editor.place_family"),
+                    ));
                     parent
                         .spawn(Node {
                             width: Val::Percent(100.0),
                             height: Val::Percent(100.0),
                             justify_content: JustifyContent::Center,
+                            align_items: AlignItems::FlexStart,
+                            column_gap: theme.gap.normal,
                             ..Default::default()
                         })
                         .with_children(|parent| {
-                            // TODO: Use combobox.
-                            for (city_entity, name) in cities {
-                                parent
-                                    .spawn(Node {
-                                        column_gap: theme.gap.normal,
-                                        ..Default::default()
-                                    })
-                                    .with_children(|parent| {
-                                        parent.spawn((LabelKind::Normal, Text::new(name)));
-                                        parent
-                                            .spawn(PlaceCityButton { city_entity })
-                                            .with_child(Text::new("Place & play"))
-                                            .observe(EditorMenuPlugin::place_and_play);
-                                        parent
-                                            .spawn(PlaceCityButton { city_entity })
-                                            .with_child(Text::new("Place"))
-                                            .observe(EditorMenuPlugin::place);
-                                    });
+                            if cities.is_empty() {
+                                parent.spawn((
+                                    LabelKind::Normal,
+                                    LocalizedText::new("menu.editor.no_cities"),
+                                ));
+                                return;
                             }
+
+                            let (city_entities, names): (Vec<_>, Vec<_>) = cities
+                                .iter()
+                                .map(|(entity, name)| (entity, name.to_string()))
+                                .unzip();
+                            parent.spawn((
+                                ComboBox {
+                                    options: names,
+                                    selected: 0,
+                                },
+                                CityComboBox(city_entities),
+                            ));
+                            parent
+                                .spawn(ButtonKind::Normal)
+                                .with_child(LocalizedText::new("menu.editor.place_and_play"))
+                                .observe(EditorMenuPlugin::place_and_play);
+                            parent
+                                .spawn(ButtonKind::Normal)
+                                .with_child(LocalizedText::new("menu.editor.place"))
+                                .observe(EditorMenuPlugin::place);
                         });
 
                     parent
@@ -497,11 +573,11 @@ fn setup_place_family_dialog(
                         .with_children(|parent| {
                             parent
                                 .spawn(ButtonKind::Normal)
-                                .with_child(Text::new("Cancel"))
+                                .with_child(LocalizedText::new("menu.cancel"))
                                 .observe(EditorMenuPlugin::cancel_placing);
                             parent
                                 .spawn(ButtonKind::Normal)
-                                .with_child(Text::new("Create new"))
+                                .with_child(LocalizedText::new("menu.editor.create_new"))
                                 .observe(EditorMenuPlugin::create_new);
                         });
                 });
@@ -520,6 +596,14 @@ struct LastNameEdit;
 #[require(Name(|| Name::new("Actors node")), Node)]
 struct ActorsNode;
 
+#[derive(Component)]
+#[require(Name(|| Name::new("Add actor button")), ButtonKind(|| ButtonKind::Symbol))]
+struct AddActorButton;
+
+/// Shown instead of [`AddActorButton`] when the family reaches [`MAX_FAMILY_SIZE`].
+#[derive(Component)]
+struct FamilyLimitLabel;
+
 #[derive(Component, Debug, Deref, Clone, Copy)]
 #[require(
     Name(|| Name::new("Actor button")), 
@@ -532,8 +616,7 @@ struct ActorButton(Entity);
 #[derive(Component)]
 struct FamilyNameEdit;
 
-#[derive(Component)]
-#[require(Name(|| Name::new("Place city button")), ButtonKind(|| ButtonKind::Normal))]
-struct PlaceCityButton {
-    city_entity: Entity,
-}
+/// City entities in the order of [`ComboBox::options`].
+#[derive(Component, Deref)]
+#[require(Name(|| Name::new("City combo box")))]
+struct CityComboBox(Vec<Entity>);
@@ -1,14 +1,21 @@
-use bevy::{app::AppExit, prelude::*};
+use bevy::{app::AppExit, prelude::*, window::WindowCloseRequested};
 use bevy_enhanced_input::prelude::*;
+use bevy_replicon::prelude::*;
 use project_harmonia_base::{
-    core::GameState,
-    game_world::{GameSave, WorldState},
+    common_conditions::in_multiplayer,
+    core::{localization::LocalizedText, GameState},
+    game_world::{spectator::Spectator, GameSave, UnsavedChanges, WorldState},
+    stepping::SetSimulationPaused,
 };
 use project_harmonia_widgets::{
-    button::ButtonKind, dialog::Dialog, label::LabelKind, theme::Theme,
+    button::ButtonKind,
+    dialog::{Dialog, Layer, LayerStack},
+    label::LabelKind,
+    theme::Theme,
 };
 
-use super::settings_menu::SettingsMenuOpen;
+use super::{players_menu::PlayersMenuOpen, settings_menu::SettingsMenuOpen};
+use crate::transition::TransitionTo;
 
 pub(super) struct InGameMenuPlugin;
 
@@ -16,6 +23,9 @@ impl Plugin for InGameMenuPlugin {
     fn build(&self, app: &mut App) {
         app.add_input_context::<IngameMenu>()
             .add_observer(Self::toggle)
+            .add_observer(Self::resume_on_removal)
+            .add_systems(Update, (Self::request_exit, Self::update_pause))
+            .add_systems(OnEnter(WorldState::World), Self::setup)
             .add_systems(OnEnter(WorldState::Family), Self::setup)
             .add_systems(OnEnter(WorldState::City), Self::setup);
     }
@@ -26,8 +36,13 @@ impl InGameMenuPlugin {
         mut commands: Commands,
         world_state: Res<State<WorldState>>,
         theme: Res<Theme>,
+        spectator: Option<Res<Spectator>>,
+        server: Option<Res<RepliconServer>>,
+        client: Res<RepliconClient>,
         root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
     ) {
+        let hosting = server.is_some_and(|server| server.is_running());
+        let can_save = can_save(spectator, &client);
         commands.entity(*root_entity).with_children(|parent| {
             parent
                 .spawn((
@@ -52,31 +67,48 @@ impl InGameMenuPlugin {
                             theme.panel_background,
                         ))
                         .with_children(|parent| {
-                            parent.spawn((LabelKind::Normal, Text::new("Main menu")));
+                            parent.spawn((
+                                LabelKind::Normal,
+                                LocalizedText::new("menu.ingame.main_menu"),
+                            ));
 
                             parent
                                 .spawn(ButtonKind::Normal)
-                                .with_child(Text::new("Resume"))
+                                .with_child(LocalizedText::new("menu.ingame.resume"))
                                 .observe(Self::resume);
+                            if can_save {
+                                parent
+                                    .spawn(ButtonKind::Normal)
+                                    .with_child(LocalizedText::new("menu.ingame.save"))
+                                    .observe(Self::save);
+                                parent
+                                    .spawn(ButtonKind::Normal)
+                                    .with_child(LocalizedText::new("menu.ingame.save_and_exit"))
+                                    .observe(Self::save_and_exit_to_main_menu);
+                            }
                             parent
                                 .spawn(ButtonKind::Normal)
-                                .with_child(Text::new("Save"))
-                                .observe(Self::save);
-                            parent
-                                .spawn(ButtonKind::Normal)
-                                .with_child(Text::new("Settings"))
+                                .with_child(LocalizedText::new("menu.ingame.settings"))
                                 .observe(Self::open_settings);
+                            if hosting {
+                                parent
+                                    .spawn(ButtonKind::Normal)
+                                    .with_child(LocalizedText::new("menu.ingame.players"))
+                                    .observe(Self::open_players);
+                            }
+                            if **world_state != WorldState::World {
+                                parent
+                                    .spawn(ButtonKind::Normal)
+                                    .with_child(LocalizedText::new("menu.ingame.world"))
+                                    .observe(Self::open_world);
+                            }
                             parent
                                 .spawn(ButtonKind::Normal)
-                                .with_child(Text::new("World"))
-                                .observe(Self::open_world);
-                            parent
-                                .spawn(ButtonKind::Normal)
-                                .with_child(Text::new("Main menu"))
+                                .with_child(LocalizedText::new("menu.ingame.main_menu"))
                                 .observe(Self::exit_to_main_menu);
                             parent
                                 .spawn(ButtonKind::Normal)
-                                .with_child(Text::new("Exit game"))
+                                .with_child(LocalizedText::new("menu.ingame.exit_game"))
                                 .observe(Self::exit_game);
                         });
                 });
@@ -86,10 +118,17 @@ impl InGameMenuPlugin {
     fn toggle(
         _trigger: Trigger<Started<ToggleIngameMenu>>,
         mut commands: Commands,
-        menu: Single<(Entity, &Parent, &mut Node), With<IngameMenu>>,
+        stack: Res<LayerStack>,
+        mut nodes: Query<&mut Node>,
+        menu: Single<(Entity, &Parent), With<IngameMenu>>,
     ) {
-        let (entity, parent, mut node) = menu.into_inner();
+        let (entity, parent) = *menu;
+        let top_layer = stack.top(&nodes.to_readonly());
+        let mut node = nodes.get_mut(entity).unwrap();
         match node.display {
+            // Let other layers be closed first.
+            Display::Flex if top_layer != Some(entity) => return,
+            Display::None if top_layer.is_some() => return,
             Display::Flex => {
                 info!("closing in-game menu");
                 node.display = Display::None;
@@ -105,6 +144,40 @@ impl InGameMenuPlugin {
         commands.entity(entity).set_parent(**parent);
     }
 
+    /// Pauses the simulation while the menu is visible.
+    ///
+    /// The simulation is shared in multiplayer, so it keeps running.
+    fn update_pause(
+        mut commands: Commands,
+        server: Option<Res<RepliconServer>>,
+        client: Res<RepliconClient>,
+        menu_nodes: Query<Ref<Node>, With<IngameMenu>>,
+    ) {
+        if in_multiplayer(server, client) {
+            return;
+        }
+
+        for node in &menu_nodes {
+            if node.is_changed() && !node.is_added() {
+                commands.trigger(SetSimulationPaused(node.display != Display::None));
+            }
+        }
+    }
+
+    /// Resumes the simulation if the menu was despawned while visible.
+    fn resume_on_removal(
+        trigger: Trigger<OnRemove, IngameMenu>,
+        mut commands: Commands,
+        server: Option<Res<RepliconServer>>,
+        client: Res<RepliconClient>,
+        nodes: Query<&Node>,
+    ) {
+        let node = nodes.get(trigger.entity()).unwrap();
+        if node.display != Display::None && !in_multiplayer(server, client) {
+            commands.trigger(SetSimulationPaused(false));
+        }
+    }
+
     fn resume(
         _trigger: Trigger<Pointer<Click>>,
         mut menu_node: Single<&mut Node, With<IngameMenu>>,
@@ -123,22 +196,34 @@ impl InGameMenuPlugin {
         menu_node.display = Display::None;
     }
 
+    fn save_and_exit_to_main_menu(_trigger: Trigger<Pointer<Click>>, mut commands: Commands) {
+        commands.trigger(GameSave);
+        commands.queue(TransitionTo(GameState::Menu));
+    }
+
     fn open_settings(_trigger: Trigger<Pointer<Click>>, mut commands: Commands) {
         commands.trigger(SettingsMenuOpen);
     }
 
+    fn open_players(_trigger: Trigger<Pointer<Click>>, mut commands: Commands) {
+        commands.trigger(PlayersMenuOpen);
+    }
+
     fn open_world(_trigger: Trigger<Pointer<Click>>, mut commands: Commands) {
-        commands.set_state(WorldState::World);
+        commands.queue(TransitionTo(WorldState::World));
     }
 
     fn exit_to_main_menu(
         _trigger: Trigger<Pointer<Click>>,
         mut commands: Commands,
         theme: Res<Theme>,
+        spectator: Option<Res<Spectator>>,
+        client: Res<RepliconClient>,
         menu_entity: Single<Entity, With<IngameMenu>>,
     ) {
+        let can_save = can_save(spectator, &client);
         commands.entity(*menu_entity).with_children(|parent| {
-            setup_exit_dialog(parent, &theme, ExitDialog::MainMenu);
+            setup_exit_dialog(parent, &theme, ExitDialog::MainMenu, can_save);
         });
     }
 
@@ -146,13 +231,53 @@ impl InGameMenuPlugin {
         _trigger: Trigger<Pointer<Click>>,
         mut commands: Commands,
         theme: Res<Theme>,
+        spectator: Option<Res<Spectator>>,
+        client: Res<RepliconClient>,
         menu_entity: Single<Entity, With<IngameMenu>>,
     ) {
+        let can_save = can_save(spectator, &client);
         commands.entity(*menu_entity).with_children(|parent| {
-            setup_exit_dialog(parent, &theme, ExitDialog::Game);
+            setup_exit_dialog(parent, &theme, ExitDialog::Game, can_save);
         });
     }
 
+    /// Handles window closing.
+    ///
+    /// Asks for confirmation only when the loaded world has unsaved changes.
+    fn request_exit(
+        mut commands: Commands,
+        mut close_events: EventReader<WindowCloseRequested>,
+        mut exit_events: EventWriter<AppExit>,
+        theme: Res<Theme>,
+        game_state: Res<State<GameState>>,
+        spectator: Option<Res<Spectator>>,
+        client: Res<RepliconClient>,
+        unsaved: Res<UnsavedChanges>,
+        roots: Query<Entity, (With<Node>, Without<Parent>)>,
+        exit_dialogs: Query<(), With<ExitDialog>>,
+    ) {
+        if close_events.read().count() == 0 {
+            return;
+        }
+
+        let can_save = can_save(spectator, &client);
+        // The dialog needs a UI root, which could be missing during transitions.
+        let root_entity = roots.iter().next();
+        match root_entity {
+            Some(root_entity) if *game_state == GameState::InGame && can_save && **unsaved => {
+                if exit_dialogs.is_empty() {
+                    commands.entity(root_entity).with_children(|parent| {
+                        setup_exit_dialog(parent, &theme, ExitDialog::Game, can_save);
+                    });
+                }
+            }
+            _ => {
+                info!("exiting game");
+                exit_events.send_default();
+            }
+        }
+    }
+
     fn save_and_exit(
         _trigger: Trigger<Pointer<Click>>,
         mut commands: Commands,
@@ -161,7 +286,7 @@ impl InGameMenuPlugin {
     ) {
         commands.trigger(GameSave);
         match *exit_dialog {
-            ExitDialog::MainMenu => commands.set_state(GameState::Menu),
+            ExitDialog::MainMenu => commands.queue(TransitionTo(GameState::Menu)),
             ExitDialog::Game => {
                 info!("exiting game");
                 exit_events.send_default();
@@ -176,7 +301,7 @@ impl InGameMenuPlugin {
         exit_dialog: Single<&ExitDialog>,
     ) {
         match *exit_dialog {
-            ExitDialog::MainMenu => commands.set_state(GameState::Menu),
+            ExitDialog::MainMenu => commands.queue(TransitionTo(GameState::Menu)),
             ExitDialog::Game => {
                 info!("exiting game");
                 exit_events.send_default();
@@ -194,7 +319,19 @@ impl InGameMenuPlugin {
     }
 }
 
-fn setup_exit_dialog(parent: &mut ChildBuilder, theme: &Theme, exit_dialog: ExitDialog) {
+/// Returns `true` if the local player can save the world.
+///
+/// Only the host or a singleplayer player owns the save file.
+fn can_save(spectator: Option<Res<Spectator>>, client: &RepliconClient) -> bool {
+    spectator.is_none() && !client.is_connected()
+}
+
+fn setup_exit_dialog(
+    parent: &mut ChildBuilder,
+    theme: &Theme,
+    exit_dialog: ExitDialog,
+    can_save: bool,
+) {
     info!("showing exit dialog");
     parent.spawn(exit_dialog).with_children(|parent| {
         parent
@@ -210,7 +347,10 @@ fn setup_exit_dialog(parent: &mut ChildBuilder, theme: &Theme, exit_dialog: Exit
                 theme.panel_background,
             ))
             .with_children(|parent| {
-                parent.spawn((LabelKind::Normal, Text::new(exit_dialog.label())));
+                parent.spawn((
+                    LabelKind::Normal,
+                    LocalizedText::new(exit_dialog.label_key()),
+                ));
 
                 parent
                     .spawn(Node {
@@ -218,17 +358,19 @@ fn setup_exit_dialog(parent: &mut ChildBuilder, theme: &Theme, exit_dialog: Exit
                         ..Default::default()
                     })
                     .with_children(|parent| {
+                        if can_save {
+                            parent
+                                .spawn(ButtonKind::Normal)
+                                .with_child(LocalizedText::new("menu.ingame.save_and_exit"))
+                                .observe(InGameMenuPlugin::save_and_exit);
+                        }
                         parent
                             .spawn(ButtonKind::Normal)
-                            .with_child(Text::new("Save & exit"))
-                            .observe(InGameMenuPlugin::save_and_exit);
-                        parent
-                            .spawn(ButtonKind::Normal)
-                            .with_child(Text::new("Exit"))
+                            .with_child(LocalizedText::new("menu.ingame.exit"))
                             .observe(InGameMenuPlugin::exit_without_saving);
                         parent
                             .spawn(ButtonKind::Normal)
-                            .with_child(Text::new("Cancel"))
+                            .with_child(LocalizedText::new("menu.cancel"))
                             .observe(InGameMenuPlugin::cancel_exit);
                     });
             });
@@ -236,7 +378,11 @@ fn setup_exit_dialog(parent: &mut ChildBuilder, theme: &Theme, exit_dialog: Exit
 }
 
 #[derive(Component)]
-#[require(Name(|| Name::new("Ingame menu")), Dialog)]
+#[require(
+    Name(|| Name::new("Ingame menu")),
+    Dialog,
+    Layer(|| Layer::Manual)
+)]
 struct IngameMenu;
 
 impl InputContext for IngameMenu {
@@ -255,17 +401,21 @@ impl InputContext for IngameMenu {
 struct ToggleIngameMenu;
 
 #[derive(Component, Clone, Copy)]
-#[require(Name(|| Name::new("Exit dialog")), Dialog)]
+#[require(
+    Name(|| Name::new("Exit dialog")),
+    StateScoped::<GameState>(|| StateScoped(GameState::InGame)),
+    Dialog,
+)]
 enum ExitDialog {
     MainMenu,
     Game,
 }
 
 impl ExitDialog {
-    fn label(&self) -> &'static str {
+    fn label_key(&self) -> &'static str {
         match self {
-            ExitDialog::MainMenu => "Are you sure you want to exit to the main menu?",
-            ExitDialog::Game => "Are you sure you want to exit the game?",
+            ExitDialog::MainMenu => "menu.ingame.exit_to_main_menu_confirm",
+            ExitDialog::Game => "menu.ingame.exit_game_confirm",
         }
     }
 }
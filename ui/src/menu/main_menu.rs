@@ -1,13 +1,20 @@
+mod background;
+
 use bevy::{app::AppExit, prelude::*};
 
 use super::{settings_menu::SettingsMenuOpen, MenuState};
-use project_harmonia_widgets::{button::ButtonKind, theme::Theme};
+use background::BackgroundPlugin;
+use project_harmonia_base::{build_info::BuildInfo, core::localization::LocalizedText};
+use project_harmonia_widgets::{
+    button::ButtonKind, dialog::Dialog, label::LabelKind, theme::Theme,
+};
 
 pub(super) struct MainMenuPlugin;
 
 impl Plugin for MainMenuPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(MenuState::MainMenu), Self::setup);
+        app.add_plugins(BackgroundPlugin)
+            .add_systems(OnEnter(MenuState::MainMenu), Self::setup);
     }
 }
 
@@ -36,16 +43,24 @@ impl MainMenuPlugin {
                 .with_children(|parent| {
                     parent
                         .spawn(ButtonKind::Large)
-                        .with_child(Text::new("Play"))
+                        .with_child(LocalizedText::new("menu.main.play"))
                         .observe(Self::play);
                     parent
                         .spawn(ButtonKind::Large)
-                        .with_child(Text::new("Settings"))
+                        .with_child(LocalizedText::new("menu.main.settings"))
                         .observe(Self::open_settings);
+                    parent
+                        .spawn(ButtonKind::Large)
+                        .with_child(LocalizedText::new("menu.main.credits"))
+                        .observe(Self::open_credits);
+                    parent
+                        .spawn(ButtonKind::Large)
+                        .with_child(LocalizedText::new("menu.main.about"))
+                        .observe(Self::open_about);
 
                     parent
                         .spawn(ButtonKind::Large)
-                        .with_child(Text::new("Exit"))
+                        .with_child(LocalizedText::new("menu.main.exit"))
                         .observe(Self::exit);
                 });
         });
@@ -59,8 +74,65 @@ impl MainMenuPlugin {
         commands.trigger(SettingsMenuOpen);
     }
 
+    fn open_credits(_trigger: Trigger<Pointer<Click>>, mut commands: Commands) {
+        commands.set_state(MenuState::Credits);
+    }
+
+    fn open_about(
+        _trigger: Trigger<Pointer<Click>>,
+        mut commands: Commands,
+        theme: Res<Theme>,
+        build_info: Res<BuildInfo>,
+        root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+    ) {
+        info!("opening about dialog");
+        commands.entity(*root_entity).with_children(|parent| {
+            parent.spawn(AboutDialog).with_children(|parent| {
+                parent
+                    .spawn((
+                        Node {
+                            flex_direction: FlexDirection::Column,
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            padding: theme.padding.normal,
+                            row_gap: theme.gap.normal,
+                            ..Default::default()
+                        },
+                        theme.panel_background,
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((LabelKind::Normal, Text::new("Project Harmonia")));
+                        parent.spawn((
+                            LabelKind::Small,
+                            LocalizedText::new("menu.main.about_text")
+                                .with_arg("version", build_info.version)
+                                .with_arg("commit", build_info.git_hash)
+                                .with_arg("date", build_info.build_date),
+                        ));
+                        parent
+                            .spawn(ButtonKind::Normal)
+                            .with_child(LocalizedText::new("menu.ok"))
+                            .observe(Self::close_about);
+                    });
+            });
+        });
+    }
+
+    fn close_about(
+        _trigger: Trigger<Pointer<Click>>,
+        mut commands: Commands,
+        dialog_entity: Single<Entity, With<AboutDialog>>,
+    ) {
+        info!("closing about dialog");
+        commands.entity(*dialog_entity).despawn_recursive();
+    }
+
     fn exit(_trigger: Trigger<Pointer<Click>>, mut exit_events: EventWriter<AppExit>) {
         info!("exiting game");
         exit_events.send_default();
     }
 }
+
+#[derive(Component)]
+#[require(Name(|| Name::new("About dialog")), Dialog)]
+struct AboutDialog;
@@ -0,0 +1,134 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use bevy_atmosphere::prelude::*;
+
+use crate::menu::MenuState;
+use project_harmonia_base::settings::Settings;
+use project_harmonia_widgets::theme::ReduceMotion;
+
+/// Renders a slowly orbiting 3D scene behind the main menu.
+pub(super) struct BackgroundPlugin;
+
+impl Plugin for BackgroundPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(MenuState::MainMenu),
+            Self::spawn.run_if(|settings: Res<Settings>| settings.video.menu_background),
+        )
+        .add_systems(
+            OnExit(MenuState::MainMenu),
+            Self::enable_ui_camera.never_param_warn(),
+        )
+        .add_systems(
+            Update,
+            Self::orbit_camera
+                .never_param_warn()
+                .run_if(in_state(MenuState::MainMenu)),
+        );
+    }
+}
+
+/// Full camera rotations per second.
+const ORBIT_SPEED: f32 = 0.01;
+const ORBIT_RADIUS: f32 = 14.0;
+const CAMERA_HEIGHT: f32 = 5.0;
+
+impl BackgroundPlugin {
+    fn spawn(
+        mut commands: Commands,
+        asset_server: Res<AssetServer>,
+        mut meshes: ResMut<Assets<Mesh>>,
+        mut ui_camera: Single<&mut Camera, With<Camera2d>>,
+    ) {
+        debug!("spawning main menu background");
+
+        // The 3D camera will render UI instead.
+        ui_camera.is_active = false;
+
+        commands.spawn(BackgroundCamera);
+        commands.spawn((
+            Name::new("Background sun"),
+            StateScoped(MenuState::MainMenu),
+            DirectionalLight {
+                shadows_enabled: true,
+                ..Default::default()
+            },
+            Transform::from_xyz(4.0, 7.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ));
+        commands.spawn((
+            Name::new("Background ground"),
+            StateScoped(MenuState::MainMenu),
+            Mesh3d(meshes.add(Plane3d::default().mesh().size(60.0, 60.0))),
+            MeshMaterial3d::<StandardMaterial>(
+                asset_server.load("base/ground/spring_grass/spring_glass.ron"),
+            ),
+        ));
+
+        for (path, translation, angle) in [
+            (
+                "base/objects/outdoor_activities/sandbox/sandbox.gltf",
+                Vec3::new(-2.0, 0.0, 1.0),
+                0.0,
+            ),
+            (
+                "base/objects/outdoor_activities/slide/slide.gltf",
+                Vec3::new(3.0, 0.0, -1.5),
+                90.0,
+            ),
+            (
+                "base/objects/outdoor_activities/carousel/carousel.gltf",
+                Vec3::new(0.5, 0.0, 4.0),
+                0.0,
+            ),
+            (
+                "base/objects/outdoor_activities/beater/beater.gltf",
+                Vec3::new(-4.0, 0.0, -4.0),
+                45.0,
+            ),
+        ] {
+            commands.spawn((
+                Name::new("Background object"),
+                StateScoped(MenuState::MainMenu),
+                SceneRoot(asset_server.load(GltfAssetLabel::Scene(0).from_asset(path))),
+                Transform::from_translation(translation)
+                    .with_rotation(Quat::from_rotation_y(f32::to_radians(angle))),
+            ));
+        }
+    }
+
+    fn orbit_camera(
+        time: Res<Time>,
+        reduce_motion: Res<ReduceMotion>,
+        mut camera_transform: Single<&mut Transform, With<BackgroundCamera>>,
+    ) {
+        // Keep the camera still at the initial position.
+        let elapsed = if **reduce_motion {
+            0.0
+        } else {
+            time.elapsed_secs()
+        };
+        let angle = elapsed * ORBIT_SPEED * TAU;
+        let (sin, cos) = angle.sin_cos();
+        camera_transform.translation =
+            Vec3::new(sin * ORBIT_RADIUS, CAMERA_HEIGHT, cos * ORBIT_RADIUS);
+        camera_transform.look_at(Vec3::ZERO, Vec3::Y);
+    }
+
+    fn enable_ui_camera(mut ui_camera: Single<&mut Camera, With<Camera2d>>) {
+        if !ui_camera.is_active {
+            debug!("enabling camera menu after background");
+            ui_camera.is_active = true;
+        }
+    }
+}
+
+#[derive(Component)]
+#[require(
+    Name(|| Name::new("Background camera")),
+    StateScoped::<MenuState>(|| StateScoped(MenuState::MainMenu)),
+    Camera3d,
+    Camera(|| Camera { hdr: true, ..Default::default() }),
+    AtmosphereCamera,
+)]
+struct BackgroundCamera;
@@ -0,0 +1,195 @@
+use bevy::prelude::*;
+use bevy_replicon::prelude::*;
+
+use project_harmonia_base::{game_world::moderation::KickPlayer, network};
+use project_harmonia_widgets::{
+    button::ButtonKind, dialog::Dialog, label::LabelKind, theme::Theme,
+};
+
+/// Host-only list of connected players with moderation controls.
+pub(super) struct PlayersMenuPlugin;
+
+impl Plugin for PlayersMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(Self::setup);
+    }
+}
+
+impl PlayersMenuPlugin {
+    fn setup(
+        _trigger: Trigger<PlayersMenuOpen>,
+        mut commands: Commands,
+        theme: Res<Theme>,
+        connected_clients: Res<ConnectedClients>,
+        root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+    ) {
+        info!("opening players menu");
+        commands.entity(*root_entity).with_children(|parent| {
+            parent.spawn(PlayersMenu).with_children(|parent| {
+                parent
+                    .spawn((
+                        Node {
+                            flex_direction: FlexDirection::Column,
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            padding: theme.padding.normal,
+                            row_gap: theme.gap.normal,
+                            ..Default::default()
+                        },
+                        theme.panel_background,
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((LabelKind::Normal, Text::new("Players")));
+
+                        parent
+                            .spawn(Node {
+                                display: Display::Grid,
+                                column_gap: theme.gap.normal,
+                                row_gap: theme.gap.normal,
+                                grid_template_columns: vec![GridTrack::auto(); 3],
+                                ..Default::default()
+                            })
+                            .with_children(|parent| {
+                                for client in connected_clients.iter() {
+                                    let client_id = client.id();
+                                    parent.spawn((
+                                        LabelKind::Normal,
+                                        Text::new(network::player_label(client_id)),
+                                    ));
+                                    parent
+                                        .spawn((
+                                            ButtonKind::Normal,
+                                            ModerationAction {
+                                                client_id,
+                                                ban: false,
+                                            },
+                                        ))
+                                        .with_child(Text::new("Kick"))
+                                        .observe(Self::confirm);
+                                    parent
+                                        .spawn((
+                                            ButtonKind::Normal,
+                                            ModerationAction {
+                                                client_id,
+                                                ban: true,
+                                            },
+                                        ))
+                                        .with_child(Text::new("Ban"))
+                                        .observe(Self::confirm);
+                                }
+                            });
+
+                        if connected_clients.is_empty() {
+                            parent.spawn((LabelKind::Normal, Text::new("No connected players")));
+                        }
+
+                        parent
+                            .spawn(ButtonKind::Normal)
+                            .with_child(Text::new("Close"))
+                            .observe(Self::close);
+                    });
+            });
+        });
+    }
+
+    fn confirm(
+        trigger: Trigger<Pointer<Click>>,
+        mut commands: Commands,
+        theme: Res<Theme>,
+        root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+        actions: Query<&ModerationAction>,
+    ) {
+        let action = *actions.get(trigger.entity()).unwrap();
+        let label = network::player_label(action.client_id);
+        let text = if action.ban {
+            format!("Ban {label}? They won't be able to join this world again.")
+        } else {
+            format!("Kick {label}?")
+        };
+
+        info!("showing moderation dialog");
+        commands.entity(*root_entity).with_children(|parent| {
+            parent.spawn((Dialog, action)).with_children(|parent| {
+                parent
+                    .spawn((
+                        Node {
+                            flex_direction: FlexDirection::Column,
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            padding: theme.padding.normal,
+                            row_gap: theme.gap.normal,
+                            ..Default::default()
+                        },
+                        theme.panel_background,
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((LabelKind::Normal, Text::new(text)));
+
+                        parent
+                            .spawn(Node {
+                                column_gap: theme.gap.normal,
+                                ..Default::default()
+                            })
+                            .with_children(|parent| {
+                                parent
+                                    .spawn(ButtonKind::Normal)
+                                    .with_child(Text::new("Confirm"))
+                                    .observe(Self::apply);
+                                parent
+                                    .spawn(ButtonKind::Normal)
+                                    .with_child(Text::new("Cancel"))
+                                    .observe(Self::cancel);
+                            });
+                    });
+            });
+        });
+    }
+
+    fn apply(
+        _trigger: Trigger<Pointer<Click>>,
+        mut commands: Commands,
+        dialog: Single<(Entity, &ModerationAction), With<Dialog>>,
+        menu_entity: Single<Entity, With<PlayersMenu>>,
+    ) {
+        let (dialog_entity, action) = *dialog;
+        commands.trigger(KickPlayer {
+            client_id: action.client_id,
+            ban: action.ban,
+        });
+        commands.entity(dialog_entity).despawn_recursive();
+        // Close the list since it now contains the removed player.
+        commands.entity(*menu_entity).despawn_recursive();
+    }
+
+    fn cancel(
+        _trigger: Trigger<Pointer<Click>>,
+        mut commands: Commands,
+        dialog_entity: Single<Entity, (With<Dialog>, With<ModerationAction>)>,
+    ) {
+        info!("cancelling moderation");
+        commands.entity(*dialog_entity).despawn_recursive();
+    }
+
+    fn close(
+        _trigger: Trigger<Pointer<Click>>,
+        mut commands: Commands,
+        menu_entity: Single<Entity, With<PlayersMenu>>,
+    ) {
+        info!("closing players menu");
+        commands.entity(*menu_entity).despawn_recursive();
+    }
+}
+
+/// Creates a players menu node.
+#[derive(Event)]
+pub(super) struct PlayersMenuOpen;
+
+#[derive(Component)]
+#[require(Dialog)]
+struct PlayersMenu;
+
+#[derive(Clone, Component, Copy)]
+struct ModerationAction {
+    client_id: ClientId,
+    ban: bool,
+}
@@ -1,38 +1,69 @@
 use std::fmt::Write;
 
-use bevy::{input::keyboard::KeyboardInput, prelude::*, reflect::GetPath};
+use bevy::{input::keyboard::KeyboardInput, prelude::*, reflect::GetPath, ui::FocusPolicy};
+use bevy_enhanced_input::prelude::*;
+use bevy_simple_text_input::TextInputValue;
 use strum::{EnumIter, IntoEnumIterator};
 
-use project_harmonia_base::settings::{Settings, SettingsApply};
+use project_harmonia_base::{
+    build_info::BuildInfo,
+    core::localization::{Locale, Localization, LocalizedText},
+    gpu_support::GpuSupport,
+    settings::{
+        AntiAliasing, GraphicsPreset, Settings, SettingsApply, MAX_CAMERA_SPEED, MAX_HISTORY_DEPTH,
+        MAX_SHADOW_CASCADES, MAX_SHADOW_MAP_SIZE, MIN_AUTOSAVE_INTERVAL, MIN_CAMERA_SPEED,
+        MIN_HISTORY_DEPTH, MIN_RENDER_DISTANCE, MIN_SHADOW_MAP_SIZE,
+    },
+};
 use project_harmonia_widgets::{
-    button::{ButtonKind, TabContent, Toggled},
+    button::{ButtonKind, Disabled, ExclusiveButton, RadioGroup, TabContent, Toggled},
     checkbox::Checkbox,
-    dialog::Dialog,
+    combo_box::ComboBox,
+    dialog::{Dialog, Layer},
     label::LabelKind,
-    theme::Theme,
+    number_input::NumberInput,
+    slider::Slider,
+    theme::{ReduceMotion, Theme},
 };
 
 pub(super) struct SettingsMenuPlugin;
 
 impl Plugin for SettingsMenuPlugin {
     fn build(&self, app: &mut App) {
-        app.add_observer(Self::setup).add_systems(
-            Update,
-            (
-                Self::update_mapping_text,
-                Self::read_binding.never_param_warn(),
-            )
-                .run_if(any_with_component::<SettingsMenu>),
-        );
+        app.add_observer(Self::setup)
+            .add_observer(Self::block_input)
+            .add_observer(Self::restore_input)
+            .add_observer(Self::restore_language)
+            .add_systems(
+                Update,
+                (
+                    Self::sync_reduce_motion.run_if(resource_changed::<Settings>),
+                    (
+                        Self::update_mapping_text,
+                        Self::read_binding.never_param_warn(),
+                        Self::preview_language.never_param_warn(),
+                    )
+                        .run_if(any_with_component::<SettingsMenu>),
+                ),
+            );
     }
 }
 
 impl SettingsMenuPlugin {
+    /// Widgets don't depend on settings, so the setting is mirrored into a separate resource.
+    fn sync_reduce_motion(settings: Res<Settings>, mut reduce_motion: ResMut<ReduceMotion>) {
+        reduce_motion.set_if_neq(ReduceMotion(settings.video.reduce_motion));
+    }
+
     fn setup(
         _trigger: Trigger<SettingsMenuOpen>,
         mut commands: Commands,
         mut tab_commands: Commands,
         settings: Res<Settings>,
+        gpu_support: Res<GpuSupport>,
+        build_info: Res<BuildInfo>,
+        localization: Res<Localization>,
+        locales: Res<Assets<Locale>>,
         theme: Res<Theme>,
         root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
     ) {
@@ -63,10 +94,22 @@ impl SettingsMenuPlugin {
 
                     for tab in SettingsTab::iter() {
                         let content_entity = match tab {
-                            SettingsTab::Video => setup_video_tab(parent, &theme, &settings),
+                            SettingsTab::Video => {
+                                setup_video_tab(parent, &theme, &settings, *gpu_support)
+                            }
+                            SettingsTab::Audio => setup_audio_tab(parent, &theme, &settings),
+                            SettingsTab::Interface => setup_interface_tab(
+                                parent,
+                                &theme,
+                                &settings,
+                                &localization,
+                                &locales,
+                            ),
+                            SettingsTab::Game => setup_game_tab(parent, &theme, &settings),
+                            SettingsTab::Camera => setup_camera_tab(parent, &theme, &settings),
                             SettingsTab::Keyboard => setup_keyboard_tab(parent, &theme, &settings),
                             SettingsTab::Developer => {
-                                setup_developer_tab(parent, &theme, &settings)
+                                setup_developer_tab(parent, &theme, &settings, *gpu_support)
                             }
                         };
 
@@ -76,7 +119,7 @@ impl SettingsMenuPlugin {
                                 TabContent(content_entity),
                                 Toggled(tab == Default::default()),
                             ))
-                            .with_child(Text::new(tab.text()))
+                            .with_child(LocalizedText::new(tab.key()))
                             .set_parent(tabs_entity);
                     }
 
@@ -90,31 +133,92 @@ impl SettingsMenuPlugin {
                             ..Default::default()
                         })
                         .with_children(|parent| {
+                            parent.spawn((
+                                LabelKind::Small,
+                                LocalizedText::new("menu.settings.version")
+                                    .with_arg("version", &*build_info),
+                                Node {
+                                    margin: UiRect::right(Val::Auto),
+                                    ..Default::default()
+                                },
+                            ));
                             parent
                                 .spawn(ButtonKind::Normal)
-                                .with_child(Text::new("Ok"))
+                                .with_child(LocalizedText::new("menu.ok"))
                                 .observe(Self::ok);
                             parent
                                 .spawn(ButtonKind::Normal)
-                                .with_child(Text::new("Cancel"))
+                                .with_child(LocalizedText::new("menu.cancel"))
                                 .observe(Self::cancel);
                         });
                 });
         });
     }
 
+    /// Prevents actions underneath the menu from triggering while it's open.
+    ///
+    /// The menu itself reads keys directly, so it's unaffected.
+    fn block_input(_trigger: Trigger<OnAdd, SettingsMenu>, mut sources: ResMut<ActionSources>) {
+        debug!("blocking actions input");
+        sources.keyboard = false;
+        sources.mouse_buttons = false;
+        sources.mouse_wheel = false;
+        sources.gamepad = false;
+    }
+
+    fn restore_input(
+        _trigger: Trigger<OnRemove, SettingsMenu>,
+        mut sources: ResMut<ActionSources>,
+    ) {
+        debug!("restoring actions input");
+        *sources = Default::default();
+    }
+
+    /// Shows the selected language immediately, before the settings are confirmed.
+    fn preview_language(
+        language_box: Single<(&ComboBox, &LanguageComboBox), Changed<ComboBox>>,
+        locales: Res<Assets<Locale>>,
+        mut localization: ResMut<Localization>,
+    ) {
+        let (combo_box, locale_ids) = *language_box;
+        if let Some(locale_id) = locale_ids.get(combo_box.selected) {
+            if localization.current() != locale_id {
+                info!("previewing locale `{locale_id}`");
+                localization.select(locale_id, &locales);
+            }
+        }
+    }
+
+    /// Returns the language from settings if the previewed one wasn't confirmed.
+    fn restore_language(
+        _trigger: Trigger<OnRemove, SettingsMenu>,
+        settings: Res<Settings>,
+        locales: Res<Assets<Locale>>,
+        mut localization: ResMut<Localization>,
+    ) {
+        if localization.current() != settings.language.locale {
+            debug!("restoring locale `{}`", settings.language.locale);
+            localization.select(&settings.language.locale, &locales);
+        }
+    }
+
     fn update_mapping_text(
-        buttons: Query<(&MappingButton, &Children), Changed<MappingButton>>,
+        localization: Res<Localization>,
+        buttons: Query<(Ref<MappingButton>, &Children)>,
         mut text: Query<&mut Text>,
     ) {
         for (mapping, children) in &buttons {
+            if !mapping.is_changed() && !localization.is_changed() {
+                continue;
+            }
+
             let mut iter = text.iter_many_mut(children);
             let mut text = iter.fetch_next().unwrap();
             text.clear();
             if let Some(key) = mapping.key {
                 write!(text, "{key:?}").unwrap();
             } else {
-                write!(text, "Empty").unwrap();
+                text.push_str(&localization.tr("menu.settings.empty_binding"));
             };
         }
     }
@@ -123,6 +227,7 @@ impl SettingsMenuPlugin {
         trigger: Trigger<Pointer<Click>>,
         mut commands: Commands,
         theme: Res<Theme>,
+        localization: Res<Localization>,
         root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
         buttons: Query<&MappingButton>,
     ) {
@@ -148,7 +253,8 @@ impl SettingsMenuPlugin {
                         .with_children(|parent| {
                             parent.spawn((
                                 BindingLabel,
-                                Text::new(format!("Binding \"{}\", press any key", mapping.name)),
+                                LocalizedText::new("menu.settings.binding")
+                                    .with_arg("action", localization.tr(mapping.name)),
                             ));
                             parent
                                 .spawn(Node {
@@ -166,17 +272,17 @@ impl SettingsMenuPlugin {
                                                 ..Default::default()
                                             },
                                         ))
-                                        .with_child(Text::new("Replace"))
+                                        .with_child(LocalizedText::new("menu.settings.replace"))
                                         .observe(Self::replace_binding);
 
                                     parent
                                         .spawn(ButtonKind::Normal)
-                                        .with_child(Text::new("Delete"))
+                                        .with_child(LocalizedText::new("menu.settings.delete"))
                                         .observe(Self::delete_binding);
 
                                     parent
                                         .spawn(ButtonKind::Normal)
-                                        .with_child(Text::new("Cancel"))
+                                        .with_child(LocalizedText::new("menu.cancel"))
                                         .observe(Self::cancel_binding);
                                 });
                         });
@@ -187,9 +293,10 @@ impl SettingsMenuPlugin {
     fn read_binding(
         mut commands: Commands,
         mut key_events: EventReader<KeyboardInput>,
+        localization: Res<Localization>,
         dialog: Single<(Entity, &mut BindingDialog)>,
+        label_entity: Single<Entity, With<BindingLabel>>,
         mut buttons: Query<(Entity, &mut MappingButton)>,
-        mut labels: Query<&mut Text, With<BindingLabel>>,
         mut replace_nodes: Query<&mut Node, With<ReplaceButton>>,
     ) {
         let Some(&KeyboardInput { key_code, .. }) = key_events.read().last() else {
@@ -197,13 +304,20 @@ impl SettingsMenuPlugin {
         };
 
         let (dialog_entity, mut dialog) = dialog.into_inner();
+        let (_, binding) = buttons
+            .get(dialog.binding_button)
+            .expect("binding dialog should point to a button with mapping");
+        let mod_keys = binding.mod_keys;
         if let Some((conflict_entity, mapping)) = buttons
             .iter()
-            .find(|(_, mapping)| mapping.key == Some(key_code))
+            .find(|(_, mapping)| mapping.key == Some(key_code) && mapping.mod_keys == mod_keys)
         {
             info!("found conflict with '{}' for `{key_code:?}`", mapping.name);
-            **labels.single_mut() =
-                format!("\"{key_code:?}\" is already used by \"{:?}\"", mapping.name);
+            commands.entity(*label_entity).insert(
+                LocalizedText::new("menu.settings.binding_conflict")
+                    .with_arg("key", format!("{key_code:?}"))
+                    .with_arg("action", localization.tr(mapping.name)),
+            );
 
             dialog.conflict_button = Some(conflict_entity);
 
@@ -273,15 +387,44 @@ impl SettingsMenuPlugin {
         menu_entity: Single<Entity, With<SettingsMenu>>,
         buttons: Query<(&MappingButton, &SettingsField)>,
         checkboxes: Query<(&Checkbox, &SettingsField)>,
+        number_inputs: Query<(&NumberInput, &SettingsField)>,
+        sliders: Query<(&Slider, &SettingsField)>,
+        anti_aliasing_buttons: Query<(&AntiAliasingButton, &Toggled)>,
+        language_box: Single<(&ComboBox, &LanguageComboBox)>,
     ) {
         info!("confirming settings");
 
+        let (combo_box, locale_ids) = *language_box;
+        if let Some(locale_id) = locale_ids.get(combo_box.selected) {
+            settings.language.locale = locale_id.clone();
+        }
+
+        if let Some((button, _)) = anti_aliasing_buttons.iter().find(|(_, toggled)| ***toggled) {
+            settings.video.anti_aliasing = button.0;
+        }
+
         for (checkbox, field) in &checkboxes {
             let field_value = settings
                 .path_mut::<bool>(field.0)
                 .expect("fields with checkboxes should be stored as bools");
             *field_value = checkbox.0;
         }
+        for (number_input, field) in &number_inputs {
+            let field_value = settings
+                .path_mut::<u32>(field.0)
+                .expect("fields with number inputs should be stored as u32");
+            *field_value = number_input
+                .value
+                .try_into()
+                .expect("number inputs for settings shouldn't allow negative values");
+        }
+        for (slider, field) in &sliders {
+            let field_value = settings
+                .path_mut::<f32>(field.0)
+                .expect("fields with sliders should be stored as f32");
+            // Sliders display percents.
+            *field_value = slider.value as f32 / 100.0;
+        }
         settings.keyboard.clear();
         for (mapping, field) in &buttons {
             if let Some(key) = mapping.key {
@@ -296,6 +439,37 @@ impl SettingsMenuPlugin {
         commands.entity(*menu_entity).despawn_recursive();
     }
 
+    /// Fills inputs marked with [`PresetField`] with values from the preset.
+    ///
+    /// Applied together with other changes on confirmation.
+    fn apply_preset(
+        trigger: Trigger<Pointer<Click>>,
+        settings: Res<Settings>,
+        buttons: Query<&PresetButton>,
+        number_inputs: Query<(&Children, &SettingsField), (With<NumberInput>, With<PresetField>)>,
+        mut checkboxes: Query<(&mut Checkbox, &SettingsField), With<PresetField>>,
+        mut edits: Query<&mut TextInputValue>,
+    ) {
+        let preset = **buttons.get(trigger.entity()).unwrap();
+        let mut preset_settings = settings.clone();
+        preset_settings.video.set_preset(preset);
+
+        info!("applying `{}` preset", preset.text());
+        for (children, field) in &number_inputs {
+            let value = preset_settings
+                .path::<u32>(field.0)
+                .expect("fields with number inputs should be stored as u32");
+            let mut iter = edits.iter_many_mut(children);
+            let mut text = iter.fetch_next().expect("number input should have edit");
+            text.0 = value.to_string();
+        }
+        for (mut checkbox, field) in &mut checkboxes {
+            checkbox.0 = *preset_settings
+                .path::<bool>(field.0)
+                .expect("fields with checkboxes should be stored as bools");
+        }
+    }
+
     fn cancel(
         _trigger: Trigger<Pointer<Click>>,
         mut commands: Commands,
@@ -317,7 +491,16 @@ macro_rules! settings_field {
 #[derive(Component, Clone, Copy)]
 struct SettingsField(&'static str);
 
-fn setup_video_tab(parent: &mut ChildBuilder, theme: &Theme, settings: &Settings) -> Entity {
+/// Marks inputs that are filled by [`GraphicsPreset`].
+#[derive(Component)]
+struct PresetField;
+
+fn setup_video_tab(
+    parent: &mut ChildBuilder,
+    theme: &Theme,
+    settings: &Settings,
+    gpu_support: GpuSupport,
+) -> Entity {
     parent
         .spawn(Node {
             padding: theme.padding.normal,
@@ -332,11 +515,395 @@ fn setup_video_tab(parent: &mut ChildBuilder, theme: &Theme, settings: &Settings
                     Checkbox(video.fullscreen),
                     settings_field!(video.fullscreen),
                 ))
-                .with_child(Text::new("Fullscreen"));
+                .with_child(LocalizedText::new("menu.settings.fullscreen"));
+            parent
+                .spawn((
+                    Checkbox(video.nameplates),
+                    settings_field!(video.nameplates),
+                ))
+                .with_child(LocalizedText::new("menu.settings.nameplates"));
+            parent
+                .spawn((
+                    Checkbox(video.selection_ring),
+                    settings_field!(video.selection_ring),
+                ))
+                .with_child(LocalizedText::new("menu.settings.selection_ring"));
+            parent
+                .spawn((
+                    Checkbox(video.menu_background),
+                    settings_field!(video.menu_background),
+                ))
+                .with_child(LocalizedText::new("menu.settings.menu_background"));
+            parent
+                .spawn((
+                    Checkbox(video.reduce_motion),
+                    settings_field!(video.reduce_motion),
+                ))
+                .with_child(LocalizedText::new("menu.settings.reduce_motion"));
+            parent
+                .spawn(Node {
+                    align_items: AlignItems::Center,
+                    column_gap: theme.gap.normal,
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        LabelKind::Normal,
+                        LocalizedText::new("menu.settings.anti_aliasing"),
+                    ));
+                    parent.spawn(RadioGroup).with_children(|parent| {
+                        for anti_aliasing in AntiAliasing::iter() {
+                            let mut button = parent.spawn((
+                                AntiAliasingButton(anti_aliasing),
+                                Toggled(anti_aliasing == video.anti_aliasing),
+                            ));
+                            if !gpu_support.anti_aliasing(anti_aliasing) {
+                                button.insert(Disabled);
+                            }
+                            button.with_child(LocalizedText::new(anti_aliasing_key(anti_aliasing)));
+                        }
+                    });
+                });
+            parent.spawn((
+                LabelKind::Small,
+                LocalizedText::new("menu.settings.msaa_hint"),
+            ));
+            parent
+                .spawn(Node {
+                    align_items: AlignItems::Center,
+                    column_gap: theme.gap.normal,
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        LabelKind::Normal,
+                        LocalizedText::new("menu.settings.preset"),
+                    ));
+                    for preset in GraphicsPreset::iter() {
+                        parent
+                            .spawn((PresetButton(preset), ButtonKind::Normal))
+                            .with_child(LocalizedText::new(preset_key(preset)))
+                            .observe(SettingsMenuPlugin::apply_preset);
+                    }
+                });
+            parent
+                .spawn((
+                    Checkbox(video.dynamic_sky),
+                    settings_field!(video.dynamic_sky),
+                    PresetField,
+                ))
+                .with_child(LocalizedText::new("menu.settings.dynamic_sky"));
+            setup_input_row(
+                parent,
+                theme,
+                "menu.settings.shadow_map_size",
+                (
+                    NumberInput {
+                        value: video.shadow_map_size as i32,
+                        min: MIN_SHADOW_MAP_SIZE as i32,
+                        max: MAX_SHADOW_MAP_SIZE as i32,
+                        step: MIN_SHADOW_MAP_SIZE as i32,
+                    },
+                    settings_field!(video.shadow_map_size),
+                    PresetField,
+                ),
+            );
+            setup_input_row(
+                parent,
+                theme,
+                "menu.settings.shadow_cascades",
+                (
+                    NumberInput {
+                        value: video.shadow_cascades as i32,
+                        min: 1,
+                        max: MAX_SHADOW_CASCADES as i32,
+                        step: 1,
+                    },
+                    settings_field!(video.shadow_cascades),
+                    PresetField,
+                ),
+            );
+            setup_input_row(
+                parent,
+                theme,
+                "menu.settings.render_distance",
+                (
+                    NumberInput {
+                        value: video.render_distance as i32,
+                        min: MIN_RENDER_DISTANCE as i32,
+                        max: 2000,
+                        step: 50,
+                    },
+                    settings_field!(video.render_distance),
+                    PresetField,
+                ),
+            );
+        })
+        .id()
+}
+
+/// Spawns a row with a label for the input.
+///
+/// Used for all inputs that don't carry their own label, such as number inputs, sliders and combo boxes.
+fn anti_aliasing_key(anti_aliasing: AntiAliasing) -> &'static str {
+    match anti_aliasing {
+        AntiAliasing::None => "menu.settings.anti_aliasing_none",
+        AntiAliasing::Msaa2 => "menu.settings.anti_aliasing_msaa2",
+        AntiAliasing::Msaa4 => "menu.settings.anti_aliasing_msaa4",
+        AntiAliasing::Taa => "menu.settings.anti_aliasing_taa",
+    }
+}
+
+fn preset_key(preset: GraphicsPreset) -> &'static str {
+    match preset {
+        GraphicsPreset::Low => "menu.settings.preset_low",
+        GraphicsPreset::Medium => "menu.settings.preset_medium",
+        GraphicsPreset::High => "menu.settings.preset_high",
+    }
+}
+
+fn setup_input_row(
+    parent: &mut ChildBuilder,
+    theme: &Theme,
+    label_key: &'static str,
+    input: impl Bundle,
+) {
+    parent
+        .spawn(Node {
+            align_items: AlignItems::Center,
+            column_gap: theme.gap.normal,
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent.spawn((LabelKind::Normal, LocalizedText::new(label_key)));
+            parent.spawn(input);
+        });
+}
+
+fn setup_audio_tab(parent: &mut ChildBuilder, theme: &Theme, settings: &Settings) -> Entity {
+    parent
+        .spawn(Node {
+            padding: theme.padding.normal,
+            row_gap: theme.gap.normal,
+            flex_direction: FlexDirection::Column,
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            let audio = &settings.audio;
+            setup_input_row(
+                parent,
+                theme,
+                "menu.settings.master_volume",
+                (volume_slider(audio.master), settings_field!(audio.master)),
+            );
+            setup_input_row(
+                parent,
+                theme,
+                "menu.settings.music_volume",
+                (volume_slider(audio.music), settings_field!(audio.music)),
+            );
+            setup_input_row(
+                parent,
+                theme,
+                "menu.settings.sfx_volume",
+                (volume_slider(audio.sfx), settings_field!(audio.sfx)),
+            );
         })
         .id()
 }
 
+fn volume_slider(volume: f32) -> Slider {
+    Slider {
+        value: (volume * 100.0).round() as i32,
+        min: 0,
+        max: 100,
+        step: 1,
+        suffix: "%",
+    }
+}
+
+fn setup_interface_tab(
+    parent: &mut ChildBuilder,
+    theme: &Theme,
+    settings: &Settings,
+    localization: &Localization,
+    locales: &Assets<Locale>,
+) -> Entity {
+    parent
+        .spawn(Node {
+            padding: theme.padding.normal,
+            row_gap: theme.gap.normal,
+            flex_direction: FlexDirection::Column,
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            let (mut locale_ids, mut names): (Vec<_>, Vec<_>) = localization
+                .locales(locales)
+                .map(|(id, name)| (id.to_string(), name.to_string()))
+                .unzip();
+            let locale_id = &settings.language.locale;
+            let selected = match locale_ids.iter().position(|id| id == locale_id) {
+                Some(index) => index,
+                None => {
+                    // Keep the missing locale as an option to avoid silently replacing it on confirmation.
+                    warn!("locale `{locale_id}` from settings doesn't exist");
+                    locale_ids.push(locale_id.clone());
+                    names.push(locale_id.clone());
+                    locale_ids.len() - 1
+                }
+            };
+            setup_input_row(
+                parent,
+                theme,
+                "menu.settings.language",
+                (
+                    ComboBox {
+                        options: names,
+                        selected,
+                    },
+                    LanguageComboBox(locale_ids),
+                ),
+            );
+
+            let interface = &settings.interface;
+            parent
+                .spawn(Node {
+                    align_items: AlignItems::Center,
+                    column_gap: theme.gap.normal,
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        LabelKind::Normal,
+                        LocalizedText::new("menu.settings.recent_objects"),
+                    ));
+                    parent.spawn((
+                        NumberInput {
+                            value: interface.recent_objects as i32,
+                            min: 0,
+                            max: MAX_RECENT_OBJECTS,
+                            step: 1,
+                        },
+                        settings_field!(interface.recent_objects),
+                    ));
+                });
+            parent
+                .spawn((
+                    Checkbox(interface.minimap),
+                    settings_field!(interface.minimap),
+                ))
+                .with_child(LocalizedText::new("menu.settings.minimap"));
+            setup_input_row(
+                parent,
+                theme,
+                "menu.settings.history_depth",
+                (
+                    NumberInput {
+                        value: interface.history_depth() as i32,
+                        min: MIN_HISTORY_DEPTH as i32,
+                        max: MAX_HISTORY_DEPTH as i32,
+                        step: 5,
+                    },
+                    settings_field!(interface.history_depth),
+                ),
+            );
+        })
+        .id()
+}
+
+/// Maximum value for [`InterfaceSettings::recent_objects`](project_harmonia_base::settings::InterfaceSettings::recent_objects).
+const MAX_RECENT_OBJECTS: i32 = 20;
+
+fn setup_game_tab(parent: &mut ChildBuilder, theme: &Theme, settings: &Settings) -> Entity {
+    parent
+        .spawn(Node {
+            padding: theme.padding.normal,
+            row_gap: theme.gap.normal,
+            flex_direction: FlexDirection::Column,
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            let autosave = &settings.autosave;
+            parent
+                .spawn((
+                    Checkbox(autosave.enabled),
+                    settings_field!(autosave.enabled),
+                ))
+                .with_child(LocalizedText::new("menu.settings.autosave"));
+            setup_input_row(
+                parent,
+                theme,
+                "menu.settings.autosave_interval",
+                (
+                    NumberInput {
+                        value: autosave.interval_secs as i32,
+                        min: MIN_AUTOSAVE_INTERVAL as i32,
+                        max: 60 * 60,
+                        step: 30,
+                    },
+                    settings_field!(autosave.interval_secs),
+                ),
+            );
+        })
+        .id()
+}
+
+fn setup_camera_tab(parent: &mut ChildBuilder, theme: &Theme, settings: &Settings) -> Entity {
+    parent
+        .spawn(Node {
+            padding: theme.padding.normal,
+            row_gap: theme.gap.normal,
+            flex_direction: FlexDirection::Column,
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            let camera = &settings.camera;
+            setup_input_row(
+                parent,
+                theme,
+                "menu.settings.pan_speed",
+                (
+                    camera_speed_input(camera.pan_speed),
+                    settings_field!(camera.pan_speed),
+                ),
+            );
+            setup_input_row(
+                parent,
+                theme,
+                "menu.settings.rotation_speed",
+                (
+                    camera_speed_input(camera.rotation_speed),
+                    settings_field!(camera.rotation_speed),
+                ),
+            );
+            setup_input_row(
+                parent,
+                theme,
+                "menu.settings.zoom_speed",
+                (
+                    camera_speed_input(camera.zoom_speed),
+                    settings_field!(camera.zoom_speed),
+                ),
+            );
+            parent
+                .spawn((Checkbox(camera.invert_x), settings_field!(camera.invert_x)))
+                .with_child(LocalizedText::new("menu.settings.invert_x"));
+            parent
+                .spawn((Checkbox(camera.invert_y), settings_field!(camera.invert_y)))
+                .with_child(LocalizedText::new("menu.settings.invert_y"));
+        })
+        .id()
+}
+
+fn camera_speed_input(speed: u32) -> NumberInput {
+    NumberInput {
+        value: speed as i32,
+        min: MIN_CAMERA_SPEED as i32,
+        max: MAX_CAMERA_SPEED as i32,
+        step: 10,
+    }
+}
+
 const INPUTS_PER_ACTION: usize = 3;
 
 fn setup_keyboard_tab(parent: &mut ChildBuilder, theme: &Theme, settings: &Settings) -> Entity {
@@ -352,70 +919,106 @@ fn setup_keyboard_tab(parent: &mut ChildBuilder, theme: &Theme, settings: &Setti
             let keyboard = &settings.keyboard;
             setup_action_row(
                 parent,
-                "Camera forward",
+                "menu.settings.camera_forward",
                 &keyboard.camera_forward,
                 settings_field!(keyboard.camera_forward),
             );
             setup_action_row(
                 parent,
-                "Camera left",
+                "menu.settings.camera_left",
                 &keyboard.camera_left,
                 settings_field!(keyboard.camera_left),
             );
             setup_action_row(
                 parent,
-                "Camera backward",
+                "menu.settings.camera_backward",
                 &keyboard.camera_backward,
                 settings_field!(keyboard.camera_backward),
             );
             setup_action_row(
                 parent,
-                "Camera right",
+                "menu.settings.camera_right",
                 &keyboard.camera_right,
                 settings_field!(keyboard.camera_right),
             );
             setup_action_row(
                 parent,
-                "Rotate left",
+                "menu.settings.rotate_left",
                 &keyboard.rotate_left,
                 settings_field!(keyboard.rotate_left),
             );
             setup_action_row(
                 parent,
-                "Rotate right",
+                "menu.settings.rotate_right",
                 &keyboard.rotate_right,
                 settings_field!(keyboard.rotate_right),
             );
             setup_action_row(
                 parent,
-                "Zoom in",
+                "menu.settings.zoom_in",
                 &keyboard.zoom_in,
                 settings_field!(keyboard.zoom_in),
             );
             setup_action_row(
                 parent,
-                "Zoom out",
+                "menu.settings.zoom_out",
                 &keyboard.zoom_out,
                 settings_field!(keyboard.zoom_out),
             );
             setup_action_row(
                 parent,
-                "Delete object",
+                "menu.settings.delete_object",
                 &keyboard.delete,
                 settings_field!(keyboard.delete),
             );
             setup_action_row(
                 parent,
-                "Free placement",
+                "menu.settings.free_placement",
                 &keyboard.free_placement,
                 settings_field!(keyboard.free_placement),
             );
             setup_action_row(
                 parent,
-                "Ordinal placement",
+                "menu.settings.ordinal_placement",
                 &keyboard.ordinal_placement,
                 settings_field!(keyboard.ordinal_placement),
             );
+            setup_action_row(
+                parent,
+                "menu.settings.next_member",
+                &keyboard.next_member,
+                settings_field!(keyboard.next_member),
+            );
+            setup_action_row(
+                parent,
+                "menu.settings.previous_member",
+                &keyboard.previous_member,
+                settings_field!(keyboard.previous_member),
+            );
+            setup_modified_action_row(
+                parent,
+                "menu.settings.undo",
+                ModKeys::CONTROL,
+                &keyboard.undo,
+                settings_field!(keyboard.undo),
+            );
+            setup_modified_action_row(
+                parent,
+                "menu.settings.redo",
+                ModKeys::CONTROL | ModKeys::SHIFT,
+                &keyboard.redo,
+                settings_field!(keyboard.redo),
+            );
+            parent
+                .spawn((
+                    Checkbox(keyboard.keyboard_placement),
+                    settings_field!(keyboard.keyboard_placement),
+                    Node {
+                        grid_column: GridPlacement::span(INPUTS_PER_ACTION as u16 + 1),
+                        ..Default::default()
+                    },
+                ))
+                .with_child(LocalizedText::new("menu.settings.keyboard_placement"));
         })
         .id()
 }
@@ -426,13 +1029,25 @@ fn setup_action_row(
     keys: &[KeyCode],
     field: SettingsField,
 ) {
-    parent.spawn((LabelKind::Normal, Text::new(name)));
+    setup_modified_action_row(parent, name, ModKeys::empty(), keys, field);
+}
+
+/// Like [`setup_action_row`], but for actions that are bound together with modifiers.
+fn setup_modified_action_row(
+    parent: &mut ChildBuilder,
+    name: &'static str,
+    mod_keys: ModKeys,
+    keys: &[KeyCode],
+    field: SettingsField,
+) {
+    parent.spawn((LabelKind::Normal, LocalizedText::new(name)));
     for index in 0..INPUTS_PER_ACTION {
         parent
             .spawn((
                 field,
                 MappingButton {
                     name,
+                    mod_keys,
                     key: keys.get(index).copied(),
                 },
             ))
@@ -441,7 +1056,12 @@ fn setup_action_row(
     }
 }
 
-fn setup_developer_tab(parent: &mut ChildBuilder, theme: &Theme, settings: &Settings) -> Entity {
+fn setup_developer_tab(
+    parent: &mut ChildBuilder,
+    theme: &Theme,
+    settings: &Settings,
+    gpu_support: GpuSupport,
+) -> Entity {
     parent
         .spawn(Node {
             padding: theme.padding.normal,
@@ -456,28 +1076,48 @@ fn setup_developer_tab(parent: &mut ChildBuilder, theme: &Theme, settings: &Sett
                     Checkbox(developer.free_camera_rotation),
                     settings_field!(developer.free_camera_rotation),
                 ))
-                .with_child(Text::new("Free camera rotation"));
-            parent
-                .spawn((
-                    Checkbox(developer.wireframe),
-                    settings_field!(developer.wireframe),
-                ))
-                .with_child(Text::new("Display wireframe"));
+                .with_child(LocalizedText::new("menu.settings.free_camera_rotation"));
+            let mut wireframe = parent.spawn((
+                Checkbox(developer.wireframe),
+                settings_field!(developer.wireframe),
+            ));
+            if !gpu_support.wireframe {
+                wireframe.insert(Disabled);
+            }
+            wireframe.with_child(LocalizedText::new("menu.settings.wireframe"));
             parent
                 .spawn((
                     Checkbox(developer.colliders),
                     settings_field!(developer.colliders),
                 ))
-                .with_child(Text::new("Display colliders"));
+                .with_child(LocalizedText::new("menu.settings.colliders"));
             parent
                 .spawn((Checkbox(developer.paths), settings_field!(developer.paths)))
-                .with_child(Text::new("Display navigation paths"));
+                .with_child(LocalizedText::new("menu.settings.paths"));
             parent
                 .spawn((
                     Checkbox(developer.nav_mesh),
                     settings_field!(developer.nav_mesh),
                 ))
-                .with_child(Text::new("Display navigation mesh"));
+                .with_child(LocalizedText::new("menu.settings.nav_mesh"));
+            parent
+                .spawn((
+                    Checkbox(developer.lazy_colliders),
+                    settings_field!(developer.lazy_colliders),
+                ))
+                .with_child(LocalizedText::new("menu.settings.lazy_colliders"));
+            parent
+                .spawn((
+                    Checkbox(developer.cheats),
+                    settings_field!(developer.cheats),
+                ))
+                .with_child(LocalizedText::new("menu.settings.cheats"));
+            parent
+                .spawn((
+                    Checkbox(developer.debug_logs),
+                    settings_field!(developer.debug_logs),
+                ))
+                .with_child(LocalizedText::new("menu.settings.debug_logs"));
         })
         .id()
 }
@@ -486,27 +1126,56 @@ fn setup_developer_tab(parent: &mut ChildBuilder, theme: &Theme, settings: &Sett
 #[derive(Event)]
 pub(super) struct SettingsMenuOpen;
 
+/// Full-screen menu that closes on Escape like a regular layer.
+///
+/// Spawned as the last child of the root node, so it covers the HUD and the in-game menu.
 #[derive(Component)]
+#[require(Layer, FocusPolicy(|| FocusPolicy::Block))]
 struct SettingsMenu;
 
 #[derive(Default, EnumIter, PartialEq, Clone, Copy)]
 enum SettingsTab {
     #[default]
     Video,
+    Audio,
+    Interface,
+    Game,
+    Camera,
     Keyboard,
     Developer,
 }
 
 impl SettingsTab {
-    fn text(self) -> &'static str {
+    fn key(self) -> &'static str {
         match self {
-            SettingsTab::Video => "Video",
-            SettingsTab::Keyboard => "Keyboard",
-            SettingsTab::Developer => "Developer",
+            SettingsTab::Video => "menu.settings.video",
+            SettingsTab::Audio => "menu.settings.audio",
+            SettingsTab::Interface => "menu.settings.interface",
+            SettingsTab::Game => "menu.settings.game",
+            SettingsTab::Camera => "menu.settings.camera",
+            SettingsTab::Keyboard => "menu.settings.keyboard",
+            SettingsTab::Developer => "menu.settings.developer",
         }
     }
 }
 
+/// Stores locale IDs in the order of the combo box options.
+#[derive(Component, Deref)]
+#[require(Name(|| Name::new("Language combo box")))]
+struct LanguageComboBox(Vec<String>);
+
+#[derive(Component, Deref)]
+#[require(Name(|| Name::new("Preset button")))]
+struct PresetButton(GraphicsPreset);
+
+#[derive(Component)]
+#[require(
+    Name(|| Name::new("Anti-aliasing button")),
+    ButtonKind(|| ButtonKind::Normal),
+    ExclusiveButton
+)]
+struct AntiAliasingButton(AntiAliasing);
+
 #[derive(Component)]
 #[require(Name(|| Name::new("Replace button")), ButtonKind(|| ButtonKind::Normal))]
 struct ReplaceButton;
@@ -515,12 +1184,17 @@ struct ReplaceButton;
 #[derive(Component)]
 #[require(Name(|| Name::new("Mapping button")), ButtonKind(|| ButtonKind::Normal))]
 struct MappingButton {
+    /// Localization key of the action name.
     name: &'static str,
+    /// Modifiers that the action requires in addition to the key.
+    ///
+    /// Not editable, used only to detect conflicts.
+    mod_keys: ModKeys,
     key: Option<KeyCode>,
 }
 
 #[derive(Component)]
-#[require(Dialog)]
+#[require(Dialog, Layer(|| Layer::Manual))]
 struct BindingDialog {
     binding_button: Entity,
     conflict_button: Option<Entity>,
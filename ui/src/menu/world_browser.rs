@@ -1,31 +1,56 @@
-use std::{fs, net::Ipv4Addr};
+#[cfg(feature = "networking")]
+use std::net::Ipv4Addr;
 
-use anyhow::{Context, Result};
+use anyhow::Result;
+#[cfg(feature = "networking")]
+use anyhow::{ensure, Context};
 use bevy::prelude::*;
+#[cfg(feature = "networking")]
 use bevy_replicon::prelude::*;
+#[cfg(feature = "networking")]
 use bevy_replicon_renet::{
     renet::{ConnectionConfig, RenetClient, RenetServer},
     RenetChannelsExt,
 };
 use bevy_simple_text_input::TextInputValue;
+use strum::IntoEnumIterator;
 
 use super::MenuState;
+use crate::transition::TransitionTo;
 use project_harmonia_base::{
-    core::GameState,
+    core::{
+        localization::{Localization, LocalizedText},
+        GameState,
+    },
     error_message::error_message,
     game_paths::GamePaths,
-    game_world::{GameLoad, WorldName},
-    network::{self, DEFAULT_PORT},
+    game_world::{
+        difficulty::Difficulty, AutosaveLoad, CorruptWorld, GameLoad, WorldDescription, WorldName,
+    },
 };
+#[cfg(feature = "networking")]
+use project_harmonia_base::{
+    game_world::spectator::Spectator,
+    network::{self, ConnectionInfo, DEFAULT_PORT},
+};
+use project_harmonia_widgets::{
+    button::{ButtonKind, ExclusiveButton, RadioGroup, Toggled},
+    dialog::Dialog,
+    label::LabelKind,
+    text_edit::{DefaultButton, TextEdit},
+    theme::Theme,
+};
+#[cfg(feature = "networking")]
 use project_harmonia_widgets::{
-    button::ButtonKind, dialog::Dialog, label::LabelKind, text_edit::TextEdit, theme::Theme,
+    checkbox::Checkbox, number_input::NumberInput, text_edit::InvalidEdit,
 };
 
 pub(super) struct WorldBrowserPlugin;
 
 impl Plugin for WorldBrowserPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(MenuState::WorldBrowser), Self::setup);
+        app.add_observer(Self::show_corrupt_dialog)
+            .add_systems(OnEnter(MenuState::WorldBrowser), Self::setup);
     }
 }
 
@@ -52,7 +77,10 @@ impl WorldBrowserPlugin {
                     },
                 ))
                 .with_children(|parent| {
-                    parent.spawn((LabelKind::Large, Text::new("World browser")));
+                    parent.spawn((
+                        LabelKind::Large,
+                        LocalizedText::new("menu.world_browser.title"),
+                    ));
                     parent
                         .spawn(Node {
                             width: Val::Percent(100.0),
@@ -84,7 +112,7 @@ impl WorldBrowserPlugin {
                         .with_children(|parent| {
                             parent
                                 .spawn(ButtonKind::Normal)
-                                .with_child(Text::new("Back"))
+                                .with_child(LocalizedText::new("menu.back"))
                                 .observe(Self::back);
                             parent.spawn(Node {
                                 width: Val::Percent(100.0),
@@ -92,11 +120,12 @@ impl WorldBrowserPlugin {
                             });
                             parent
                                 .spawn(ButtonKind::Normal)
-                                .with_child(Text::new("Create"))
+                                .with_child(LocalizedText::new("menu.world_browser.create"))
                                 .observe(Self::create);
+                            #[cfg(feature = "networking")]
                             parent
                                 .spawn(ButtonKind::Normal)
-                                .with_child(Text::new("Join"))
+                                .with_child(LocalizedText::new("menu.world_browser.join"))
                                 .observe(Self::join);
                         });
                 });
@@ -118,6 +147,76 @@ impl WorldBrowserPlugin {
         commands.trigger(GameLoad);
     }
 
+    fn show_corrupt_dialog(
+        _trigger: Trigger<CorruptWorld>,
+        mut commands: Commands,
+        theme: Res<Theme>,
+        world_name: Res<WorldName>,
+        root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+    ) {
+        commands.entity(*root_entity).with_children(|parent| {
+            info!("showing corrupt world dialog");
+            parent.spawn(CorruptWorldDialog).with_children(|parent| {
+                parent
+                    .spawn((
+                        Node {
+                            flex_direction: FlexDirection::Column,
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            padding: theme.padding.normal,
+                            row_gap: theme.gap.normal,
+                            ..Default::default()
+                        },
+                        theme.panel_background,
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            LabelKind::Normal,
+                            LocalizedText::new("menu.world_browser.corrupt_world")
+                                .with_arg("world", &world_name.0),
+                        ));
+
+                        parent
+                            .spawn(Node {
+                                column_gap: theme.gap.normal,
+                                ..Default::default()
+                            })
+                            .with_children(|parent| {
+                                parent
+                                    .spawn(ButtonKind::Normal)
+                                    .with_child(LocalizedText::new(
+                                        "menu.world_browser.load_autosave",
+                                    ))
+                                    .observe(Self::load_autosave);
+                                parent
+                                    .spawn(ButtonKind::Normal)
+                                    .with_child(LocalizedText::new("menu.cancel"))
+                                    .observe(Self::cancel_autosave);
+                            });
+                    });
+            });
+        });
+    }
+
+    fn load_autosave(
+        _trigger: Trigger<Pointer<Click>>,
+        mut commands: Commands,
+        dialog_entity: Single<Entity, With<CorruptWorldDialog>>,
+    ) {
+        commands.trigger(AutosaveLoad);
+        commands.entity(*dialog_entity).despawn_recursive();
+    }
+
+    fn cancel_autosave(
+        _trigger: Trigger<Pointer<Click>>,
+        mut commands: Commands,
+        dialog_entity: Single<Entity, With<CorruptWorldDialog>>,
+    ) {
+        info!("cancelling autosave loading");
+        commands.entity(*dialog_entity).despawn_recursive();
+    }
+
+    #[cfg(feature = "networking")]
     fn host(
         trigger: Trigger<Pointer<Click>>,
         mut commands: Commands,
@@ -149,7 +248,8 @@ impl WorldBrowserPlugin {
                     .with_children(|parent| {
                         parent.spawn((
                             LabelKind::Normal,
-                            Text::new(format!("Host {}", &**world_name)),
+                            LocalizedText::new("menu.world_browser.host_world")
+                                .with_arg("world", &**world_name),
                         ));
 
                         parent
@@ -159,10 +259,17 @@ impl WorldBrowserPlugin {
                                 ..Default::default()
                             })
                             .with_children(|parent| {
-                                parent.spawn((LabelKind::Normal, Text::new("Port:")));
-                                parent.spawn((PortEdit, TextInputValue(DEFAULT_PORT.to_string())));
+                                parent.spawn((
+                                    LabelKind::Normal,
+                                    LocalizedText::new("menu.world_browser.port"),
+                                ));
+                                parent.spawn(PortInput);
                             });
 
+                        parent
+                            .spawn(SpectatorCheckbox)
+                            .with_child(LocalizedText::new("menu.world_browser.join_as_spectator"));
+
                         parent
                             .spawn(Node {
                                 column_gap: theme.gap.normal,
@@ -170,12 +277,12 @@ impl WorldBrowserPlugin {
                             })
                             .with_children(|parent| {
                                 parent
-                                    .spawn(ButtonKind::Normal)
-                                    .with_child(Text::new("Host"))
+                                    .spawn((ButtonKind::Normal, DefaultButton))
+                                    .with_child(LocalizedText::new("menu.world_browser.host"))
                                     .observe(Self::confirm_host.pipe(error_message));
                                 parent
                                     .spawn(ButtonKind::Normal)
-                                    .with_child(Text::new("Cancel"))
+                                    .with_child(LocalizedText::new("menu.cancel"))
                                     .observe(Self::cancel_host);
                             });
                     });
@@ -214,10 +321,8 @@ impl WorldBrowserPlugin {
                     .with_children(|parent| {
                         parent.spawn((
                             LabelKind::Normal,
-                            Text::new(format!(
-                                "Are you sure you want to remove world {}?",
-                                &**world_name
-                            )),
+                            LocalizedText::new("menu.world_browser.remove_world")
+                                .with_arg("world", &**world_name),
                         ));
 
                         parent
@@ -228,11 +333,11 @@ impl WorldBrowserPlugin {
                             .with_children(|parent| {
                                 parent
                                     .spawn(ButtonKind::Normal)
-                                    .with_child(Text::new("Remove"))
+                                    .with_child(LocalizedText::new("menu.world_browser.remove"))
                                     .observe(Self::confirm_remove.pipe(error_message));
                                 parent
                                     .spawn(ButtonKind::Normal)
-                                    .with_child(Text::new("Cancel"))
+                                    .with_child(LocalizedText::new("menu.cancel"))
                                     .observe(Self::cancel_remove);
                             });
                     });
@@ -240,14 +345,17 @@ impl WorldBrowserPlugin {
         });
     }
 
+    #[cfg(feature = "networking")]
     fn confirm_host(
         _trigger: Trigger<Pointer<Click>>,
         mut commands: Commands,
         network_channels: Res<RepliconChannels>,
         dialog: Single<(Entity, &WorldNode), With<Dialog>>,
-        port: Single<&TextInputValue, With<PortEdit>>,
+        port: Single<&NumberInput, With<PortInput>>,
+        invalid_edits: Query<(), With<InvalidEdit>>,
         labels: Query<&Text>,
     ) -> Result<()> {
+        ensure!(invalid_edits.is_empty(), "port should be a valid number");
         let (dialog_entity, world_node) = *dialog;
 
         let server = RenetServer::new(ConnectionConfig {
@@ -256,7 +364,7 @@ impl WorldBrowserPlugin {
             ..Default::default()
         });
         let transport =
-            network::create_server(port.0.parse()?).context("unable to create server")?;
+            network::create_server(port.value.try_into()?).context("unable to create server")?;
 
         commands.insert_resource(server);
         commands.insert_resource(transport);
@@ -272,6 +380,7 @@ impl WorldBrowserPlugin {
         Ok(())
     }
 
+    #[cfg(feature = "networking")]
     fn cancel_host(
         _trigger: Trigger<Pointer<Click>>,
         mut commands: Commands,
@@ -292,8 +401,7 @@ impl WorldBrowserPlugin {
         let world_name = labels
             .get(world_node.label_entity)
             .expect("world label should contain text");
-        let world_path = game_paths.world_path(world_name);
-        fs::remove_file(&world_path).with_context(|| format!("unable to remove {world_path:?}"))?;
+        game_paths.delete_world(world_name)?;
 
         commands.entity(world_node.node_entity).despawn_recursive();
         commands.entity(dialog_entity).despawn_recursive();
@@ -318,6 +426,7 @@ impl WorldBrowserPlugin {
         _trigger: Trigger<Pointer<Click>>,
         mut commands: Commands,
         theme: Res<Theme>,
+        localization: Res<Localization>,
         root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
     ) {
         commands.entity(*root_entity).with_children(|parent| {
@@ -336,8 +445,42 @@ impl WorldBrowserPlugin {
                         theme.panel_background,
                     ))
                     .with_children(|parent| {
-                        parent.spawn((LabelKind::Normal, Text::new("Create world")));
-                        parent.spawn((TextEdit, TextInputValue("New world".to_string())));
+                        parent.spawn((
+                            LabelKind::Normal,
+                            LocalizedText::new("menu.world_browser.create_world"),
+                        ));
+                        parent.spawn((
+                            WorldNameEdit,
+                            TextInputValue(localization.tr("menu.world_browser.new_world")),
+                        ));
+                        parent.spawn((
+                            LabelKind::Normal,
+                            LocalizedText::new("menu.world_browser.description"),
+                        ));
+                        parent.spawn(WorldDescriptionEdit);
+                        parent.spawn((
+                            LabelKind::Normal,
+                            LocalizedText::new("menu.world_browser.difficulty"),
+                        ));
+                        parent
+                            .spawn((
+                                RadioGroup,
+                                Node {
+                                    column_gap: theme.gap.normal,
+                                    ..Default::default()
+                                },
+                            ))
+                            .with_children(|parent| {
+                                for difficulty in Difficulty::iter() {
+                                    let text: &str = difficulty.into();
+                                    parent
+                                        .spawn((
+                                            DifficultyButton(difficulty),
+                                            Toggled(difficulty == Difficulty::default()),
+                                        ))
+                                        .with_child(Text::new(text));
+                                }
+                            });
                         parent
                             .spawn(Node {
                                 column_gap: theme.gap.normal,
@@ -345,12 +488,12 @@ impl WorldBrowserPlugin {
                             })
                             .with_children(|parent| {
                                 parent
-                                    .spawn(ButtonKind::Normal)
-                                    .with_child(Text::new("Create"))
+                                    .spawn((ButtonKind::Normal, DefaultButton))
+                                    .with_child(LocalizedText::new("menu.world_browser.create"))
                                     .observe(Self::confirm_create);
                                 parent
                                     .spawn(ButtonKind::Normal)
-                                    .with_child(Text::new("Cancel"))
+                                    .with_child(LocalizedText::new("menu.cancel"))
                                     .observe(Self::cancel_create);
                             });
                     });
@@ -358,6 +501,7 @@ impl WorldBrowserPlugin {
         });
     }
 
+    #[cfg(feature = "networking")]
     fn join(
         _trigger: Trigger<Pointer<Click>>,
         mut commands: Commands,
@@ -380,7 +524,10 @@ impl WorldBrowserPlugin {
                         theme.panel_background,
                     ))
                     .with_children(|parent| {
-                        parent.spawn((LabelKind::Normal, Text::new("Join world")));
+                        parent.spawn((
+                            LabelKind::Normal,
+                            LocalizedText::new("menu.world_browser.join_world"),
+                        ));
 
                         parent
                             .spawn(Node {
@@ -391,14 +538,20 @@ impl WorldBrowserPlugin {
                                 ..Default::default()
                             })
                             .with_children(|parent| {
-                                parent.spawn((LabelKind::Normal, Text::new("IP:")));
+                                parent.spawn((
+                                    LabelKind::Normal,
+                                    LocalizedText::new("menu.world_browser.ip"),
+                                ));
                                 parent.spawn((
                                     IpEdit,
                                     TextInputValue(Ipv4Addr::LOCALHOST.to_string()),
                                 ));
 
-                                parent.spawn((LabelKind::Normal, Text::new("Port:")));
-                                parent.spawn((PortEdit, TextInputValue(DEFAULT_PORT.to_string())));
+                                parent.spawn((
+                                    LabelKind::Normal,
+                                    LocalizedText::new("menu.world_browser.port"),
+                                ));
+                                parent.spawn(PortInput);
                             });
 
                         parent
@@ -408,12 +561,12 @@ impl WorldBrowserPlugin {
                             })
                             .with_children(|parent| {
                                 parent
-                                    .spawn(ButtonKind::Normal)
-                                    .with_child(Text::new("Join"))
+                                    .spawn((ButtonKind::Normal, DefaultButton))
+                                    .with_child(LocalizedText::new("menu.world_browser.join"))
                                     .observe(Self::confirm_join.pipe(error_message));
                                 parent
                                     .spawn(ButtonKind::Normal)
-                                    .with_child(Text::new("Cancel"))
+                                    .with_child(LocalizedText::new("menu.cancel"))
                                     .observe(Self::cancel_join);
                             });
                     });
@@ -424,11 +577,22 @@ impl WorldBrowserPlugin {
     fn confirm_create(
         _trigger: Trigger<Pointer<Click>>,
         mut commands: Commands,
-        world_name: Single<&TextInputValue>,
+        world_name: Single<&TextInputValue, With<WorldNameEdit>>,
+        description: Single<&TextInputValue, With<WorldDescriptionEdit>>,
+        difficulty_buttons: Query<(&DifficultyButton, &Toggled)>,
         dialog_entity: Single<Entity, With<Dialog>>,
     ) {
+        let difficulty = difficulty_buttons
+            .iter()
+            .find(|(_, toggled)| toggled.0)
+            .map(|(&difficulty, _)| *difficulty)
+            .expect("one difficulty should always be selected");
+
+        info!("creating world with `{difficulty:?}` difficulty");
         commands.insert_resource(WorldName(world_name.0.clone()));
-        commands.set_state(GameState::InGame);
+        commands.insert_resource(WorldDescription(description.0.trim().to_string()));
+        commands.insert_resource(difficulty);
+        commands.queue(TransitionTo(GameState::InGame));
         commands.entity(*dialog_entity).despawn_recursive();
     }
 
@@ -441,29 +605,44 @@ impl WorldBrowserPlugin {
         commands.entity(*dialog_entity).despawn_recursive();
     }
 
+    #[cfg(feature = "networking")]
     fn confirm_join(
         _trigger: Trigger<Pointer<Click>>,
         mut commands: Commands,
         network_channels: Res<RepliconChannels>,
-        port: Single<&TextInputValue, With<PortEdit>>,
+        game_paths: Res<GamePaths>,
+        port: Single<&NumberInput, With<PortInput>>,
         ip: Single<&TextInputValue, With<IpEdit>>,
+        spectator: Single<&Checkbox, With<SpectatorCheckbox>>,
+        invalid_edits: Query<(), With<InvalidEdit>>,
         dialog_entity: Single<Entity, With<Dialog>>,
     ) -> Result<()> {
+        ensure!(invalid_edits.is_empty(), "port should be a valid number");
         let client = RenetClient::new(ConnectionConfig {
             server_channels_config: network_channels.get_server_configs(),
             client_channels_config: network_channels.get_client_configs(),
             ..Default::default()
         });
-        let transport = network::create_client(port.0.parse()?, ip.0.parse()?)
+        let info = ConnectionInfo {
+            player_id: network::load_player_id(&game_paths.player_id)?,
+            spectator: spectator.0,
+        };
+        let transport = network::create_client(ip.0.parse()?, port.value.try_into()?, info)
             .context("unable to create connection")?;
 
         commands.insert_resource(client);
         commands.insert_resource(transport);
+        if spectator.0 {
+            commands.insert_resource(Spectator);
+        } else {
+            commands.remove_resource::<Spectator>();
+        }
         commands.entity(*dialog_entity).despawn_recursive(); // Despawn only on transport creation.
 
         Ok(())
     }
 
+    #[cfg(feature = "networking")]
     fn cancel_join(
         _trigger: Trigger<Pointer<Click>>,
         mut commands: Commands,
@@ -508,15 +687,16 @@ fn setup_world_node(parent: &mut ChildBuilder, theme: &Theme, label: impl Into<S
                 .with_children(|parent| {
                     parent
                         .spawn((ButtonKind::Normal, world_node))
-                        .with_child(Text::new("Play"))
+                        .with_child(LocalizedText::new("menu.world_browser.play"))
                         .observe(WorldBrowserPlugin::play);
+                    #[cfg(feature = "networking")]
                     parent
                         .spawn((ButtonKind::Normal, world_node))
-                        .with_child(Text::new("Host"))
+                        .with_child(LocalizedText::new("menu.world_browser.host"))
                         .observe(WorldBrowserPlugin::host);
                     parent
                         .spawn((ButtonKind::Normal, world_node))
-                        .with_child(Text::new("Remove"))
+                        .with_child(LocalizedText::new("menu.world_browser.remove"))
                         .observe(WorldBrowserPlugin::remove);
                 });
         });
@@ -530,9 +710,37 @@ struct WorldNode {
 }
 
 #[derive(Component)]
-#[require(TextEdit)]
-struct PortEdit;
+#[require(Name(|| Name::new("Corrupt world dialog")), Dialog)]
+struct CorruptWorldDialog;
 
+#[cfg(feature = "networking")]
+#[derive(Component)]
+#[require(NumberInput(|| NumberInput {
+    value: DEFAULT_PORT.into(),
+    min: 1,
+    max: u16::MAX.into(),
+    step: 1,
+}))]
+struct PortInput;
+
+#[cfg(feature = "networking")]
 #[derive(Component)]
 #[require(TextEdit)]
 struct IpEdit;
+
+#[cfg(feature = "networking")]
+#[derive(Component)]
+#[require(Checkbox(|| Checkbox(false)))]
+struct SpectatorCheckbox;
+
+#[derive(Component)]
+#[require(TextEdit)]
+struct WorldNameEdit;
+
+#[derive(Component, Clone, Copy, Deref)]
+#[require(ButtonKind(|| ButtonKind::Normal), ExclusiveButton)]
+struct DifficultyButton(Difficulty);
+
+#[derive(Component)]
+#[require(TextEdit(|| TextEdit { multiline: true, ..Default::default() }))]
+struct WorldDescriptionEdit;
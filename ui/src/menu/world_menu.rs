@@ -9,7 +9,7 @@ use project_harmonia_base::{
         actor::SelectedActor,
         city::{ActiveCity, City},
         family::{Family, FamilyDelete, FamilyMembers},
-        WorldName, WorldState,
+        WorldDescription, WorldName, WorldState,
     },
 };
 use project_harmonia_widgets::{
@@ -21,6 +21,8 @@ use project_harmonia_widgets::{
 };
 use strum::{EnumIter, IntoEnumIterator};
 
+use crate::transition::TransitionTo;
+
 pub(super) struct WorldMenuPlugin;
 
 impl Plugin for WorldMenuPlugin {
@@ -29,7 +31,11 @@ impl Plugin for WorldMenuPlugin {
             .add_observer(Self::remove_entity_nodes::<City>)
             .add_observer(Self::create_family_nodes)
             .add_observer(Self::create_city_nodes)
-            .add_systems(OnEnter(WorldState::World), Self::setup);
+            .add_systems(OnEnter(WorldState::World), Self::setup)
+            .add_systems(
+                Update,
+                Self::update_labels.run_if(in_state(WorldState::World)),
+            );
     }
 }
 
@@ -39,6 +45,7 @@ impl WorldMenuPlugin {
         mut tab_commands: Commands,
         theme: Res<Theme>,
         world_name: Res<WorldName>,
+        description: Option<Res<WorldDescription>>,
         root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
         families: Query<(Entity, &Name), With<Family>>,
         cities: Query<(Entity, &Name), With<City>>,
@@ -60,6 +67,9 @@ impl WorldMenuPlugin {
                 ))
                 .with_children(|parent| {
                     parent.spawn((LabelKind::Large, Text::new(world_name.0.clone())));
+                    if let Some(description) = description.filter(|d| !d.0.is_empty()) {
+                        parent.spawn((LabelKind::Normal, Text::new(description.0.clone())));
+                    }
 
                     let tabs_entity = parent
                         .spawn(Node {
@@ -183,6 +193,19 @@ impl WorldMenuPlugin {
         }
     }
 
+    /// Updates labels when families or cities are renamed, including replicated renames.
+    fn update_labels(
+        world_entities: Query<&Name, (Changed<Name>, Or<(With<Family>, With<City>)>)>,
+        mut labels: Query<(&mut Text, &WorldEntity), With<WorldEntityLabel>>,
+    ) {
+        for (mut text, &world_entity) in &mut labels {
+            if let Ok(name) = world_entities.get(*world_entity) {
+                debug!("updating label for renamed `{}`", *world_entity);
+                text.0 = name.to_string();
+            }
+        }
+    }
+
     fn play_family(
         trigger: Trigger<Pointer<Click>>,
         mut commands: Commands,
@@ -201,7 +224,7 @@ impl WorldMenuPlugin {
 
         info!("starting playing for family `{world_entity}`");
         commands.entity(actor_entity).insert(SelectedActor);
-        commands.set_state(WorldState::Family);
+        commands.queue(TransitionTo(WorldState::Family));
     }
 
     fn delete_family(
@@ -228,7 +251,7 @@ impl WorldMenuPlugin {
 
         info!("starting editing city `{world_entity}`");
         commands.entity(world_entity).insert(ActiveCity);
-        commands.set_state(WorldState::City);
+        commands.queue(TransitionTo(WorldState::City));
     }
 
     fn delete_city(
@@ -246,7 +269,7 @@ impl WorldMenuPlugin {
     }
 
     fn exit_world(_trigger: Trigger<Pointer<Click>>, mut commands: Commands) {
-        commands.set_state(GameState::Menu);
+        commands.queue(TransitionTo(GameState::Menu));
     }
 
     fn create(
@@ -263,7 +286,7 @@ impl WorldMenuPlugin {
 
         info!("starting creation for `{current_tab:?}`");
         match current_tab {
-            WorldTab::Families => commands.set_state(WorldState::FamilyEditor),
+            WorldTab::Families => commands.queue(TransitionTo(WorldState::FamilyEditor)),
             WorldTab::Cities => {
                 commands.entity(*root_entity).with_children(|parent| {
                     setup_create_city_dialog(parent, &theme);
@@ -335,7 +358,12 @@ fn setup_entity_node(
                     ..Default::default()
                 })
                 .with_children(|parent| {
-                    parent.spawn((LabelKind::Large, Text::new(label)));
+                    parent.spawn((
+                        WorldEntityLabel,
+                        WorldEntity(entity),
+                        LabelKind::Large,
+                        Text::new(label),
+                    ));
                 });
             parent
                 .spawn(Node {
@@ -390,7 +418,7 @@ fn setup_create_city_dialog(parent: &mut ChildBuilder, theme: &Theme) {
                 parent.spawn((
                     CityNameEdit,
                     // HACK: For some reason it can't be required component, it messes the edit.
-                    TextEdit,
+                    TextEdit::default(),
                     TextInputValue("New city".to_string()),
                 ));
                 parent
@@ -436,5 +464,9 @@ struct WorldEntity(Entity);
 #[require(Node)]
 struct WorldNode;
 
+/// Displays the name of the referenced [`WorldEntity`].
+#[derive(Component)]
+struct WorldEntityLabel;
+
 #[derive(Component)]
 struct CityNameEdit;
@@ -1,4 +1,10 @@
-use std::f32::consts::PI;
+use std::{
+    f32::consts::PI,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
 
 use bevy::{
     asset::RecursiveDependencyLoadState,
@@ -7,18 +13,24 @@ use bevy::{
     render::{
         camera::RenderTarget,
         render_resource::{Extent3d, TextureUsages},
-        view::{NoFrustumCulling, RenderLayers},
+        view::{
+            screenshot::{Screenshot, ScreenshotCaptured},
+            NoFrustumCulling, RenderLayers,
+        },
     },
     scene,
 };
 
-use project_harmonia_base::asset::manifest::object_manifest::ObjectManifest;
+use project_harmonia_base::{
+    asset::manifest::object_manifest::ObjectManifest, game_paths::GamePaths,
+};
 
 pub(super) struct PreviewPlugin;
 
 impl Plugin for PreviewPlugin {
     fn build(&self, app: &mut App) {
         app.init_state::<PreviewState>()
+            .add_observer(Self::generate_thumbnails)
             .add_systems(Startup, Self::setup)
             .add_systems(
                 OnEnter(PreviewState::Inactive),
@@ -49,6 +61,55 @@ impl PreviewPlugin {
         ));
     }
 
+    /// Requests previews for all objects to save them as thumbnails.
+    fn generate_thumbnails(
+        _trigger: Trigger<GenerateThumbnails>,
+        mut commands: Commands,
+        mut exit_events: EventWriter<AppExit>,
+        manifests: Res<Assets<ObjectManifest>>,
+    ) {
+        let total = manifests.len();
+        info!("generating {total} thumbnails");
+        for id in manifests.ids() {
+            commands.spawn((
+                Preview::Object(id),
+                ThumbnailRequest,
+                Visibility::Hidden,
+                Node {
+                    width: Val::Px(THUMBNAIL_SIZE),
+                    height: Val::Px(THUMBNAIL_SIZE),
+                    ..Default::default()
+                },
+            ));
+        }
+
+        let progress = ThumbnailsProgress {
+            total,
+            processed: 0,
+            failed: Vec::new(),
+        };
+        progress.exit_if_finished(&mut exit_events);
+        commands.insert_resource(progress);
+    }
+
+    fn save_thumbnail(
+        trigger: Trigger<ScreenshotCaptured>,
+        mut exit_events: EventWriter<AppExit>,
+        mut progress: ResMut<ThumbnailsProgress>,
+        thumbnails: Query<&Thumbnail>,
+    ) {
+        let thumbnail = thumbnails.get(trigger.entity()).unwrap();
+        progress.processed += 1;
+        match save_image(trigger.event().0.clone(), &thumbnail.path) {
+            Ok(()) => info!("saved thumbnail {}/{}", progress.processed, progress.total),
+            Err(e) => {
+                error!("unable to save thumbnail for '{}': {e:#}", thumbnail.name);
+                progress.failed.push(thumbnail.name.clone());
+            }
+        }
+        progress.exit_if_finished(&mut exit_events);
+    }
+
     fn wait_for_request(
         mut commands: Commands,
         asset_server: Res<AssetServer>,
@@ -98,9 +159,13 @@ impl PreviewPlugin {
         mut commands: Commands,
         mut images: ResMut<Assets<Image>>,
         asset_server: Res<AssetServer>,
+        mut exit_events: EventWriter<AppExit>,
+        mut progress: Option<ResMut<ThumbnailsProgress>>,
         preview_scene: Single<(Entity, &PreviewTarget, &SceneRoot)>,
         mut preview_cameras: Query<&mut Camera, With<PreviewCamera>>,
         targets: Query<&Node>,
+        thumbnails: Query<&Preview, With<ThumbnailRequest>>,
+        manifests: Res<Assets<ObjectManifest>>,
         children: Query<&Children>,
         meshes: Query<Entity, With<Mesh3d>>,
     ) {
@@ -145,6 +210,14 @@ impl PreviewPlugin {
             }
             RecursiveDependencyLoadState::Failed(e) => {
                 error!("unable to load asset: {e:#}");
+                if let (Some(progress), Ok(&Preview::Object(id))) =
+                    (&mut progress, thumbnails.get(preview_target.0))
+                {
+                    let manifest = manifests.get(id).expect("manifests should be preloaded");
+                    progress.failed.push(manifest.general.name.clone());
+                    progress.processed += 1;
+                    progress.exit_if_finished(&mut exit_events);
+                }
                 commands.set_state(PreviewState::Inactive);
             }
             RecursiveDependencyLoadState::NotLoaded | RecursiveDependencyLoadState::Loading => (),
@@ -160,8 +233,12 @@ impl PreviewPlugin {
     fn despawn_scene(
         mut commands: Commands,
         mut preview_camera: Single<&mut Camera, With<PreviewCamera>>,
+        asset_server: Res<AssetServer>,
+        game_paths: Res<GamePaths>,
+        manifests: Res<Assets<ObjectManifest>>,
         preview_scene: Single<(Entity, &PreviewTarget)>,
         mut targets: Query<&mut ImageNode>,
+        thumbnails: Query<&Preview, With<ThumbnailRequest>>,
     ) {
         preview_camera.is_active = false;
 
@@ -172,6 +249,23 @@ impl PreviewPlugin {
             };
             target_handle.image = image_handle.clone();
             debug!("preview is ready");
+
+            if let Ok(&Preview::Object(id)) = thumbnails.get(**preview_target) {
+                let manifest_path = asset_server
+                    .get_path(id)
+                    .expect("manifests should always come from files");
+                let manifest = manifests.get(id).expect("manifests should be preloaded");
+                commands
+                    .spawn((
+                        Screenshot::image(image_handle.clone()),
+                        Thumbnail {
+                            path: game_paths.thumbnail_path(&manifest_path),
+                            name: manifest.general.name.clone(),
+                        },
+                    ))
+                    .observe(Self::save_thumbnail);
+                commands.entity(**preview_target).despawn_recursive();
+            }
         } else {
             info!("preview target is no longer valid");
         }
@@ -182,6 +276,9 @@ impl PreviewPlugin {
 
 const PREVIEW_RENDER_LAYER: RenderLayers = RenderLayers::layer(1);
 
+/// Width and height of generated thumbnails.
+const THUMBNAIL_SIZE: f32 = 256.0;
+
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, States)]
 enum PreviewState {
     #[default]
@@ -228,3 +325,64 @@ pub(super) struct PreviewProcessed;
     SceneRoot,
 )]
 struct PreviewTarget(Entity);
+
+/// Renders previews for all objects and saves them to [`GamePaths::thumbnails`].
+///
+/// Exits the app when finished.
+#[derive(Event)]
+pub struct GenerateThumbnails;
+
+/// Marks [`Preview`] that should be saved to disk instead of displayed.
+#[derive(Component)]
+struct ThumbnailRequest;
+
+/// Where to save the captured screenshot.
+#[derive(Component)]
+struct Thumbnail {
+    path: PathBuf,
+    /// Object name for error reporting.
+    name: String,
+}
+
+#[derive(Resource)]
+struct ThumbnailsProgress {
+    total: usize,
+    processed: usize,
+    /// Names of objects whose scenes failed to load or thumbnails failed to save.
+    failed: Vec<String>,
+}
+
+impl ThumbnailsProgress {
+    fn exit_if_finished(&self, exit_events: &mut EventWriter<AppExit>) {
+        if self.processed < self.total {
+            return;
+        }
+
+        if self.failed.is_empty() {
+            info!("all thumbnails were generated");
+            exit_events.send(AppExit::Success);
+        } else {
+            error!(
+                "unable to generate thumbnails for: {}",
+                self.failed.join(", ")
+            );
+            exit_events.send(AppExit::error());
+        }
+    }
+}
+
+/// Writes the image to the path, creating all missing directories.
+///
+/// The format is determined by the extension.
+fn save_image(image: Image, path: &Path) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("unable to create {dir:?}"))?;
+    }
+    let image = image
+        .try_into_dynamic()
+        .context("unable to convert screenshot")?;
+    image
+        .to_rgb8()
+        .save(path)
+        .with_context(|| format!("unable to write {path:?}"))
+}
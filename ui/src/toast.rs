@@ -0,0 +1,196 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use project_harmonia_base::{
+    game_world::{
+        family::{bill::BillIssued, SelectedFamily},
+        object::reachability::Unreachable,
+        GameSaved,
+    },
+    gpu_support::GpuSupport,
+    settings::Settings,
+};
+use project_harmonia_widgets::{label::LabelKind, theme::Theme};
+
+/// Short non-blocking notifications that disappear over time.
+pub(super) struct ToastPlugin;
+
+impl Plugin for ToastPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(Self::show_unreachable)
+            .add_observer(Self::show_saved)
+            .add_systems(
+                Update,
+                (
+                    Self::show_gpu_limits.run_if(run_once),
+                    Self::show_bills.never_param_warn(),
+                    Self::despawn_expired,
+                ),
+            );
+    }
+}
+
+/// How long a toast stays on screen.
+const TOAST_LIFETIME: Duration = Duration::from_secs(4);
+
+impl ToastPlugin {
+    fn show_unreachable(
+        trigger: Trigger<OnAdd, Unreachable>,
+        mut commands: Commands,
+        theme: Res<Theme>,
+        time: Res<Time<Real>>,
+        root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+        areas: Query<Entity, With<ToastArea>>,
+        names: Query<&Name>,
+    ) {
+        let name = names
+            .get(trigger.entity())
+            .map(|name| name.as_str())
+            .unwrap_or("Object");
+
+        debug!("showing toast for unreachable `{}`", trigger.entity());
+        spawn_toast(
+            &mut commands,
+            &theme,
+            &time,
+            *root_entity,
+            &areas,
+            format!("{name} can't be reached by actors"),
+        );
+    }
+
+    fn show_saved(
+        _trigger: Trigger<GameSaved>,
+        mut commands: Commands,
+        theme: Res<Theme>,
+        time: Res<Time<Real>>,
+        root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+        areas: Query<Entity, With<ToastArea>>,
+    ) {
+        debug!("showing toast for saved world");
+        spawn_toast(
+            &mut commands,
+            &theme,
+            &time,
+            *root_entity,
+            &areas,
+            "World saved".to_string(),
+        );
+    }
+
+    /// Reports options from settings that are ignored on this GPU.
+    fn show_gpu_limits(
+        mut commands: Commands,
+        theme: Res<Theme>,
+        time: Res<Time<Real>>,
+        settings: Res<Settings>,
+        gpu_support: Res<GpuSupport>,
+        root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+        areas: Query<Entity, With<ToastArea>>,
+    ) {
+        let options = gpu_support.disabled_settings(&settings);
+        if options.is_empty() {
+            return;
+        }
+
+        debug!("showing toast for unsupported options");
+        spawn_toast(
+            &mut commands,
+            &theme,
+            &time,
+            *root_entity,
+            &areas,
+            format!("Disabled unsupported by GPU: {}", options.join(", ")),
+        );
+    }
+
+    fn show_bills(
+        mut commands: Commands,
+        mut issued_events: EventReader<BillIssued>,
+        theme: Res<Theme>,
+        time: Res<Time<Real>>,
+        root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+        areas: Query<Entity, With<ToastArea>>,
+        families: Query<(), With<SelectedFamily>>,
+    ) {
+        for event in issued_events.read() {
+            if families.get(event.family_entity).is_err() {
+                continue;
+            }
+
+            let mut text = format!("Bills due: ${}", event.amount);
+            if event.unpaid != 0 {
+                text += &format!(", unpaid: ${}", event.unpaid);
+            }
+
+            debug!("showing toast for bills of `{}`", event.family_entity);
+            spawn_toast(&mut commands, &theme, &time, *root_entity, &areas, text);
+        }
+    }
+
+    fn despawn_expired(
+        mut commands: Commands,
+        time: Res<Time<Real>>,
+        toasts: Query<(Entity, &Toast)>,
+    ) {
+        for (entity, toast) in &toasts {
+            if time.elapsed() >= toast.expires {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+    }
+}
+
+fn spawn_toast(
+    commands: &mut Commands,
+    theme: &Theme,
+    time: &Time<Real>,
+    root_entity: Entity,
+    areas: &Query<Entity, With<ToastArea>>,
+    text: String,
+) {
+    let area_entity = areas.get_single().unwrap_or_else(|_| {
+        let area_entity = commands.spawn(ToastArea).id();
+        commands.entity(root_entity).add_child(area_entity);
+        area_entity
+    });
+
+    commands.entity(area_entity).with_children(|parent| {
+        parent
+            .spawn((
+                Toast {
+                    expires: time.elapsed() + TOAST_LIFETIME,
+                },
+                Node {
+                    padding: theme.padding.normal,
+                    ..Default::default()
+                },
+                theme.panel_background,
+            ))
+            .with_child((LabelKind::Normal, Text::new(text)));
+    });
+}
+
+/// Column at the bottom of the screen that holds toasts.
+#[derive(Component)]
+#[require(
+    Name(|| Name::new("Toast area")),
+    PickingBehavior(|| PickingBehavior::IGNORE),
+    Node(|| Node {
+        position_type: PositionType::Absolute,
+        width: Val::Percent(100.0),
+        bottom: Val::Px(120.0),
+        flex_direction: FlexDirection::ColumnReverse,
+        align_items: AlignItems::Center,
+        row_gap: Val::Px(5.0),
+        ..Default::default()
+    })
+)]
+struct ToastArea;
+
+#[derive(Component)]
+#[require(PickingBehavior(|| PickingBehavior::IGNORE))]
+struct Toast {
+    expires: Duration,
+}
@@ -0,0 +1,129 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, state::state::FreelyMutableState};
+
+use project_harmonia_widgets::theme::ReduceMotion;
+
+/// Fades the screen to black around state changes.
+///
+/// Use [`TransitionTo`] instead of setting [`NextState`] directly.
+pub(super) struct TransitionPlugin;
+
+impl Plugin for TransitionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Transition>()
+            .add_systems(PostStartup, Self::spawn)
+            .add_systems(Update, Self::animate);
+    }
+}
+
+/// Duration of each half of the transition.
+const FADE_DURATION: Duration = Duration::from_millis(250);
+
+/// Maximum step per frame.
+///
+/// Loading a world may stall a frame, which would otherwise skip the fade-in.
+const MAX_STEP: f32 = 1.0 / 30.0;
+
+impl TransitionPlugin {
+    fn spawn(mut commands: Commands, root_entity: Single<Entity, (With<Node>, Without<Parent>)>) {
+        debug!("spawning transition overlay");
+        commands.entity(*root_entity).with_child(TransitionOverlay);
+    }
+
+    fn animate(
+        mut commands: Commands,
+        time: Res<Time<Real>>,
+        mut transition: ResMut<Transition>,
+        overlay: Single<(&mut BackgroundColor, &mut PickingBehavior), With<TransitionOverlay>>,
+    ) {
+        if transition.phase == FadePhase::Idle {
+            return;
+        }
+
+        let step = time.delta_secs().min(MAX_STEP) / FADE_DURATION.as_secs_f32();
+        match transition.phase {
+            FadePhase::Idle => unreachable!("idle transition should return early"),
+            FadePhase::Out => {
+                transition.alpha = (transition.alpha + step).min(1.0);
+                if transition.alpha == 1.0 {
+                    if let Some(change) = transition.pending.take() {
+                        commands.queue(change);
+                    }
+                    transition.phase = FadePhase::In;
+                }
+            }
+            FadePhase::In => {
+                transition.alpha = (transition.alpha - step).max(0.0);
+                if transition.alpha == 0.0 {
+                    debug!("finishing transition");
+                    transition.phase = FadePhase::Idle;
+                }
+            }
+        }
+
+        let (mut background, mut picking) = overlay.into_inner();
+        background.0 = Color::BLACK.with_alpha(transition.alpha);
+        // Block clicks only while fading out to avoid triggering a change twice.
+        *picking = if transition.phase == FadePhase::Out {
+            PickingBehavior::default()
+        } else {
+            PickingBehavior::IGNORE
+        };
+    }
+}
+
+/// Fades out, switches to the state and fades back in.
+///
+/// Switches instantly if [`ReduceMotion`] is enabled.
+/// If requested during another transition, replaces its pending state
+/// and continues fading out from the current opacity.
+pub(crate) struct TransitionTo<S>(pub(crate) S);
+
+impl<S: FreelyMutableState> Command for TransitionTo<S> {
+    fn apply(self, world: &mut World) {
+        let state = self.0;
+        if **world.resource::<ReduceMotion>() {
+            debug!("switching to `{state:?}` without transition");
+            world.resource_mut::<NextState<S>>().set(state);
+            return;
+        }
+
+        debug!("starting transition to `{state:?}`");
+        let mut transition = world.resource_mut::<Transition>();
+        transition.phase = FadePhase::Out;
+        transition.pending = Some(Box::new(move |world: &mut World| {
+            world.resource_mut::<NextState<S>>().set(state);
+        }));
+    }
+}
+
+#[derive(Resource, Default)]
+struct Transition {
+    phase: FadePhase,
+    alpha: f32,
+    pending: Option<Box<dyn FnOnce(&mut World) + Send + Sync>>,
+}
+
+#[derive(Default, Clone, Copy, PartialEq)]
+enum FadePhase {
+    #[default]
+    Idle,
+    Out,
+    In,
+}
+
+#[derive(Component)]
+#[require(
+    Name(|| Name::new("Transition overlay")),
+    PickingBehavior(|| PickingBehavior::IGNORE),
+    GlobalZIndex(|| GlobalZIndex(i32::MAX)),
+    BackgroundColor(|| BackgroundColor(Color::NONE)),
+    Node(|| Node {
+        position_type: PositionType::Absolute,
+        width: Val::Percent(100.0),
+        height: Val::Percent(100.0),
+        ..Default::default()
+    })
+)]
+struct TransitionOverlay;
@@ -1,6 +1,16 @@
-use bevy::prelude::*;
+use std::time::Duration;
 
-use crate::theme::Theme;
+use bevy::{
+    picking::{
+        backend::HitData,
+        pointer::{Location, PointerButton, PointerId},
+    },
+    prelude::*,
+    render::camera::NormalizedRenderTarget,
+    window::{PrimaryWindow, WindowRef},
+};
+
+use crate::theme::{ReduceMotion, Theme};
 
 pub(super) struct ButtonPlugin;
 
@@ -10,11 +20,17 @@ impl Plugin for ButtonPlugin {
             .add_observer(Self::theme_image)
             .add_observer(Self::theme_text)
             .add_observer(Self::toggle)
+            .add_systems(Update, Self::repeat_clicks.never_param_warn())
             .add_systems(
                 PostUpdate,
                 (
-                    Self::update_background,
-                    (Self::ensure_single_toggle, Self::switch_tabs).chain(),
+                    (Self::update_background, Self::animate).chain(),
+                    (
+                        Self::update_radio_groups,
+                        Self::ensure_single_toggle,
+                        Self::switch_tabs,
+                    )
+                        .chain(),
                 ),
             );
     }
@@ -112,14 +128,28 @@ impl ButtonPlugin {
 
     fn update_background(
         theme: Res<Theme>,
+        reduce_motion: Res<ReduceMotion>,
         mut buttons: Query<
-            (&Interaction, &mut BackgroundColor, Option<&Toggled>),
-            (Or<(Changed<Interaction>, Changed<Toggled>)>, With<Button>),
+            (
+                Ref<Interaction>,
+                &mut BackgroundColor,
+                &mut Transform,
+                Option<&Toggled>,
+                Option<&mut ButtonAnimation>,
+                Has<Disabled>,
+            ),
+            (
+                Or<(Changed<Interaction>, Changed<Toggled>, Added<Disabled>)>,
+                With<Button>,
+            ),
         >,
     ) {
-        for (&interaction, mut background, toggled) in &mut buttons {
+        for (interaction, mut background, mut transform, toggled, animation, disabled) in
+            &mut buttons
+        {
             let toggled = toggled.map(|toggled| toggled.0).unwrap_or_default();
-            *background = match (interaction, toggled) {
+            let target_background = match (*interaction, toggled) {
+                _ if disabled => theme.button.disabled_background,
                 (Interaction::Pressed, _) | (Interaction::None, true) => {
                     theme.button.pressed_background
                 }
@@ -127,6 +157,93 @@ impl ButtonPlugin {
                 (Interaction::Hovered, false) => theme.button.hovered_background,
                 (Interaction::None, false) => theme.button.normal_background,
             };
+            // Scale reacts only to the pointer, toggled state is shown by color.
+            let target_scale = match *interaction {
+                _ if disabled => 1.0,
+                Interaction::Pressed => theme.button.pressed_scale,
+                Interaction::Hovered => theme.button.hovered_scale,
+                Interaction::None => 1.0,
+            };
+
+            match animation {
+                Some(mut animation) if !interaction.is_added() && !**reduce_motion => {
+                    animation.start_background = background.0;
+                    animation.target_background = target_background.0;
+                    animation.start_scale = transform.scale.x;
+                    animation.target_scale = target_scale;
+                    animation.elapsed = 0.0;
+                }
+                Some(mut animation) => {
+                    // Snap to the final state without animating.
+                    *background = target_background;
+                    transform.scale = Vec3::splat(target_scale);
+                    animation.target_background = target_background.0;
+                    animation.target_scale = target_scale;
+                    animation.elapsed = theme.button.transition_secs;
+                }
+                None => *background = target_background,
+            }
+        }
+    }
+
+    fn animate(
+        time: Res<Time>,
+        theme: Res<Theme>,
+        mut buttons: Query<(&mut ButtonAnimation, &mut BackgroundColor, &mut Transform)>,
+    ) {
+        let duration = theme.button.transition_secs;
+        for (mut animation, mut background, mut transform) in &mut buttons {
+            if animation.elapsed >= duration {
+                continue;
+            }
+
+            animation.elapsed += time.delta_secs();
+            let t = (animation.elapsed / duration).min(1.0);
+            background.0 = animation
+                .start_background
+                .mix(&animation.target_background, t);
+            transform.scale = Vec3::splat(animation.start_scale.lerp(animation.target_scale, t));
+        }
+    }
+
+    fn repeat_clicks(
+        mut commands: Commands,
+        time: Res<Time<Real>>,
+        primary_window: Single<Entity, With<PrimaryWindow>>,
+        camera_entity: Single<Entity, With<Camera2d>>,
+        mut buttons: Query<(
+            Entity,
+            &Interaction,
+            &Repeatable,
+            &mut RepeatTimer,
+            &GlobalTransform,
+        )>,
+    ) {
+        for (entity, &interaction, repeatable, mut repeat_timer, transform) in &mut buttons {
+            if interaction != Interaction::Pressed {
+                repeat_timer.0 = None;
+                continue;
+            }
+
+            let timer = repeat_timer
+                .0
+                .get_or_insert_with(|| Timer::new(repeatable.delay, TimerMode::Once));
+            timer.tick(time.delta());
+            let clicks = timer.times_finished_this_tick();
+            if timer.mode() == TimerMode::Once && timer.finished() {
+                *timer = Timer::new(repeatable.interval, TimerMode::Repeating);
+            }
+
+            for _ in 0..clicks {
+                trace!("repeating click for `{entity}`");
+                let click = primary_click(
+                    entity,
+                    transform.translation().truncate(),
+                    *primary_window,
+                    *camera_entity,
+                );
+                commands.trigger_targets(click, entity);
+            }
         }
     }
 
@@ -182,10 +299,69 @@ impl ButtonPlugin {
             };
         }
     }
+
+    /// Keeps exactly one option toggled and notifies about user selection.
+    ///
+    /// Runs before [`Self::ensure_single_toggle`] to see the newly toggled option.
+    fn update_radio_groups(
+        mut commands: Commands,
+        groups: Query<(Entity, &Children), With<RadioGroup>>,
+        mut options: Query<&mut Toggled, With<ExclusiveButton>>,
+    ) {
+        for (group_entity, children) in &groups {
+            let mut any_toggled = false;
+            let mut iter = options.iter_many_mut(children);
+            let mut index = 0;
+            while let Some(toggled) = iter.fetch_next() {
+                if **toggled {
+                    any_toggled = true;
+                    if toggled.is_changed() && !toggled.is_added() {
+                        debug!("selecting option {index} for `{group_entity}`");
+                        commands.trigger_targets(RadioChanged(index), group_entity);
+                    }
+                }
+                index += 1;
+            }
+
+            if !any_toggled {
+                let mut iter = options.iter_many_mut(children);
+                if let Some(mut toggled) = iter.fetch_next() {
+                    debug!("selecting first option for `{group_entity}`");
+                    **toggled = true;
+                }
+            }
+        }
+    }
+}
+
+/// Creates a primary button click on the entity to emulate the user interaction.
+pub(crate) fn primary_click(
+    entity: Entity,
+    position: Vec2,
+    window_entity: Entity,
+    camera_entity: Entity,
+) -> Pointer<Click> {
+    let window_ref = WindowRef::Primary
+        .normalize(Some(window_entity))
+        .expect("primary window should be passed");
+
+    Pointer {
+        target: entity,
+        pointer_id: PointerId::Mouse,
+        pointer_location: Location {
+            target: NormalizedRenderTarget::Window(window_ref),
+            position,
+        },
+        event: Click {
+            button: PointerButton::Primary,
+            hit: HitData::new(camera_entity, 0.0, None, None),
+            duration: Duration::ZERO,
+        },
+    }
 }
 
 #[derive(Component, Clone, Copy, PartialEq, Eq)]
-#[require(Button)]
+#[require(Button, ButtonAnimation)]
 pub enum ButtonKind {
     Normal,
     Large,
@@ -198,6 +374,13 @@ pub enum ButtonKind {
 #[require(ExclusiveButton)]
 pub struct TabContent(pub Entity);
 
+/// Ignores the pointer and shows the button greyed out.
+///
+/// For checkboxes, also prevents toggling.
+#[derive(Component)]
+#[require(PickingBehavior(|| PickingBehavior::IGNORE))]
+pub struct Disabled;
+
 /// Makes the button togglable.
 #[derive(Component, Default, Deref, DerefMut)]
 pub struct Toggled(pub bool);
@@ -209,6 +392,133 @@ pub struct Toggled(pub bool);
 #[require(Toggled)]
 pub struct ExclusiveButton;
 
+/// Node whose children with [`ExclusiveButton`] act as options.
+///
+/// Ensures that exactly one option is toggled and triggers [`RadioChanged`]
+/// on itself when another option is selected.
+#[derive(Component, Default)]
+#[require(Node)]
+pub struct RadioGroup;
+
+/// Triggered on [`RadioGroup`] with the index of the selected option.
+///
+/// Not triggered for the initial selection.
+#[derive(Event, Clone, Copy, Deref)]
+pub struct RadioChanged(pub usize);
+
 /// Stores previous [`Display`] since last toggle.
 #[derive(Component, Deref, DerefMut)]
 struct PreviousDisplay(Display);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn radio_group() {
+        let mut app = App::new();
+        app.init_resource::<LastSelection>().add_systems(
+            Update,
+            (
+                ButtonPlugin::update_radio_groups,
+                ButtonPlugin::ensure_single_toggle,
+            )
+                .chain(),
+        );
+
+        let mut option_entities = Vec::new();
+        app.world_mut()
+            .spawn(RadioGroup)
+            .with_children(|parent| {
+                for _ in 0..3 {
+                    option_entities.push(parent.spawn(ExclusiveButton).id());
+                }
+            })
+            .observe(
+                |trigger: Trigger<RadioChanged>, mut last_selection: ResMut<LastSelection>| {
+                    last_selection.0 = Some(**trigger);
+                },
+            );
+
+        app.update();
+
+        assert_eq!(
+            toggled_options(app.world(), &option_entities),
+            [true, false, false],
+            "first option should be selected by default"
+        );
+        assert_eq!(app.world().resource::<LastSelection>().0, None);
+
+        **app
+            .world_mut()
+            .get_mut::<Toggled>(option_entities[2])
+            .unwrap() = true;
+
+        app.update();
+
+        assert_eq!(
+            toggled_options(app.world(), &option_entities),
+            [false, false, true]
+        );
+        assert_eq!(app.world().resource::<LastSelection>().0, Some(2));
+    }
+
+    fn toggled_options(world: &World, entities: &[Entity]) -> Vec<bool> {
+        entities
+            .iter()
+            .map(|&entity| world.get::<Toggled>(entity).unwrap().0)
+            .collect()
+    }
+
+    #[derive(Resource, Default)]
+    struct LastSelection(Option<usize>);
+}
+
+/// Repeats clicks while the button is held, like for number spinners.
+///
+/// Buttons without it emit a single click.
+#[derive(Component, Clone, Copy)]
+#[require(RepeatTimer)]
+pub struct Repeatable {
+    /// Hold duration before the first repeat.
+    pub delay: Duration,
+    /// Time between repeats.
+    pub interval: Duration,
+}
+
+impl Default for Repeatable {
+    fn default() -> Self {
+        Self {
+            delay: Duration::from_millis(500),
+            interval: Duration::from_millis(80),
+        }
+    }
+}
+
+/// Started when a [`Repeatable`] button is pressed.
+#[derive(Component, Default)]
+struct RepeatTimer(Option<Timer>);
+
+/// Transition of a button's background and scale between interaction states.
+///
+/// Durations and scales are taken from [`Theme`]. Snaps instantly when [`ReduceMotion`] is enabled.
+#[derive(Component)]
+struct ButtonAnimation {
+    start_background: Color,
+    target_background: Color,
+    start_scale: f32,
+    target_scale: f32,
+    elapsed: f32,
+}
+
+impl Default for ButtonAnimation {
+    fn default() -> Self {
+        Self {
+            start_background: Color::NONE,
+            target_background: Color::NONE,
+            start_scale: 1.0,
+            target_scale: 1.0,
+            elapsed: f32::MAX,
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use super::theme::Theme;
+use super::{button::Disabled, theme::Theme};
 
 pub(crate) struct CheckboxPlugin;
 
@@ -44,22 +44,29 @@ impl CheckboxPlugin {
         trigger: Trigger<OnAdd, Parent>,
         theme: Res<Theme>,
         mut text: Query<(&Parent, &mut TextFont, &mut TextColor)>,
-        buttons: Query<(), With<Checkbox>>,
+        buttons: Query<Has<Disabled>, With<Checkbox>>,
     ) {
         let Ok((parent, mut font, mut color)) = text.get_mut(trigger.entity()) else {
             return;
         };
 
-        if buttons.get(**parent).is_err() {
+        let Ok(disabled) = buttons.get(**parent) else {
             return;
         };
 
         font.font = theme.label.normal.font.clone();
         font.font_size = theme.label.normal.font_size;
-        *color = theme.label.normal.color;
+        *color = if disabled {
+            theme.checkbox.disabled_color
+        } else {
+            theme.label.normal.color
+        };
     }
 
-    fn toggle(trigger: Trigger<Pointer<Click>>, mut checkboxes: Query<&mut Checkbox>) {
+    fn toggle(
+        trigger: Trigger<Pointer<Click>>,
+        mut checkboxes: Query<&mut Checkbox, Without<Disabled>>,
+    ) {
         if let Ok(mut checkbox) = checkboxes.get_mut(trigger.entity()) {
             checkbox.0 = !checkbox.0;
         }
@@ -0,0 +1,260 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+pub(super) struct ClickPlugin;
+
+impl Plugin for ClickPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ClickSettings>()
+            .init_resource::<ClickTracker>()
+            .add_observer(Self::press)
+            .add_observer(Self::click);
+    }
+}
+
+impl ClickPlugin {
+    fn press(trigger: Trigger<Pointer<Down>>, mut tracker: ResMut<ClickTracker>) {
+        // Global observers are also triggered for propagated events.
+        let event = trigger.event();
+        if trigger.entity() != event.target {
+            return;
+        }
+
+        tracker.press(event.target, event.button, event.pointer_location.position);
+    }
+
+    fn click(
+        trigger: Trigger<Pointer<Click>>,
+        mut commands: Commands,
+        time: Res<Time<Real>>,
+        settings: Res<ClickSettings>,
+        mut tracker: ResMut<ClickTracker>,
+    ) {
+        let event = trigger.event();
+        if trigger.entity() != event.target {
+            return;
+        }
+
+        if event.button != PointerButton::Primary
+            && !tracker.is_drag(
+                event.target,
+                event.button,
+                event.pointer_location.position,
+                &settings,
+            )
+        {
+            debug!(
+                "detected secondary click with `{:?}` for `{}`",
+                event.button, event.target
+            );
+            commands.trigger_targets(
+                SecondaryClick {
+                    button: event.button,
+                },
+                event.target,
+            );
+        }
+
+        if tracker.click(
+            event.target,
+            event.button,
+            event.pointer_location.position,
+            time.elapsed(),
+            &settings,
+        ) {
+            debug!("detected double click for `{}`", event.target);
+            commands.trigger_targets(
+                DoubleClick {
+                    button: event.button,
+                },
+                event.target,
+            );
+        }
+    }
+}
+
+/// Timings and distances used to interpret pointer clicks consistently across widgets.
+#[derive(Resource)]
+pub struct ClickSettings {
+    /// Maximum delay between two clicks to count them as a double click.
+    pub double_click_ms: u64,
+
+    /// Pointer movement between press and release after which it's considered a drag instead of a click.
+    pub drag_threshold_px: f32,
+}
+
+impl ClickSettings {
+    pub fn double_click_time(&self) -> Duration {
+        Duration::from_millis(self.double_click_ms)
+    }
+
+    pub fn is_drag(&self, press_position: Vec2, release_position: Vec2) -> bool {
+        press_position.distance(release_position) > self.drag_threshold_px
+    }
+}
+
+impl Default for ClickSettings {
+    fn default() -> Self {
+        Self {
+            double_click_ms: 400,
+            drag_threshold_px: 5.0,
+        }
+    }
+}
+
+/// Triggered on an entity after two quick clicks with the same button.
+///
+/// Triggered after the second [`Click`], drags are not counted.
+#[derive(Event, Clone, Copy)]
+pub struct DoubleClick {
+    pub button: PointerButton,
+}
+
+/// Triggered on an entity after a click with a non-primary button, like for context menus.
+///
+/// Unlike [`Click`], not triggered if the pointer was dragged,
+/// so it won't fire after panning the camera with the same button.
+#[derive(Event, Clone, Copy)]
+pub struct SecondaryClick {
+    pub button: PointerButton,
+}
+
+#[derive(Resource, Default)]
+struct ClickTracker {
+    last_press: Option<(Entity, PointerButton, Vec2)>,
+    last_click: Option<(Entity, PointerButton, Duration)>,
+}
+
+impl ClickTracker {
+    fn press(&mut self, entity: Entity, button: PointerButton, position: Vec2) {
+        self.last_press = Some((entity, button, position));
+    }
+
+    /// Returns `true` if the pointer moved too far since the press on the same entity.
+    fn is_drag(
+        &self,
+        entity: Entity,
+        button: PointerButton,
+        position: Vec2,
+        settings: &ClickSettings,
+    ) -> bool {
+        self.last_press
+            .is_some_and(|(press_entity, press_button, press_position)| {
+                press_entity == entity
+                    && press_button == button
+                    && settings.is_drag(press_position, position)
+            })
+    }
+
+    /// Registers a click and returns `true` if it completes a double click.
+    fn click(
+        &mut self,
+        entity: Entity,
+        button: PointerButton,
+        position: Vec2,
+        now: Duration,
+        settings: &ClickSettings,
+    ) -> bool {
+        let drag = self.is_drag(entity, button, position, settings);
+        self.last_press = None;
+        if drag {
+            self.last_click = None;
+            return false;
+        }
+
+        match self.last_click.take() {
+            Some((last_entity, last_button, last_time))
+                if last_entity == entity
+                    && last_button == button
+                    && now - last_time <= settings.double_click_time() =>
+            {
+                // Keep the state empty to avoid counting the third click as another double click.
+                true
+            }
+            _ => {
+                self.last_click = Some((entity, button, now));
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn double_click_timing() {
+        let settings = ClickSettings::default();
+        let mut tracker = ClickTracker::default();
+        let mut time = Time::<()>::default();
+        let entity = Entity::from_raw(0);
+        let other_entity = Entity::from_raw(1);
+        let button = PointerButton::Primary;
+
+        assert!(!tracker.click(entity, button, Vec2::ZERO, time.elapsed(), &settings));
+        time.advance_by(settings.double_click_time() / 2);
+        assert!(
+            tracker.click(entity, button, Vec2::ZERO, time.elapsed(), &settings),
+            "second click in time should be a double click"
+        );
+
+        time.advance_by(settings.double_click_time() / 2);
+        assert!(
+            !tracker.click(entity, button, Vec2::ZERO, time.elapsed(), &settings),
+            "third click should start a new sequence"
+        );
+
+        time.advance_by(settings.double_click_time() * 2);
+        assert!(
+            !tracker.click(entity, button, Vec2::ZERO, time.elapsed(), &settings),
+            "late click shouldn't be a double click"
+        );
+
+        time.advance_by(settings.double_click_time() / 2);
+        assert!(
+            !tracker.click(other_entity, button, Vec2::ZERO, time.elapsed(), &settings),
+            "clicks on different entities shouldn't be combined"
+        );
+
+        time.advance_by(settings.double_click_time() / 2);
+        assert!(
+            !tracker.click(
+                other_entity,
+                PointerButton::Secondary,
+                Vec2::ZERO,
+                time.elapsed(),
+                &settings
+            ),
+            "clicks with different buttons shouldn't be combined"
+        );
+    }
+
+    #[test]
+    fn drag_threshold() {
+        let settings = ClickSettings::default();
+        let mut tracker = ClickTracker::default();
+        let mut time = Time::<()>::default();
+        let entity = Entity::from_raw(0);
+        let button = PointerButton::Primary;
+
+        assert!(!tracker.click(entity, button, Vec2::ZERO, time.elapsed(), &settings));
+        time.advance_by(settings.double_click_time() / 2);
+        let drag_position = Vec2::X * (settings.drag_threshold_px + 1.0);
+        tracker.press(entity, button, Vec2::ZERO);
+        assert!(
+            !tracker.click(entity, button, drag_position, time.elapsed(), &settings),
+            "drag shouldn't complete a double click"
+        );
+
+        tracker.press(entity, button, Vec2::ZERO);
+        assert!(!tracker.click(entity, button, Vec2::ZERO, time.elapsed(), &settings));
+        tracker.press(entity, button, Vec2::ZERO);
+        let small_move = Vec2::X * (settings.drag_threshold_px - 1.0);
+        assert!(
+            tracker.click(entity, button, small_move, time.elapsed(), &settings),
+            "small movement should still count as a click"
+        );
+    }
+}
@@ -0,0 +1,376 @@
+use bevy::{
+    input::mouse::{MouseScrollUnit, MouseWheel},
+    prelude::*,
+};
+
+use crate::{
+    button::{ButtonKind, Toggled},
+    popup::{Dismiss, Popup},
+    theme::Theme,
+};
+
+pub(super) struct ComboBoxPlugin;
+
+impl Plugin for ComboBoxPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(Self::init)
+            .add_observer(Self::close_removed)
+            .add_systems(
+                Update,
+                (Self::navigate.never_param_warn(), Self::scroll_wheel),
+            )
+            .add_systems(PostUpdate, Self::update_label);
+    }
+}
+
+/// Scroll distance for a single mouse wheel line.
+const SCROLL_LINE_HEIGHT: f32 = 20.0;
+
+impl ComboBoxPlugin {
+    fn init(trigger: Trigger<OnAdd, ComboBox>, mut commands: Commands) {
+        commands.entity(trigger.entity()).with_children(|parent| {
+            parent
+                .spawn(ComboBoxButton)
+                .with_child(Text::default())
+                .observe(Self::toggle_popup);
+        });
+    }
+
+    fn toggle_popup(
+        trigger: Trigger<Pointer<Click>>,
+        mut commands: Commands,
+        theme: Res<Theme>,
+        root_entity: Single<Entity, (With<Node>, Without<Parent>)>,
+        buttons: Query<&Parent>,
+        combo_boxes: Query<&ComboBox>,
+        popups: Query<(Entity, &ComboBoxPopup)>,
+    ) {
+        let combo_box_entity = **buttons.get(trigger.entity()).unwrap();
+        if let Some((popup_entity, _)) = popups
+            .iter()
+            .find(|(_, popup)| popup.combo_box_entity == combo_box_entity)
+        {
+            debug!("closing popup for `{combo_box_entity}`");
+            commands.entity(popup_entity).despawn_recursive();
+            return;
+        }
+
+        let combo_box = combo_boxes
+            .get(combo_box_entity)
+            .expect("combo box button should be a child of combo box");
+
+        debug!("opening popup for `{combo_box_entity}`");
+        commands.entity(*root_entity).with_children(|parent| {
+            parent
+                .spawn((
+                    ComboBoxPopup { combo_box_entity },
+                    Popup {
+                        button_entity: trigger.entity(),
+                    },
+                    Dismiss::ClickOutside,
+                ))
+                .with_children(|parent| {
+                    parent
+                        .spawn((
+                            OptionsList,
+                            Node {
+                                flex_direction: FlexDirection::Column,
+                                row_gap: theme.gap.normal,
+                                max_height: theme.combo_box.max_height,
+                                overflow: Overflow::scroll_y(),
+                                ..Default::default()
+                            },
+                        ))
+                        .with_children(|parent| {
+                            for (index, option) in combo_box.options.iter().enumerate() {
+                                parent
+                                    .spawn((
+                                        ComboBoxOption {
+                                            combo_box_entity,
+                                            index,
+                                        },
+                                        Toggled(index == combo_box.selected),
+                                    ))
+                                    .with_child(Text::new(option.clone()))
+                                    .observe(Self::select);
+                            }
+                        });
+                });
+        });
+    }
+
+    fn select(
+        trigger: Trigger<Pointer<Click>>,
+        mut commands: Commands,
+        options: Query<&ComboBoxOption>,
+        mut combo_boxes: Query<&mut ComboBox>,
+        popups: Query<(Entity, &ComboBoxPopup)>,
+    ) {
+        let option = *options.get(trigger.entity()).unwrap();
+        let mut combo_box = combo_boxes.get_mut(option.combo_box_entity).unwrap();
+        set_selected(
+            &mut commands,
+            option.combo_box_entity,
+            &mut combo_box,
+            option.index,
+        );
+
+        for (popup_entity, popup) in &popups {
+            if popup.combo_box_entity == option.combo_box_entity {
+                commands.entity(popup_entity).despawn_recursive();
+            }
+        }
+    }
+
+    /// Changes the selection with arrow keys while the popup is open.
+    ///
+    /// Enter closes the popup.
+    fn navigate(
+        mut commands: Commands,
+        keys: Res<ButtonInput<KeyCode>>,
+        popup: Single<(Entity, &ComboBoxPopup)>,
+        mut combo_boxes: Query<&mut ComboBox>,
+        mut options: Query<(
+            &ComboBoxOption,
+            &mut Toggled,
+            &ComputedNode,
+            &GlobalTransform,
+        )>,
+        mut lists: Query<(&mut ScrollPosition, &ComputedNode, &GlobalTransform), With<OptionsList>>,
+    ) {
+        let (popup_entity, popup) = *popup;
+        if keys.just_pressed(KeyCode::Enter) {
+            debug!("closing popup for `{}`", popup.combo_box_entity);
+            commands.entity(popup_entity).despawn_recursive();
+            return;
+        }
+
+        let mut combo_box = combo_boxes
+            .get_mut(popup.combo_box_entity)
+            .expect("popup should point to a combo box");
+        let index = if keys.just_pressed(KeyCode::ArrowUp) {
+            combo_box.selected.saturating_sub(1)
+        } else if keys.just_pressed(KeyCode::ArrowDown) {
+            (combo_box.selected + 1).min(combo_box.options.len().saturating_sub(1))
+        } else {
+            return;
+        };
+        set_selected(&mut commands, popup.combo_box_entity, &mut combo_box, index);
+
+        for (option, mut toggled, option_node, option_transform) in &mut options {
+            if option.combo_box_entity != popup.combo_box_entity {
+                continue;
+            }
+
+            let selected = option.index == index;
+            if **toggled != selected {
+                **toggled = selected;
+            }
+
+            if selected {
+                if let Ok((mut scroll_position, list_node, list_transform)) = lists.get_single_mut()
+                {
+                    scroll_to(
+                        &mut scroll_position,
+                        list_node,
+                        list_transform,
+                        option_node,
+                        option_transform,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Scrolls hovered option lists.
+    ///
+    /// Bevy doesn't scroll nodes by itself.
+    fn scroll_wheel(
+        mut wheel_events: EventReader<MouseWheel>,
+        mut lists: Query<(&mut ScrollPosition, &Interaction), With<OptionsList>>,
+    ) {
+        for event in wheel_events.read() {
+            let delta = match event.unit {
+                MouseScrollUnit::Line => event.y * SCROLL_LINE_HEIGHT,
+                MouseScrollUnit::Pixel => event.y,
+            };
+            for (mut scroll_position, &interaction) in &mut lists {
+                if interaction != Interaction::None {
+                    // Will be clamped to the content size by layout.
+                    scroll_position.offset_y -= delta;
+                }
+            }
+        }
+    }
+
+    fn update_label(
+        combo_boxes: Query<(&ComboBox, &Children), Changed<ComboBox>>,
+        buttons: Query<&Children, With<ComboBoxButton>>,
+        mut texts: Query<&mut Text>,
+    ) {
+        for (combo_box, children) in &combo_boxes {
+            let button_children = buttons
+                .iter_many(children)
+                .next()
+                .expect("combo box should have a button");
+            let mut iter = texts.iter_many_mut(button_children);
+            let mut text = iter
+                .fetch_next()
+                .expect("combo box button should have a text");
+            text.0 = combo_box
+                .options
+                .get(combo_box.selected)
+                .cloned()
+                .unwrap_or_default();
+        }
+    }
+
+    fn close_removed(
+        trigger: Trigger<OnRemove, ComboBox>,
+        mut commands: Commands,
+        popups: Query<(Entity, &ComboBoxPopup)>,
+    ) {
+        for (popup_entity, popup) in &popups {
+            if popup.combo_box_entity == trigger.entity() {
+                commands.entity(popup_entity).despawn_recursive();
+            }
+        }
+    }
+}
+
+fn set_selected(commands: &mut Commands, entity: Entity, combo_box: &mut ComboBox, index: usize) {
+    if combo_box.selected != index {
+        debug!("selecting option {index} for `{entity}`");
+        combo_box.selected = index;
+        commands.trigger_targets(ComboBoxChanged(index), entity);
+    }
+}
+
+/// Moves the list just enough to make the option fully visible.
+fn scroll_to(
+    scroll_position: &mut ScrollPosition,
+    list_node: &ComputedNode,
+    list_transform: &GlobalTransform,
+    option_node: &ComputedNode,
+    option_transform: &GlobalTransform,
+) {
+    let list_rect =
+        Rect::from_center_size(list_transform.translation().truncate(), list_node.size());
+    let option_rect = Rect::from_center_size(
+        option_transform.translation().truncate(),
+        option_node.size(),
+    );
+
+    // Node sizes are in physical pixels, but the scroll position is in logical.
+    let scale = list_node.inverse_scale_factor();
+    if option_rect.min.y < list_rect.min.y {
+        scroll_position.offset_y -= (list_rect.min.y - option_rect.min.y) * scale;
+    } else if option_rect.max.y > list_rect.max.y {
+        scroll_position.offset_y += (option_rect.max.y - list_rect.max.y) * scale;
+    }
+}
+
+/// Button that shows the selected option and opens a popup with all options on click.
+///
+/// Triggers [`ComboBoxChanged`] on itself when another option is selected.
+/// While the popup is open, the selection can be changed with arrow keys.
+#[derive(Component)]
+#[require(Node)]
+pub struct ComboBox {
+    pub options: Vec<String>,
+    pub selected: usize,
+}
+
+/// Triggered on [`ComboBox`] with the index of the selected option.
+///
+/// Not triggered for the initial selection.
+#[derive(Event, Clone, Copy, Deref)]
+pub struct ComboBoxChanged(pub usize);
+
+#[derive(Component)]
+#[require(Name(|| Name::new("Combo box button")), ButtonKind(|| ButtonKind::Normal))]
+struct ComboBoxButton;
+
+#[derive(Component)]
+#[require(Name(|| Name::new("Combo box popup")))]
+struct ComboBoxPopup {
+    combo_box_entity: Entity,
+}
+
+#[derive(Component)]
+#[require(Name(|| Name::new("Options list")), Interaction)]
+struct OptionsList;
+
+#[derive(Component, Clone, Copy)]
+#[require(Name(|| Name::new("Combo box option")), ButtonKind(|| ButtonKind::Normal))]
+struct ComboBoxOption {
+    combo_box_entity: Entity,
+    index: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keyboard_navigation() {
+        let mut app = App::new();
+        app.init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<LastChange>()
+            .add_systems(Update, ComboBoxPlugin::navigate);
+
+        let combo_box_entity = app
+            .world_mut()
+            .spawn(ComboBox {
+                options: vec!["First".to_string(), "Second".to_string()],
+                selected: 0,
+            })
+            .observe(
+                |trigger: Trigger<ComboBoxChanged>, mut last_change: ResMut<LastChange>| {
+                    last_change.0 = Some(**trigger);
+                },
+            )
+            .id();
+        let popup_entity = app
+            .world_mut()
+            .spawn(ComboBoxPopup { combo_box_entity })
+            .id();
+
+        press(&mut app, KeyCode::ArrowDown);
+        assert_eq!(selected(&app, combo_box_entity), 1);
+        assert_eq!(app.world().resource::<LastChange>().0, Some(1));
+
+        app.world_mut().resource_mut::<LastChange>().0 = None;
+        press(&mut app, KeyCode::ArrowDown);
+        assert_eq!(
+            selected(&app, combo_box_entity),
+            1,
+            "selection should stop at the last option"
+        );
+        assert_eq!(app.world().resource::<LastChange>().0, None);
+
+        press(&mut app, KeyCode::ArrowUp);
+        assert_eq!(selected(&app, combo_box_entity), 0);
+        assert_eq!(app.world().resource::<LastChange>().0, Some(0));
+
+        press(&mut app, KeyCode::Enter);
+        assert!(
+            app.world().get_entity(popup_entity).is_err(),
+            "popup should be closed on confirmation"
+        );
+    }
+
+    fn press(app: &mut App, key: KeyCode) {
+        let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keys.reset_all();
+        keys.press(key);
+        app.update();
+    }
+
+    fn selected(app: &App, entity: Entity) -> usize {
+        app.world().get::<ComboBox>(entity).unwrap().selected
+    }
+
+    #[derive(Resource, Default)]
+    struct LastChange(Option<usize>);
+}
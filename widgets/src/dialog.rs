@@ -1,4 +1,4 @@
-use bevy::{prelude::*, ui::FocusPolicy};
+use bevy::{prelude::*, ui::FocusPolicy, utils::HashMap};
 
 use crate::theme::Theme;
 
@@ -6,7 +6,17 @@ pub(super) struct DialogPlugin;
 
 impl Plugin for DialogPlugin {
     fn build(&self, app: &mut App) {
-        app.add_observer(Self::init);
+        app.init_resource::<LayerStack>()
+            .init_resource::<DialogSizes>()
+            .add_observer(Self::init)
+            .add_observer(Self::init_resizable)
+            .add_observer(Self::push_layer)
+            .add_observer(Self::pop_layer)
+            .add_systems(
+                Update,
+                Self::close_top
+                    .run_if(|keys: Res<ButtonInput<KeyCode>>| keys.just_pressed(KeyCode::Escape)),
+            );
     }
 }
 
@@ -28,8 +38,154 @@ impl DialogPlugin {
         *focus_policy = FocusPolicy::Block;
         *background_color = theme.modal_background;
     }
+
+    fn init_resizable(
+        trigger: Trigger<OnAdd, Resizable>,
+        mut commands: Commands,
+        theme: Res<Theme>,
+        sizes: Res<DialogSizes>,
+        mut dialogs: Query<(&Resizable, &mut Node)>,
+    ) {
+        let (resizable, mut node) = dialogs.get_mut(trigger.entity()).unwrap();
+        if let Some(size) = sizes.get(resizable.id) {
+            debug!("restoring size {size} for '{}'", resizable.id);
+            node.width = Val::Px(size.x);
+            node.height = Val::Px(size.y);
+        }
+
+        commands.entity(trigger.entity()).with_children(|parent| {
+            parent
+                .spawn((
+                    Name::new("Resize handle"),
+                    Node {
+                        position_type: PositionType::Absolute,
+                        right: Val::Px(0.0),
+                        bottom: Val::Px(0.0),
+                        width: theme.resize_handle_size,
+                        height: theme.resize_handle_size,
+                        ..Default::default()
+                    },
+                    theme.button.normal_background,
+                ))
+                .observe(Self::resize)
+                .observe(Self::remember_size);
+        });
+    }
+
+    fn resize(
+        mut trigger: Trigger<Pointer<Drag>>,
+        window: Single<&Window>,
+        handles: Query<&Parent>,
+        mut dialogs: Query<(&Resizable, &mut Node, &ComputedNode)>,
+    ) {
+        trigger.propagate(false);
+        let parent = handles.get(trigger.entity()).unwrap();
+        let (resizable, mut node, computed_node) = dialogs
+            .get_mut(**parent)
+            .expect("resize handle should be a child of a resizable dialog");
+
+        let size = computed_node.size() * computed_node.inverse_scale_factor();
+        let max_size = resizable.max_size.min(window.size());
+        // Dialogs are centered, so both sides move.
+        let new_size = (size + trigger.delta * 2.0)
+            .min(max_size)
+            .max(resizable.min_size);
+
+        node.width = Val::Px(new_size.x);
+        node.height = Val::Px(new_size.y);
+    }
+
+    fn remember_size(
+        trigger: Trigger<Pointer<DragEnd>>,
+        mut sizes: ResMut<DialogSizes>,
+        handles: Query<&Parent>,
+        dialogs: Query<(&Resizable, &Node)>,
+    ) {
+        let parent = handles.get(trigger.entity()).unwrap();
+        let (resizable, node) = dialogs.get(**parent).unwrap();
+        if let (Val::Px(width), Val::Px(height)) = (node.width, node.height) {
+            debug!("remembering size for '{}'", resizable.id);
+            sizes.insert(resizable.id, Vec2::new(width, height));
+        }
+    }
+
+    fn push_layer(trigger: Trigger<OnAdd, Layer>, mut stack: ResMut<LayerStack>) {
+        debug!("pushing layer `{}`", trigger.entity());
+        stack.0.push(trigger.entity());
+    }
+
+    fn pop_layer(trigger: Trigger<OnRemove, Layer>, mut stack: ResMut<LayerStack>) {
+        debug!("removing layer `{}`", trigger.entity());
+        stack.0.retain(|&entity| entity != trigger.entity());
+    }
+
+    fn close_top(
+        mut commands: Commands,
+        stack: Res<LayerStack>,
+        nodes: Query<&Node>,
+        layers: Query<&Layer>,
+    ) {
+        let Some(entity) = stack.top(&nodes) else {
+            return;
+        };
+
+        match layers.get(entity).unwrap() {
+            Layer::Closable => {
+                info!("closing layer `{entity}`");
+                commands.entity(entity).despawn_recursive();
+            }
+            Layer::Manual => debug!("ignoring close for manual layer `{entity}`"),
+        }
+    }
 }
 
 #[derive(Component, Default)]
-#[require(Node)]
+#[require(Node, Layer)]
 pub struct Dialog;
+
+/// Allows resizing the node by dragging a handle in its bottom right corner.
+///
+/// Intended for panel nodes inside [`Dialog`].
+/// The last size is remembered for each [`Self::id`] until the game is closed.
+#[derive(Component, Clone, Copy)]
+#[require(Node)]
+pub struct Resizable {
+    pub id: &'static str,
+    pub min_size: Vec2,
+    pub max_size: Vec2,
+}
+
+/// Last sizes of [`Resizable`] dialogs.
+///
+/// Kept only for the current session. Widgets don't depend on settings,
+/// so sizes aren't written to disk and reset to the theme defaults after a restart.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct DialogSizes(HashMap<&'static str, Vec2>);
+
+/// Part of the UI navigation stack.
+///
+/// Pressing Escape despawns the topmost visible layer.
+#[derive(Component, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Layer {
+    #[default]
+    Closable,
+    /// Layer that handles closing by itself.
+    ///
+    /// Blocks closing of layers below it.
+    Manual,
+}
+
+/// Open UI layers in the order they were spawned.
+#[derive(Resource, Default)]
+pub struct LayerStack(Vec<Entity>);
+
+impl LayerStack {
+    /// Returns the topmost layer that is not hidden.
+    pub fn top(&self, nodes: &Query<&Node>) -> Option<Entity> {
+        self.0.iter().rev().copied().find(|&entity| {
+            nodes
+                .get(entity)
+                .is_ok_and(|node| node.display != Display::None)
+        })
+    }
+}
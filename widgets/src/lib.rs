@@ -1,9 +1,13 @@
 pub mod button;
 pub mod checkbox;
+pub mod click;
+pub mod combo_box;
 pub mod dialog;
 pub mod label;
+pub mod number_input;
 pub mod popup;
 pub mod progress_bar;
+pub mod slider;
 pub mod text_edit;
 pub mod theme;
 
@@ -11,10 +15,14 @@ use bevy::prelude::*;
 
 use button::ButtonPlugin;
 use checkbox::CheckboxPlugin;
+use click::ClickPlugin;
+use combo_box::ComboBoxPlugin;
 use dialog::DialogPlugin;
 use label::LabelPlugin;
+use number_input::NumberInputPlugin;
 use popup::PopupPlugin;
 use progress_bar::ProgressBarPlugin;
+use slider::SliderPlugin;
 use text_edit::TextEditPlugin;
 use theme::ThemePlugin;
 
@@ -26,9 +34,13 @@ impl Plugin for WidgetsPlugin {
             ButtonPlugin,
             DialogPlugin,
             LabelPlugin,
+            NumberInputPlugin,
             CheckboxPlugin,
+            ClickPlugin,
+            ComboBoxPlugin,
             PopupPlugin,
             ProgressBarPlugin,
+            SliderPlugin,
             TextEditPlugin,
             ThemePlugin,
         ));
@@ -0,0 +1,156 @@
+use bevy::prelude::*;
+use bevy_simple_text_input::{TextInputCursorPos, TextInputValue};
+
+use super::{
+    button::{ButtonKind, Repeatable},
+    text_edit::{InvalidEdit, TextEdit},
+    theme::Theme,
+};
+
+pub(super) struct NumberInputPlugin;
+
+impl Plugin for NumberInputPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(Self::init)
+            .add_systems(PostUpdate, Self::validate);
+    }
+}
+
+impl NumberInputPlugin {
+    fn init(
+        trigger: Trigger<OnAdd, NumberInput>,
+        mut commands: Commands,
+        theme: Res<Theme>,
+        mut inputs: Query<(&NumberInput, &mut Node)>,
+    ) {
+        let (number_input, mut node) = inputs.get_mut(trigger.entity()).unwrap();
+        node.align_items = AlignItems::Center;
+        node.column_gap = theme.gap.normal;
+
+        commands.entity(trigger.entity()).with_children(|parent| {
+            parent
+                .spawn((ButtonKind::Symbol, Repeatable::default()))
+                .with_child(Text::new("➖"))
+                .observe(Self::decrement);
+            parent.spawn((
+                NumberEdit,
+                // HACK: For some reason it can't be required component, it messes the edit.
+                TextEdit::default(),
+                TextInputValue(number_input.value.to_string()),
+            ));
+            parent
+                .spawn((ButtonKind::Symbol, Repeatable::default()))
+                .with_child(Text::new("➕"))
+                .observe(Self::increment);
+        });
+    }
+
+    fn increment(
+        trigger: Trigger<Pointer<Click>>,
+        buttons: Query<&Parent>,
+        inputs: Query<(&NumberInput, &Children)>,
+        edits: Query<&mut TextInputValue, With<NumberEdit>>,
+    ) {
+        step(trigger.entity(), 1, &buttons, &inputs, edits);
+    }
+
+    fn decrement(
+        trigger: Trigger<Pointer<Click>>,
+        buttons: Query<&Parent>,
+        inputs: Query<(&NumberInput, &Children)>,
+        edits: Query<&mut TextInputValue, With<NumberEdit>>,
+    ) {
+        step(trigger.entity(), -1, &buttons, &inputs, edits);
+    }
+
+    /// Removes non-numeric characters and updates [`NumberInput::value`] if it's in range.
+    fn validate(
+        mut commands: Commands,
+        mut edits: Query<
+            (
+                Entity,
+                &Parent,
+                &mut TextInputValue,
+                &mut TextInputCursorPos,
+            ),
+            (Changed<TextInputValue>, With<NumberEdit>),
+        >,
+        mut inputs: Query<&mut NumberInput>,
+    ) {
+        for (entity, parent, mut text, mut cursor_pos) in &mut edits {
+            let mut number_input = inputs
+                .get_mut(**parent)
+                .expect("number edit should be a child of number input");
+
+            let mut filtered = String::with_capacity(text.0.len());
+            let mut removed_before_cursor = 0;
+            for (index, c) in text.0.chars().enumerate() {
+                let allowed =
+                    c.is_ascii_digit() || (c == '-' && filtered.is_empty() && number_input.min < 0);
+                if allowed {
+                    filtered.push(c);
+                } else if index < cursor_pos.0 {
+                    removed_before_cursor += 1;
+                }
+            }
+            if filtered != text.0 {
+                debug!("removing invalid characters from `{entity}`");
+                cursor_pos.0 -= removed_before_cursor;
+                text.0 = filtered;
+            }
+
+            match text.0.parse() {
+                Ok(value) if (number_input.min..=number_input.max).contains(&value) => {
+                    number_input.value = value;
+                    commands.entity(entity).remove::<InvalidEdit>();
+                }
+                _ => {
+                    debug!("marking `{entity}` with value '{}' as invalid", text.0);
+                    commands.entity(entity).insert(InvalidEdit);
+                }
+            }
+        }
+    }
+}
+
+/// Changes the value of the input that contains the button by the given number of steps.
+fn step(
+    button_entity: Entity,
+    steps: i32,
+    buttons: &Query<&Parent>,
+    inputs: &Query<(&NumberInput, &Children)>,
+    mut edits: Query<&mut TextInputValue, With<NumberEdit>>,
+) {
+    let parent = buttons.get(button_entity).unwrap();
+    let (number_input, children) = inputs
+        .get(**parent)
+        .expect("step buttons should be children of number input");
+
+    let value = number_input
+        .value
+        .saturating_add(steps.saturating_mul(number_input.step))
+        .clamp(number_input.min, number_input.max);
+
+    let mut iter = edits.iter_many_mut(children);
+    let mut text = iter
+        .fetch_next()
+        .expect("number input should have child edit");
+    debug!("changing number input `{}` to {value}", **parent);
+    text.0 = value.to_string();
+}
+
+/// Text edit with buttons that accepts only integers in range.
+///
+/// The value is updated only when the typed text is a valid number within the range.
+/// Otherwise the edit will be marked with [`InvalidEdit`].
+#[derive(Component, Clone, Copy)]
+#[require(Node)]
+pub struct NumberInput {
+    pub value: i32,
+    pub min: i32,
+    pub max: i32,
+    pub step: i32,
+}
+
+#[derive(Component)]
+struct NumberEdit;
@@ -1,4 +1,4 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, ui::UiSystem};
 
 use crate::theme::Theme;
 
@@ -7,6 +7,15 @@ pub(super) struct PopupPlugin;
 impl Plugin for PopupPlugin {
     fn build(&self, app: &mut App) {
         app.add_observer(Self::init)
+            .add_systems(
+                PreUpdate,
+                Self::dismiss_on_click
+                    .never_param_warn()
+                    .after(UiSystem::Focus)
+                    .run_if(|mouse: Res<ButtonInput<MouseButton>>| {
+                        mouse.any_just_pressed([MouseButton::Left, MouseButton::Right])
+                    }),
+            )
             .add_systems(PostUpdate, Self::close);
     }
 }
@@ -41,20 +50,82 @@ impl PopupPlugin {
 
     fn close(
         mut commands: Commands,
-        popups: Query<(Entity, &Popup)>,
+        popups: Query<(Entity, &Popup, &Dismiss)>,
         buttons: Query<&Interaction>,
     ) {
-        for (entity, popup) in &popups {
+        for (entity, popup, &dismiss) in &popups {
+            if dismiss != Dismiss::Unhover {
+                continue;
+            }
+
             match buttons.get(popup.button_entity) {
                 Ok(Interaction::Hovered) | Ok(Interaction::Pressed) => (),
                 _ => commands.entity(entity).despawn_recursive(),
             }
         }
     }
+
+    /// Despawns nodes with [`Dismiss::ClickOutside`] if they don't contain the cursor.
+    ///
+    /// For popups, clicks on the button that opened it are considered inside.
+    fn dismiss_on_click(
+        mut commands: Commands,
+        window: Single<&Window>,
+        nodes: Query<(
+            Entity,
+            &Dismiss,
+            &ComputedNode,
+            &GlobalTransform,
+            Option<&Popup>,
+        )>,
+        buttons: Query<(&ComputedNode, &GlobalTransform)>,
+    ) {
+        let Some(cursor_pos) = window.physical_cursor_position() else {
+            return;
+        };
+
+        let contains_cursor = |node: &ComputedNode, transform: &GlobalTransform| {
+            Rect::from_center_size(transform.translation().truncate(), node.size())
+                .contains(cursor_pos)
+        };
+
+        for (entity, &dismiss, node, transform, popup) in &nodes {
+            if dismiss != Dismiss::ClickOutside || contains_cursor(node, transform) {
+                continue;
+            }
+
+            if let Some((button_node, button_transform)) =
+                popup.and_then(|popup| buttons.get(popup.button_entity).ok())
+            {
+                if contains_cursor(button_node, button_transform) {
+                    continue;
+                }
+            }
+
+            debug!("dismissing `{entity}` after click outside");
+            commands.entity(entity).despawn_recursive();
+        }
+    }
 }
 
 #[derive(Component)]
-#[require(Node)]
+#[require(Node, Dismiss)]
 pub struct Popup {
     pub button_entity: Entity,
 }
+
+/// Controls automatic closing of popups and non-modal dialogs.
+///
+/// Modal [`Dialog`](crate::dialog::Dialog)s shouldn't use it since they block input.
+#[derive(Component, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Dismiss {
+    /// Despawn when the popup button is no longer hovered.
+    ///
+    /// Only works with [`Popup`].
+    #[default]
+    Unhover,
+    /// Despawn when a pointer click lands outside of the node.
+    ClickOutside,
+    /// Never despawn automatically.
+    Never,
+}
@@ -0,0 +1,227 @@
+use bevy::prelude::*;
+
+use super::{label::LabelKind, theme::Theme};
+
+pub(super) struct SliderPlugin;
+
+impl Plugin for SliderPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(Self::init)
+            .add_systems(PostUpdate, Self::update_handle);
+    }
+}
+
+impl SliderPlugin {
+    fn init(
+        trigger: Trigger<OnAdd, Slider>,
+        mut commands: Commands,
+        theme: Res<Theme>,
+        mut sliders: Query<&mut Node>,
+    ) {
+        let mut node = sliders.get_mut(trigger.entity()).unwrap();
+        node.align_items = AlignItems::Center;
+        node.column_gap = theme.gap.normal;
+
+        commands.entity(trigger.entity()).with_children(|parent| {
+            parent
+                .spawn((
+                    SliderTrack,
+                    Node {
+                        align_items: AlignItems::Center,
+                        width: theme.slider.width,
+                        height: theme.slider.height,
+                        ..Default::default()
+                    },
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        PickingBehavior::IGNORE,
+                        Node {
+                            width: Val::Percent(100.0),
+                            height: theme.slider.rail_height,
+                            ..Default::default()
+                        },
+                        theme.slider.rail_color,
+                    ));
+                    parent.spawn((
+                        SliderHandle,
+                        Node {
+                            position_type: PositionType::Absolute,
+                            width: Val::Px(theme.slider.handle_width),
+                            height: Val::Percent(100.0),
+                            margin: UiRect::left(Val::Px(-theme.slider.handle_width / 2.0)),
+                            ..Default::default()
+                        },
+                        theme.slider.handle_color,
+                    ));
+                })
+                .observe(Self::press)
+                .observe(Self::drag);
+            parent.spawn((SliderLabel, LabelKind::Normal));
+        });
+    }
+
+    fn press(
+        trigger: Trigger<Pointer<Down>>,
+        mut commands: Commands,
+        mut sliders: Query<&mut Slider>,
+        tracks: Query<(&Parent, &ComputedNode, &GlobalTransform)>,
+    ) {
+        let position = trigger.event().pointer_location.position;
+        move_handle(
+            &mut commands,
+            trigger.entity(),
+            position,
+            &mut sliders,
+            &tracks,
+        );
+    }
+
+    fn drag(
+        trigger: Trigger<Pointer<Drag>>,
+        mut commands: Commands,
+        mut sliders: Query<&mut Slider>,
+        tracks: Query<(&Parent, &ComputedNode, &GlobalTransform)>,
+    ) {
+        let position = trigger.event().pointer_location.position;
+        move_handle(
+            &mut commands,
+            trigger.entity(),
+            position,
+            &mut sliders,
+            &tracks,
+        );
+    }
+
+    fn update_handle(
+        sliders: Query<(&Slider, &Children), Changed<Slider>>,
+        tracks: Query<&Children, With<SliderTrack>>,
+        mut handles: Query<&mut Node, With<SliderHandle>>,
+        mut labels: Query<&mut Text, With<SliderLabel>>,
+    ) {
+        for (slider, children) in &sliders {
+            let track_children = tracks
+                .iter_many(children)
+                .next()
+                .expect("slider should have a track");
+            let mut iter = handles.iter_many_mut(track_children);
+            let mut handle_node = iter.fetch_next().expect("track should have a handle");
+            handle_node.left = Val::Percent(slider.fraction() * 100.0);
+
+            let mut iter = labels.iter_many_mut(children);
+            let mut text = iter.fetch_next().expect("slider should have a label");
+            text.0 = format!("{}{}", slider.value, slider.suffix);
+        }
+    }
+}
+
+/// Sets the value of the slider that contains the track from the pointer position.
+fn move_handle(
+    commands: &mut Commands,
+    track_entity: Entity,
+    position: Vec2,
+    sliders: &mut Query<&mut Slider>,
+    tracks: &Query<(&Parent, &ComputedNode, &GlobalTransform)>,
+) {
+    let (parent, track_node, track_transform) = tracks.get(track_entity).unwrap();
+
+    // Node sizes are in physical pixels, but the pointer position is in logical.
+    let scale = track_node.inverse_scale_factor();
+    let track_rect = Rect::from_center_size(
+        track_transform.translation().truncate() * scale,
+        track_node.size() * scale,
+    );
+    let fraction = ((position.x - track_rect.min.x) / track_rect.width()).clamp(0.0, 1.0);
+
+    let mut slider = sliders
+        .get_mut(**parent)
+        .expect("track should be a child of slider");
+    let value = slider.value_at(fraction);
+    if slider.value != value {
+        debug!("changing slider `{}` to {value}", **parent);
+        slider.value = value;
+        commands.trigger_targets(SliderChanged(value), **parent);
+    }
+}
+
+/// Draggable handle on a track that selects an integer in range.
+///
+/// The value is snapped to the step and displayed next to the track with the suffix.
+/// Triggers [`SliderChanged`] on itself when the value is changed by the user.
+#[derive(Component, Clone, Copy)]
+#[require(Node)]
+pub struct Slider {
+    pub value: i32,
+    pub min: i32,
+    pub max: i32,
+    pub step: i32,
+    /// Unit displayed after the value, such as `%`.
+    pub suffix: &'static str,
+}
+
+impl Slider {
+    /// Returns the position of the value on the track from 0 to 1.
+    fn fraction(&self) -> f32 {
+        if self.max == self.min {
+            return 0.0;
+        }
+        (self.value - self.min) as f32 / (self.max - self.min) as f32
+    }
+
+    /// Returns the value at the position on the track from 0 to 1, rounded to the step.
+    fn value_at(&self, fraction: f32) -> i32 {
+        let steps = ((self.max - self.min) as f32 * fraction / self.step as f32).round() as i32;
+        (self.min + steps * self.step).min(self.max)
+    }
+}
+
+/// Triggered on [`Slider`] with the new value.
+///
+/// Not triggered for the initial value.
+#[derive(Event, Clone, Copy, Deref)]
+pub struct SliderChanged(pub i32);
+
+#[derive(Component)]
+#[require(Name(|| Name::new("Slider track")))]
+struct SliderTrack;
+
+#[derive(Component)]
+#[require(
+    Name(|| Name::new("Slider handle")),
+    PickingBehavior(|| PickingBehavior::IGNORE)
+)]
+struct SliderHandle;
+
+#[derive(Component)]
+#[require(Name(|| Name::new("Slider label")), Text)]
+struct SliderLabel;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapping() {
+        let slider = Slider {
+            value: 0,
+            min: 0,
+            max: 100,
+            step: 1,
+            suffix: "",
+        };
+        assert_eq!(slider.value_at(0.0), 0);
+        assert_eq!(slider.value_at(0.424), 42);
+        assert_eq!(slider.value_at(0.426), 43);
+        assert_eq!(slider.value_at(1.0), 100);
+
+        let slider = Slider {
+            value: 10,
+            min: 10,
+            max: 35,
+            step: 10,
+            suffix: "",
+        };
+        assert_eq!(slider.value_at(0.5), 20, "should snap to the nearest step");
+        assert_eq!(slider.value_at(1.0), 35, "shouldn't exceed the maximum");
+    }
+}
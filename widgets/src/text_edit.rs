@@ -1,10 +1,14 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, window::PrimaryWindow};
 use bevy_simple_text_input::{
-    TextInput, TextInputCursorPos, TextInputInactive, TextInputTextColor, TextInputTextFont,
-    TextInputValue,
+    TextInput, TextInputCursorPos, TextInputInactive, TextInputSettings, TextInputSubmitEvent,
+    TextInputTextColor, TextInputTextFont, TextInputValue,
 };
 
-use super::theme::Theme;
+use super::{
+    button::{self, ButtonKind, Toggled},
+    dialog::{Layer, LayerStack},
+    theme::Theme,
+};
 
 /// Adds focus functionality to `bevy_simple_text_input`.
 pub(super) struct TextEditPlugin;
@@ -12,7 +16,23 @@ pub(super) struct TextEditPlugin;
 impl Plugin for TextEditPlugin {
     fn build(&self, app: &mut App) {
         app.add_observer(Self::theme)
-            .add_systems(PostUpdate, Self::update_border_colors);
+            .add_observer(Self::init_mask_toggle)
+            .add_systems(
+                Update,
+                (
+                    Self::switch_focus
+                        .run_if(|keys: Res<ButtonInput<KeyCode>>| keys.just_pressed(KeyCode::Tab)),
+                    Self::press_default_button,
+                ),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    Self::update_border_colors,
+                    Self::insert_newlines,
+                    (Self::toggle_masks, Self::update_settings).chain(),
+                ),
+            );
     }
 }
 
@@ -22,6 +42,7 @@ impl TextEditPlugin {
         mut commands: Commands,
         theme: Res<Theme>,
         mut text: Query<(
+            &TextEdit,
             &mut Node,
             &mut BackgroundColor,
             &mut TextInputTextColor,
@@ -33,6 +54,7 @@ impl TextEditPlugin {
         other_edits: Query<(), With<TextEdit>>,
     ) {
         let (
+            text_edit,
             mut node,
             mut background,
             mut text_color,
@@ -50,23 +72,209 @@ impl TextEditPlugin {
         text_font.0.font_size = theme.text_edit.font_size;
         text_color.0 = theme.text_edit.text_color;
         cursor_pos.0 = text.0.len();
+        if text_edit.multiline {
+            node.max_height = theme.text_edit.max_height;
+            node.overflow = Overflow::scroll_y();
+        }
 
         // Activate if the input is single.
         // TODO 0.16: iterate only onver neighbors when hierarchy will be available.
         inactive.0 = other_edits.get_single().is_err();
-        commands.entity(trigger.entity()).observe(Self::activate);
+        let mut entity = commands.entity(trigger.entity());
+        entity.observe(Self::activate);
+        if text_edit.masked {
+            // Placed inside the edit to not affect the layout of the parent.
+            entity.with_child((
+                MaskToggle(trigger.entity()),
+                Node {
+                    position_type: PositionType::Absolute,
+                    right: Val::ZERO,
+                    ..Default::default()
+                },
+            ));
+        }
     }
 
     fn update_border_colors(
         theme: Res<Theme>,
-        mut text_inputs: Query<(&TextInputInactive, &mut BorderColor), Changed<TextInputInactive>>,
+        mut text_inputs: Query<(&TextInputInactive, Has<InvalidEdit>, &mut BorderColor)>,
     ) {
-        for (inactive, mut border_color) in &mut text_inputs {
-            *border_color = if inactive.0 {
+        for (inactive, invalid, mut border_color) in &mut text_inputs {
+            let color = if invalid {
+                theme.text_edit.invalid_border
+            } else if inactive.0 {
                 theme.text_edit.inactive_border
             } else {
                 theme.text_edit.active_border
             };
+            border_color.set_if_neq(color);
+        }
+    }
+
+    fn init_mask_toggle(trigger: Trigger<OnAdd, MaskToggle>, mut commands: Commands) {
+        commands.entity(trigger.entity()).with_child(Text::new("👁"));
+    }
+
+    fn toggle_masks(
+        toggles: Query<(&MaskToggle, &Toggled), Changed<Toggled>>,
+        mut text_edits: Query<&mut TextEdit>,
+    ) {
+        for (toggle, toggled) in &toggles {
+            let mut text_edit = text_edits
+                .get_mut(toggle.0)
+                .expect("mask toggle should point to a text edit");
+            text_edit.masked = !toggled.0;
+        }
+    }
+
+    fn update_settings(
+        mut text_edits: Query<(&TextEdit, &mut TextInputSettings), Changed<TextEdit>>,
+    ) {
+        for (text_edit, mut settings) in &mut text_edits {
+            // Only the displayed text is replaced, so the value and the cursor stay unaffected.
+            settings.mask_character = text_edit.masked.then_some(MASK_CHARACTER);
+            // Values are read by buttons, submission is used only for newlines and default buttons.
+            settings.retain_on_submit = true;
+        }
+    }
+
+    /// Inserts newlines on Enter for multi-line edits and scrolls to the cursor.
+    fn insert_newlines(
+        mut submit_events: EventReader<TextInputSubmitEvent>,
+        mut text_edits: Query<(
+            &TextEdit,
+            &mut TextInputValue,
+            &mut TextInputCursorPos,
+            &mut ScrollPosition,
+        )>,
+    ) {
+        for event in submit_events.read() {
+            let Ok((text_edit, mut text, mut cursor_pos, mut scroll_position)) =
+                text_edits.get_mut(event.entity)
+            else {
+                continue;
+            };
+            if !text_edit.multiline {
+                continue;
+            }
+
+            let index = text
+                .0
+                .char_indices()
+                .nth(cursor_pos.0)
+                .map(|(index, _)| index)
+                .unwrap_or(text.0.len());
+            text.0.insert(index, '\n');
+            cursor_pos.0 += 1;
+
+            // Will be clamped to the content size by layout.
+            scroll_position.offset_y = f32::MAX;
+        }
+    }
+
+    /// Moves focus to the next or previous edit in the same layer.
+    ///
+    /// If no edit is active, the first edit from the topmost layer will be focused.
+    fn switch_focus(
+        keys: Res<ButtonInput<KeyCode>>,
+        stack: Res<LayerStack>,
+        nodes: Query<&Node>,
+        roots: Query<Entity, (With<Node>, Without<Parent>)>,
+        children: Query<&Children>,
+        parents: Query<&Parent>,
+        layers: Query<(), With<Layer>>,
+        mut text_edits: Query<(Entity, &mut TextInputInactive, Option<&TabIndex>), With<TextEdit>>,
+    ) {
+        let active_entity = text_edits
+            .iter()
+            .find(|(_, inactive, _)| !inactive.0)
+            .map(|(entity, ..)| entity);
+        let layer_entity = match active_entity {
+            Some(entity) => nearest_layer(entity, &parents, &layers),
+            None => stack.top(&nodes),
+        };
+
+        // Traverse in depth-first order to match the visual order.
+        let mut edits = Vec::new();
+        let mut pending: Vec<_> = match layer_entity {
+            Some(entity) => vec![entity],
+            None => roots.iter().collect(),
+        };
+        pending.reverse();
+        while let Some(entity) = pending.pop() {
+            if let Ok((_, _, tab_index)) = text_edits.get(entity) {
+                if nearest_layer(entity, &parents, &layers) == layer_entity {
+                    edits.push((entity, tab_index.copied().unwrap_or_default()));
+                }
+            }
+            if let Ok(entity_children) = children.get(entity) {
+                pending.extend(entity_children.iter().rev());
+            }
+        }
+        edits.sort_by_key(|&(_, tab_index)| tab_index);
+
+        if edits.is_empty() {
+            return;
+        }
+
+        let backward = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+        let current_index = active_entity.and_then(|active_entity| {
+            edits
+                .iter()
+                .position(|&(entity, _)| entity == active_entity)
+        });
+        let next_index = match (current_index, backward) {
+            (Some(index), false) => (index + 1) % edits.len(),
+            (Some(index), true) => (index + edits.len() - 1) % edits.len(),
+            (None, false) => 0,
+            (None, true) => edits.len() - 1,
+        };
+
+        let (next_entity, _) = edits[next_index];
+        debug!("moving focus to `{next_entity}`");
+        for (entity, mut inactive, _) in &mut text_edits {
+            let value = entity != next_entity;
+            if inactive.0 != value {
+                inactive.0 = value;
+            }
+        }
+    }
+
+    /// Clicks [`DefaultButton`] from the same layer on Enter in a single-line edit.
+    fn press_default_button(
+        mut commands: Commands,
+        mut submit_events: EventReader<TextInputSubmitEvent>,
+        primary_window: Single<Entity, With<PrimaryWindow>>,
+        camera_entity: Single<Entity, With<Camera2d>>,
+        parents: Query<&Parent>,
+        layers: Query<(), With<Layer>>,
+        text_edits: Query<&TextEdit>,
+        buttons: Query<(Entity, &GlobalTransform), With<DefaultButton>>,
+    ) {
+        for event in submit_events.read() {
+            let Ok(text_edit) = text_edits.get(event.entity) else {
+                continue;
+            };
+            if text_edit.multiline {
+                continue;
+            }
+
+            let layer_entity = nearest_layer(event.entity, &parents, &layers);
+            let Some((button_entity, transform)) = buttons
+                .iter()
+                .find(|&(entity, _)| nearest_layer(entity, &parents, &layers) == layer_entity)
+            else {
+                continue;
+            };
+
+            debug!("pressing default button `{button_entity}`");
+            let click = button::primary_click(
+                button_entity,
+                transform.translation().truncate(),
+                *primary_window,
+                *camera_entity,
+            );
+            commands.trigger_targets(click, button_entity);
         }
     }
 
@@ -83,6 +291,149 @@ impl TextEditPlugin {
     }
 }
 
+/// Returns the closest ancestor with [`Layer`].
+fn nearest_layer(
+    entity: Entity,
+    parents: &Query<&Parent>,
+    layers: &Query<(), With<Layer>>,
+) -> Option<Entity> {
+    parents
+        .iter_ancestors(entity)
+        .find(|&entity| layers.get(entity).is_ok())
+}
+
+const MASK_CHARACTER: char = '•';
+
 #[derive(Component, Default)]
-#[require(TextInput, TextInputCursorPos)]
-pub struct TextEdit;
+#[require(TextInput, TextInputCursorPos, ScrollPosition)]
+pub struct TextEdit {
+    /// Displays bullets instead of characters.
+    ///
+    /// Spawns a button inside the edit to show or hide the text.
+    pub masked: bool,
+
+    /// Inserts a newline on Enter instead of submitting.
+    ///
+    /// Grows vertically up to the [`max_height`](crate::theme::TextEditTheme::max_height)
+    /// from the theme and scrolls after it.
+    pub multiline: bool,
+}
+
+/// Position of the edit in the focus order of its layer.
+///
+/// Edits without it have index 0 and are ordered by their position in the hierarchy.
+#[derive(Component, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TabIndex(pub i32);
+
+/// Button that will be clicked on Enter in a single-line [`TextEdit`] from the same layer.
+#[derive(Component)]
+pub struct DefaultButton;
+
+/// Button that shows or hides text of the stored masked [`TextEdit`] entity.
+#[derive(Component)]
+#[require(
+    Name(|| Name::new("Mask toggle")),
+    ButtonKind(|| ButtonKind::Symbol),
+    Toggled
+)]
+struct MaskToggle(Entity);
+
+/// Marks [`TextEdit`] with invalid content.
+#[derive(Component)]
+pub struct InvalidEdit;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tab_order() {
+        let mut app = App::new();
+        app.init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<LayerStack>()
+            .add_systems(Update, TextEditPlugin::switch_focus);
+
+        let mut edit_entities = Vec::new();
+        app.world_mut()
+            .spawn(Node::default())
+            .with_children(|parent| {
+                edit_entities.push(spawn_edit(parent, Some(TabIndex(1))));
+                parent.spawn(Node::default()).with_children(|parent| {
+                    edit_entities.push(spawn_edit(parent, None));
+                });
+                edit_entities.push(spawn_edit(parent, None));
+            });
+
+        press(&mut app, &[KeyCode::Tab]);
+        assert_eq!(active_edit(&mut app), edit_entities[1]);
+
+        press(&mut app, &[KeyCode::Tab]);
+        assert_eq!(active_edit(&mut app), edit_entities[2]);
+
+        press(&mut app, &[KeyCode::Tab]);
+        assert_eq!(
+            active_edit(&mut app),
+            edit_entities[0],
+            "edit with higher index should be the last"
+        );
+
+        press(&mut app, &[KeyCode::Tab]);
+        assert_eq!(active_edit(&mut app), edit_entities[1], "order should wrap");
+
+        press(&mut app, &[KeyCode::ShiftLeft, KeyCode::Tab]);
+        assert_eq!(active_edit(&mut app), edit_entities[0]);
+    }
+
+    #[test]
+    fn mask_toggle() {
+        let mut app = App::new();
+        app.add_systems(Update, TextEditPlugin::toggle_masks);
+
+        let edit_entity = app
+            .world_mut()
+            .spawn(TextEdit {
+                masked: true,
+                ..Default::default()
+            })
+            .id();
+        let toggle_entity = app.world_mut().spawn(MaskToggle(edit_entity)).id();
+
+        app.update();
+        assert!(app.world().get::<TextEdit>(edit_entity).unwrap().masked);
+
+        **app.world_mut().get_mut::<Toggled>(toggle_entity).unwrap() = true;
+        app.update();
+        assert!(
+            !app.world().get::<TextEdit>(edit_entity).unwrap().masked,
+            "toggled button should reveal the text"
+        );
+    }
+
+    fn spawn_edit(parent: &mut WorldChildBuilder, tab_index: Option<TabIndex>) -> Entity {
+        let mut entity = parent.spawn((TextEdit::default(), TextInputInactive(true)));
+        if let Some(tab_index) = tab_index {
+            entity.insert(tab_index);
+        }
+        entity.id()
+    }
+
+    fn press(app: &mut App, keys: &[KeyCode]) {
+        let mut input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        input.reset_all();
+        for &key in keys {
+            input.press(key);
+        }
+        app.update();
+    }
+
+    fn active_edit(app: &mut App) -> Entity {
+        let mut query = app.world_mut().query::<(Entity, &TextInputInactive)>();
+        let active: Vec<_> = query
+            .iter(app.world())
+            .filter(|(_, inactive)| !inactive.0)
+            .map(|(entity, _)| entity)
+            .collect();
+        assert_eq!(active.len(), 1, "only one edit should be active");
+        active[0]
+    }
+}
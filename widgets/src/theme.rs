@@ -5,6 +5,7 @@ pub(super) struct ThemePlugin;
 impl Plugin for ThemePlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<Theme>()
+            .init_resource::<ReduceMotion>()
             .add_systems(Startup, Self::set_clear_color);
     }
 }
@@ -15,15 +16,32 @@ impl ThemePlugin {
     }
 }
 
+/// Accessibility option that replaces animations with instant changes.
+///
+/// Every system that animates something should check this resource and snap
+/// to the final state when it's enabled. Currently governs:
+/// - Button hover and press transitions.
+/// - Camera orbit of the main menu background.
+/// - Fade between game states.
+///
+/// Mirrors the persisted video setting.
+#[derive(Resource, Default, Deref, DerefMut, PartialEq)]
+pub struct ReduceMotion(pub bool);
+
 #[derive(Resource)]
 pub struct Theme {
     pub button: ButtonTheme,
     pub label: LabelTheme,
     pub checkbox: CheckboxTheme,
+    pub combo_box: ComboBoxTheme,
     pub text_edit: TextEditTheme,
     pub progress_bar: ProgressBarTheme,
+    pub slider: SliderTheme,
+    pub selection_ring: SelectionRingTheme,
+    pub outline: OutlineTheme,
     pub gap: GapTheme,
     pub padding: PaddingTheme,
+    pub resize_handle_size: Val,
     pub modal_background: BackgroundColor,
     pub popup_background: BackgroundColor,
     pub panel_background: BackgroundColor,
@@ -68,6 +86,10 @@ impl FromWorld for Theme {
                 hovered_background: Color::srgb(0.25, 0.25, 0.25).into(),
                 pressed_background: Color::srgb(0.35, 0.75, 0.35).into(),
                 hovered_pressed_background: Color::srgb(0.25, 0.65, 0.25).into(),
+                disabled_background: Color::srgb(0.45, 0.45, 0.45).into(),
+                transition_secs: 0.1,
+                hovered_scale: 1.05,
+                pressed_scale: 0.95,
             },
             label: LabelTheme {
                 small: LabelTextTheme {
@@ -98,9 +120,14 @@ impl FromWorld for Theme {
                 tick_width: Val::Px(14.0),
                 tick_height: Val::Px(14.0),
                 tick_color: Color::srgb(0.35, 0.75, 0.35).into(),
+                disabled_color: Color::srgb(0.5, 0.5, 0.5).into(),
+            },
+            combo_box: ComboBoxTheme {
+                max_height: Val::Px(300.0),
             },
             text_edit: TextEditTheme {
                 min_width: Val::Px(200.0),
+                max_height: Val::Px(120.0),
                 border: UiRect::all(Val::Px(5.0)),
                 padding: UiRect::all(Val::Px(5.0)),
                 font: text_handle,
@@ -109,11 +136,29 @@ impl FromWorld for Theme {
                 background_color: Color::srgb(0.15, 0.15, 0.15).into(),
                 active_border: Color::srgb(0.35, 0.75, 0.35).into(),
                 inactive_border: Color::srgb(0.35, 0.35, 0.35).into(),
+                invalid_border: Color::srgb(0.75, 0.3, 0.3).into(),
             },
             progress_bar: ProgressBarTheme {
                 background_color: Color::srgb(0.5, 0.5, 0.5).into(),
                 fill_color: Color::srgb(0.35, 0.75, 0.35).into(),
             },
+            slider: SliderTheme {
+                width: Val::Px(200.0),
+                height: Val::Px(20.0),
+                rail_height: Val::Px(4.0),
+                handle_width: 10.0,
+                rail_color: Color::srgb(0.5, 0.5, 0.5).into(),
+                handle_color: Color::srgb(0.15, 0.15, 0.15).into(),
+            },
+            selection_ring: SelectionRingTheme {
+                selected_color: Color::srgba(0.35, 0.75, 0.35, 0.8),
+                hovered_color: Color::srgba(0.9, 0.9, 0.9, 0.4),
+            },
+            outline: OutlineTheme {
+                width: 3.0,
+                hovered_color: Color::srgba(1.0, 1.0, 1.0, 0.3),
+                selected_color: Color::srgba(0.35, 0.75, 0.35, 0.8),
+            },
             gap: GapTheme {
                 normal: Val::Px(10.0),
                 large: Val::Px(20.0),
@@ -122,6 +167,7 @@ impl FromWorld for Theme {
                 normal: UiRect::all(Val::Px(8.0)),
                 global: UiRect::all(Val::Px(15.0)),
             },
+            resize_handle_size: Val::Px(12.0),
             modal_background: Color::srgba(1.0, 1.0, 1.0, 0.3).into(),
             popup_background: Color::srgb(0.75, 0.75, 0.75).into(),
             panel_background: Color::srgb(0.8, 0.8, 0.8).into(),
@@ -139,6 +185,11 @@ pub struct ButtonTheme {
     pub hovered_background: BackgroundColor,
     pub pressed_background: BackgroundColor,
     pub hovered_pressed_background: BackgroundColor,
+    pub disabled_background: BackgroundColor,
+    /// Duration of the hover and press transitions.
+    pub transition_secs: f32,
+    pub hovered_scale: f32,
+    pub pressed_scale: f32,
 }
 
 pub struct TextButtonTheme {
@@ -176,10 +227,18 @@ pub struct CheckboxTheme {
     pub tick_width: Val,
     pub tick_height: Val,
     pub tick_color: BackgroundColor,
+    /// Label color for checkboxes with [`Disabled`](crate::button::Disabled).
+    pub disabled_color: TextColor,
+}
+
+pub struct ComboBoxTheme {
+    /// Maximum height of the options list, longer lists are scrolled.
+    pub max_height: Val,
 }
 
 pub struct TextEditTheme {
     pub min_width: Val,
+    pub max_height: Val,
     pub border: UiRect,
     pub padding: UiRect,
     pub font: Handle<Font>,
@@ -188,6 +247,7 @@ pub struct TextEditTheme {
     pub background_color: BackgroundColor,
     pub inactive_border: BorderColor,
     pub active_border: BorderColor,
+    pub invalid_border: BorderColor,
 }
 
 pub struct ProgressBarTheme {
@@ -195,6 +255,32 @@ pub struct ProgressBarTheme {
     pub fill_color: BackgroundColor,
 }
 
+pub struct SliderTheme {
+    pub width: Val,
+    pub height: Val,
+    pub rail_height: Val,
+    /// Width in logical pixels.
+    ///
+    /// Stored as a number because the handle is offset by half of it to be centered on the value.
+    pub handle_width: f32,
+    pub rail_color: BackgroundColor,
+    pub handle_color: BackgroundColor,
+}
+
+pub struct SelectionRingTheme {
+    pub selected_color: Color,
+    pub hovered_color: Color,
+}
+
+/// Outline around hovered actors and objects in the world.
+pub struct OutlineTheme {
+    /// Width in logical pixels.
+    pub width: f32,
+    pub hovered_color: Color,
+    /// Color for the hovered entity while it's being moved.
+    pub selected_color: Color,
+}
+
 pub struct GapTheme {
     pub normal: Val,
     pub large: Val,